@@ -24,11 +24,12 @@ use std::env;
 /// # Panics
 /// - If the `.env` file can't be loaded.
 /// - If the `PRIVATE_KEY` is not set or is invalid.
+/// - If the keypair is not a required signer of the transaction.
 /// - If decoding/encoding fails at any step (base64, base58, bincode).
 ///
 /// # Notes
-/// - This function assumes the transaction requires a single signature.
-/// - Modify the signature insertion logic if multiple signatures are needed.
+/// - The signature goes in the keypair's slot among the required signers, so signatures of
+///   other signers (e.g. a separate fee payer) are kept.
 ///
 /// # Example
 /// ```
@@ -64,14 +65,22 @@ pub fn sign_transaction(transaction: String) -> String {
     // sign the transaction with the keypair
     let signature = keypair.sign_message(&message);
 
-    // If there is a need for multiple signatures (fee payer), you can modify this part accordingly.
+    // Find the keypair's slot: signatures are ordered like the first
+    // `num_required_signatures` account keys of the message, fee payer first.
+    let required = tx.message.header().num_required_signatures as usize;
+    let slot = tx
+        .message
+        .static_account_keys()
+        .iter()
+        .take(required)
+        .position(|key| *key == keypair.pubkey())
+        .expect("Keypair is not a signer of the transaction");
 
-    // Inject the signature into the transaction
-    if tx.signatures.is_empty() {
-        tx.signatures.push(signature);
-    } else {
-        tx.signatures[0] = signature;
-    };
+    // Inject the signature into the transaction, keeping the other signers' signatures
+    if tx.signatures.len() < required {
+        tx.signatures.resize(required, Default::default());
+    }
+    tx.signatures[slot] = signature;
 
     // Serialize the signed transaction
     let signed_tx_bytes = serialize(&tx).unwrap();
@@ -25,10 +25,8 @@ async fn recurring() {
 
     let base64_signed_tx = sign_transaction(create_order_response.transaction);
 
-    let execute_request = ExecuteRecurringRequest {
-        request_id: create_order_response.request_id,
-        signed_transaction: base64_signed_tx,
-    };
+    let execute_request =
+        ExecuteRecurringRequest::new(create_order_response.request_id, base64_signed_tx);
 
     let execute_res = client
         .execute_recurring_order(&execute_request)
@@ -1,4 +1,4 @@
-use jup_ag_sdk::{JupiterClient, client};
+use jup_ag_sdk::{JupiterClient, client, types::TokenTag};
 
 pub async fn token_balances() {
     let client = JupiterClient::new("https://lite-api.jup.ag");
@@ -78,12 +78,10 @@ pub async fn token_info() {
 }
 
 pub async fn get_tokens_from_tags() {
-    let tags = vec![String::from("lst")];
-
     let client = JupiterClient::new("https:://lite-api.jup.ag");
 
     let mints = client
-        .get_mints_by_tags(&tags)
+        .get_mints_by_tags(&[TokenTag::Lst])
         .await
         .expect("failed to get mint");
 
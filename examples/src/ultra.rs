@@ -15,7 +15,7 @@ pub async fn ultra() {
 
     // Create an order request and specify the taker's wallet address
     let order_request = UltraOrderRequest::new(input_token, output_token, amount)
-        .add_taker("your taker wallet address");
+        .taker("your taker wallet address");
 
     // Fetch the unsigned transaction and request ID from Jupiter
     let order_response = client
@@ -31,10 +31,8 @@ pub async fn ultra() {
     let signed_tx_base64 = sign_transaction(unsigned_tx_base64);
 
     // Prepare the execution request with the signed transaction
-    let execute_request = UltraExecuteOrderRequest {
-        signed_transaction: signed_tx_base64,
-        request_id: order_response.request_id,
-    };
+    let execute_request =
+        UltraExecuteOrderRequest::new(&signed_tx_base64, &order_response.request_id);
 
     // Send the signed transaction to Jupiter for execution
     let execute_response = client
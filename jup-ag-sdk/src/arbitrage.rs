@@ -0,0 +1,288 @@
+//! Two-leg arbitrage scanning.
+//!
+//! [`ArbitrageScanner`] quotes round trips from a base mint through each mint of a list and
+//! back, e.g. SOL → X → SOL, and reports those returning more than they cost. It only
+//! finds opportunities; executing them is up to the caller. Requires the `runtime` feature.
+
+use std::time::Duration;
+
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
+
+use crate::{
+    JupiterClient,
+    client::DEFAULT_QUOTE_CONCURRENCY,
+    error::JupiterClientError,
+    types::{QuoteRequest, QuoteResponse, SIGNATURE_FEE_LAMPORTS, WRAPPED_SOL_MINT},
+};
+
+/// How often [`ArbitrageScanner::run`] scans by default.
+pub const DEFAULT_ARBITRAGE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A round trip returning more than `min_edge_bps` after costs.
+///
+/// Amounts are raw. Quoted output amounts already exclude route LP fees and the platform
+/// fee, see [`QuoteResponse::fee_breakdown`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbitrageOpportunity {
+    pub base_mint: String,
+
+    /// The mint traded through.
+    pub mint: String,
+
+    /// Base amount sent into the first leg.
+    pub in_amount: u64,
+
+    /// Base amount quoted out of the second leg.
+    pub out_amount: u64,
+
+    /// `out_amount - in_amount - fixed_cost`, in the base mint.
+    pub net_profit: i128,
+
+    /// `net_profit` relative to `in_amount`, in basis points.
+    pub edge_bps: f64,
+
+    /// Base to `mint`.
+    pub first_leg: QuoteResponse,
+
+    /// `mint` back to base.
+    pub second_leg: QuoteResponse,
+}
+
+/// Something [`ArbitrageScanner`] found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArbitrageEvent {
+    Opportunity(Box<ArbitrageOpportunity>),
+
+    /// Quoting a leg through `mint` failed; the scanner keeps scanning.
+    QuoteFailed {
+        mint: String,
+        error: String,
+    },
+}
+
+/// Quotes round trips from a base mint through a list of mints and reports profitable ones.
+///
+/// Each scan quotes the first leg of every mint, then the second leg with the first leg's
+/// output, both through [`JupiterClient::get_quotes_with_concurrency`]. Quotes move between
+/// the scan and any execution, so an opportunity is a lead, not a guarantee.
+///
+/// # Example
+///
+/// ```
+/// let (scanner, mut events) = ArbitrageScanner::new(client.clone(), USDC_MINT, 1_000_000_000);
+/// let scanner = scanner
+///     .mints([SOL_MINT, JUP_MINT])
+///     .min_edge_bps(15.0)
+///     .fixed_cost(10_000);
+///
+/// tokio::spawn(scanner.run());
+/// while let Some(event) = events.next().await {
+///     if let ArbitrageEvent::Opportunity(found) = event {
+///         println!("{} -> {} -> {}: {:.1} bps", found.base_mint, found.mint, found.base_mint, found.edge_bps);
+///     }
+/// }
+/// ```
+pub struct ArbitrageScanner {
+    client: JupiterClient,
+    base_mint: String,
+    amount: u64,
+    mints: Vec<String>,
+    min_edge_bps: f64,
+    fixed_cost: u64,
+    slippage_bps: Option<u16>,
+    concurrency: usize,
+    poll_interval: Duration,
+    events: UnboundedSender<ArbitrageEvent>,
+}
+
+impl ArbitrageScanner {
+    /// Returns a scanner of round trips of `amount` (raw) of `base_mint` through no mint
+    /// yet, with the receiving end of its event channel.
+    ///
+    /// With SOL as base, the fixed cost defaults to the signature fees of two transactions;
+    /// otherwise it defaults to 0.
+    pub fn new(
+        client: JupiterClient,
+        base_mint: &str,
+        amount: u64,
+    ) -> (Self, UnboundedReceiver<ArbitrageEvent>) {
+        let (events, receiver) = unbounded();
+        let fixed_cost = if base_mint == WRAPPED_SOL_MINT {
+            2 * SIGNATURE_FEE_LAMPORTS
+        } else {
+            0
+        };
+        let scanner = Self {
+            client,
+            base_mint: base_mint.to_string(),
+            amount,
+            mints: Vec::new(),
+            min_edge_bps: 0.0,
+            fixed_cost,
+            slippage_bps: None,
+            concurrency: DEFAULT_QUOTE_CONCURRENCY,
+            poll_interval: DEFAULT_ARBITRAGE_POLL_INTERVAL,
+            events,
+        };
+        (scanner, receiver)
+    }
+
+    /// Adds mints to trade through. The base mint itself is ignored.
+    pub fn mints<I, M>(mut self, mints: I) -> Self
+    where
+        I: IntoIterator<Item = M>,
+        M: AsRef<str>,
+    {
+        for mint in mints {
+            let mint = mint.as_ref();
+            if mint != self.base_mint && !self.mints.iter().any(|known| known == mint) {
+                self.mints.push(mint.to_string());
+            }
+        }
+        self
+    }
+
+    /// Reports only round trips with a net edge above `bps` basis points. Default: 0.
+    pub fn min_edge_bps(mut self, bps: f64) -> Self {
+        self.min_edge_bps = bps;
+        self
+    }
+
+    /// Sets the cost of executing a round trip, e.g. network and priority fees, in raw
+    /// units of the base mint.
+    pub fn fixed_cost(mut self, amount: u64) -> Self {
+        self.fixed_cost = amount;
+        self
+    }
+
+    /// Sets the slippage of both legs' quotes, which affects routing.
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = Some(slippage_bps);
+        self
+    }
+
+    /// Sets how many quotes are requested at once. Default: [`DEFAULT_QUOTE_CONCURRENCY`].
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets how often the round trips are scanned.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Mints traded through.
+    pub fn mints_scanned(&self) -> impl Iterator<Item = &str> {
+        self.mints.iter().map(String::as_str)
+    }
+
+    /// Scans until the event receiver is dropped, sending events to the channel.
+    pub async fn run(self) {
+        while !self.events.is_closed() {
+            for event in self.scan().await {
+                self.emit(event);
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Quotes every round trip once and returns the opportunities above the threshold and
+    /// the failed quotes.
+    pub async fn scan(&self) -> Vec<ArbitrageEvent> {
+        let first_legs: Vec<QuoteRequest> = self
+            .mints
+            .iter()
+            .map(|mint| self.request(&self.base_mint, mint, self.amount))
+            .collect();
+        let first_results = self
+            .client
+            .get_quotes_with_concurrency(&first_legs, self.concurrency)
+            .await;
+
+        let mut events = Vec::new();
+        let mut quoted = Vec::new();
+        for (mint, result) in self.mints.iter().zip(first_results) {
+            match result.and_then(|quote| {
+                let out = leg_amount(quote.out_amount_u64())?;
+                Ok((quote, out))
+            }) {
+                Ok((quote, out)) => quoted.push((mint, quote, out)),
+                Err(e) => events.push(quote_failed(mint, e)),
+            }
+        }
+
+        let second_legs: Vec<QuoteRequest> = quoted
+            .iter()
+            .map(|(mint, _, out)| self.request(mint, &self.base_mint, *out))
+            .collect();
+        let second_results = self
+            .client
+            .get_quotes_with_concurrency(&second_legs, self.concurrency)
+            .await;
+
+        for ((mint, first_leg, _), result) in quoted.into_iter().zip(second_results) {
+            let second_leg = match result {
+                Ok(quote) => quote,
+                Err(e) => {
+                    events.push(quote_failed(mint, e));
+                    continue;
+                }
+            };
+            let out_amount = match leg_amount(second_leg.out_amount_u64()) {
+                Ok(out) => out,
+                Err(e) => {
+                    events.push(quote_failed(mint, e));
+                    continue;
+                }
+            };
+
+            let net_profit =
+                i128::from(out_amount) - i128::from(self.amount) - i128::from(self.fixed_cost);
+            let edge_bps = net_profit as f64 / self.amount as f64 * 10_000.0;
+            if edge_bps > self.min_edge_bps {
+                events.push(ArbitrageEvent::Opportunity(Box::new(
+                    ArbitrageOpportunity {
+                        base_mint: self.base_mint.clone(),
+                        mint: mint.clone(),
+                        in_amount: self.amount,
+                        out_amount,
+                        net_profit,
+                        edge_bps,
+                        first_leg,
+                        second_leg,
+                    },
+                )));
+            }
+        }
+        events
+    }
+
+    fn request(&self, input_mint: &str, output_mint: &str, amount: u64) -> QuoteRequest {
+        let request = QuoteRequest::new(input_mint, output_mint, amount);
+        match self.slippage_bps {
+            Some(slippage_bps) => request.slippage_bps(slippage_bps),
+            None => request,
+        }
+    }
+
+    fn emit(&self, event: ArbitrageEvent) {
+        // the receiver may have been dropped, `run` stops at the next scan
+        let _ = self.events.unbounded_send(event);
+    }
+}
+
+fn quote_failed(mint: &str, error: JupiterClientError) -> ArbitrageEvent {
+    ArbitrageEvent::QuoteFailed {
+        mint: mint.to_string(),
+        error: error.to_string(),
+    }
+}
+
+fn leg_amount(amount: Option<u64>) -> Result<u64, JupiterClientError> {
+    amount.ok_or_else(|| {
+        JupiterClientError::DeserializationError("invalid outAmount in quote".to_string())
+    })
+}
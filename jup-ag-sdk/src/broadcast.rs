@@ -0,0 +1,175 @@
+//! Sending a signed transaction to several RPC endpoints at once.
+//!
+//! A transaction lands if any leader receives it, so submitting it through several RPC
+//! providers in parallel improves landing rates and latency. Requires the `solana` feature.
+
+use std::time::{Duration, Instant};
+
+use futures::{StreamExt, stream::FuturesUnordered};
+
+use crate::{
+    error::JupiterClientError,
+    rpc::{CommitmentLevel, SolanaRpc},
+};
+
+/// Result of submitting the transaction to one endpoint.
+#[derive(Debug)]
+pub struct EndpointResult {
+    pub url: String,
+
+    /// The signature returned by the endpoint, or why it refused the transaction.
+    pub result: Result<String, JupiterClientError>,
+
+    /// Time from the start of the broadcast until the endpoint answered.
+    pub elapsed: Duration,
+}
+
+/// Outcome of a broadcast accepted by at least one endpoint.
+#[derive(Debug)]
+pub struct BroadcastResult {
+    /// The transaction signature, as returned by the first endpoint that accepted it.
+    pub signature: String,
+
+    /// URL of the first endpoint that accepted the transaction.
+    pub first_endpoint: String,
+
+    /// Every endpoint's result, in the order they answered.
+    pub results: Vec<EndpointResult>,
+}
+
+impl BroadcastResult {
+    /// Number of endpoints that accepted the transaction.
+    pub fn accepted(&self) -> usize {
+        self.results.iter().filter(|r| r.result.is_ok()).count()
+    }
+}
+
+/// Submits signed transactions to several RPC endpoints simultaneously.
+///
+/// # Example
+///
+/// ```
+/// let broadcaster = Broadcaster::from_urls([
+///     "https://api.mainnet-beta.solana.com",
+///     "https://mainnet.helius-rpc.com/?api-key=...",
+/// ])
+/// .skip_preflight(true);
+///
+/// let swap = api.get_swap_transaction(&payload).await?;
+/// let signed = keypair.sign_transaction(&swap.swap_transaction).await?;
+/// let sent = broadcaster.send(&signed.transaction).await?;
+/// println!("{} accepted by {} endpoints", sent.signature, sent.accepted());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Broadcaster {
+    pub endpoints: Vec<SolanaRpc>,
+
+    /// Skip the endpoints' preflight simulation. Default: `false`.
+    pub skip_preflight: bool,
+
+    /// Commitment used for preflight. Default: `Confirmed`.
+    pub preflight_commitment: CommitmentLevel,
+}
+
+impl Broadcaster {
+    pub fn new(endpoints: impl IntoIterator<Item = SolanaRpc>) -> Self {
+        Self {
+            endpoints: endpoints.into_iter().collect(),
+            skip_preflight: false,
+            preflight_commitment: CommitmentLevel::default(),
+        }
+    }
+
+    /// Creates a broadcaster with one [`SolanaRpc`] per URL.
+    pub fn from_urls<I, S>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::new(urls.into_iter().map(|url| SolanaRpc::new(url.as_ref())))
+    }
+
+    /// Enables or disables the endpoints' preflight simulation.
+    pub fn skip_preflight(mut self, skip: bool) -> Self {
+        self.skip_preflight = skip;
+        self
+    }
+
+    /// Sets the preflight commitment.
+    pub fn preflight_commitment(mut self, commitment: CommitmentLevel) -> Self {
+        self.preflight_commitment = commitment;
+        self
+    }
+
+    /// Sends a signed, base64-encoded transaction to every endpoint in parallel and waits
+    /// for all of them to answer.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` if no endpoint is configured.
+    /// * `BroadcastFailed` with every endpoint's result, in the order they answered, if
+    ///   every endpoint refused the transaction.
+    pub async fn send(&self, transaction: &str) -> Result<BroadcastResult, JupiterClientError> {
+        self.broadcast(transaction, false).await
+    }
+
+    /// Same as [`Broadcaster::send`], but returns as soon as one endpoint accepts the
+    /// transaction, with the results received until then.
+    ///
+    /// Requests still in flight are dropped, which may cancel them.
+    pub async fn send_first(
+        &self,
+        transaction: &str,
+    ) -> Result<BroadcastResult, JupiterClientError> {
+        self.broadcast(transaction, true).await
+    }
+
+    async fn broadcast(
+        &self,
+        transaction: &str,
+        stop_at_first_success: bool,
+    ) -> Result<BroadcastResult, JupiterClientError> {
+        if self.endpoints.is_empty() {
+            return Err(JupiterClientError::InvalidRequest(
+                "no RPC endpoint to broadcast to".to_string(),
+            ));
+        }
+
+        let started = Instant::now();
+        let mut pending: FuturesUnordered<_> = self
+            .endpoints
+            .iter()
+            .map(|rpc| async move {
+                let result = rpc
+                    .send_transaction(transaction, self.skip_preflight, self.preflight_commitment)
+                    .await;
+                EndpointResult {
+                    url: rpc.url.clone(),
+                    result,
+                    elapsed: started.elapsed(),
+                }
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(self.endpoints.len());
+        let mut first_success = None;
+        while let Some(result) = pending.next().await {
+            if let (None, Ok(signature)) = (&first_success, &result.result) {
+                first_success = Some((signature.clone(), result.url.clone()));
+            }
+            results.push(result);
+            if stop_at_first_success && first_success.is_some() {
+                break;
+            }
+        }
+
+        match first_success {
+            Some((signature, first_endpoint)) => Ok(BroadcastResult {
+                signature,
+                first_endpoint,
+                results,
+            }),
+            None => Err(JupiterClientError::BroadcastFailed(results)),
+        }
+    }
+}
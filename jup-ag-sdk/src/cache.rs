@@ -0,0 +1,108 @@
+//! Token metadata caching for [`JupiterClient::get_tokens_info`].
+//!
+//! [`InMemoryTokenCache`] keeps tokens in process memory for a fixed TTL. Implement
+//! [`TokenCache`] to share metadata across processes, e.g. in Redis.
+//!
+//! [`JupiterClient::get_tokens_info`]: crate::JupiterClient::get_tokens_info
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+use futures::future::BoxFuture;
+
+use crate::types::TokenInfo;
+
+/// How long [`InMemoryTokenCache::default`] keeps a token before looking it up again.
+pub const DEFAULT_TOKEN_CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// A store of token metadata keyed by mint address.
+///
+/// Implementations decide how long a token stays valid; `get` returns `None` for missing
+/// and expired entries alike. Both methods are async so that a store reached over the
+/// network does not block the runtime.
+pub trait TokenCache: Send + Sync + fmt::Debug {
+    /// Returns the cached metadata of `mint`, if still valid.
+    fn get(&self, mint: &str) -> impl Future<Output = Option<TokenInfo>> + Send;
+
+    /// Stores `token` under its mint address (`token.id`).
+    fn insert(&self, token: TokenInfo) -> impl Future<Output = ()> + Send;
+}
+
+/// Object-safe form of [`TokenCache`], to keep any implementation behind one pointer.
+pub(crate) trait DynTokenCache: Send + Sync + fmt::Debug {
+    fn get<'a>(&'a self, mint: &'a str) -> BoxFuture<'a, Option<TokenInfo>>;
+
+    fn insert(&self, token: TokenInfo) -> BoxFuture<'_, ()>;
+}
+
+impl<T: TokenCache> DynTokenCache for T {
+    fn get<'a>(&'a self, mint: &'a str) -> BoxFuture<'a, Option<TokenInfo>> {
+        Box::pin(TokenCache::get(self, mint))
+    }
+
+    fn insert(&self, token: TokenInfo) -> BoxFuture<'_, ()> {
+        Box::pin(TokenCache::insert(self, token))
+    }
+}
+
+/// An in-process [`TokenCache`] expiring entries `ttl` after they were inserted.
+///
+/// # Example
+///
+/// ```
+/// let api = JupiterClient::new("https://lite-api.jup.ag")
+///     .with_token_cache(InMemoryTokenCache::new(Duration::from_secs(600)));
+/// ```
+#[derive(Debug)]
+pub struct InMemoryTokenCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, (Instant, TokenInfo)>>,
+}
+
+impl InMemoryTokenCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::default(),
+        }
+    }
+
+    /// Number of stored entries, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every entry.
+    pub fn clear(&self) {
+        self.entries.write().unwrap().clear();
+    }
+}
+
+impl Default for InMemoryTokenCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOKEN_CACHE_TTL)
+    }
+}
+
+impl TokenCache for InMemoryTokenCache {
+    async fn get(&self, mint: &str) -> Option<TokenInfo> {
+        let entries = self.entries.read().unwrap();
+        let (inserted_at, token) = entries.get(mint)?;
+        (inserted_at.elapsed() < self.ttl).then(|| token.clone())
+    }
+
+    async fn insert(&self, token: TokenInfo) {
+        let mut entries = self.entries.write().unwrap();
+        entries.retain(|_, (inserted_at, _)| inserted_at.elapsed() < self.ttl);
+        entries.insert(token.id.clone(), (Instant::now(), token));
+    }
+}
@@ -1,14 +1,98 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
 use reqwest::{
     Client,
     header::{HeaderMap, HeaderValue},
 };
 
+#[cfg(feature = "runtime")]
+use crate::rate_limit::RateLimiter;
+use crate::{
+    cache::{DynTokenCache, TokenCache},
+    http_cache::HttpCache,
+    types::Router,
+};
+#[cfg(feature = "solana")]
+use crate::{
+    error::JupiterClientError,
+    introspection::TransactionPolicy,
+    signer::{SignedTransaction, TransactionSigner},
+};
+
+/// How long the `/ultra/v1/order/routers` response is reused before being fetched again.
+pub const DEFAULT_ROUTERS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// How long the `program-id-to-label` response is reused before being fetched again.
+pub const DEFAULT_PROGRAM_LABELS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// A reasonable number of Ultra `request_id`s for [`JupiterClient::with_execute_guard`] to
+/// remember.
+pub const DEFAULT_EXECUTE_GUARD_CAPACITY: usize = 1024;
+
+/// A reasonable number of bytes of response bodies for [`JupiterClient::with_http_cache`]
+/// to keep.
+pub const DEFAULT_HTTP_CACHE_CAPACITY: usize = 16 * 1024 * 1024;
+
+/// Router list and the time it was fetched.
+type RoutersCache = Arc<RwLock<Option<(Instant, Vec<Router>)>>>;
+
+/// Program-id-to-label map and the time it was fetched.
+type ProgramLabelsCache = Arc<RwLock<Option<(Instant, HashMap<String, String>)>>>;
+
 /// `JupiterClient` is a client wrapper to interact with the Jupiter Aggregator APIs.
 /// It is your gateway to interact with the Jupiter exchange API
 #[derive(Debug, Clone)]
 pub struct JupiterClient {
     pub client: Client,
     pub base_url: String,
+    /// Route layout used by the Swap API methods (`get_quote`, `get_swap_transaction`, ...).
+    pub swap_api_mode: SwapApiMode,
+    /// How long [`JupiterClient::routers`] reuses a fetched router list.
+    pub routers_cache_ttl: Duration,
+    /// Router list shared by all clones of this client.
+    pub(crate) routers_cache: RoutersCache,
+    /// How long [`JupiterClient::program_id_to_label`] reuses a fetched label map.
+    pub program_labels_cache_ttl: Duration,
+    /// Program-id-to-label map shared by all clones of this client.
+    pub(crate) program_labels_cache: ProgramLabelsCache,
+    /// Ultra `request_id`s already executed successfully, shared by all clones. Disabled
+    /// unless set with [`JupiterClient::with_execute_guard`].
+    pub(crate) submitted_orders: Arc<Mutex<SubmittedOrders>>,
+    /// Token metadata cache used by [`JupiterClient::get_tokens_info`], if any.
+    pub(crate) token_cache: Option<Arc<dyn DynTokenCache>>,
+    /// Conditionally revalidated responses, shared by all clones, if enabled.
+    pub(crate) http_cache: Option<Arc<Mutex<HttpCache>>>,
+    /// Waited on before every request, shared by all clones, if set.
+    #[cfg(feature = "runtime")]
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    /// Checked by the sign-and-execute helpers before signing, if set.
+    #[cfg(feature = "solana")]
+    pub(crate) transaction_policy: Option<Arc<TransactionPolicy>>,
+}
+
+/// Route layout of the Swap API the client talks to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SwapApiMode {
+    /// Jupiter hosted APIs (`lite-api.jup.ag`, `api.jup.ag`), routes under `/swap/v1`.
+    #[default]
+    Hosted,
+
+    /// A self-hosted `jupiter-swap-api` binary (v6), routes at the root (`/quote`, `/swap`).
+    SelfHosted,
+}
+
+impl SwapApiMode {
+    /// Path prefix prepended to Swap API routes.
+    pub fn path_prefix(&self) -> &'static str {
+        match self {
+            SwapApiMode::Hosted => "/swap/v1",
+            SwapApiMode::SelfHosted => "",
+        }
+    }
 }
 
 impl JupiterClient {
@@ -37,9 +121,207 @@ impl JupiterClient {
         JupiterClient {
             client,
             base_url: base_url.to_string(),
+            swap_api_mode: SwapApiMode::default(),
+            routers_cache_ttl: DEFAULT_ROUTERS_CACHE_TTL,
+            routers_cache: Arc::default(),
+            program_labels_cache_ttl: DEFAULT_PROGRAM_LABELS_CACHE_TTL,
+            program_labels_cache: Arc::default(),
+            submitted_orders: Arc::new(Mutex::new(SubmittedOrders::new(0))),
+            token_cache: None,
+            http_cache: None,
+            #[cfg(feature = "runtime")]
+            rate_limiter: None,
+            #[cfg(feature = "solana")]
+            transaction_policy: None,
+        }
+    }
+
+    /// Creates a client for a self-hosted `jupiter-swap-api` binary.
+    ///
+    /// Only the Swap API methods are served by the binary; Ultra, Trigger, Recurring and
+    /// Token endpoints are not available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::self_hosted("http://127.0.0.1:8080");
+    /// let quote = api.get_quote(&req).await?; // GET http://127.0.0.1:8080/quote
+    /// ```
+    pub fn self_hosted(base_url: &str) -> Self {
+        Self::new(base_url).with_swap_api_mode(SwapApiMode::SelfHosted)
+    }
+
+    /// Returns the client with a different Swap API route layout.
+    pub fn with_swap_api_mode(mut self, mode: SwapApiMode) -> Self {
+        self.swap_api_mode = mode;
+        self
+    }
+
+    /// Returns the client with a different TTL for the cached router list.
+    ///
+    /// A TTL of zero disables caching.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_routers_cache_ttl(Duration::from_secs(60));
+    /// ```
+    pub fn with_routers_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.routers_cache_ttl = ttl;
+        self
+    }
+
+    /// Returns the client with a different TTL for the cached program-id-to-label map.
+    ///
+    /// A TTL of zero disables caching.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_program_labels_cache_ttl(Duration::from_secs(60));
+    /// ```
+    pub fn with_program_labels_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.program_labels_cache_ttl = ttl;
+        self
+    }
+
+    /// Returns the client with a duplicate execute guard remembering `capacity` orders.
+    ///
+    /// [`JupiterClient::ultra_execute_order`] then refuses a `request_id` it has already
+    /// sent, so code re-running a whole swap cannot fill the same order twice. The
+    /// `request_id` is reserved before the request is sent, so concurrent calls and retries
+    /// after a timeout are refused too. It is only released when the request provably never
+    /// reached Jupiter: it could not be built or connected, or was rejected with a 4xx.
+    /// Once `capacity` is reached the least recently seen `request_id` is forgotten. A
+    /// capacity of zero, the default, disables the guard.
+    ///
+    /// The guard also refuses re-sending a successful order to poll its status, which
+    /// Jupiter supports; keep it off if you rely on that.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_execute_guard(DEFAULT_EXECUTE_GUARD_CAPACITY);
+    /// ```
+    pub fn with_execute_guard(self, capacity: usize) -> Self {
+        *self.submitted_orders.lock().unwrap() = SubmittedOrders::new(capacity);
+        self
+    }
+
+    /// Returns the client with a token metadata cache in front of
+    /// [`JupiterClient::get_tokens_info`].
+    ///
+    /// The cache is shared by all clones of the client.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_token_cache(InMemoryTokenCache::default());
+    /// ```
+    pub fn with_token_cache(mut self, cache: impl TokenCache + 'static) -> Self {
+        self.token_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Returns the client with conditional request caching for large, slow-changing
+    /// responses: token lists, tags and routers.
+    ///
+    /// Responses carrying an `ETag` or `Last-Modified` header are kept in memory and
+    /// refreshed with `If-None-Match` / `If-Modified-Since`; on `304 Not Modified` the
+    /// kept copy is returned without downloading the body again. The cache is shared by all
+    /// clones of the client.
+    ///
+    /// At most `capacity` bytes of response bodies are kept: the least recently used are
+    /// dropped to make room, and a body larger than `capacity` is never kept. The bulk lists
+    /// of `get_all_tokens` and `get_tradable_mints` are the exception: the latest copy of
+    /// each is kept apart, outside `capacity`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_http_cache(DEFAULT_HTTP_CACHE_CAPACITY);
+    /// let lst = api.get_mints_by_tags(&[TokenTag::Lst]).await?; // downloaded
+    /// let lst = api.get_mints_by_tags(&[TokenTag::Lst]).await?; // 304, reused
+    /// ```
+    pub fn with_http_cache(mut self, capacity: usize) -> Self {
+        self.http_cache = Some(Arc::new(Mutex::new(HttpCache::new(capacity))));
+        self
+    }
+
+    /// Returns the client with a [`RateLimiter`] every request to the Jupiter APIs waits on
+    /// before being sent. Requires the `runtime` feature.
+    ///
+    /// The limiter is shared by all clones of the client, so tasks using clones share the
+    /// same budget.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_rate_limiter(RateLimiter::per_second(10));
+    /// let quotes = api.get_quotes(&requests).await; // at most 10 requests per second
+    /// ```
+    #[cfg(feature = "runtime")]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(Arc::new(limiter));
+        self
+    }
+
+    /// Waits on the rate limiter, if any, before a request is sent.
+    pub(crate) async fn throttle(&self) {
+        #[cfg(feature = "runtime")]
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
         }
     }
 
+    /// Returns the client with a [`TransactionPolicy`] every sign-and-execute helper checks
+    /// before signing, failing with `PolicyViolation` instead. Requires the `solana` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_transaction_policy(TransactionPolicy::default());
+    /// let res = api.ultra_swap(&req, &keypair).await?; // verified, then signed
+    /// ```
+    #[cfg(feature = "solana")]
+    pub fn with_transaction_policy(mut self, policy: TransactionPolicy) -> Self {
+        self.transaction_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Verifies `transaction` against the transaction policy, if any, then signs it.
+    #[cfg(feature = "solana")]
+    pub(crate) async fn verify_and_sign<S>(
+        &self,
+        transaction: &str,
+        signer: &S,
+    ) -> Result<SignedTransaction, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        if let Some(policy) = &self.transaction_policy {
+            policy.verify_base64(transaction)?;
+        }
+        signer.sign_transaction(transaction).await
+    }
+
+    /// Full URL of a Swap API route, e.g. `swap_url("quote")`.
+    pub(crate) fn swap_url(&self, route: &str) -> String {
+        format!(
+            "{}{}/{}",
+            self.base_url,
+            self.swap_api_mode.path_prefix(),
+            route
+        )
+    }
+
     /// Returns a new JupiterClient with the API key set in headers.
     ///
     /// # Arguments
@@ -65,13 +347,86 @@ impl JupiterClient {
         JupiterClient {
             client,
             base_url: self.base_url,
+            swap_api_mode: self.swap_api_mode,
+            routers_cache_ttl: self.routers_cache_ttl,
+            routers_cache: self.routers_cache,
+            program_labels_cache_ttl: self.program_labels_cache_ttl,
+            program_labels_cache: self.program_labels_cache,
+            submitted_orders: self.submitted_orders,
+            token_cache: self.token_cache,
+            http_cache: self.http_cache,
+            #[cfg(feature = "runtime")]
+            rate_limiter: self.rate_limiter,
+            #[cfg(feature = "solana")]
+            transaction_policy: self.transaction_policy,
         }
     }
 }
 
 // Include all the API method implementations
+#[cfg(feature = "solana")]
+mod execute_api;
 mod recurring_api;
 mod swap_api;
 mod token_api;
 mod trigger_api;
 mod ultra_api;
+
+#[cfg(feature = "solana")]
+pub use execute_api::Executable;
+pub use swap_api::DEFAULT_QUOTE_CONCURRENCY;
+pub use token_api::{DEFAULT_PRICE_CONCURRENCY, MAX_PRICE_IDS, MAX_SEARCH_QUERIES};
+pub use trigger_api::MAX_TRIGGER_ORDERS_PER_CANCEL;
+#[cfg(feature = "solana")]
+pub(crate) use trigger_api::TriggerOrderAges;
+pub use ultra_api::{DEFAULT_BALANCES_CONCURRENCY, MAX_SHIELD_MINTS};
+
+/// A bounded set of recently submitted Ultra `request_id`s, evicting the least recently seen.
+#[derive(Debug)]
+pub(crate) struct SubmittedOrders {
+    capacity: usize,
+    order: VecDeque<String>,
+    ids: HashSet<String>,
+}
+
+impl SubmittedOrders {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            order: VecDeque::new(),
+            ids: HashSet::new(),
+        }
+    }
+
+    /// Records `request_id`, returning `false` if it was already recorded.
+    pub(crate) fn insert(&mut self, request_id: &str) -> bool {
+        if self.capacity == 0 {
+            return true;
+        }
+
+        if self.ids.contains(request_id) {
+            self.order.retain(|id| id != request_id);
+            self.order.push_back(request_id.to_string());
+            return false;
+        }
+
+        if self.order.len() == self.capacity
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.ids.remove(&oldest);
+        }
+        self.order.push_back(request_id.to_string());
+        self.ids.insert(request_id.to_string());
+        true
+    }
+
+    /// Forgets `request_id`, returning `true` if it was recorded.
+    pub(crate) fn remove(&mut self, request_id: &str) -> bool {
+        if self.ids.remove(request_id) {
+            self.order.retain(|id| id != request_id);
+            true
+        } else {
+            false
+        }
+    }
+}
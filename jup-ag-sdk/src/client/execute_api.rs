@@ -0,0 +1,243 @@
+use std::{fmt::Display, future::Future, time::Duration};
+
+use crate::{
+    error::JupiterClientError,
+    rpc::{CommitmentLevel, SignatureStatus, SolanaRpc, confirm_signature},
+    signer::TransactionSigner,
+    types::{
+        ExecuteRecurringRequest, ExecuteRecurringResponse, ExecuteTriggerOrder,
+        ExecuteTriggerOrderResponse, RecurringResponse, Status, TriggerResponse,
+        UltraExecuteOrderRequest, UltraExecuteOrderResponse, UltraOrderResponse,
+    },
+};
+
+use super::JupiterClient;
+
+/// A response holding an unsigned transaction and the `request_id` of its execute endpoint.
+///
+/// Implemented by [`UltraOrderResponse`], [`TriggerResponse`] and [`RecurringResponse`], so
+/// [`JupiterClient::sign_and_execute`] works with any of them.
+pub trait Executable {
+    /// Response of the execute endpoint.
+    type Output;
+
+    /// The request ID to send with the signed transaction.
+    fn request_id(&self) -> &str;
+
+    /// The unsigned, base64-encoded transaction, if the response has one.
+    fn transaction(&self) -> Option<&str>;
+
+    /// Sends a signed transaction to the execute endpoint.
+    fn execute(
+        client: &JupiterClient,
+        request_id: &str,
+        signed_transaction: &str,
+    ) -> impl Future<Output = Result<Self::Output, JupiterClientError>> + Send;
+
+    /// The signature of the executed transaction, if the execute endpoint reports success.
+    fn executed_signature(output: &Self::Output) -> Option<&str>;
+}
+
+impl Executable for UltraOrderResponse {
+    type Output = UltraExecuteOrderResponse;
+
+    fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    fn transaction(&self) -> Option<&str> {
+        self.transaction.as_deref()
+    }
+
+    async fn execute(
+        client: &JupiterClient,
+        request_id: &str,
+        signed_transaction: &str,
+    ) -> Result<Self::Output, JupiterClientError> {
+        client
+            .ultra_execute_order(&UltraExecuteOrderRequest::new(
+                signed_transaction,
+                request_id,
+            ))
+            .await
+    }
+
+    fn executed_signature(output: &Self::Output) -> Option<&str> {
+        output
+            .signature
+            .as_deref()
+            .filter(|_| output.status == Status::Success)
+    }
+}
+
+impl Executable for TriggerResponse {
+    type Output = ExecuteTriggerOrderResponse;
+
+    fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    fn transaction(&self) -> Option<&str> {
+        Some(&self.transaction)
+    }
+
+    async fn execute(
+        client: &JupiterClient,
+        request_id: &str,
+        signed_transaction: &str,
+    ) -> Result<Self::Output, JupiterClientError> {
+        client
+            .execute_trigger_order(&ExecuteTriggerOrder::new(request_id, signed_transaction))
+            .await
+    }
+
+    fn executed_signature(output: &Self::Output) -> Option<&str> {
+        output
+            .status
+            .eq_ignore_ascii_case("success")
+            .then_some(output.signature.as_str())
+    }
+}
+
+impl Executable for RecurringResponse {
+    type Output = ExecuteRecurringResponse;
+
+    fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    fn transaction(&self) -> Option<&str> {
+        Some(&self.transaction)
+    }
+
+    async fn execute(
+        client: &JupiterClient,
+        request_id: &str,
+        signed_transaction: &str,
+    ) -> Result<Self::Output, JupiterClientError> {
+        client
+            .execute_recurring_order(&ExecuteRecurringRequest::new(
+                request_id,
+                signed_transaction,
+            ))
+            .await
+    }
+
+    fn executed_signature(output: &Self::Output) -> Option<&str> {
+        output
+            .status
+            .eq_ignore_ascii_case("success")
+            .then_some(output.signature.as_str())
+    }
+}
+
+impl JupiterClient {
+    /// Signs the transaction of an Ultra order, trigger or recurring response and sends it to
+    /// the matching execute endpoint. Requires the `solana` feature.
+    ///
+    /// The transaction is checked against the client's transaction policy first, if any. The
+    /// execute response is returned as is, whatever its status.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` if the response has no transaction to sign.
+    /// * `PolicyViolation` if the transaction breaks the transaction policy.
+    /// * `SigningError` if signing fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let order = api.get_ultra_order(&req).await?;
+    /// let res = api.sign_and_execute(&order, &keypair).await?;
+    ///
+    /// let created = api.create_trigger_order(&trigger).await?;
+    /// let res = api.sign_and_execute(&created, &keypair).await?;
+    /// ```
+    pub async fn sign_and_execute<T, S>(
+        &self,
+        unsigned: &T,
+        signer: &S,
+    ) -> Result<T::Output, JupiterClientError>
+    where
+        T: Executable + ?Sized,
+        S: TransactionSigner + ?Sized,
+    {
+        let Some(transaction) = unsigned.transaction().filter(|tx| !tx.is_empty()) else {
+            return Err(JupiterClientError::InvalidRequest(
+                "response has no transaction to sign".to_string(),
+            ));
+        };
+        self.sign_and_execute_parts::<T, S>(unsigned.request_id(), transaction, signer)
+            .await
+    }
+
+    /// Same as [`JupiterClient::sign_and_execute`], then waits with
+    /// [`confirm_signature`] for the executed transaction to reach `commitment` on `rpc`.
+    /// Requires the `solana` feature.
+    ///
+    /// The execute endpoints report a transaction once it is submitted; this also checks
+    /// that it landed and succeeded.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((output, status))` with the execute response and the signature status once the
+    ///   transaction reached `commitment`.
+    ///
+    /// # Errors
+    ///
+    /// * Any error of [`JupiterClient::sign_and_execute`].
+    /// * `TransactionFailed` if the execute endpoint reports a failure, or the transaction
+    ///   fails on-chain, expires or is still pending after `timeout`.
+    /// * `RpcError` or `RequestError` if polling `rpc` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let order = api.get_ultra_order(&req).await?;
+    /// let (res, status) = api
+    ///     .sign_execute_and_confirm(&order, &keypair, &rpc, CommitmentLevel::Confirmed, Duration::from_secs(60))
+    ///     .await?;
+    /// println!("{res} landed in slot {}", status.slot);
+    /// ```
+    pub async fn sign_execute_and_confirm<T, S>(
+        &self,
+        unsigned: &T,
+        signer: &S,
+        rpc: &SolanaRpc,
+        commitment: CommitmentLevel,
+        timeout: Duration,
+    ) -> Result<(T::Output, SignatureStatus), JupiterClientError>
+    where
+        T: Executable + ?Sized,
+        T::Output: Display,
+        S: TransactionSigner + ?Sized,
+    {
+        let output = self.sign_and_execute(unsigned, signer).await?;
+        let Some(signature) = T::executed_signature(&output) else {
+            return Err(JupiterClientError::TransactionFailed {
+                signature: String::new(),
+                reason: output.to_string(),
+            });
+        };
+        let status = confirm_signature(rpc, signature, commitment, timeout)
+            .await?
+            .into_result(signature)?;
+        Ok((output, status))
+    }
+
+    /// Same as [`JupiterClient::sign_and_execute`], with the request ID and transaction
+    /// given separately, e.g. one of several cancel transactions sharing a request ID.
+    pub(crate) async fn sign_and_execute_parts<T, S>(
+        &self,
+        request_id: &str,
+        transaction: &str,
+        signer: &S,
+    ) -> Result<T::Output, JupiterClientError>
+    where
+        T: Executable + ?Sized,
+        S: TransactionSigner + ?Sized,
+    {
+        let signed = self.verify_and_sign(transaction, signer).await?;
+        T::execute(self, request_id, &signed.transaction).await
+    }
+}
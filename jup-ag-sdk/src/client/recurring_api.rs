@@ -1,23 +1,32 @@
+use futures::{Stream, StreamExt, TryStreamExt, stream};
+
 use crate::{
     JupiterClientError,
     error::handle_response,
     types::{
         CancelRecurringOrderRequest, CreateRecurringOrderRequest, ExecuteRecurringRequest,
-        ExecuteRecurringResponse, GetRecurringOrders, PriceDeposit, PriceWithdraw, RecurringOrders,
-        RecurringResponse,
+        ExecuteRecurringResponse, GetRecurringOrders, PriceDeposit, PriceWithdraw, RecurringOrder,
+        RecurringOrders, RecurringResponse,
     },
 };
 
 use super::JupiterClient;
+#[cfg(feature = "solana")]
+use crate::signer::TransactionSigner;
 
 impl JupiterClient {
     /// Sends a request to create a new recurring order.
     ///
     /// Returns a base64-encoded unsigned transaction to be signed and a request _id.
+    ///
+    /// The request is checked with [`CreateRecurringOrderRequest::validate`] first.
     pub async fn create_recurring_order(
         &self,
         data: &CreateRecurringOrderRequest,
     ) -> Result<RecurringResponse, JupiterClientError> {
+        data.validate()?;
+
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/recurring/v1/createOrder", self.base_url))
@@ -38,10 +47,14 @@ impl JupiterClient {
     }
 
     /// Request for a base64-encoded unsigned recurring order cancellation transaction
+    ///
+    /// Cancelling returns the remaining deposit and any undrawn output to the user; it is
+    /// also the only way to pull funds out of a time-based order.
     pub async fn cancel_recurring_order(
         &self,
         data: &CancelRecurringOrderRequest,
     ) -> Result<RecurringResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/recurring/v1/cancelOrder", self.base_url))
@@ -66,6 +79,7 @@ impl JupiterClient {
         &self,
         data: &PriceDeposit,
     ) -> Result<RecurringResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/recurring/v1/priceDeposit", self.base_url))
@@ -86,10 +100,15 @@ impl JupiterClient {
     }
 
     /// Request for a base64-encoded unsigned price-based recurring order withdrawal transaction
+    ///
+    /// The Recurring API only supports withdrawals from price-based orders. Time-based
+    /// orders have no withdraw endpoint: their unswapped input and received output are
+    /// returned to the user by [`JupiterClient::cancel_recurring_order`].
     pub async fn price_withdraw_recurring(
         &self,
         data: &PriceWithdraw,
     ) -> Result<RecurringResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/recurring/v1/priceWithdraw", self.base_url))
@@ -114,6 +133,7 @@ impl JupiterClient {
         &self,
         data: &ExecuteRecurringRequest,
     ) -> Result<ExecuteRecurringResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/recurring/v1/execute", self.base_url))
@@ -138,6 +158,7 @@ impl JupiterClient {
         &self,
         data: &GetRecurringOrders,
     ) -> Result<RecurringOrders, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/recurring/v1/getRecurringOrders", self.base_url))
@@ -156,4 +177,148 @@ impl JupiterClient {
             Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
         }
     }
+
+    /// Streams every recurring order matching `data`, fetching pages as they are consumed.
+    ///
+    /// Starts at `data.page` and stops after the last page reported by the API. A failed
+    /// page request ends the stream with its error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures::TryStreamExt;
+    ///
+    /// let params = GetRecurringOrders::new(RecurringOrderType::All, OrderStatus::History, user);
+    /// let mut orders = client.get_recurring_orders_stream(&params);
+    /// while let Some(order) = orders.try_next().await? {
+    ///     println!("{} {} trades", order.order_key(), order.trades().len());
+    /// }
+    /// ```
+    pub fn get_recurring_orders_stream(
+        &self,
+        data: &GetRecurringOrders,
+    ) -> impl Stream<Item = Result<RecurringOrder, JupiterClientError>> + '_ {
+        stream::try_unfold(
+            (data.clone(), Some(data.page.max(1))),
+            move |(params, page)| async move {
+                let Some(page) = page else {
+                    return Ok::<_, JupiterClientError>(None);
+                };
+
+                let response = self
+                    .get_recurring_orders(&params.clone().page(page))
+                    .await?;
+
+                let total_pages = response.total_pages;
+                let orders = response.into_orders();
+                let next_page = (!orders.is_empty() && page < total_pages).then_some(page + 1);
+                Ok(Some((orders, (params, next_page))))
+            },
+        )
+        .map_ok(|orders| stream::iter(orders.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Creates a recurring order, signs it and executes it. Requires the `solana` feature.
+    ///
+    /// # Arguments
+    /// * `data` - `&CreateRecurringOrderRequest` - The order to create, with `user` matching
+    ///   the signer
+    /// * `signer` - Any [`TransactionSigner`] for the user, e.g. a `Keypair`
+    ///
+    /// # Returns
+    /// * `Ok(ExecuteRecurringResponse)` - The executed creation transaction
+    /// * `Err(JupiterClientError::SigningError)` - If the signer is not a required signer
+    /// * `Err(JupiterClientError::TransactionFailed)` - If the execute status is not `Success`
+    ///
+    /// # Example
+    /// ```rust
+    /// let order = CreateRecurringOrderRequest::new_time_order(user, USDC_MINT, SOL_MINT, 1_000_000_000, 10, 86_400);
+    /// let executed = client.create_and_execute_recurring_order(&order, &keypair).await?;
+    /// println!("created in {}", executed.signature);
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn create_and_execute_recurring_order<S>(
+        &self,
+        data: &CreateRecurringOrderRequest,
+        signer: &S,
+    ) -> Result<ExecuteRecurringResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let created = self.create_recurring_order(data).await?;
+        self.sign_and_execute_recurring(&created, signer).await
+    }
+
+    /// Cancels a recurring order, signing and executing the cancellation. Requires the
+    /// `solana` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// let cancel = CancelRecurringOrderRequest::new(order_key, RecurringOrderType::Time, user);
+    /// let executed = client.cancel_and_execute_recurring_order(&cancel, &keypair).await?;
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn cancel_and_execute_recurring_order<S>(
+        &self,
+        data: &CancelRecurringOrderRequest,
+        signer: &S,
+    ) -> Result<ExecuteRecurringResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let cancel = self.cancel_recurring_order(data).await?;
+        self.sign_and_execute_recurring(&cancel, signer).await
+    }
+
+    /// Deposits into a price-based recurring order, signing and executing the deposit.
+    /// Requires the `solana` feature.
+    #[cfg(feature = "solana")]
+    pub async fn price_deposit_and_execute_recurring<S>(
+        &self,
+        data: &PriceDeposit,
+        signer: &S,
+    ) -> Result<ExecuteRecurringResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let deposit = self.price_deposit_recurring(data).await?;
+        self.sign_and_execute_recurring(&deposit, signer).await
+    }
+
+    /// Withdraws from a price-based recurring order, signing and executing the withdrawal.
+    /// Requires the `solana` feature.
+    #[cfg(feature = "solana")]
+    pub async fn price_withdraw_and_execute_recurring<S>(
+        &self,
+        data: &PriceWithdraw,
+        signer: &S,
+    ) -> Result<ExecuteRecurringResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let withdraw = self.price_withdraw_recurring(data).await?;
+        self.sign_and_execute_recurring(&withdraw, signer).await
+    }
+
+    /// Signs a Recurring API transaction and executes it, failing unless the status is `Success`.
+    #[cfg(feature = "solana")]
+    async fn sign_and_execute_recurring<S>(
+        &self,
+        unsigned: &RecurringResponse,
+        signer: &S,
+    ) -> Result<ExecuteRecurringResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let executed = self.sign_and_execute(unsigned, signer).await?;
+
+        if !executed.status.eq_ignore_ascii_case("success") {
+            return Err(JupiterClientError::TransactionFailed {
+                signature: executed.signature,
+                reason: format!("recurring execute returned status {}", executed.status),
+            });
+        }
+        Ok(executed)
+    }
 }
@@ -1,8 +1,27 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use futures::StreamExt;
+
 use super::JupiterClient;
 use crate::{
     error::{JupiterClientError, handle_response},
-    types::{QuoteRequest, QuoteResponse, SwapInstructions, SwapRequest, SwapResponse},
+    types::{
+        BestQuote, FreshQuote, QuoteComparison, QuoteGetSwapModeEnum, QuoteLadder, QuoteRequest,
+        QuoteResponse, QuoteVariant, SwapInstructions, SwapRequest, SwapResponse,
+    },
 };
+#[cfg(feature = "solana")]
+use crate::{
+    rpc::{SimulationResult, SolanaRpc},
+    signer::{SignedTransaction, TransactionSigner},
+    types::{SwapAndSendOptions, SwapAndSendResult},
+};
+
+/// Default number of in-flight requests used by [`JupiterClient::get_quotes`].
+pub const DEFAULT_QUOTE_CONCURRENCY: usize = 8;
 
 impl JupiterClient {
     /// Fetches a token swap quote from Jupiter based on the provided parameters.
@@ -14,8 +33,12 @@ impl JupiterClient {
     /// # Returns
     ///
     /// * `Ok(QuoteResponse)` on success.
+    /// * `Err(JupiterClientError::ValidationError)` if [`QuoteRequest::validate`] fails.
     /// * `Err` with error details if the request or deserialization fails.
     ///
+    /// If `exclude_program_ids` is set, they are resolved into `exclude_dexes` with the
+    /// program-id-to-label map cached by [`JupiterClient::program_id_to_label`].
+    ///
     /// # Jupiter API Reference
     ///
     /// - [Quote Endpoint](https://dev.jup.ag/docs/api/swap-api/quote)
@@ -33,9 +56,23 @@ impl JupiterClient {
         &self,
         params: &QuoteRequest,
     ) -> Result<QuoteResponse, JupiterClientError> {
+        params.validate()?;
+
+        let resolved;
+        let params = match &params.exclude_program_ids {
+            Some(ids) if !ids.is_empty() => {
+                let labels = self.program_id_to_label().await?;
+                resolved = params.clone().resolve_program_ids(&labels)?;
+                resolved.validate()?;
+                &resolved
+            }
+            _ => params,
+        };
+
+        self.throttle().await;
         let response = match self
             .client
-            .get(format!("{}/swap/v1/quote", &self.base_url))
+            .get(self.swap_url("quote"))
             .query(&params)
             .send()
             .await
@@ -52,6 +89,266 @@ impl JupiterClient {
         }
     }
 
+    /// The map of DEX program IDs to the labels used by `dexes` / `excludeDexes`.
+    ///
+    /// The map is cached in the client for [`JupiterClient::program_labels_cache_ttl`]; use
+    /// [`JupiterClient::get_program_id_to_label`] to bypass the cache.
+    pub async fn program_id_to_label(&self) -> Result<HashMap<String, String>, JupiterClientError> {
+        if let Some((fetched_at, labels)) = self.program_labels_cache.read().unwrap().as_ref()
+            && fetched_at.elapsed() < self.program_labels_cache_ttl
+        {
+            return Ok(labels.clone());
+        }
+
+        self.get_program_id_to_label().await
+    }
+
+    /// Fetches the map of DEX program IDs to the labels used by `dexes` / `excludeDexes`
+    /// and replaces the cached copy.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<String, String>)` keyed by program ID.
+    /// * `Err` with error details if the request or deserialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let labels = api.get_program_id_to_label().await?;
+    /// let request = request.resolve_program_ids(&labels)?;
+    /// ```
+    pub async fn get_program_id_to_label(
+        &self,
+    ) -> Result<HashMap<String, String>, JupiterClientError> {
+        self.throttle().await;
+        let response = match self
+            .client
+            .get(self.swap_url("program-id-to-label"))
+            .send()
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => return Err(JupiterClientError::RequestError(e)),
+        };
+
+        let response = handle_response(response).await?;
+
+        let labels = match response.json::<HashMap<String, String>>().await {
+            Ok(labels) => labels,
+            Err(e) => return Err(JupiterClientError::DeserializationError(e.to_string())),
+        };
+
+        *self.program_labels_cache.write().unwrap() = Some((Instant::now(), labels.clone()));
+        Ok(labels)
+    }
+
+    /// Fetches a quote and records when it was received.
+    ///
+    /// The returned [`FreshQuote`] keeps a copy of `params` so it can be re-quoted later
+    /// with [`JupiterClient::refresh_if_stale`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let req = QuoteRequest::new(inputMint, outputMint, amount);
+    /// let quote = api.get_fresh_quote(&req).await?;
+    /// println!("quote age: {:?}", quote.age());
+    /// ```
+    pub async fn get_fresh_quote(
+        &self,
+        params: &QuoteRequest,
+    ) -> Result<FreshQuote, JupiterClientError> {
+        let quote = self.get_quote(params).await?;
+        Ok(FreshQuote::new(params.clone(), quote))
+    }
+
+    /// Re-quotes with the original [`QuoteRequest`] if `quote` is older than `max_age`.
+    ///
+    /// Returns the quote unchanged when it is still fresh.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let quote = api.get_fresh_quote(&req).await?;
+    /// // ... user confirms the trade ...
+    /// let quote = api.refresh_if_stale(quote, Duration::from_secs(10)).await?;
+    /// let payload = SwapRequest::new(user, user, quote.into_quote());
+    /// ```
+    pub async fn refresh_if_stale(
+        &self,
+        quote: FreshQuote,
+        max_age: Duration,
+    ) -> Result<FreshQuote, JupiterClientError> {
+        if !quote.is_stale(max_age) {
+            return Ok(quote);
+        }
+
+        self.get_fresh_quote(&quote.request).await
+    }
+
+    /// Fetches `ExactIn` and `ExactOut` quotes for the same pair concurrently.
+    ///
+    /// The `ExactIn` quote uses `params.amount` as the input amount, the `ExactOut` quote
+    /// uses `exact_out_amount` as the desired output amount. All other parameters are shared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let req = QuoteRequest::new(SOL_MINT, USDC_MINT, 1_000_000_000); // 1 SOL in
+    /// let cmp = api.compare_swap_modes(&req, 150_000_000).await?; // 150 USDC out
+    /// println!("price difference: {:?}%", cmp.price_difference_pct());
+    /// ```
+    pub async fn compare_swap_modes(
+        &self,
+        params: &QuoteRequest,
+        exact_out_amount: u64,
+    ) -> Result<QuoteComparison, JupiterClientError> {
+        let exact_in_req = params.clone().swap_mode(QuoteGetSwapModeEnum::ExactIn);
+        let mut exact_out_req = params.clone().swap_mode(QuoteGetSwapModeEnum::ExactOut);
+        exact_out_req.amount = exact_out_amount;
+
+        let (exact_in, exact_out) = futures::try_join!(
+            self.get_quote(&exact_in_req),
+            self.get_quote(&exact_out_req)
+        )?;
+
+        Ok(QuoteComparison {
+            exact_in,
+            exact_out,
+        })
+    }
+
+    /// Quotes several `ExactIn` trade sizes concurrently and returns the resulting depth curve.
+    ///
+    /// Sizes are quoted through [`JupiterClient::get_quotes`], so at most
+    /// [`DEFAULT_QUOTE_CONCURRENCY`] requests are in flight. Sizes whose quote fails are
+    /// listed in [`QuoteLadder::failed_sizes`]; an error is only returned if every size failed.
+    ///
+    /// # Arguments
+    /// * `input_mint` - The mint address of the input token.
+    /// * `output_mint` - The mint address of the output token.
+    /// * `sizes` - Raw input amounts to quote.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let sizes = [1_000_000_000, 10_000_000_000, 100_000_000_000];
+    /// let ladder = api.quote_ladder(SOL_MINT, USDC_MINT, &sizes).await?;
+    /// println!("max size within 1% impact: {:?}", ladder.max_size_within_impact(1.0));
+    /// ```
+    pub async fn quote_ladder(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        sizes: &[u64],
+    ) -> Result<QuoteLadder, JupiterClientError> {
+        let requests: Vec<QuoteRequest> = sizes
+            .iter()
+            .map(|size| QuoteRequest::new(input_mint, output_mint, *size))
+            .collect();
+
+        let mut quotes = Vec::new();
+        let mut failed_sizes = Vec::new();
+        let mut last_error = None;
+        for (size, result) in sizes.iter().zip(self.get_quotes(&requests).await) {
+            match result {
+                Ok(quote) => quotes.push(quote),
+                Err(e) => {
+                    failed_sizes.push(*size);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if quotes.is_empty()
+            && let Some(e) = last_error
+        {
+            return Err(e);
+        }
+
+        let mut ladder = QuoteLadder::from_quotes(input_mint, output_mint, &quotes);
+        ladder.failed_sizes = failed_sizes;
+        Ok(ladder)
+    }
+
+    /// Fetches quotes for many requests, at most [`DEFAULT_QUOTE_CONCURRENCY`] at a time.
+    ///
+    /// Results are returned in the same order as `requests`; a failing request does not
+    /// abort the others. Requests wait on the client's rate limiter, if set with
+    /// [`JupiterClient::with_rate_limiter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let requests = vec![
+    ///     QuoteRequest::new(SOL_MINT, USDC_MINT, 1_000_000_000),
+    ///     QuoteRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000),
+    /// ];
+    /// for result in api.get_quotes(&requests).await {
+    ///     match result {
+    ///         Ok(quote) => println!("{} -> {}", quote.input_mint, quote.out_amount),
+    ///         Err(e) => eprintln!("quote failed: {e}"),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_quotes(
+        &self,
+        requests: &[QuoteRequest],
+    ) -> Vec<Result<QuoteResponse, JupiterClientError>> {
+        self.get_quotes_with_concurrency(requests, DEFAULT_QUOTE_CONCURRENCY)
+            .await
+    }
+
+    /// Same as [`JupiterClient::get_quotes`] with an explicit concurrency limit.
+    ///
+    /// A `concurrency` of `0` is treated as `1`.
+    pub async fn get_quotes_with_concurrency(
+        &self,
+        requests: &[QuoteRequest],
+        concurrency: usize,
+    ) -> Vec<Result<QuoteResponse, JupiterClientError>> {
+        futures::stream::iter(requests.iter().map(|req| self.get_quote(req)))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Quotes every variant concurrently and returns the best one with a per-variant report.
+    ///
+    /// The best quote has the highest output for `ExactIn` and the lowest input for
+    /// `ExactOut`. Fails only if every variant fails, returning the last variant's error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let base = QuoteRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000);
+    /// let mut variants = QuoteVariant::defaults(&base);
+    /// variants.push(QuoteVariant::with_dexes("orca", &base, vec![DexEnum::Whirlpool]));
+    ///
+    /// let best = api.best_quote(&variants).await?;
+    /// println!("{} won with {}", best.variant, best.quote.out_amount);
+    /// ```
+    pub async fn best_quote(
+        &self,
+        variants: &[QuoteVariant],
+    ) -> Result<BestQuote, JupiterClientError> {
+        let requests: Vec<QuoteRequest> = variants.iter().map(|v| v.request.clone()).collect();
+        let mut results = self.get_quotes(&requests).await;
+
+        if results.iter().all(Result::is_err) {
+            return match results.pop() {
+                Some(Err(e)) => Err(e),
+                _ => Err(JupiterClientError::InvalidRequest(
+                    "best_quote requires at least one variant".to_string(),
+                )),
+            };
+        }
+
+        BestQuote::select(variants, results).map_err(|_| {
+            JupiterClientError::InvalidRequest("no quote variant succeeded".to_string())
+        })
+    }
+
     /// Fetches a swap transaction from Jupiter's `/swap` endpoint.
     ///
     /// # Arguments
@@ -69,9 +366,12 @@ impl JupiterClient {
         &self,
         data: &SwapRequest,
     ) -> Result<SwapResponse, JupiterClientError> {
+        data.validate()?;
+
+        self.throttle().await;
         let response = match self
             .client
-            .post(format!("{}/swap/v1/swap", self.base_url))
+            .post(self.swap_url("swap"))
             .json(&data)
             .send()
             .await
@@ -105,9 +405,12 @@ impl JupiterClient {
         &self,
         data: &SwapRequest,
     ) -> Result<SwapInstructions, JupiterClientError> {
+        data.validate()?;
+
+        self.throttle().await;
         let response = match self
             .client
-            .post(format!("{}/swap/v1/swap-instructions", self.base_url))
+            .post(self.swap_url("swap-instructions"))
             .json(&data)
             .send()
             .await
@@ -123,4 +426,160 @@ impl JupiterClient {
             Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
         }
     }
+
+    /// Decodes the swap transaction and simulates it against a Solana RPC node.
+    ///
+    /// Runs before anything is signed: signature verification is disabled and the blockhash
+    /// is replaced by the node. Requires the `solana` feature.
+    ///
+    /// # Returns
+    /// The [`SimulationResult`] with the typed transaction error (if any), logs and consumed
+    /// compute units.
+    ///
+    /// # Example
+    /// ```
+    /// let rpc = SolanaRpc::new("https://api.mainnet-beta.solana.com");
+    /// let swap = api.get_swap_transaction(&payload).await?;
+    /// let simulation = api.simulate_swap(&swap, &rpc).await?;
+    /// if let Some(err) = simulation.err {
+    ///     eprintln!("swap would fail: {:?}", err);
+    /// }
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn simulate_swap(
+        &self,
+        swap: &SwapResponse,
+        rpc: &SolanaRpc,
+    ) -> Result<SimulationResult, JupiterClientError> {
+        crate::codec::decode_base64(&swap.swap_transaction)?;
+
+        rpc.simulate_transaction(&swap.swap_transaction).await
+    }
+
+    /// Returns `swap` if its blockhash is still valid, otherwise builds it again from
+    /// `payload`.
+    ///
+    /// Call it before signing a transaction that may have waited, e.g. for user confirmation.
+    /// The rebuilt swap reuses the quote in `payload`. Requires the `solana` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let swap = api.get_swap_transaction(&payload).await?;
+    /// // ... user reviews the swap ...
+    /// let swap = api.refresh_swap_transaction(swap, &payload, &rpc).await?;
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn refresh_swap_transaction(
+        &self,
+        swap: SwapResponse,
+        payload: &SwapRequest,
+        rpc: &SolanaRpc,
+    ) -> Result<SwapResponse, JupiterClientError> {
+        if rpc.is_transaction_fresh(&swap.swap_transaction).await? {
+            return Ok(swap);
+        }
+        self.get_swap_transaction(payload).await
+    }
+
+    /// Quotes, builds, signs and submits a swap, then waits for confirmation.
+    ///
+    /// The signer's public key is used as both the user and the fee payer.
+    /// Requires the `solana` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The [`QuoteRequest`] describing the trade.
+    /// * `signer` - Any [`TransactionSigner`], e.g. a `Keypair`.
+    /// * `rpc` - The [`SolanaRpc`] used to submit and confirm the transaction.
+    /// * `options` - Commitment, timeout and swap build options.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SwapAndSendResult)` once the transaction reaches the requested commitment.
+    /// * `Err(JupiterClientError::TransactionFailed)` if it fails on-chain, expires or times out.
+    /// * `Err` with error details if quoting, building, signing or sending fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let quote = QuoteRequest::new(SOL_MINT, USDC_MINT, 1_000_000_000);
+    /// let result = api
+    ///     .swap_and_send(&quote, &keypair, &rpc, SwapAndSendOptions::default())
+    ///     .await?;
+    /// println!("swapped in {}", result.signature);
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn swap_and_send<S>(
+        &self,
+        params: &QuoteRequest,
+        signer: &S,
+        rpc: &SolanaRpc,
+        options: SwapAndSendOptions,
+    ) -> Result<SwapAndSendResult, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let quote = self.get_quote(params).await?;
+        self.swap_and_send_quote(quote, signer, rpc, options).await
+    }
+
+    /// Same as [`JupiterClient::swap_and_send`] for an already fetched `quote`, e.g. one
+    /// checked against a price impact limit first. Requires the `solana` feature.
+    #[cfg(feature = "solana")]
+    pub async fn swap_and_send_quote<S>(
+        &self,
+        quote: QuoteResponse,
+        signer: &S,
+        rpc: &SolanaRpc,
+        options: SwapAndSendOptions,
+    ) -> Result<SwapAndSendResult, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let amount = |value: Option<u64>, field: &str| {
+            value.ok_or_else(|| {
+                JupiterClientError::DeserializationError(format!("invalid {field} in quote"))
+            })
+        };
+        let in_amount = amount(quote.in_amount_u64(), "inAmount")?;
+        let out_amount = amount(quote.out_amount_u64(), "outAmount")?;
+        let other_amount_threshold =
+            amount(quote.other_amount_threshold_u64(), "otherAmountThreshold")?;
+
+        let user = signer.address().to_string();
+        let mut payload = SwapRequest::new(&user, &user, quote.clone())
+            .dynamic_compute_unit_limit(options.dynamic_compute_unit_limit);
+        payload.prioritization_fee_lamports = options.prioritization_fee_lamports;
+
+        let swap = self.get_swap_transaction(&payload).await?;
+        let SignedTransaction {
+            transaction,
+            signature,
+        } = self.verify_and_sign(&swap.swap_transaction, signer).await?;
+
+        rpc.send_transaction(&transaction, options.skip_preflight, options.commitment)
+            .await?;
+
+        let status = rpc
+            .poll_confirmation(
+                &signature,
+                options.commitment,
+                options.timeout,
+                options.poll_interval,
+                Some(swap.last_valid_block_height),
+            )
+            .await?
+            .into_result(&signature)?;
+
+        Ok(SwapAndSendResult {
+            signature,
+            slot: status.slot,
+            in_amount,
+            out_amount,
+            other_amount_threshold,
+            prioritization_fee_lamports: swap.prioritization_fee_lamports,
+            quote,
+        })
+    }
 }
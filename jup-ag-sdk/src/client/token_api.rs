@@ -1,14 +1,31 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use futures::{Stream, StreamExt, TryStreamExt, future::try_join_all, stream};
+#[cfg(feature = "runtime")]
+use tokio::io::AsyncWrite;
 
 use super::JupiterClient;
+#[cfg(feature = "runtime")]
+use crate::json_stream::download_to;
 use crate::{
     error::{JupiterClientError, handle_response},
+    http_cache::CacheSlot,
+    json_stream::json_array_stream,
     types::{
-        Category, Interval, NewTokens, Price, TokenInfo, TokenInfoResponse, TokenPriceRequest,
-        TokenPriceResponse,
+        Category, DenominatedPrice, Interval, NewTokens, Price, SearchOptions, TokenInfo,
+        TokenInfoResponse, TokenPriceRequest, TokenPriceResponse, TokenTag,
     },
 };
 
+/// Maximum number of mints accepted by a single `/price/v3` request.
+pub const MAX_PRICE_IDS: usize = 50;
+
+/// Number of `/price/v3` requests [`JupiterClient::get_tokens_price`] sends at a time.
+pub const DEFAULT_PRICE_CONCURRENCY: usize = 4;
+
+/// Maximum number of queries accepted by a single `/tokens/v2/search` request.
+pub const MAX_SEARCH_QUERIES: usize = 100;
+
 impl JupiterClient {
     /// search for a token and its information by its symbol, name or mint address
     ///
@@ -43,6 +60,7 @@ impl JupiterClient {
     ) -> Result<Vec<TokenInfo>, JupiterClientError> {
         let query_params = vec![("query", mints.join(","))];
 
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/tokens/v2/search", self.base_url))
@@ -62,39 +80,106 @@ impl JupiterClient {
         }
     }
 
+    /// Same as [`JupiterClient::token_search`], with the results filtered by `options`.
+    ///
+    /// `/tokens/v2/search` has no limit, verified or sort parameters, so the full result is
+    /// still downloaded; `options` only trims and orders it afterwards. A `limit` does not
+    /// make the request cheaper.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let options = SearchOptions::default().limit(3).verified_only(true);
+    /// let tokens = client
+    ///     .token_search_with_options(&["USDC".to_string()], &options)
+    ///     .await?;
+    /// ```
+    pub async fn token_search_with_options(
+        &self,
+        mints: &[String],
+        options: &SearchOptions,
+    ) -> Result<Vec<TokenInfo>, JupiterClientError> {
+        let tokens = self.token_search(mints).await?;
+        Ok(options.apply(tokens))
+    }
+
+    /// Returns the metadata of each mint, keyed by mint address.
+    ///
+    /// Mints found in the cache set with [`JupiterClient::with_token_cache`] are not looked
+    /// up again; the others are searched in requests of [`MAX_SEARCH_QUERIES`] and stored
+    /// in the cache. Mints unknown to the API are left out of the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = JupiterClient::new("https://lite-api.jup.ag")
+    ///     .with_token_cache(InMemoryTokenCache::default());
+    ///
+    /// let tokens = api.get_tokens_info(&[USDC_MINT.to_string()]).await?; // searched
+    /// let tokens = api.get_tokens_info(&[USDC_MINT.to_string()]).await?; // from the cache
+    /// println!("USDC decimals: {}", tokens[USDC_MINT].decimals);
+    /// ```
+    pub async fn get_tokens_info(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, TokenInfo>, JupiterClientError> {
+        let mut tokens = HashMap::new();
+        let mut missing = Vec::new();
+        for mint in mints {
+            if tokens.contains_key(mint) || missing.contains(mint) {
+                continue;
+            }
+            let cached = match &self.token_cache {
+                Some(cache) => cache.get(mint).await,
+                None => None,
+            };
+            match cached {
+                Some(token) => {
+                    tokens.insert(mint.clone(), token);
+                }
+                None => missing.push(mint.clone()),
+            }
+        }
+
+        for chunk in missing.chunks(MAX_SEARCH_QUERIES) {
+            for token in self.token_search(chunk).await? {
+                if !chunk.contains(&token.id) {
+                    continue;
+                }
+                if let Some(cache) = &self.token_cache {
+                    cache.insert(token.clone()).await;
+                }
+                tokens.insert(token.id.clone(), token);
+            }
+        }
+
+        Ok(tokens)
+    }
+
     /// Returns a list of mints with specified tag(s) along with their metadata.
-    /// tags: verified, lst, token-2022, etc
+    ///
+    /// Revalidated rather than downloaded again with [`JupiterClient::with_http_cache`].
     /// ```
     ///
-    /// let tags = vec![String::from("verified")];
     /// let tagged = client
-    /// .get_mints_by_tags(&tags)
+    ///    .get_mints_by_tags(&[TokenTag::Verified, TokenTag::Lst])
     ///    .await
     ///    .expect("failed to get mints by tags");
     /// ```
     pub async fn get_mints_by_tags(
         &self,
-        tags: &[String],
+        tags: &[TokenTag],
     ) -> Result<Vec<TokenInfo>, JupiterClientError> {
+        let tags: Vec<String> = tags.iter().map(TokenTag::to_string).collect();
         let query_params = vec![("query", tags.join(","))];
 
-        let response = match self
-            .client
-            .get(format!("{}/tokens/v2/tag", self.base_url))
-            .query(&query_params)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
-
-        match response.json::<Vec<TokenInfo>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+        self.get_json_cached(
+            self.client
+                .get(format!("{}/tokens/v2/tag", self.base_url))
+                .query(&query_params),
+            CacheSlot::Shared,
+        )
+        .await
     }
 
     /// Returns a list of mints and their information for the given category and time interval.
@@ -135,6 +220,7 @@ impl JupiterClient {
             request = request.query(&[("limit", limit)]);
         }
 
+        self.throttle().await;
         let response = match request.send().await {
             Ok(resp) => resp,
             Err(e) => return Err(JupiterClientError::RequestError(e)),
@@ -148,11 +234,59 @@ impl JupiterClient {
         }
     }
 
+    /// Fetches several category and interval rankings concurrently and merges them.
+    ///
+    /// Tokens are kept in the order of `queries`, the first occurrence of a mint winning,
+    /// then `options` is applied, e.g. to re-rank the merged list by liquidity.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - Category and interval pairs to fetch.
+    /// * `limit` - Maximum number of tokens per query, see [`JupiterClient::get_tokens_by_category`].
+    /// * `options` - Filters, sort and limit applied to the merged list.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let discover = client
+    ///     .get_tokens_by_categories(
+    ///         &[
+    ///             (Category::TopTrending, Interval::OneHour),
+    ///             (Category::TopTraded, Interval::TwentyFourHours),
+    ///         ],
+    ///         Some(50),
+    ///         &SearchOptions::default().sort_by(SearchSort::OrganicScore).limit(20),
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn get_tokens_by_categories(
+        &self,
+        queries: &[(Category, Interval)],
+        limit: Option<u8>,
+        options: &SearchOptions,
+    ) -> Result<Vec<TokenInfo>, JupiterClientError> {
+        let lists =
+            try_join_all(queries.iter().map(|(category, interval)| {
+                self.get_tokens_by_category(*category, *interval, limit)
+            }))
+            .await?;
+
+        let mut seen = HashSet::new();
+        let tokens = lists
+            .into_iter()
+            .flatten()
+            .filter(|token| seen.insert(token.id.clone()))
+            .collect();
+
+        Ok(options.apply(tokens))
+    }
+
     /// Returns an vec of mints that recently had their first created pool
     /// Default to 30 mints in response
     pub async fn get_recent_tokens(&self) -> Result<Vec<TokenInfo>, JupiterClientError> {
         let url = format!("{}/tokens/v2/recent", self.base_url);
 
+        self.throttle().await;
         let response = match self.client.get(&url).send().await {
             Ok(resp) => resp,
             Err(e) => return Err(JupiterClientError::RequestError(e)),
@@ -168,6 +302,10 @@ impl JupiterClient {
 
     /// Returns prices of specified tokens.
     ///
+    /// Any number of mints can be passed: they are split into requests of [`MAX_PRICE_IDS`],
+    /// at most [`DEFAULT_PRICE_CONCURRENCY`] in flight, and the results are merged. Any
+    /// failing request fails the call.
+    ///
     /// ```
     /// let client = JupiterClient::new("https://lite-api.jup.ag");
     ///
@@ -182,9 +320,116 @@ impl JupiterClient {
     pub async fn get_tokens_price(
         &self,
         mints: &[String],
+    ) -> Result<HashMap<String, Price>, JupiterClientError> {
+        self.get_tokens_price_with_concurrency(mints, DEFAULT_PRICE_CONCURRENCY)
+            .await
+    }
+
+    /// Same as [`JupiterClient::get_tokens_price`], with at most `concurrency` requests in
+    /// flight.
+    pub async fn get_tokens_price_with_concurrency(
+        &self,
+        mints: &[String],
+        concurrency: usize,
+    ) -> Result<HashMap<String, Price>, JupiterClientError> {
+        stream::iter(mints.chunks(MAX_PRICE_IDS))
+            .map(|chunk| self.get_tokens_price_chunk(chunk))
+            .buffer_unordered(concurrency.max(1))
+            .try_fold(HashMap::new(), |mut prices, chunk| async move {
+                prices.extend(chunk);
+                Ok(prices)
+            })
+            .await
+    }
+
+    /// Returns prices of specified tokens denominated in `quote_mint` instead of USD.
+    ///
+    /// Price v3 only quotes USD, so both sides are fetched in USD and divided, the way
+    /// `vsToken` did in Price v2. Mints without a price are left out of the result.
+    ///
+    /// # Errors
+    ///
+    /// * `PriceUnavailable` if `quote_mint` has no usable price.
+    ///
+    /// ```
+    /// let mints = vec![String::from("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN")];
+    ///
+    /// // JUP priced in SOL
+    /// let prices = client
+    ///     .get_tokens_price_in(&mints, "So11111111111111111111111111111111111111112")
+    ///     .await?;
+    /// println!("1 JUP = {} SOL", prices[&mints[0]]);
+    /// ```
+    pub async fn get_tokens_price_in(
+        &self,
+        mints: &[String],
+        quote_mint: &str,
+    ) -> Result<HashMap<String, f64>, JupiterClientError> {
+        let prices = self.get_prices_denominated(mints, quote_mint).await?;
+        Ok(prices
+            .into_iter()
+            .map(|(mint, price)| (mint, price.price))
+            .collect())
+    }
+
+    /// Returns prices of specified tokens in terms of `denominator_mint`, with the decimals
+    /// of both sides to convert raw amounts.
+    ///
+    /// All mints are priced in USD in one batch, see [`JupiterClient::get_tokens_price`],
+    /// and divided client-side. Mints without a price are left out of the result.
+    ///
+    /// # Errors
+    ///
+    /// * `PriceUnavailable` if `denominator_mint` has no usable price.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // BONK priced in JUP
+    /// let prices = client.get_prices_denominated(&[BONK_MINT.to_string()], JUP_MINT).await?;
+    /// let bonk = &prices[BONK_MINT];
+    /// println!("1 BONK = {} JUP", bonk.price);
+    /// println!("1M BONK = {:?} raw JUP", bonk.convert_raw(100_000_000_000));
+    /// ```
+    pub async fn get_prices_denominated(
+        &self,
+        mints: &[String],
+        denominator_mint: &str,
+    ) -> Result<HashMap<String, DenominatedPrice>, JupiterClientError> {
+        let mut ids = mints.to_vec();
+        if !ids.iter().any(|mint| mint == denominator_mint) {
+            ids.push(denominator_mint.to_string());
+        }
+
+        let prices = self.get_tokens_price(&ids).await?;
+        let denominator = prices
+            .get(denominator_mint)
+            .filter(|price| price.usd_price.is_finite() && price.usd_price > 0.0)
+            .ok_or_else(|| JupiterClientError::PriceUnavailable(denominator_mint.to_string()))?;
+
+        Ok(mints
+            .iter()
+            .filter_map(|mint| {
+                let price = prices.get(mint)?;
+                let denominated = DenominatedPrice {
+                    denominator_mint: denominator_mint.to_string(),
+                    price: price.usd_price / denominator.usd_price,
+                    decimals: price.decimals,
+                    denominator_decimals: denominator.decimals,
+                };
+                Some((mint.clone(), denominated))
+            })
+            .collect())
+    }
+
+    /// A single `/price/v3` request, for at most [`MAX_PRICE_IDS`] mints.
+    async fn get_tokens_price_chunk(
+        &self,
+        mints: &[String],
     ) -> Result<HashMap<String, Price>, JupiterClientError> {
         let query_params = vec![("ids", mints.join(","))];
 
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/price/v3", self.base_url))
@@ -214,7 +459,7 @@ impl JupiterClient {
     ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN".to_string(),
     ///  ];
     /// let params = TokenPriceRequest::new(&token_mints)
-    ///     .with_vs_token("So11111111111111111111111111111111111111112"); // default is USD
+    ///     .vs_token("So11111111111111111111111111111111111111112"); // default is USD
     ///
     /// let price = client.get_token_price(&params).await
     ///     .expect("Failed to get token price");
@@ -233,6 +478,7 @@ impl JupiterClient {
         &self,
         params: &TokenPriceRequest,
     ) -> Result<TokenPriceResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/price/v2", self.base_url))
@@ -266,6 +512,7 @@ impl JupiterClient {
         mint_address: &str,
     ) -> Result<TokenInfoResponse, JupiterClientError> {
         let url = format!("{}/tokens/v1/token/{}", self.base_url, mint_address);
+        self.throttle().await;
         let response = match self.client.get(&url).send().await {
             Ok(resp) => resp,
             Err(e) => return Err(JupiterClientError::RequestError(e)),
@@ -289,6 +536,7 @@ impl JupiterClient {
             "{}/tokens/v1/market/{}/mints",
             self.base_url, market_address
         );
+        self.throttle().await;
         let response = match self.client.get(&url).send().await {
             Ok(resp) => resp,
             Err(e) => return Err(JupiterClientError::RequestError(e)),
@@ -307,17 +555,46 @@ impl JupiterClient {
     /// This endpoint returns greater than 32MB amount of data. May take a while to complete.
     pub async fn get_tradable_mints(&self) -> Result<Vec<String>, JupiterClientError> {
         let url = format!("{}/tokens/v1/mints/tradable", self.base_url);
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+        // too large to share the HTTP cache capacity, kept in an entry of its own
+        self.get_json_cached(self.client.get(&url), CacheSlot::Dedicated)
+            .await
+    }
 
-        let response = handle_response(response).await?;
+    /// Same as [`JupiterClient::get_tradable_mints`], yielding mints as the response is
+    /// downloaded instead of buffering it whole.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut mints = std::pin::pin!(client.get_tradable_mints_stream());
+    /// while let Some(mint) = mints.try_next().await? {
+    ///     println!("{mint}");
+    /// }
+    /// ```
+    pub fn get_tradable_mints_stream(
+        &self,
+    ) -> impl Stream<Item = Result<String, JupiterClientError>> + '_ {
+        json_array_stream(
+            self,
+            self.client
+                .get(format!("{}/tokens/v1/mints/tradable", self.base_url)),
+        )
+    }
 
-        match response.json::<Vec<String>>().await {
-            Ok(mints) => Ok(mints),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+    /// Writes the raw JSON response of [`JupiterClient::get_tradable_mints`] to `writer`
+    /// as it is downloaded, returning the number of bytes written.
+    #[cfg(feature = "runtime")]
+    pub async fn download_tradable_mints<W: AsyncWrite + Unpin>(
+        &self,
+        writer: W,
+    ) -> Result<u64, JupiterClientError> {
+        download_to(
+            self,
+            self.client
+                .get(format!("{}/tokens/v1/mints/tradable", self.base_url)),
+            writer,
+        )
+        .await
     }
 
     #[deprecated(note = "This fn is deprecated. Use `get_recent_tokens` instead.")]
@@ -338,6 +615,7 @@ impl JupiterClient {
                 url.push_str(&format!("?offset={}", o));
             }
         }
+        self.throttle().await;
         let response = match self.client.get(&url).send().await {
             Ok(resp) => resp,
             Err(e) => return Err(JupiterClientError::RequestError(e)),
@@ -357,17 +635,51 @@ impl JupiterClient {
     /// Please use carefully and intentionally, else utilize the other endpoints.
     pub async fn get_all_tokens(&self) -> Result<Vec<TokenInfoResponse>, JupiterClientError> {
         let url = format!("{}/tokens/v1/all", self.base_url);
+        // too large to share the HTTP cache capacity, kept in an entry of its own
+        self.get_json_cached(self.client.get(&url), CacheSlot::Dedicated)
+            .await
+    }
 
-        let response = match self.client.get(&url).send().await {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
-
-        let response = handle_response(response).await?;
+    /// Same as [`JupiterClient::get_all_tokens`], yielding tokens as the response is
+    /// downloaded so only one token is held in memory at a time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let verified = client
+    ///     .get_all_tokens_stream()
+    ///     .try_filter(|token| std::future::ready(token.tags.iter().flatten().any(|t| t == "verified")))
+    ///     .try_collect::<Vec<_>>()
+    ///     .await?;
+    /// ```
+    pub fn get_all_tokens_stream(
+        &self,
+    ) -> impl Stream<Item = Result<TokenInfoResponse, JupiterClientError>> + '_ {
+        json_array_stream(
+            self,
+            self.client.get(format!("{}/tokens/v1/all", self.base_url)),
+        )
+    }
 
-        match response.json::<Vec<TokenInfoResponse>>().await {
-            Ok(tokens) => Ok(tokens),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
-        }
+    /// Writes the raw JSON response of [`JupiterClient::get_all_tokens`] to `writer` as it
+    /// is downloaded, returning the number of bytes written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let file = tokio::io::BufWriter::new(tokio::fs::File::create("tokens.json").await?);
+    /// let bytes = client.download_all_tokens(file).await?;
+    /// ```
+    #[cfg(feature = "runtime")]
+    pub async fn download_all_tokens<W: AsyncWrite + Unpin>(
+        &self,
+        writer: W,
+    ) -> Result<u64, JupiterClientError> {
+        download_to(
+            self,
+            self.client.get(format!("{}/tokens/v1/all", self.base_url)),
+            writer,
+        )
+        .await
     }
 }
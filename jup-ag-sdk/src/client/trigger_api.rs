@@ -1,14 +1,69 @@
+use futures::{Stream, StreamExt, TryStreamExt, future::try_join_all, stream};
+
 use crate::{
     JupiterClientError,
     error::handle_response,
     types::{
         CancelTriggerOrder, CancelTriggerOrders, CreateTriggerOrder, ExecuteTriggerOrder,
-        ExecuteTriggerOrderResponse, GetTriggerOrders, OrderResponse, TriggerResponse,
+        ExecuteTriggerOrderResponse, GetTriggerOrders, OrderResponse, TriggerOrder,
+        TriggerOrderFilter, TriggerResponse,
     },
 };
 
 use super::JupiterClient;
 
+/// Maximum number of orders cancelled by one `cancelOrders` transaction.
+pub const MAX_TRIGGER_ORDERS_PER_CANCEL: usize = 5;
+#[cfg(feature = "solana")]
+use crate::rpc::SolanaRpc;
+#[cfg(feature = "solana")]
+use crate::signer::TransactionSigner;
+#[cfg(feature = "solana")]
+use crate::types::{OrderStatus, Params, PlacedTriggerOrder, TriggerOrderState};
+#[cfg(feature = "solana")]
+use std::collections::{HashMap, HashSet};
+
+/// Creation times of watched trigger orders, recorded while the orders are active.
+///
+/// A history lookup stops at the first order that closed before the oldest watched order
+/// was created: whether the history is sorted by creation or by close time, every order
+/// after it closed earlier still, so none of them is a watched order.
+#[cfg(feature = "solana")]
+#[derive(Debug, Default)]
+pub(crate) struct TriggerOrderAges(HashMap<String, String>);
+
+#[cfg(feature = "solana")]
+impl TriggerOrderAges {
+    /// Records when `order` was created.
+    pub(crate) fn record(&mut self, order: &TriggerOrder) {
+        self.0
+            .insert(order.order_key.clone(), order.created_at.clone());
+    }
+
+    /// The creation time of the oldest of `orders`, or `None` if one was never recorded.
+    fn oldest(&self, orders: &HashSet<String>) -> Option<&str> {
+        orders
+            .iter()
+            .map(|order| self.0.get(order).and_then(|at| whole_seconds(at)))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .min()
+    }
+}
+
+/// The `YYYY-MM-DDTHH:MM:SS` prefix of a UTC timestamp as the Trigger API returns it, which
+/// sorts like the time it stands for, or `None` for any other format.
+#[cfg(feature = "solana")]
+fn whole_seconds(timestamp: &str) -> Option<&str> {
+    let prefix = timestamp.get(..19)?;
+    let bytes = prefix.as_bytes();
+    let separators = [(4, b'-'), (7, b'-'), (10, b'T'), (13, b':'), (16, b':')];
+    separators
+        .iter()
+        .all(|&(index, separator)| bytes[index] == separator)
+        .then_some(prefix)
+}
+
 impl JupiterClient {
     /// Creates a new trigger order on Jupiter
     ///
@@ -34,7 +89,7 @@ impl JupiterClient {
     ///     1000000000, // 1 SOL (in lamports)
     ///     400000000,  // 400 JUP (in smallest unit)
     /// )
-    /// .slippage_bps("50") // 0.5% slippage
+    /// .slippage_bps(50) // 0.5% slippage
     /// .expired_at("1704067200"); // Unix timestamp
     ///
     /// let response = client.create_trigger_order(&create_order).await?;
@@ -44,6 +99,9 @@ impl JupiterClient {
         &self,
         data: &CreateTriggerOrder,
     ) -> Result<TriggerResponse, JupiterClientError> {
+        data.validate()?;
+
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/trigger/v1/createOrder", self.base_url))
@@ -63,6 +121,30 @@ impl JupiterClient {
         }
     }
 
+    /// Returns `created` if its transaction's blockhash is still valid, otherwise creates
+    /// the order again from `data`.
+    ///
+    /// Requires the `solana` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// let created = client.create_trigger_order(&create_order).await?;
+    /// // ... user reviews the order ...
+    /// let created = client.refresh_trigger_order(created, &create_order, &rpc).await?;
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn refresh_trigger_order(
+        &self,
+        created: TriggerResponse,
+        data: &CreateTriggerOrder,
+        rpc: &SolanaRpc,
+    ) -> Result<TriggerResponse, JupiterClientError> {
+        if rpc.is_transaction_fresh(&created.transaction).await? {
+            return Ok(created);
+        }
+        self.create_trigger_order(data).await
+    }
+
     /// Executes a trigger(create, cancel) order by submitting the signed transaction
     ///
     /// # Arguments
@@ -90,6 +172,7 @@ impl JupiterClient {
         &self,
         data: &ExecuteTriggerOrder,
     ) -> Result<ExecuteTriggerOrderResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/trigger/v1/execute", self.base_url))
@@ -116,7 +199,7 @@ impl JupiterClient {
     /// * `data` - `&CancelTriggerOrder` - Contains:
     ///   - `maker: String` - Maker wallet address
     ///   - `order: String` - Base-58 account which is the Trigger Order account
-    ///   - `compute_unit_price: Option<String>` - Priority fee in microlamports (optional)
+    ///   - `compute_unit_price: Option<ComputeUnitPrice>` - Priority fee, `auto` by default (optional)
     ///
     /// # Returns
     /// * `Result<TriggerResponse, JupiterClientError>` - Returns unsigned cancellation transaction to be signed and executed
@@ -137,6 +220,7 @@ impl JupiterClient {
         &self,
         data: &CancelTriggerOrder,
     ) -> Result<TriggerResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/trigger/v1/cancelOrder", self.base_url))
@@ -156,16 +240,21 @@ impl JupiterClient {
         }
     }
 
-    /// Cancels multiple trigger orders in a single transaction
+    /// Cancels multiple trigger orders
+    ///
+    /// Lists longer than [`MAX_TRIGGER_ORDERS_PER_CANCEL`] are split into one request per
+    /// chunk, sent concurrently. An empty list asks the API to cancel every open order of
+    /// the maker.
     ///
     /// # Arguments
     /// * `data` - `&CancelTriggerOrders` - Contains:
     ///   - `maker: String` - Maker wallet address
     ///   - `order: Vec<String>` - Vector of Base-58 trigger order account addresses
-    ///   - `compute_unit_price: Option<String>` - Priority fee in microlamports (optional)
+    ///   - `compute_unit_price: Option<ComputeUnitPrice>` - Priority fee, `auto` by default (optional)
     ///
     /// # Returns
-    /// * `Result<TriggerResponse, JupiterClientError>` - Returns unsigned batch cancellation transaction
+    /// * `Result<Vec<TriggerResponse>, JupiterClientError>` - One unsigned batch cancellation
+    ///   response per chunk, in the order of `data.order`
     ///
     /// # Example
     /// ```rust
@@ -178,16 +267,45 @@ impl JupiterClient {
     ///         "TriggerOrderAccount2...".to_string(),
     ///         "TriggerOrderAccount3...".to_string(),
     ///     ],
-    ///     compute_unit_price: Some("1000".to_string()), // 1000 microlamports
+    ///     compute_unit_price: Some(ComputeUnitPrice::MicroLamports(1000)),
     /// };
     ///
-    /// // Get unsigned batch cancellation transaction
-    /// let cancel_response = client.cancel_trigger_orders(&cancel_orders).await?;
+    /// // Get unsigned batch cancellation transactions
+    /// for batch in client.cancel_trigger_orders(&cancel_orders).await? {
+    ///     println!("{} {:?}", batch.request_id, batch.transactions);
+    /// }
     /// ```
     pub async fn cancel_trigger_orders(
         &self,
         data: &CancelTriggerOrders,
+    ) -> Result<Vec<TriggerResponse>, JupiterClientError> {
+        if data.order.len() <= MAX_TRIGGER_ORDERS_PER_CANCEL {
+            return Ok(vec![self.cancel_trigger_orders_chunk(data).await?]);
+        }
+
+        let chunks: Vec<CancelTriggerOrders> = data
+            .order
+            .chunks(MAX_TRIGGER_ORDERS_PER_CANCEL)
+            .map(|orders| CancelTriggerOrders {
+                maker: data.maker.clone(),
+                order: orders.to_vec(),
+                compute_unit_price: data.compute_unit_price,
+            })
+            .collect();
+
+        try_join_all(
+            chunks
+                .iter()
+                .map(|chunk| self.cancel_trigger_orders_chunk(chunk)),
+        )
+        .await
+    }
+
+    async fn cancel_trigger_orders_chunk(
+        &self,
+        data: &CancelTriggerOrders,
     ) -> Result<TriggerResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .post(format!("{}/trigger/v1/cancelOrders", self.base_url))
@@ -207,24 +325,107 @@ impl JupiterClient {
         }
     }
 
+    /// Builds cancellation transactions for every active order of `maker` matching `filter`
+    ///
+    /// Orders are fetched across all pages, then cancelled through
+    /// [`JupiterClient::cancel_trigger_orders`], one batch per
+    /// [`MAX_TRIGGER_ORDERS_PER_CANCEL`] orders.
+    ///
+    /// # Arguments
+    /// * `maker` - Maker wallet address
+    /// * `filter` - `&TriggerOrderFilter` - Restricts the cancelled orders by mint
+    ///
+    /// # Returns
+    /// * `Result<Vec<TriggerResponse>, JupiterClientError>` - One unsigned response per batch,
+    ///   empty if no order matches. Sign each transaction and execute it with the batch's
+    ///   `request_id`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let filter = TriggerOrderFilter::pair(SOL_MINT, USDC_MINT);
+    /// let batches = client.cancel_all_trigger_orders(maker, &filter).await?;
+    /// ```
+    pub async fn cancel_all_trigger_orders(
+        &self,
+        maker: &str,
+        filter: &TriggerOrderFilter,
+    ) -> Result<Vec<TriggerResponse>, JupiterClientError> {
+        let orders: Vec<String> = self
+            .get_trigger_orders_stream(&filter.active_orders(maker))
+            .map_ok(|order| order.order_key)
+            .try_collect()
+            .await?;
+
+        // an empty `cancelOrders` request cancels every order, ignoring the filter
+        if orders.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.cancel_trigger_orders(&CancelTriggerOrders::new(maker, orders))
+            .await
+    }
+
+    /// Same as [`JupiterClient::cancel_all_trigger_orders`], then signs and executes every
+    /// cancellation transaction. Requires the `solana` feature.
+    ///
+    /// Stops at the first failing transaction; batches executed before it stay cancelled.
+    ///
+    /// # Returns
+    /// * `Result<Vec<ExecuteTriggerOrderResponse>, JupiterClientError>` - One response per
+    ///   executed transaction
+    ///
+    /// # Example
+    /// ```rust
+    /// let executed = client
+    ///     .cancel_all_trigger_orders_signed(maker, &TriggerOrderFilter::default(), &keypair)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn cancel_all_trigger_orders_signed<S>(
+        &self,
+        maker: &str,
+        filter: &TriggerOrderFilter,
+        signer: &S,
+    ) -> Result<Vec<ExecuteTriggerOrderResponse>, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let batches = self.cancel_all_trigger_orders(maker, filter).await?;
+
+        let mut executed = Vec::new();
+        for batch in batches {
+            let transactions = match batch.transactions {
+                Some(transactions) if !transactions.is_empty() => transactions,
+                _ => vec![batch.transaction],
+            };
+            for transaction in transactions {
+                executed.push(
+                    self.sign_and_execute_trigger(&batch.request_id, &transaction, signer)
+                        .await?,
+                );
+            }
+        }
+        Ok(executed)
+    }
+
     /// Retrieves existing trigger orders for a user wallet
     ///
     /// # Arguments
     /// * `data` - `&GetTriggerOrders` - Query parameters containing:
     ///   - `user: String` - User wallet address to retrieve orders for
     ///   - `order_status: OrderStatus` - Filter by order status (Active or History)
-    ///   - `page: Option<String>` - Page number for pagination (default: 1)
-    ///   - `include_failed_tx: Option<String>` - Include failed transactions ("true"/"false")
+    ///   - `page: u64` - Page number for pagination (default: 1)
+    ///   - `include_failed_tx: bool` - Include failed transactions (default: false)
     ///   - `input_mint: Option<String>` - Filter by input token mint address
     ///   - `output_mint: Option<String>` - Filter by output token mint address
     ///
     /// # Returns
     /// * `Result<OrderResponse, JupiterClientError>` - Success returns OrderResponse with:
     ///   - `user: String` - User wallet address
-    ///   - `order_status: String` - Current order status filter
-    ///   - `orders: Vec<Order>` - List of trigger orders with detailed information
-    ///   - `total_pages: u32` - Total number of pages available
-    ///   - `page: u32` - Current page number
+    ///   - `order_status: OrderStatus` - Current order status filter
+    ///   - `orders: Vec<TriggerOrder>` - List of trigger orders with detailed information
+    ///   - `total_pages: u64` - Total number of pages available
+    ///   - `page: u64` - Current page number
     ///
     /// # Example
     /// ```rust
@@ -258,6 +459,7 @@ impl JupiterClient {
         &self,
         data: &GetTriggerOrders,
     ) -> Result<OrderResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/trigger/v1/getTriggerOrders", self.base_url))
@@ -276,4 +478,269 @@ impl JupiterClient {
             Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
         }
     }
+
+    /// Streams every trigger order matching `data`, fetching pages as they are consumed.
+    ///
+    /// Starts at `data.page` (default 1) and stops after the last page reported by the API.
+    /// A failed page request ends the stream with its error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use futures::TryStreamExt;
+    ///
+    /// let params = GetTriggerOrders::new("YourWalletAddress...", OrderStatus::History);
+    /// let mut orders = client.get_trigger_orders_stream(&params);
+    /// while let Some(order) = orders.try_next().await? {
+    ///     println!("{} {}", order.order_key, order.status);
+    /// }
+    /// ```
+    pub fn get_trigger_orders_stream(
+        &self,
+        data: &GetTriggerOrders,
+    ) -> impl Stream<Item = Result<TriggerOrder, JupiterClientError>> + '_ {
+        stream::try_unfold(
+            (data.clone(), Some(data.page.max(1))),
+            move |(params, page)| async move {
+                let Some(page) = page else {
+                    return Ok::<_, JupiterClientError>(None);
+                };
+
+                let response = self.get_trigger_orders(&params.clone().page(page)).await?;
+
+                let next_page = (!response.orders.is_empty() && page < response.total_pages)
+                    .then_some(page + 1);
+                Ok(Some((response.orders, (params, next_page))))
+            },
+        )
+        .map_ok(|orders| stream::iter(orders.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Creates a trigger order, signs it and executes it. Requires the `solana` feature.
+    ///
+    /// # Arguments
+    /// * `data` - `&CreateTriggerOrder` - The order to place, with `maker` matching the signer
+    /// * `signer` - Any [`TransactionSigner`] for the maker, e.g. a `Keypair`
+    ///
+    /// # Returns
+    /// * `Ok(PlacedTriggerOrder)` - The order account and creation signature
+    /// * `Err(JupiterClientError::SigningError)` - If the signer is not a required signer
+    /// * `Err(JupiterClientError::TransactionFailed)` - If the execute status is not `Success`
+    ///
+    /// # Example
+    /// ```rust
+    /// let order = CreateTriggerOrder::from_price(SOL_MINT, USDC_MINT, maker, 1_000_000_000, 200.0, 9, 6)?;
+    /// let placed = client.create_and_execute_trigger_order(&order, &keypair).await?;
+    /// println!("order account: {}", placed.order);
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn create_and_execute_trigger_order<S>(
+        &self,
+        data: &CreateTriggerOrder,
+        signer: &S,
+    ) -> Result<PlacedTriggerOrder, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let created = self.create_trigger_order(data).await?;
+        let Some(order) = created.order.filter(|order| !order.is_empty()) else {
+            return Err(JupiterClientError::InvalidRequest(
+                "trigger order response has no order account".to_string(),
+            ));
+        };
+
+        let executed = self
+            .sign_and_execute_trigger(&created.request_id, &created.transaction, signer)
+            .await?;
+
+        Ok(PlacedTriggerOrder {
+            order,
+            signature: executed.signature,
+        })
+    }
+
+    /// Cancels a trigger order, signing and executing the cancellation. Requires the
+    /// `solana` feature.
+    ///
+    /// # Arguments
+    /// * `data` - `&CancelTriggerOrder` - The order to cancel, with `maker` matching the signer
+    /// * `signer` - Any [`TransactionSigner`] for the maker
+    ///
+    /// # Returns
+    /// * `Ok(ExecuteTriggerOrderResponse)` - The executed cancellation
+    /// * `Err(JupiterClientError::SigningError)` - If the signer is not a required signer
+    /// * `Err(JupiterClientError::TransactionFailed)` - If the execute status is not `Success`
+    ///
+    /// # Example
+    /// ```rust
+    /// let cancel = CancelTriggerOrder::new(maker, &placed.order);
+    /// let executed = client.cancel_and_execute_trigger_order(&cancel, &keypair).await?;
+    /// println!("cancelled in {}", executed.signature);
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn cancel_and_execute_trigger_order<S>(
+        &self,
+        data: &CancelTriggerOrder,
+        signer: &S,
+    ) -> Result<ExecuteTriggerOrderResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let cancel = self.cancel_trigger_order(data).await?;
+        self.sign_and_execute_trigger(&cancel.request_id, &cancel.transaction, signer)
+            .await
+    }
+
+    /// Replaces an open trigger order with one selling the same pair at new amounts.
+    /// Requires the `solana` feature.
+    ///
+    /// The Trigger API cannot edit an order, so `old_order` is cancelled first, returning its
+    /// remaining input to the maker, and a new order is then created from `params` for the
+    /// same maker and mints.
+    ///
+    /// # Arguments
+    /// * `old_order` - `&TriggerOrder` - The open order, as returned by `get_trigger_orders`
+    /// * `params` - `Params` - Amounts, expiry and slippage of the replacement order
+    /// * `signer` - Any [`TransactionSigner`] for the maker
+    ///
+    /// # Returns
+    /// * `Ok(PlacedTriggerOrder)` - The replacement order account and creation signature
+    /// * `Err(JupiterClientError::ValidationError)` - If the replacement order is invalid,
+    ///   e.g. a zero amount or `fee_bps` without a fee account; nothing is cancelled
+    /// * `Err(JupiterClientError::ReplacementFailed)` - If the old order was cancelled but
+    ///   the replacement could not be placed; the maker holds the released funds and no
+    ///   order is open
+    /// * Any other error - The cancellation failed and the old order is left untouched
+    ///
+    /// # Example
+    /// ```rust
+    /// // move a 1 SOL limit order to 210 USDC
+    /// let placed = client
+    ///     .amend_trigger_order(&order, Params::new(1_000_000_000, 210_000_000), &keypair)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn amend_trigger_order<S>(
+        &self,
+        old_order: &TriggerOrder,
+        params: Params,
+        signer: &S,
+    ) -> Result<PlacedTriggerOrder, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let maker = &old_order.user_pubkey;
+        let replacement = CreateTriggerOrder {
+            input_mint: old_order.input_mint.clone(),
+            output_mint: old_order.output_mint.clone(),
+            maker: maker.clone(),
+            payer: maker.clone(),
+            params,
+            compute_unit_price: None,
+            fee_account: None,
+            wrap_and_unwrap_sol: None,
+        };
+        // checked before cancelling, so an invalid replacement leaves the old order open
+        replacement.validate()?;
+
+        let cancelled = self
+            .cancel_and_execute_trigger_order(
+                &CancelTriggerOrder::new(maker, &old_order.order_key),
+                signer,
+            )
+            .await?;
+
+        self.create_and_execute_trigger_order(&replacement, signer)
+            .await
+            .map_err(|e| JupiterClientError::ReplacementFailed {
+                order: old_order.order_key.clone(),
+                cancel_signature: cancelled.signature,
+                source: Box::new(e),
+            })
+    }
+
+    /// Signs a Trigger API transaction and executes it, failing unless the status is `Success`.
+    #[cfg(feature = "solana")]
+    pub(crate) async fn sign_and_execute_trigger<S>(
+        &self,
+        request_id: &str,
+        transaction: &str,
+        signer: &S,
+    ) -> Result<ExecuteTriggerOrderResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let executed = self
+            .sign_and_execute_parts::<TriggerResponse, S>(request_id, transaction, signer)
+            .await?;
+
+        if !executed.status.eq_ignore_ascii_case("success") {
+            return Err(JupiterClientError::TransactionFailed {
+                signature: executed.signature,
+                reason: format!("trigger execute returned status {}", executed.status),
+            });
+        }
+        Ok(executed)
+    }
+}
+
+#[cfg(feature = "solana")]
+impl JupiterClient {
+    /// Returns the orders of `watched` that are no longer among `maker`'s active orders,
+    /// reading the active orders only until every watched one was found.
+    ///
+    /// The creation time of every watched order found is recorded in `ages`.
+    pub(crate) async fn inactive_trigger_orders(
+        &self,
+        maker: &str,
+        watched: impl IntoIterator<Item = &str>,
+        ages: &mut TriggerOrderAges,
+    ) -> Result<HashSet<String>, JupiterClientError> {
+        let mut inactive: HashSet<String> = watched.into_iter().map(str::to_string).collect();
+        let params = GetTriggerOrders::new(maker, OrderStatus::Active);
+        let mut stream = std::pin::pin!(self.get_trigger_orders_stream(&params));
+        while !inactive.is_empty() {
+            let Some(order) = stream.try_next().await? else {
+                break;
+            };
+            if inactive.remove(&order.order_key) {
+                ages.record(&order);
+            }
+        }
+        Ok(inactive)
+    }
+
+    /// Looks up the statuses of `orders` in `maker`'s order history.
+    ///
+    /// The history is read until every order was found or, if `ages` holds the creation
+    /// time of all of them, until an order that closed before the oldest one was created.
+    /// Orders not in the history yet are left out of the result; the ones found are
+    /// forgotten by `ages`.
+    pub(crate) async fn closed_trigger_orders(
+        &self,
+        maker: &str,
+        orders: &HashSet<String>,
+        ages: &mut TriggerOrderAges,
+    ) -> Result<HashMap<String, TriggerOrderState>, JupiterClientError> {
+        let oldest = ages.oldest(orders).map(str::to_string);
+        let mut closed = HashMap::new();
+        let params = GetTriggerOrders::new(maker, OrderStatus::History);
+        let mut stream = std::pin::pin!(self.get_trigger_orders_stream(&params));
+        while closed.len() < orders.len() {
+            let Some(order) = stream.try_next().await? else {
+                break;
+            };
+            if orders.contains(&order.order_key) {
+                ages.0.remove(&order.order_key);
+                closed.insert(order.order_key, order.status);
+            } else if whole_seconds(&order.updated_at)
+                .zip(oldest.as_deref())
+                .is_some_and(|(closed_at, oldest)| closed_at < oldest)
+            {
+                break;
+            }
+        }
+        Ok(closed)
+    }
 }
@@ -1,12 +1,45 @@
+use std::{collections::HashMap, time::Instant};
+
+use futures::{
+    StreamExt,
+    future::{try_join, try_join_all},
+};
+
 use crate::{
     error::{JupiterClientError, handle_response},
+    http_cache::CacheSlot,
     types::{
-        Router, Shield, TokenBalancesResponse, TokenInfo, UltraExecuteOrderRequest,
-        UltraExecuteOrderResponse, UltraOrderRequest, UltraOrderResponse,
+        NATIVE_SOL_KEY, PolicyDecision, PreTrade, Router, SearchOptions, Shield,
+        TokenBalancesResponse, TokenInfo, TokenPolicy, UltraExecuteOrderRequest,
+        UltraExecuteOrderResponse, UltraOrderRequest, UltraOrderResponse, ValuedBalances,
+        WRAPPED_SOL_MINT,
     },
 };
 
 use super::JupiterClient;
+#[cfg(feature = "solana")]
+use crate::rpc::SolanaRpc;
+#[cfg(feature = "solana")]
+use crate::signer::TransactionSigner;
+
+/// Returns the IDs in `exclude_routers` that match no router, compared case-insensitively.
+pub(crate) fn unknown_routers(routers: &[Router], exclude_routers: &[String]) -> Vec<String> {
+    exclude_routers
+        .iter()
+        .filter(|id| {
+            !routers
+                .iter()
+                .any(|router| router.id.eq_ignore_ascii_case(id))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Number of wallets [`JupiterClient::get_token_balances_many`] fetches at a time.
+pub const DEFAULT_BALANCES_CONCURRENCY: usize = 8;
+
+/// Maximum number of mints accepted by a single `/ultra/v1/shield` request.
+pub const MAX_SHIELD_MINTS: usize = 100;
 
 impl JupiterClient {
     /// Fetches a swap order from Jupiter's Ultra API based on the provided parameters.
@@ -18,6 +51,7 @@ impl JupiterClient {
     /// # Returns
     ///
     /// * `Ok(UltraOrderResponse)` on success.
+    /// * `Err(JupiterClientError::ValidationError)` if [`UltraOrderRequest::validate`] fails.
     /// * `Err` if the request fails or response can't be deserialized.
     ///
     /// # Jupiter API Reference
@@ -34,6 +68,9 @@ impl JupiterClient {
         &self,
         params: &UltraOrderRequest,
     ) -> Result<UltraOrderResponse, JupiterClientError> {
+        params.validate()?;
+
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/ultra/v1/order", self.base_url))
@@ -53,6 +90,56 @@ impl JupiterClient {
         }
     }
 
+    /// Runs the standard pre-trade checks for an Ultra swap concurrently.
+    ///
+    /// Fetches the order, Shield warnings for both mints and their current prices in
+    /// parallel, failing if any request fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_mint` - The mint to sell.
+    /// * `output_mint` - The mint to buy.
+    /// * `amount` - The raw input amount.
+    /// * `taker` - The wallet placing the order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PreTrade)` with the order, warnings and prices.
+    /// * `Err` if any request or deserialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pretrade = api.ultra_pretrade(SOL_MINT, JUP_MINT, 1_000_000_000, wallet).await?;
+    /// if pretrade.has_critical_warnings() {
+    ///     return Err("token flagged by shield".into());
+    /// }
+    /// ```
+    pub async fn ultra_pretrade(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        taker: &str,
+    ) -> Result<PreTrade, JupiterClientError> {
+        let request = UltraOrderRequest::new(input_mint, output_mint, amount).taker(taker);
+        let mints = [input_mint.to_string(), output_mint.to_string()];
+
+        let (order, mut shield, mut prices) = futures::try_join!(
+            self.get_ultra_order(&request),
+            self.shield(&mints),
+            self.get_tokens_price(&mints),
+        )?;
+
+        Ok(PreTrade {
+            order,
+            input_warnings: shield.warnings.remove(input_mint).unwrap_or_default(),
+            output_warnings: shield.warnings.remove(output_mint).unwrap_or_default(),
+            input_price: prices.remove(input_mint),
+            output_price: prices.remove(output_mint),
+        })
+    }
+
     /// Executes a signed swap order using Jupiter's Ultra API.
     ///
     /// # Arguments
@@ -62,6 +149,8 @@ impl JupiterClient {
     /// # Returns
     ///
     /// * `Ok(UltraExecuteOrderResponse)` on success.
+    /// * `Err(JupiterClientError::DuplicateSubmission)` if this `request_id` was already
+    ///   sent by this client, see [`JupiterClient::with_execute_guard`].
     /// * `Err` if the request or deserialization fails.
     ///
     /// # Jupiter API Reference
@@ -78,23 +167,59 @@ impl JupiterClient {
         &self,
         data: &UltraExecuteOrderRequest,
     ) -> Result<UltraExecuteOrderResponse, JupiterClientError> {
-        let response = match self
-            .client
-            .post(format!("{}/ultra/v1/execute", self.base_url))
-            .json(&data)
-            .send()
-            .await
+        // reserved before sending, so concurrent or retried calls see it
+        if !self
+            .submitted_orders
+            .lock()
+            .unwrap()
+            .insert(&data.request_id)
         {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
-        };
+            return Err(JupiterClientError::DuplicateSubmission(
+                data.request_id.clone(),
+            ));
+        }
 
-        let response = handle_response(response).await?;
+        let result = async {
+            self.throttle().await;
+            let response = match self
+                .client
+                .post(format!("{}/ultra/v1/execute", self.base_url))
+                .json(&data)
+                .send()
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => return Err(JupiterClientError::RequestError(e)),
+            };
 
-        match response.json::<UltraExecuteOrderResponse>().await {
-            Ok(swap_response) => Ok(swap_response),
-            Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+            let response = handle_response(response).await?;
+
+            match response.json::<UltraExecuteOrderResponse>().await {
+                Ok(swap_response) => Ok(swap_response),
+                Err(e) => Err(JupiterClientError::DeserializationError(e.to_string())),
+            }
+        }
+        .await;
+
+        if result.as_ref().is_err_and(never_submitted) {
+            self.submitted_orders
+                .lock()
+                .unwrap()
+                .remove(&data.request_id);
         }
+        result
+    }
+
+    /// Allows a `request_id` to be sent to [`JupiterClient::ultra_execute_order`] again.
+    ///
+    /// Only useful with [`JupiterClient::with_execute_guard`], e.g. to poll the status of
+    /// an order already executed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the `request_id` had been submitted.
+    pub fn forget_ultra_submission(&self, request_id: &str) -> bool {
+        self.submitted_orders.lock().unwrap().remove(request_id)
     }
 
     /// Fetches token balances for a given wallet address using Jupiter's Ultra API.
@@ -123,6 +248,7 @@ impl JupiterClient {
         &self,
         address: &str,
     ) -> Result<TokenBalancesResponse, JupiterClientError> {
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/ultra/v1/balances/{}", self.base_url, address))
@@ -141,9 +267,110 @@ impl JupiterClient {
         }
     }
 
+    /// Fetches token balances for many wallets, at most [`DEFAULT_BALANCES_CONCURRENCY`] at a
+    /// time.
+    ///
+    /// A failing wallet does not abort the others; its error is returned under its address.
+    /// Duplicate addresses are fetched once.
+    ///
+    /// # Arguments
+    ///
+    /// * `addresses` - The wallet addresses to fetch token balances for.
+    ///
+    /// # Returns
+    ///
+    /// A map from wallet address to its balances or the error fetching them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let wallets = vec![treasury.to_string(), ops.to_string()];
+    /// for (wallet, balances) in api.get_token_balances_many(&wallets).await {
+    ///     match balances {
+    ///         Ok(balances) => println!("{wallet}: {:?}", balances.get("SOL")),
+    ///         Err(e) => eprintln!("{wallet}: {e}"),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_token_balances_many(
+        &self,
+        addresses: &[String],
+    ) -> HashMap<String, Result<TokenBalancesResponse, JupiterClientError>> {
+        self.get_token_balances_many_with_concurrency(addresses, DEFAULT_BALANCES_CONCURRENCY)
+            .await
+    }
+
+    /// Same as [`JupiterClient::get_token_balances_many`] with an explicit concurrency limit.
+    ///
+    /// A `concurrency` of `0` is treated as `1`.
+    pub async fn get_token_balances_many_with_concurrency(
+        &self,
+        addresses: &[String],
+        concurrency: usize,
+    ) -> HashMap<String, Result<TokenBalancesResponse, JupiterClientError>> {
+        let mut unique: Vec<&String> = addresses.iter().collect();
+        unique.sort();
+        unique.dedup();
+
+        futures::stream::iter(unique.into_iter().map(|address| async move {
+            (address.clone(), self.get_token_balances(address).await)
+        }))
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await
+    }
+
+    /// Fetches a wallet's token balances and values them in USD.
+    ///
+    /// Joins [`JupiterClient::get_token_balances`] with [`JupiterClient::get_tokens_price`].
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The wallet address to fetch token balances for.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ValuedBalances)` with per-token and total USD values.
+    /// * `Err` if any request or deserialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let portfolio = api.get_balances_valued("3X2LFoTQecbpqCR7G5tL1kczqBKurjKPHhKSZrJ4wgWc").await?;
+    /// println!("total: ${:.2}", portfolio.total_usd_value);
+    /// for token in portfolio.tokens {
+    ///     println!("{} {} {:?}", token.mint, token.balance.ui_amount, token.usd_value);
+    /// }
+    /// ```
+    pub async fn get_balances_valued(
+        &self,
+        address: &str,
+    ) -> Result<ValuedBalances, JupiterClientError> {
+        let balances = self.get_token_balances(address).await?;
+
+        let mut mints: Vec<String> = balances
+            .keys()
+            .map(|mint| {
+                if mint == NATIVE_SOL_KEY {
+                    WRAPPED_SOL_MINT.to_string()
+                } else {
+                    mint.clone()
+                }
+            })
+            .collect();
+        mints.sort();
+        mints.dedup();
+
+        let prices = self.get_tokens_price(&mints).await?;
+
+        Ok(ValuedBalances::from_prices(balances, &prices))
+    }
+
     /// Fetches token safety information for given mints using Jupiter's Ultra Shield API.
     ///
     /// This is useful for identifying malicious or suspicious tokens before executing a swap.
+    /// Lists longer than [`MAX_SHIELD_MINTS`] are split into concurrent requests and the
+    /// warnings merged.
     ///
     /// # Arguments
     ///
@@ -169,8 +396,65 @@ impl JupiterClient {
     /// println!("{:#?}", shield_info);
     /// ```
     pub async fn shield(&self, mints: &[String]) -> Result<Shield, JupiterClientError> {
+        if mints.len() <= MAX_SHIELD_MINTS {
+            return self.shield_chunk(mints).await;
+        }
+
+        let chunks = try_join_all(
+            mints
+                .chunks(MAX_SHIELD_MINTS)
+                .map(|chunk| self.shield_chunk(chunk)),
+        )
+        .await?;
+
+        Ok(Shield {
+            warnings: chunks
+                .into_iter()
+                .flat_map(|shield| shield.warnings)
+                .collect(),
+        })
+    }
+
+    /// Evaluates mints against a token-safety [`TokenPolicy`].
+    ///
+    /// Fetches [`JupiterClient::shield`] warnings and [`JupiterClient::get_tokens_info`]
+    /// concurrently and returns one decision per mint, keyed by mint address.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let decisions = client
+    ///     .evaluate_token_policy(&mints, &TokenPolicy::default())
+    ///     .await?;
+    /// if decisions[&output_mint].is_denied() {
+    ///     return Err("output token blocked by policy".into());
+    /// }
+    /// ```
+    pub async fn evaluate_token_policy(
+        &self,
+        mints: &[String],
+        policy: &TokenPolicy,
+    ) -> Result<HashMap<String, PolicyDecision>, JupiterClientError> {
+        let (shield, tokens) = try_join(self.shield(mints), self.get_tokens_info(mints)).await?;
+
+        Ok(mints
+            .iter()
+            .map(|mint| {
+                let warnings = shield
+                    .warnings
+                    .get(mint)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let decision = policy.evaluate(mint, tokens.get(mint), warnings);
+                (mint.clone(), decision)
+            })
+            .collect())
+    }
+
+    async fn shield_chunk(&self, mints: &[String]) -> Result<Shield, JupiterClientError> {
         let query_params = vec![("mints", mints.join(","))];
 
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/ultra/v1/shield", self.base_url))
@@ -222,6 +506,7 @@ impl JupiterClient {
     ) -> Result<Vec<TokenInfo>, JupiterClientError> {
         let query_params = vec![("query", mints.join(","))];
 
+        self.throttle().await;
         let response = match self
             .client
             .get(format!("{}/ultra/v1/search", self.base_url))
@@ -241,23 +526,238 @@ impl JupiterClient {
         }
     }
 
+    /// Same as [`JupiterClient::ultra_token_search`], with the results filtered by `options`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let options = SearchOptions::default().limit(3).verified_only(true);
+    /// let tokens = client
+    ///     .ultra_token_search_with_options(&["USDC".to_string()], &options)
+    ///     .await?;
+    /// ```
+    pub async fn ultra_token_search_with_options(
+        &self,
+        mints: &[String],
+        options: &SearchOptions,
+    ) -> Result<Vec<TokenInfo>, JupiterClientError> {
+        let tokens = self.ultra_token_search(mints).await?;
+        Ok(options.apply(tokens))
+    }
+
     /// Request for the list of routers available in the routing engine of Ultra, which is Juno
+    ///
+    /// The list is cached in the client for [`JupiterClient::routers_cache_ttl`]; use
+    /// [`JupiterClient::refresh_routers`] to bypass the cache.
     pub async fn routers(&self) -> Result<Vec<Router>, JupiterClientError> {
-        let response = match self
-            .client
-            .get(format!("{}/ultra/v1/order/routers", self.base_url))
-            .send()
-            .await
+        if let Some((fetched_at, routers)) = self.routers_cache.read().unwrap().as_ref()
+            && fetched_at.elapsed() < self.routers_cache_ttl
         {
-            Ok(resp) => resp,
-            Err(e) => return Err(JupiterClientError::RequestError(e)),
+            return Ok(routers.clone());
+        }
+
+        self.refresh_routers().await
+    }
+
+    /// Fetches the list of Ultra routers and replaces the cached copy.
+    ///
+    /// With [`JupiterClient::with_http_cache`], the fetch is a conditional request.
+    pub async fn refresh_routers(&self) -> Result<Vec<Router>, JupiterClientError> {
+        let routers: Vec<Router> = self
+            .get_json_cached(
+                self.client
+                    .get(format!("{}/ultra/v1/order/routers", self.base_url)),
+                CacheSlot::Shared,
+            )
+            .await?;
+
+        *self.routers_cache.write().unwrap() = Some((Instant::now(), routers.clone()));
+        Ok(routers)
+    }
+
+    /// Checks router IDs meant for [`UltraOrderRequest::exclude_routers`] against the live
+    /// router set.
+    ///
+    /// Ultra silently ignores exclusions it does not recognise, so a typo leaves the router
+    /// enabled. Call this before placing the order to catch that.
+    ///
+    /// # Arguments
+    ///
+    /// * `exclude_routers` - Router IDs to exclude, e.g. `["okx", "pyth"]`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The IDs that do not match any router, empty if all are known.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let excluded = vec!["metis".to_string(), "jupitr-z".to_string()];
+    /// let unknown = client.validate_exclude_routers(&excluded).await?;
+    /// if !unknown.is_empty() {
+    ///     eprintln!("unknown routers: {unknown:?}");
+    /// }
+    /// ```
+    pub async fn validate_exclude_routers(
+        &self,
+        exclude_routers: &[String],
+    ) -> Result<Vec<String>, JupiterClientError> {
+        let routers = self.routers().await?;
+        Ok(unknown_routers(&routers, exclude_routers))
+    }
+
+    /// Returns `order` if its transaction's blockhash is still valid, otherwise fetches a new
+    /// order from `params`.
+    ///
+    /// Orders without a transaction are returned as is. The new order has its own
+    /// `request_id`. Requires the `solana` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let order = api.get_ultra_order(&req).await?;
+    /// // ... user reviews the order ...
+    /// let order = api.refresh_ultra_order(order, &req, &rpc).await?;
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn refresh_ultra_order(
+        &self,
+        order: UltraOrderResponse,
+        params: &UltraOrderRequest,
+        rpc: &SolanaRpc,
+    ) -> Result<UltraOrderResponse, JupiterClientError> {
+        let Some(transaction) = order.transaction.as_deref().filter(|tx| !tx.is_empty()) else {
+            return Ok(order);
         };
+        if rpc.is_transaction_fresh(transaction).await? {
+            return Ok(order);
+        }
+        self.get_ultra_order(params).await
+    }
 
-        let response = handle_response(response).await?;
+    /// Fetches an Ultra order, signs its transaction and executes it.
+    ///
+    /// The order request must have a `taker` matching the signer, otherwise Ultra returns no
+    /// transaction. For gasless orders only the taker's signature slot is filled, the
+    /// sponsor's fee payer slot is left untouched. Requires the `solana` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - An [`UltraOrderRequest`] with a taker set.
+    /// * `signer` - Any [`TransactionSigner`] for the taker, e.g. a `Keypair`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(UltraExecuteOrderResponse)` once executed; check `status` for the on-chain outcome.
+    /// * `Err(JupiterClientError::InvalidRequest)` if the order has no transaction.
+    /// * `Err(JupiterClientError::SigningError)` if the signer is not a required signer.
+    /// * `Err(JupiterClientError::PolicyViolation)` if the transaction breaks the client's
+    ///   [`TransactionPolicy`](crate::introspection::TransactionPolicy), e.g. invokes a
+    ///   program outside the allowlist.
+    /// * `Err` if any request or deserialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let api = api.with_transaction_policy(TransactionPolicy::ultra());
+    /// let req = UltraOrderRequest::new(SOL_MINT, USDC_MINT, 1_000_000_000)
+    ///     .taker(&keypair.pubkey().to_string());
+    /// let res = api.ultra_swap(&req, &keypair).await?;
+    /// println!("{:?} {:?}", res.status, res.signature);
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn ultra_swap<S>(
+        &self,
+        params: &UltraOrderRequest,
+        signer: &S,
+    ) -> Result<UltraExecuteOrderResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        self.sign_and_execute_ultra_order(params, signer).await
+    }
 
-        response
-            .json::<Vec<Router>>()
-            .await
-            .map_err(|e| JupiterClientError::DeserializationError(e.to_string()))
+    /// Same as [`JupiterClient::ultra_swap`], but fetches and signs a fresh order when the
+    /// previous one expired before landing.
+    ///
+    /// An attempt is retried only when [`UltraExecuteOrderResponse::is_expired`] is `true` or
+    /// `/execute` rejected the request as expired; any other outcome is returned as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - An [`UltraOrderRequest`] with a taker set, reused for every attempt.
+    /// * `signer` - Any [`TransactionSigner`] for the taker.
+    /// * `max_attempts` - Total number of orders to try, `0` is treated as `1`.
+    ///
+    /// # Returns
+    ///
+    /// The response of the last attempt, or its error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let res = api.ultra_swap_with_retry(&req, &keypair, 3).await?;
+    /// if res.is_expired() {
+    ///     eprintln!("order still expired after 3 attempts");
+    /// }
+    /// ```
+    #[cfg(feature = "solana")]
+    pub async fn ultra_swap_with_retry<S>(
+        &self,
+        params: &UltraOrderRequest,
+        signer: &S,
+        max_attempts: u32,
+    ) -> Result<UltraExecuteOrderResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let max_attempts = max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let result = self.sign_and_execute_ultra_order(params, signer).await;
+
+            let expired = match &result {
+                Ok(response) => response.is_expired(),
+                Err(JupiterClientError::ApiError(message, _)) => {
+                    message.to_ascii_lowercase().contains("expired")
+                }
+                Err(_) => false,
+            };
+
+            if !expired || attempt >= max_attempts {
+                return result;
+            }
+            attempt += 1;
+        }
+    }
+
+    #[cfg(feature = "solana")]
+    async fn sign_and_execute_ultra_order<S>(
+        &self,
+        params: &UltraOrderRequest,
+        signer: &S,
+    ) -> Result<UltraExecuteOrderResponse, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let order = self.get_ultra_order(params).await?;
+
+        if order.transaction.as_deref().is_none_or(str::is_empty) {
+            return Err(JupiterClientError::InvalidRequest(
+                "ultra order has no transaction, set a taker on the request".to_string(),
+            ));
+        }
+
+        self.sign_and_execute(&order, signer).await
+    }
+}
+
+/// Returns `true` if `error` proves the execute request never reached Jupiter: it could not
+/// be built or connected, or Jupiter rejected it with a 4xx.
+fn never_submitted(error: &JupiterClientError) -> bool {
+    match error {
+        JupiterClientError::RequestError(e) => e.is_builder() || e.is_connect(),
+        JupiterClientError::ApiError(_, status) => status.is_client_error(),
+        _ => false,
     }
 }
@@ -0,0 +1,61 @@
+//! Transaction encoding helpers.
+//!
+//! The Ultra, Trigger, Recurring and Swap APIs return base64 transactions, while wallets and
+//! some RPC methods use base58. These helpers convert between both encodings and
+//! [`VersionedTransaction`]. Requires the `solana` feature.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+pub use solana_transaction::versioned::VersionedTransaction;
+
+use crate::error::JupiterClientError;
+
+/// Largest serialized transaction a Solana validator accepts, in bytes: the 1280 byte IPv6
+/// MTU minus the IP and UDP headers.
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Decodes a base64-encoded `VersionedTransaction`.
+pub fn decode_base64(transaction: &str) -> Result<VersionedTransaction, JupiterClientError> {
+    let bytes = STANDARD
+        .decode(transaction.trim())
+        .map_err(|e| JupiterClientError::DeserializationError(format!("invalid base64: {e}")))?;
+    deserialize(&bytes)
+}
+
+/// Decodes a base58-encoded `VersionedTransaction`.
+pub fn decode_base58(transaction: &str) -> Result<VersionedTransaction, JupiterClientError> {
+    let bytes = bs58::decode(transaction.trim())
+        .into_vec()
+        .map_err(|e| JupiterClientError::DeserializationError(format!("invalid base58: {e}")))?;
+    deserialize(&bytes)
+}
+
+/// Encodes a transaction as base64, the encoding the Jupiter execute endpoints expect.
+pub fn encode_base64(transaction: &VersionedTransaction) -> Result<String, JupiterClientError> {
+    serialize(transaction).map(|bytes| STANDARD.encode(bytes))
+}
+
+/// Encodes a transaction as base58.
+pub fn encode_base58(transaction: &VersionedTransaction) -> Result<String, JupiterClientError> {
+    serialize(transaction).map(|bytes| bs58::encode(bytes).into_string())
+}
+
+/// Re-encodes a base64 transaction as base58, checking that it decodes.
+pub fn base64_to_base58(transaction: &str) -> Result<String, JupiterClientError> {
+    encode_base58(&decode_base64(transaction)?)
+}
+
+/// Re-encodes a base58 transaction as base64, checking that it decodes.
+pub fn base58_to_base64(transaction: &str) -> Result<String, JupiterClientError> {
+    encode_base64(&decode_base58(transaction)?)
+}
+
+fn deserialize(bytes: &[u8]) -> Result<VersionedTransaction, JupiterClientError> {
+    bincode::deserialize(bytes)
+        .map_err(|e| JupiterClientError::DeserializationError(format!("invalid transaction: {e}")))
+}
+
+pub(crate) fn serialize(transaction: &VersionedTransaction) -> Result<Vec<u8>, JupiterClientError> {
+    bincode::serialize(transaction)
+        .map_err(|e| JupiterClientError::DeserializationError(e.to_string()))
+}
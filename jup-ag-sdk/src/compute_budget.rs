@@ -0,0 +1,231 @@
+//! Compute budget overrides for unsigned transactions.
+//!
+//! Ultra, Trigger and Recurring transactions come with the compute unit limit and price
+//! chosen by Jupiter. [`ComputeBudgetOverride`] rewrites them, or inserts the instructions
+//! if missing, so callers can apply their own priority fee strategy before signing.
+//! Requires the `solana` feature.
+
+use std::str::FromStr;
+
+use solana_message::{MessageHeader, VersionedMessage, compiled_instruction::CompiledInstruction};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+
+use crate::{
+    codec::{self, MAX_TRANSACTION_SIZE, VersionedTransaction},
+    error::JupiterClientError,
+    introspection::COMPUTE_BUDGET_PROGRAM_ID,
+};
+
+/// `SetComputeUnitLimit` instruction discriminator.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+/// `SetComputeUnitPrice` instruction discriminator.
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// Compute unit limit and price to set on a transaction before signing.
+///
+/// Unset fields keep the transaction's own instruction, if any.
+///
+/// # Example
+///
+/// ```
+/// let mut order = api.get_ultra_order(&req).await?;
+/// let transaction = order.transaction.as_deref().unwrap_or_default();
+/// order.transaction = Some(
+///     ComputeBudgetOverride::new()
+///         .unit_price(50_000)
+///         .apply_base64(transaction)?,
+/// );
+/// let res = api.sign_and_execute(&order, &keypair).await?;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeBudgetOverride {
+    /// `SetComputeUnitLimit`, in compute units.
+    pub unit_limit: Option<u32>,
+
+    /// `SetComputeUnitPrice`, in micro-lamports per compute unit.
+    pub unit_price_micro_lamports: Option<u64>,
+}
+
+impl ComputeBudgetOverride {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compute unit limit.
+    pub fn unit_limit(mut self, units: u32) -> Self {
+        self.unit_limit = Some(units);
+        self
+    }
+
+    /// Sets the compute unit price, in micro-lamports per compute unit.
+    pub fn unit_price(mut self, micro_lamports: u64) -> Self {
+        self.unit_price_micro_lamports = Some(micro_lamports);
+        self
+    }
+
+    /// Rewrites the compute budget instructions of an unsigned transaction.
+    ///
+    /// Existing `SetComputeUnitLimit` and `SetComputeUnitPrice` instructions are updated in
+    /// place; missing ones are inserted at the start of the transaction, adding the compute
+    /// budget program to its accounts if needed.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` if the transaction already carries signatures, which the rewrite
+    ///   would invalidate, has no room for another account, or would exceed
+    ///   [`MAX_TRANSACTION_SIZE`] once rewritten. The transaction is left unchanged then.
+    pub fn apply(&self, transaction: &mut VersionedTransaction) -> Result<(), JupiterClientError> {
+        let mut rewritten = transaction.clone();
+        self.rewrite(&mut rewritten)?;
+
+        let size = codec::serialize(&rewritten)?.len();
+        if size > MAX_TRANSACTION_SIZE {
+            return Err(JupiterClientError::InvalidRequest(format!(
+                "transaction would be {size} bytes with the compute budget instructions, \
+                 above the {MAX_TRANSACTION_SIZE} byte limit"
+            )));
+        }
+        *transaction = rewritten;
+        Ok(())
+    }
+
+    fn rewrite(&self, transaction: &mut VersionedTransaction) -> Result<(), JupiterClientError> {
+        if transaction
+            .signatures
+            .iter()
+            .any(|signature| *signature != Signature::default())
+        {
+            return Err(JupiterClientError::InvalidRequest(
+                "cannot change the compute budget of a signed transaction".to_string(),
+            ));
+        }
+
+        let mut updates = Vec::new();
+        if let Some(units) = self.unit_limit {
+            updates.push((SET_COMPUTE_UNIT_LIMIT, units.to_le_bytes().to_vec()));
+        }
+        if let Some(micro_lamports) = self.unit_price_micro_lamports {
+            updates.push((
+                SET_COMPUTE_UNIT_PRICE,
+                micro_lamports.to_le_bytes().to_vec(),
+            ));
+        }
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let program = Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID)
+            .map_err(|e| JupiterClientError::InvalidRequest(e.to_string()))?;
+        let program_index = static_account_index(
+            &mut transaction.message,
+            program,
+            false,
+            "the compute budget program",
+        )?;
+        let (_, _, instructions) = message_parts(&mut transaction.message);
+
+        let mut inserted = 0;
+        for (discriminator, value) in updates {
+            let mut data = vec![discriminator];
+            data.extend(value);
+
+            match instructions.iter_mut().find(|instruction| {
+                instruction.program_id_index == program_index
+                    && instruction.data.first() == Some(&discriminator)
+            }) {
+                Some(existing) => existing.data = data,
+                None => {
+                    instructions.insert(
+                        inserted,
+                        CompiledInstruction::new_from_raw_parts(program_index, data, Vec::new()),
+                    );
+                    inserted += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Same as [`ComputeBudgetOverride::apply`] for a base64-encoded transaction, returning
+    /// the rewritten transaction.
+    pub fn apply_base64(&self, transaction: &str) -> Result<String, JupiterClientError> {
+        let mut decoded = codec::decode_base64(transaction)?;
+        self.apply(&mut decoded)?;
+        codec::encode_base64(&decoded)
+    }
+}
+
+/// Index of `key` among the static accounts of `message`, adding it if missing.
+///
+/// A new account goes last among the writable or read-only unsigned accounts, shifting the
+/// indexes after it, including those of accounts loaded from lookup tables, by one.
+/// `what` names the account in the error returned if the message has no room for it.
+pub(crate) fn static_account_index(
+    message: &mut VersionedMessage,
+    key: Pubkey,
+    writable: bool,
+    what: &str,
+) -> Result<u8, JupiterClientError> {
+    let (header, account_keys, instructions) = message_parts(message);
+    if let Some(index) = account_keys.iter().position(|existing| *existing == key) {
+        return Ok(index as u8);
+    }
+
+    let index = if writable {
+        account_keys.len() - usize::from(header.num_readonly_unsigned_accounts)
+    } else {
+        account_keys.len()
+    };
+    let highest = instructions
+        .iter()
+        .flat_map(|instruction| {
+            std::iter::once(instruction.program_id_index)
+                .chain(instruction.accounts.iter().copied())
+        })
+        .max()
+        .unwrap_or_default();
+    if account_keys.len() >= usize::from(u8::MAX) || highest == u8::MAX {
+        return Err(JupiterClientError::InvalidRequest(format!(
+            "transaction has no room for {what}"
+        )));
+    }
+
+    for instruction in instructions.iter_mut() {
+        for account in std::iter::once(&mut instruction.program_id_index)
+            .chain(instruction.accounts.iter_mut())
+        {
+            if usize::from(*account) >= index {
+                *account += 1;
+            }
+        }
+    }
+    account_keys.insert(index, key);
+    if !writable {
+        header.num_readonly_unsigned_accounts += 1;
+    }
+    Ok(index as u8)
+}
+
+/// Header, static accounts and instructions of either message version.
+pub(crate) fn message_parts(
+    message: &mut VersionedMessage,
+) -> (
+    &mut MessageHeader,
+    &mut Vec<Pubkey>,
+    &mut Vec<CompiledInstruction>,
+) {
+    match message {
+        VersionedMessage::Legacy(message) => (
+            &mut message.header,
+            &mut message.account_keys,
+            &mut message.instructions,
+        ),
+        VersionedMessage::V0(message) => (
+            &mut message.header,
+            &mut message.account_keys,
+            &mut message.instructions,
+        ),
+    }
+}
@@ -0,0 +1,335 @@
+//! Conversions between raw token amounts (base units, as sent to the APIs) and UI amounts
+//! (adjusted for decimals, as shown to users).
+//!
+//! [`TokenAmount`] carries a raw amount together with its decimals, and [`DecimalsResolver`]
+//! looks up the decimals of each mint once, so quotes, orders and balances all size amounts
+//! the same way.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+
+use crate::{
+    JupiterClient,
+    error::{JupiterClientError, ValidationError},
+};
+
+/// Converts a UI amount to a raw amount, rounding to the nearest base unit.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(to_raw_amount(1.5, 6), Ok(1_500_000));
+/// ```
+pub fn to_raw_amount(ui_amount: f64, decimals: u8) -> Result<u64, ValidationError> {
+    if !ui_amount.is_finite() || ui_amount < 0.0 {
+        return Err(ValidationError::InvalidAmount("amount"));
+    }
+
+    let raw = (ui_amount * 10f64.powi(decimals as i32)).round();
+    if raw >= u64::MAX as f64 {
+        return Err(ValidationError::AmountOverflow("amount"));
+    }
+    Ok(raw as u64)
+}
+
+/// Converts a raw amount to a UI amount.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(to_ui_amount(1_500_000, 6), 1.5);
+/// ```
+pub fn to_ui_amount(raw_amount: u64, decimals: u8) -> f64 {
+    raw_amount as f64 / 10f64.powi(decimals as i32)
+}
+
+/// Parses a decimal string such as `"1.5"` into a raw amount without going through `f64`.
+///
+/// Amounts with more fractional digits than `decimals` are rejected rather than truncated.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(parse_ui_amount("0.000001", 6), Ok(1));
+/// assert!(parse_ui_amount("0.0000001", 6).is_err());
+/// ```
+pub fn parse_ui_amount(ui_amount: &str, decimals: u8) -> Result<u64, ValidationError> {
+    let ui_amount = ui_amount.trim();
+    let (whole, fraction) = ui_amount.split_once('.').unwrap_or((ui_amount, ""));
+
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return Err(ValidationError::InvalidAmount("amount"));
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(ValidationError::ExcessPrecision {
+            field: "amount",
+            decimals,
+        });
+    }
+
+    let digits = format!("{whole}{fraction:0<width$}", width = decimals as usize);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    digits
+        .parse()
+        .map_err(|_| ValidationError::AmountOverflow("amount"))
+}
+
+/// A raw token amount and the decimals of its mint.
+///
+/// Request builders taking an amount accept either a raw `u64` or a `TokenAmount`, see
+/// [`RawAmount`]. Displays as the exact UI amount, e.g. `1.5`.
+///
+/// # Example
+///
+/// ```
+/// let amount = TokenAmount::parse("1.5", 6)?;
+/// assert_eq!(amount.raw, 1_500_000);
+/// assert_eq!(amount.to_string(), "1.5");
+///
+/// let request = QuoteRequest::new(USDC_MINT, SOL_MINT, amount);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenAmount {
+    pub raw: u64,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(raw: u64, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Converts a UI amount, see [`to_raw_amount`].
+    pub fn from_ui(ui_amount: f64, decimals: u8) -> Result<Self, ValidationError> {
+        Ok(Self::new(to_raw_amount(ui_amount, decimals)?, decimals))
+    }
+
+    /// Parses a decimal string UI amount, see [`parse_ui_amount`].
+    pub fn parse(ui_amount: &str, decimals: u8) -> Result<Self, ValidationError> {
+        Ok(Self::new(parse_ui_amount(ui_amount, decimals)?, decimals))
+    }
+
+    pub fn to_ui(&self) -> f64 {
+        to_ui_amount(self.raw, self.decimals)
+    }
+
+    /// The exact UI amount. Requires the `decimal` feature.
+    #[cfg(feature = "decimal")]
+    pub fn to_decimal(&self) -> Decimal {
+        Decimal::from_i128_with_scale(self.raw as i128, self.decimals as u32)
+    }
+
+    /// Converts an exact UI amount. Requires the `decimal` feature.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidAmount` if `ui_amount` is negative.
+    /// * `ExcessPrecision` if `ui_amount` has more fractional digits than `decimals`.
+    /// * `AmountOverflow` if the raw amount does not fit in a u64.
+    #[cfg(feature = "decimal")]
+    pub fn from_decimal(ui_amount: Decimal, decimals: u8) -> Result<Self, ValidationError> {
+        if ui_amount.is_sign_negative() && !ui_amount.is_zero() {
+            return Err(ValidationError::InvalidAmount("amount"));
+        }
+        if ui_amount.normalize().scale() > decimals as u32 {
+            return Err(ValidationError::ExcessPrecision {
+                field: "amount",
+                decimals,
+            });
+        }
+
+        let raw = 10u64
+            .checked_pow(decimals as u32)
+            .and_then(|scale| ui_amount.checked_mul(Decimal::from(scale)))
+            .and_then(|raw| u64::try_from(raw).ok())
+            .ok_or(ValidationError::AmountOverflow("amount"))?;
+        Ok(Self::new(raw, decimals))
+    }
+
+    /// Returns `None` if the decimals differ or the sum overflows.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        if self.decimals != other.decimals {
+            return None;
+        }
+        Some(Self::new(self.raw.checked_add(other.raw)?, self.decimals))
+    }
+
+    /// Returns `None` if the decimals differ or `other` is larger.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        if self.decimals != other.decimals {
+            return None;
+        }
+        Some(Self::new(self.raw.checked_sub(other.raw)?, self.decimals))
+    }
+
+    /// Returns `None` on overflow.
+    pub fn checked_mul(self, factor: u64) -> Option<Self> {
+        Some(Self::new(self.raw.checked_mul(factor)?, self.decimals))
+    }
+
+    /// Divides, rounding down. Returns `None` if `divisor` is 0.
+    pub fn checked_div(self, divisor: u64) -> Option<Self> {
+        Some(Self::new(self.raw.checked_div(divisor)?, self.decimals))
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!("{:0>width$}", self.raw, width = self.decimals as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{whole}")
+        } else {
+            write!(f, "{whole}.{fraction}")
+        }
+    }
+}
+
+impl From<TokenAmount> for u64 {
+    fn from(amount: TokenAmount) -> Self {
+        amount.raw
+    }
+}
+
+/// An amount in base units: a raw `u64` or a [`TokenAmount`].
+pub trait RawAmount {
+    fn raw_amount(self) -> u64;
+}
+
+impl RawAmount for u64 {
+    fn raw_amount(self) -> u64 {
+        self
+    }
+}
+
+impl RawAmount for TokenAmount {
+    fn raw_amount(self) -> u64 {
+        self.raw
+    }
+}
+
+/// Looks up and remembers token decimals to convert between raw and UI amounts.
+///
+/// Decimals are fetched with [`JupiterClient::get_tokens_info`], so a client built with
+/// [`JupiterClient::with_token_cache`] also shares them across resolvers. Clones share the
+/// same known decimals.
+///
+/// # Example
+///
+/// ```
+/// let resolver = DecimalsResolver::new(client.clone());
+///
+/// let amount = resolver.to_raw(USDC_MINT, 1.5).await?; // 1_500_000
+/// let quote = client.get_quote(&QuoteRequest::new(USDC_MINT, SOL_MINT, amount)).await?;
+/// let out = resolver.to_ui(SOL_MINT, quote.out_amount_u64().unwrap_or_default()).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecimalsResolver {
+    client: JupiterClient,
+    known: Arc<RwLock<HashMap<String, u8>>>,
+}
+
+impl DecimalsResolver {
+    pub fn new(client: JupiterClient) -> Self {
+        Self {
+            client,
+            known: Arc::default(),
+        }
+    }
+
+    /// Records the decimals of `mint` so it is never looked up.
+    pub fn with_known(self, mint: &str, decimals: u8) -> Self {
+        self.known
+            .write()
+            .unwrap()
+            .insert(mint.to_string(), decimals);
+        self
+    }
+
+    /// Returns the decimals of `mint`.
+    ///
+    /// # Errors
+    ///
+    /// * `TokenNotFound` if the token search does not know `mint`.
+    pub async fn decimals(&self, mint: &str) -> Result<u8, JupiterClientError> {
+        let decimals = self.decimals_many(&[mint.to_string()]).await?;
+        Ok(decimals[mint])
+    }
+
+    /// Returns the decimals of every mint, looking up the unknown ones in one go.
+    ///
+    /// # Errors
+    ///
+    /// * `TokenNotFound` with the first mint the token search does not know.
+    pub async fn decimals_many(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, u8>, JupiterClientError> {
+        let missing: Vec<String> = {
+            let known = self.known.read().unwrap();
+            mints
+                .iter()
+                .filter(|mint| !known.contains_key(*mint))
+                .cloned()
+                .collect()
+        };
+
+        if !missing.is_empty() {
+            let tokens = self.client.get_tokens_info(&missing).await?;
+            let mut known = self.known.write().unwrap();
+            for (mint, token) in tokens {
+                known.insert(mint, token.decimals);
+            }
+        }
+
+        let known = self.known.read().unwrap();
+        mints
+            .iter()
+            .map(|mint| match known.get(mint) {
+                Some(decimals) => Ok((mint.clone(), *decimals)),
+                None => Err(JupiterClientError::TokenNotFound(mint.clone())),
+            })
+            .collect()
+    }
+
+    /// Converts a UI amount of `mint` to a raw amount, see [`to_raw_amount`].
+    pub async fn to_raw(&self, mint: &str, ui_amount: f64) -> Result<u64, JupiterClientError> {
+        let decimals = self.decimals(mint).await?;
+        Ok(to_raw_amount(ui_amount, decimals)?)
+    }
+
+    /// Parses a decimal string amount of `mint` to a raw amount, see [`parse_ui_amount`].
+    pub async fn parse(&self, mint: &str, ui_amount: &str) -> Result<u64, JupiterClientError> {
+        let decimals = self.decimals(mint).await?;
+        Ok(parse_ui_amount(ui_amount, decimals)?)
+    }
+
+    /// Converts a UI amount of `mint` to a [`TokenAmount`].
+    pub async fn token_amount(
+        &self,
+        mint: &str,
+        ui_amount: f64,
+    ) -> Result<TokenAmount, JupiterClientError> {
+        let decimals = self.decimals(mint).await?;
+        Ok(TokenAmount::from_ui(ui_amount, decimals)?)
+    }
+
+    /// Converts a raw amount of `mint` to a UI amount, see [`to_ui_amount`].
+    pub async fn to_ui(&self, mint: &str, raw_amount: u64) -> Result<f64, JupiterClientError> {
+        let decimals = self.decimals(mint).await?;
+        Ok(to_ui_amount(raw_amount, decimals))
+    }
+}
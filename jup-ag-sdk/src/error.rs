@@ -13,6 +13,127 @@ pub enum JupiterClientError {
 
     #[error("Failed to deserialize response: {0}")]
     DeserializationError(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("RPC error: {0}")]
+    RpcError(String),
+
+    #[error("Failed to sign transaction: {0}")]
+    SigningError(String),
+
+    #[error("Transaction {signature} was not confirmed: {reason}")]
+    TransactionFailed { signature: String, reason: String },
+
+    #[error("Transaction rejected by policy: {0}")]
+    PolicyViolation(String),
+
+    #[error("Ultra order {0} was already submitted")]
+    DuplicateSubmission(String),
+
+    #[error(
+        "Trigger order {order} was cancelled in {cancel_signature} but its replacement failed: {source}"
+    )]
+    ReplacementFailed {
+        order: String,
+        cancel_signature: String,
+        source: Box<JupiterClientError>,
+    },
+
+    #[cfg(feature = "solana")]
+    #[error("All {} endpoints refused the transaction", .0.len())]
+    BroadcastFailed(Vec<crate::broadcast::EndpointResult>),
+
+    #[error("No price available for {0}")]
+    PriceUnavailable(String),
+
+    #[error("Token {0} not found")]
+    TokenNotFound(String),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Validation failed: {0}")]
+    ValidationError(#[from] ValidationError),
+}
+
+/// A request rejected client-side before being sent to the API.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationError {
+    #[error("`{field}` is not a valid base58 address: {value:?}")]
+    InvalidAddress { field: &'static str, value: String },
+
+    #[error("input and output mint must be different")]
+    SameMint,
+
+    #[error("`{0}` must be greater than 0")]
+    ZeroAmount(&'static str),
+
+    #[error("`{field}` must be between {min} and {max} bps, got {value}")]
+    BpsOutOfRange {
+        field: &'static str,
+        value: u64,
+        min: u64,
+        max: u64,
+    },
+
+    // the percentage is kept as text so the enum stays `Eq`
+    #[error("`{field}` must be between 0% and 100%, got {value}%")]
+    PercentOutOfRange { field: &'static str, value: String },
+
+    #[error("`{field}` must be an integer number of bps, got {value:?}")]
+    InvalidBps { field: &'static str, value: String },
+
+    #[error("`{0}` is required")]
+    MissingField(&'static str),
+
+    #[error("dexes {0:?} are both included and excluded")]
+    OverlappingDexes(Vec<String>),
+
+    #[error("`{0}` must be a positive, finite price")]
+    InvalidPrice(&'static str),
+
+    #[error("`{0}` does not fit in a u64")]
+    AmountOverflow(&'static str),
+
+    #[error("`{field}` must be at least {min}, got {value}")]
+    BelowMinimum {
+        field: &'static str,
+        value: u64,
+        min: u64,
+    },
+
+    #[error("`{field}` must be at most {max}, got {value}")]
+    AboveMaximum {
+        field: &'static str,
+        value: u64,
+        max: u64,
+    },
+
+    #[error("`{field}` must be worth at least {min_usd} USD")]
+    BelowMinimumNotional { field: &'static str, min_usd: u64 },
+
+    #[error("`min_price` must be below `max_price`")]
+    InvertedPriceRange,
+
+    #[error("`{0}` must be in the future")]
+    NotInFuture(&'static str),
+
+    #[error("`{0}` must be a non-negative, finite amount")]
+    InvalidAmount(&'static str),
+
+    #[error("`{field}` has more than {decimals} decimal places")]
+    ExcessPrecision { field: &'static str, decimals: u8 },
+}
+
+/// A string that does not name any variant of a closed enum such as `Category`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown {kind} {value:?}, expected one of: {expected}")]
+pub struct ParseEnumError {
+    pub kind: &'static str,
+    pub value: String,
+    pub expected: &'static str,
 }
 
 pub async fn handle_response(response: Response) -> Result<Response, JupiterClientError> {
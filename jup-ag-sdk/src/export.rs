@@ -0,0 +1,279 @@
+//! CSV and JSON Lines export of trigger and recurring order history.
+//!
+//! Raw amounts are parsed into integers, `None` when the API value cannot be parsed.
+//! Decimal-adjusted amounts are kept as the decimal strings the API sends, never rounded
+//! through a float, so totals reconcile exactly in downstream accounting tools.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::types::{RecurringOrder, RecurringTrade, TriggerOrder, TriggerTrade};
+
+/// A row that can be written as CSV.
+pub trait ExportRecord: Serialize {
+    /// Column names, in the order of [`ExportRecord::values`].
+    const COLUMNS: &'static [&'static str];
+
+    /// Cell values, empty for missing values.
+    fn values(&self) -> Vec<String>;
+}
+
+/// One fill (or other trade action) of a trigger or recurring order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FillRecord {
+    /// `"trigger"` or `"recurring"`.
+    pub source: &'static str,
+    pub order_key: String,
+    pub tx_id: String,
+    /// As sent by the API.
+    pub confirmed_at: String,
+    pub action: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub fee_mint: String,
+    /// Amounts in tokens, adjusted for decimals, as sent by the API.
+    pub input_amount: String,
+    pub output_amount: String,
+    pub fee_amount: String,
+    /// Raw amounts, before decimals.
+    pub raw_input_amount: Option<u64>,
+    pub raw_output_amount: Option<u64>,
+    pub raw_fee_amount: Option<u64>,
+}
+
+impl FillRecord {
+    /// Every trade of the given trigger orders, order by order.
+    pub fn from_trigger_orders(orders: &[TriggerOrder]) -> Vec<Self> {
+        orders
+            .iter()
+            .flat_map(|order| order.trades.iter().map(Self::from))
+            .collect()
+    }
+
+    /// Every trade of the given recurring orders, order by order.
+    pub fn from_recurring_orders(orders: &[RecurringOrder]) -> Vec<Self> {
+        orders
+            .iter()
+            .flat_map(|order| order.trades().iter().map(Self::from))
+            .collect()
+    }
+}
+
+impl From<&TriggerTrade> for FillRecord {
+    fn from(trade: &TriggerTrade) -> Self {
+        Self {
+            source: "trigger",
+            order_key: trade.order_key.clone(),
+            tx_id: trade.tx_id.clone(),
+            confirmed_at: trade.confirmed_at.clone(),
+            action: trade.action.to_string(),
+            input_mint: trade.input_mint.clone(),
+            output_mint: trade.output_mint.clone(),
+            fee_mint: trade.fee_mint.clone(),
+            input_amount: trade.input_amount.clone(),
+            output_amount: trade.output_amount.clone(),
+            fee_amount: trade.fee_amount.clone(),
+            raw_input_amount: trade.raw_input_amount.parse().ok(),
+            raw_output_amount: trade.raw_output_amount.parse().ok(),
+            raw_fee_amount: trade.raw_fee_amount.parse().ok(),
+        }
+    }
+}
+
+impl From<&RecurringTrade> for FillRecord {
+    fn from(trade: &RecurringTrade) -> Self {
+        Self {
+            source: "recurring",
+            order_key: trade.order_key.clone(),
+            tx_id: trade.tx_id.clone(),
+            confirmed_at: trade.confirmed_at.clone(),
+            action: trade.action.clone(),
+            input_mint: trade.input_mint.clone(),
+            output_mint: trade.output_mint.clone(),
+            fee_mint: trade.fee_mint.clone(),
+            input_amount: trade.input_amount.clone(),
+            output_amount: trade.output_amount.clone(),
+            fee_amount: trade.fee_amount.clone(),
+            raw_input_amount: trade.raw_input_amount.parse().ok(),
+            raw_output_amount: trade.raw_output_amount.parse().ok(),
+            raw_fee_amount: trade.raw_fee_amount.parse().ok(),
+        }
+    }
+}
+
+impl ExportRecord for FillRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "source",
+        "order_key",
+        "tx_id",
+        "confirmed_at",
+        "action",
+        "input_mint",
+        "output_mint",
+        "fee_mint",
+        "input_amount",
+        "output_amount",
+        "fee_amount",
+        "raw_input_amount",
+        "raw_output_amount",
+        "raw_fee_amount",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.source.to_string(),
+            self.order_key.clone(),
+            self.tx_id.clone(),
+            self.confirmed_at.clone(),
+            self.action.clone(),
+            self.input_mint.clone(),
+            self.output_mint.clone(),
+            self.fee_mint.clone(),
+            self.input_amount.clone(),
+            self.output_amount.clone(),
+            self.fee_amount.clone(),
+            cell(self.raw_input_amount),
+            cell(self.raw_output_amount),
+            cell(self.raw_fee_amount),
+        ]
+    }
+}
+
+/// One trigger order, without its trades.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderRecord {
+    pub order_key: String,
+    pub status: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub expired_at: Option<String>,
+    /// Amounts in tokens, adjusted for decimals, as sent by the API.
+    pub making_amount: String,
+    pub taking_amount: String,
+    pub remaining_making_amount: String,
+    pub remaining_taking_amount: String,
+    /// Raw amounts, before decimals.
+    pub raw_making_amount: Option<u64>,
+    pub raw_taking_amount: Option<u64>,
+    pub raw_remaining_making_amount: Option<u64>,
+    pub raw_remaining_taking_amount: Option<u64>,
+    pub slippage_bps: Option<u16>,
+}
+
+impl From<&TriggerOrder> for OrderRecord {
+    fn from(order: &TriggerOrder) -> Self {
+        Self {
+            order_key: order.order_key.clone(),
+            status: order.status.to_string(),
+            input_mint: order.input_mint.clone(),
+            output_mint: order.output_mint.clone(),
+            created_at: order.created_at.clone(),
+            updated_at: order.updated_at.clone(),
+            expired_at: order.expired_at.clone(),
+            making_amount: order.making_amount.clone(),
+            taking_amount: order.taking_amount.clone(),
+            remaining_making_amount: order.remaining_making_amount.clone(),
+            remaining_taking_amount: order.remaining_taking_amount.clone(),
+            raw_making_amount: order.raw_making_amount.parse().ok(),
+            raw_taking_amount: order.raw_taking_amount.parse().ok(),
+            raw_remaining_making_amount: order.raw_remaining_making_amount.parse().ok(),
+            raw_remaining_taking_amount: order.raw_remaining_taking_amount.parse().ok(),
+            slippage_bps: order.slippage_bps.parse().ok(),
+        }
+    }
+}
+
+impl ExportRecord for OrderRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "order_key",
+        "status",
+        "input_mint",
+        "output_mint",
+        "created_at",
+        "updated_at",
+        "expired_at",
+        "making_amount",
+        "taking_amount",
+        "remaining_making_amount",
+        "remaining_taking_amount",
+        "raw_making_amount",
+        "raw_taking_amount",
+        "raw_remaining_making_amount",
+        "raw_remaining_taking_amount",
+        "slippage_bps",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.order_key.clone(),
+            self.status.clone(),
+            self.input_mint.clone(),
+            self.output_mint.clone(),
+            self.created_at.clone(),
+            self.updated_at.clone(),
+            self.expired_at.clone().unwrap_or_default(),
+            self.making_amount.clone(),
+            self.taking_amount.clone(),
+            self.remaining_making_amount.clone(),
+            self.remaining_taking_amount.clone(),
+            cell(self.raw_making_amount),
+            cell(self.raw_taking_amount),
+            cell(self.raw_remaining_making_amount),
+            cell(self.raw_remaining_taking_amount),
+            cell(self.slippage_bps),
+        ]
+    }
+}
+
+/// Writes `records` as CSV with a header row.
+///
+/// # Example
+/// ```
+/// let orders = client.get_trigger_orders(&params).await?.orders;
+/// let mut file = std::fs::File::create("fills.csv")?;
+/// write_csv(&mut file, &FillRecord::from_trigger_orders(&orders))?;
+/// ```
+pub fn write_csv<W: Write, R: ExportRecord>(mut writer: W, records: &[R]) -> io::Result<()> {
+    write_csv_row(
+        &mut writer,
+        R::COLUMNS.iter().map(|column| column.to_string()),
+    )?;
+    for record in records {
+        write_csv_row(&mut writer, record.values())?;
+    }
+    writer.flush()
+}
+
+/// Writes `records` as JSON Lines, one JSON object per line.
+pub fn write_json_lines<W: Write, R: Serialize>(mut writer: W, records: &[R]) -> io::Result<()> {
+    for record in records {
+        serde_json::to_writer(&mut writer, record)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+fn write_csv_row<W: Write>(
+    writer: &mut W,
+    values: impl IntoIterator<Item = String>,
+) -> io::Result<()> {
+    let row: Vec<String> = values.into_iter().map(|value| escape_csv(&value)).collect();
+    writer.write_all(row.join(",").as_bytes())?;
+    writer.write_all(b"\r\n")
+}
+
+/// Quotes a cell when it contains a separator, a quote or a line break (RFC 4180).
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn cell<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,508 @@
+//! Grid trading on top of the Trigger API.
+//!
+//! [`GridStrategy`] lays a ladder of trigger orders over a price range: buys below the
+//! current price and sells above it. [`GridManager`] places them, polls the maker's orders
+//! and, when an order fills, places the opposite order one level away, so the grid keeps
+//! buying low and selling high while the price moves inside the range. Requires the
+//! `solana` feature.
+
+use std::time::Duration;
+
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    JupiterClient,
+    client::TriggerOrderAges,
+    error::{JupiterClientError, ValidationError},
+    signer::TransactionSigner,
+    types::{CreateTriggerOrder, TriggerOrderState},
+    validation::{check_address, check_non_zero},
+};
+
+/// Most price levels a [`GridStrategy`] may have.
+pub const MAX_GRID_LEVELS: usize = 100;
+
+/// How often [`GridManager::run`] polls the maker's orders by default.
+pub const DEFAULT_GRID_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Direction of a grid order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GridSide {
+    /// Sells the quote mint for `size` of the base mint.
+    Buy,
+    /// Sells `size` of the base mint for the quote mint.
+    Sell,
+}
+
+impl GridSide {
+    pub fn opposite(self) -> Self {
+        match self {
+            GridSide::Buy => GridSide::Sell,
+            GridSide::Sell => GridSide::Buy,
+        }
+    }
+}
+
+/// A trigger order of the grid, not placed yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridOrder {
+    /// Index of the price level, from 0 at `lower_price`.
+    pub level: usize,
+    pub price: f64,
+    pub side: GridSide,
+    pub order: CreateTriggerOrder,
+}
+
+/// A grid order placed on chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacedGridOrder {
+    pub level: usize,
+    pub side: GridSide,
+
+    /// Trigger order account.
+    pub order: String,
+}
+
+/// A ladder of trigger orders between `lower_price` and `upper_price`, one every `step`.
+///
+/// Prices are quote tokens per base token, e.g. USDC per SOL. Every order trades `size`
+/// of the base mint, raw.
+///
+/// # Example
+///
+/// ```
+/// // buy or sell 0.5 SOL every 5 USDC between 140 and 180 USDC
+/// let grid = GridStrategy::new(SOL_MINT, USDC_MINT, maker, 9, 6)
+///     .range(140.0, 180.0)
+///     .step(5.0)
+///     .size(500_000_000);
+/// let orders = grid.orders(162.5)?; // 4 buys from 140 to 155, 4 sells from 165 to 180
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridStrategy {
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub maker: String,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub step: f64,
+
+    /// Raw base amount of every order.
+    pub size: u64,
+}
+
+impl GridStrategy {
+    pub fn new(
+        base_mint: &str,
+        quote_mint: &str,
+        maker: &str,
+        base_decimals: u8,
+        quote_decimals: u8,
+    ) -> Self {
+        Self {
+            base_mint: base_mint.to_string(),
+            quote_mint: quote_mint.to_string(),
+            maker: maker.to_string(),
+            base_decimals,
+            quote_decimals,
+            lower_price: 0.0,
+            upper_price: 0.0,
+            step: 0.0,
+            size: 0,
+        }
+    }
+
+    /// Sets the lowest and highest price level.
+    pub fn range(mut self, lower_price: f64, upper_price: f64) -> Self {
+        self.lower_price = lower_price;
+        self.upper_price = upper_price;
+        self
+    }
+
+    /// Sets the price difference between two levels.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Sets the raw base amount of every order.
+    pub fn size(mut self, size: u64) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_address("base_mint", &self.base_mint)?;
+        check_address("quote_mint", &self.quote_mint)?;
+        check_address("maker", &self.maker)?;
+        if self.base_mint == self.quote_mint {
+            return Err(ValidationError::SameMint);
+        }
+        check_non_zero("size", self.size)?;
+        for (field, price) in [
+            ("lower_price", self.lower_price),
+            ("upper_price", self.upper_price),
+            ("step", self.step),
+        ] {
+            if !price.is_finite() || price <= 0.0 {
+                return Err(ValidationError::InvalidPrice(field));
+            }
+        }
+        if self.upper_price <= self.lower_price {
+            return Err(ValidationError::InvalidPrice("upper_price"));
+        }
+
+        let levels = self.level_count();
+        if levels > MAX_GRID_LEVELS as u64 {
+            return Err(ValidationError::AboveMaximum {
+                field: "levels",
+                value: levels,
+                max: MAX_GRID_LEVELS as u64,
+            });
+        }
+        Ok(())
+    }
+
+    /// Price of every level, from `lower_price` up to at most `upper_price`.
+    pub fn levels(&self) -> Vec<f64> {
+        if self.validate().is_err() {
+            return Vec::new();
+        }
+        (0..self.level_count())
+            .map(|level| self.lower_price + level as f64 * self.step)
+            .collect()
+    }
+
+    /// The orders of the grid around `current_price`: buys at the levels below it, sells at
+    /// the levels above it.
+    ///
+    /// The level nearest `current_price`, the lower one on a tie, gets no order. That empty
+    /// level is where [`GridManager`] places the opposite order of the next fill, so the
+    /// grid never holds two orders at one level.
+    ///
+    /// # Errors
+    ///
+    /// * Any error of [`GridStrategy::validate`].
+    /// * `InvalidPrice("current_price")` if `current_price` is not positive and finite.
+    /// * `ZeroAmount` or `AmountOverflow` if an order amount rounds to 0 or overflows.
+    pub fn orders(&self, current_price: f64) -> Result<Vec<GridOrder>, ValidationError> {
+        self.validate()?;
+        if !current_price.is_finite() || current_price <= 0.0 {
+            return Err(ValidationError::InvalidPrice("current_price"));
+        }
+
+        let levels = self.levels();
+        let empty = levels
+            .iter()
+            .map(|price| (price - current_price).abs())
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(level, _)| level);
+
+        levels
+            .into_iter()
+            .enumerate()
+            .filter(|(level, _)| Some(*level) != empty)
+            .map(|(level, price)| {
+                let side = if price < current_price {
+                    GridSide::Buy
+                } else {
+                    GridSide::Sell
+                };
+                self.order_at(level, side)
+            })
+            .collect()
+    }
+
+    /// The `side` order at `level`.
+    ///
+    /// # Errors
+    ///
+    /// * `AboveMaximum("level")` if the grid has no such level.
+    /// * `ZeroAmount` or `AmountOverflow` if an order amount rounds to 0 or overflows.
+    pub fn order_at(&self, level: usize, side: GridSide) -> Result<GridOrder, ValidationError> {
+        let levels = self.levels();
+        let Some(&price) = levels.get(level) else {
+            return Err(ValidationError::AboveMaximum {
+                field: "level",
+                value: level as u64,
+                max: levels.len().saturating_sub(1) as u64,
+            });
+        };
+
+        let order = match side {
+            GridSide::Sell => CreateTriggerOrder::from_price(
+                &self.base_mint,
+                &self.quote_mint,
+                &self.maker,
+                self.size,
+                price,
+                self.base_decimals,
+                self.quote_decimals,
+            )?,
+            GridSide::Buy => {
+                let making_amount = (self.size as f64
+                    * price
+                    * 10f64.powi(i32::from(self.quote_decimals) - i32::from(self.base_decimals)))
+                .round();
+                if making_amount >= u64::MAX as f64 {
+                    return Err(ValidationError::AmountOverflow("making_amount"));
+                }
+                let making_amount = making_amount as u64;
+                check_non_zero("making_amount", making_amount)?;
+
+                CreateTriggerOrder::new(
+                    &self.quote_mint,
+                    &self.base_mint,
+                    &self.maker,
+                    &self.maker,
+                    making_amount,
+                    self.size,
+                )
+            }
+        };
+
+        Ok(GridOrder {
+            level,
+            price,
+            side,
+            order,
+        })
+    }
+
+    fn level_count(&self) -> u64 {
+        // tolerates rounding, e.g. (0.3 - 0.1) / 0.1 = 1.9999999999999998
+        let steps = ((self.upper_price - self.lower_price) / self.step + 1e-9).floor();
+        if steps >= u64::MAX as f64 {
+            u64::MAX
+        } else {
+            steps as u64 + 1
+        }
+    }
+}
+
+/// Something [`GridManager`] observed or did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridEvent {
+    Placed(PlacedGridOrder),
+
+    /// The order filled completely.
+    Filled(PlacedGridOrder),
+
+    /// The order left the active set without filling, e.g. cancelled by the maker or
+    /// expired. Its level is not re-placed.
+    Closed {
+        order: PlacedGridOrder,
+        status: TriggerOrderState,
+    },
+
+    /// Placing the `side` order at `level` failed; the level stays empty.
+    PlaceFailed {
+        level: usize,
+        side: GridSide,
+        error: String,
+    },
+
+    /// Polling the Trigger API failed; the manager keeps polling.
+    PollFailed(String),
+}
+
+/// Places the orders of a [`GridStrategy`] and re-places the opposite order after fills.
+///
+/// A filled buy at level `i` is followed by a sell at level `i + 1`, a filled sell at level
+/// `i` by a buy at level `i - 1`, unless that level is outside the grid or already holds a
+/// tracked order. Only complete fills count.
+///
+/// # Example
+///
+/// ```
+/// let (grid, mut events) = GridManager::place(client.clone(), keypair, strategy, 162.5).await?;
+///
+/// tokio::spawn(grid.run());
+/// while let Some(event) = events.next().await {
+///     println!("{event:?}");
+/// }
+/// ```
+pub struct GridManager<S> {
+    client: JupiterClient,
+    signer: S,
+    strategy: GridStrategy,
+    orders: Vec<PlacedGridOrder>,
+    poll_interval: Duration,
+    events: UnboundedSender<GridEvent>,
+    ages: TriggerOrderAges,
+}
+
+impl<S> GridManager<S>
+where
+    S: TransactionSigner,
+{
+    /// Places the orders of `strategy` around `current_price` with `signer` and returns a
+    /// manager watching them, with the receiving end of its event channel.
+    ///
+    /// An order that fails to be placed is reported as [`GridEvent::PlaceFailed`] on the
+    /// channel; the other orders are still placed.
+    ///
+    /// # Errors
+    ///
+    /// * `ValidationError` if [`GridStrategy::orders`] fails; nothing is placed then.
+    pub async fn place(
+        client: JupiterClient,
+        signer: S,
+        strategy: GridStrategy,
+        current_price: f64,
+    ) -> Result<(Self, UnboundedReceiver<GridEvent>), JupiterClientError> {
+        let orders = strategy.orders(current_price)?;
+        let (mut manager, receiver) = Self::watch(client, signer, strategy, Vec::new());
+        for order in orders {
+            let event = manager.place_order(order).await;
+            manager.emit(event);
+        }
+        Ok((manager, receiver))
+    }
+
+    /// Returns a manager watching grid orders of `strategy` that are already placed.
+    pub fn watch(
+        client: JupiterClient,
+        signer: S,
+        strategy: GridStrategy,
+        orders: Vec<PlacedGridOrder>,
+    ) -> (Self, UnboundedReceiver<GridEvent>) {
+        let (events, receiver) = unbounded();
+        let manager = Self {
+            client,
+            signer,
+            strategy,
+            orders,
+            poll_interval: DEFAULT_GRID_POLL_INTERVAL,
+            events,
+            ages: TriggerOrderAges::default(),
+        };
+        (manager, receiver)
+    }
+
+    /// Sets how often the maker's orders are polled.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// The grid orders believed to be open.
+    pub fn orders(&self) -> &[PlacedGridOrder] {
+        &self.orders
+    }
+
+    /// Polls until no grid order is left open or the event receiver is dropped, sending
+    /// events to the channel.
+    pub async fn run(mut self) {
+        while !self.orders.is_empty() && !self.events.is_closed() {
+            tokio::time::sleep(self.poll_interval).await;
+            match self.poll().await {
+                Ok(events) => events.into_iter().for_each(|event| self.emit(event)),
+                Err(e) => self.emit(GridEvent::PollFailed(e.to_string())),
+            }
+        }
+    }
+
+    /// Checks the grid orders once, re-placing the opposite order of each fill, and returns
+    /// what happened.
+    ///
+    /// Orders missing from both the active orders and the history, e.g. just placed and not
+    /// indexed yet, are checked again on the next poll. The history is read only down to the
+    /// orders that closed before the grid orders looked up were created.
+    pub async fn poll(&mut self) -> Result<Vec<GridEvent>, JupiterClientError> {
+        let maker = self.strategy.maker.clone();
+        let inactive = self
+            .client
+            .inactive_trigger_orders(
+                &maker,
+                self.orders.iter().map(|placed| placed.order.as_str()),
+                &mut self.ages,
+            )
+            .await?;
+        if inactive.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut closed_status = self
+            .client
+            .closed_trigger_orders(&maker, &inactive, &mut self.ages)
+            .await?;
+
+        let mut events = Vec::new();
+        let (closed, open): (Vec<_>, Vec<_>) = std::mem::take(&mut self.orders)
+            .into_iter()
+            .partition(|placed| closed_status.contains_key(&placed.order));
+        self.orders = open;
+
+        for placed in closed {
+            let Some(status) = closed_status.remove(&placed.order) else {
+                continue;
+            };
+            if status != TriggerOrderState::Completed {
+                events.push(GridEvent::Closed {
+                    order: placed,
+                    status,
+                });
+                continue;
+            }
+
+            let next_level = match placed.side {
+                GridSide::Buy => placed.level.checked_add(1),
+                GridSide::Sell => placed.level.checked_sub(1),
+            };
+            let side = placed.side.opposite();
+            events.push(GridEvent::Filled(placed));
+
+            let Some(level) = next_level
+                .filter(|level| *level < self.strategy.levels().len())
+                .filter(|level| !self.orders.iter().any(|placed| placed.level == *level))
+            else {
+                continue;
+            };
+            match self.strategy.order_at(level, side) {
+                Ok(order) => events.push(self.place_order(order).await),
+                Err(e) => events.push(GridEvent::PlaceFailed {
+                    level,
+                    side,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Places `order`, tracking it on success.
+    async fn place_order(&mut self, order: GridOrder) -> GridEvent {
+        match self
+            .client
+            .create_and_execute_trigger_order(&order.order, &self.signer)
+            .await
+        {
+            Ok(created) => {
+                let placed = PlacedGridOrder {
+                    level: order.level,
+                    side: order.side,
+                    order: created.order,
+                };
+                self.orders.push(placed.clone());
+                GridEvent::Placed(placed)
+            }
+            Err(e) => GridEvent::PlaceFailed {
+                level: order.level,
+                side: order.side,
+                error: e.to_string(),
+            },
+        }
+    }
+
+    fn emit(&self, event: GridEvent) {
+        // the receiver may have been dropped, `run` stops after this poll
+        let _ = self.events.unbounded_send(event);
+    }
+}
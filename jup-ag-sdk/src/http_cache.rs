@@ -0,0 +1,165 @@
+//! Conditional request caching for large, slow-changing responses.
+
+use std::collections::HashMap;
+
+use reqwest::{
+    RequestBuilder, StatusCode,
+    header::{ETAG, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    JupiterClient,
+    error::{JupiterClientError, handle_response},
+};
+
+/// A response body and the validators to revalidate it with.
+#[derive(Debug)]
+struct CachedResponse {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    body: Vec<u8>,
+    /// Value of [`HttpCache::clock`] when the entry was last stored or reused.
+    last_used: u64,
+}
+
+/// Where [`HttpCache`] keeps a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheSlot {
+    /// The entries sharing `capacity`, least recently used evicted first.
+    Shared,
+    /// One entry per URL outside `capacity`, for bulk lists larger than it.
+    Dedicated,
+}
+
+/// Response bodies keyed by full request URL, at most `capacity` bytes of them, plus the
+/// [`CacheSlot::Dedicated`] ones.
+#[derive(Debug)]
+pub(crate) struct HttpCache {
+    entries: HashMap<String, CachedResponse>,
+    dedicated: HashMap<String, CachedResponse>,
+    capacity: usize,
+    size: usize,
+    clock: u64,
+}
+
+impl HttpCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            dedicated: HashMap::new(),
+            capacity,
+            size: 0,
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, key: &str, slot: CacheSlot) -> Option<&CachedResponse> {
+        if slot == CacheSlot::Dedicated {
+            return self.dedicated.get(key);
+        }
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = self.clock;
+        Some(entry)
+    }
+
+    /// Stores `response`, evicting the least recently used entries to stay within
+    /// `capacity`. A body larger than `capacity` is not stored, unless it is dedicated.
+    fn insert(&mut self, key: String, mut response: CachedResponse, slot: CacheSlot) {
+        if slot == CacheSlot::Dedicated {
+            self.dedicated.insert(key, response);
+            return;
+        }
+        if let Some(previous) = self.entries.remove(&key) {
+            self.size -= previous.body.len();
+        }
+        if response.body.len() > self.capacity {
+            return;
+        }
+        while self.size + response.body.len() > self.capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.size -= evicted.body.len();
+            }
+        }
+
+        self.clock += 1;
+        response.last_used = self.clock;
+        self.size += response.body.len();
+        self.entries.insert(key, response);
+    }
+}
+
+impl JupiterClient {
+    /// Sends the GET `request` and deserializes its JSON body.
+    ///
+    /// With [`JupiterClient::with_http_cache`], a previous response carrying an `ETag` or
+    /// `Last-Modified` header is revalidated with `If-None-Match` / `If-Modified-Since`,
+    /// and reused when the server answers `304 Not Modified`. The response is kept in
+    /// `slot`.
+    pub(crate) async fn get_json_cached<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        slot: CacheSlot,
+    ) -> Result<T, JupiterClientError> {
+        let mut request = request.build()?;
+        let key = request.url().to_string();
+
+        if let Some(cache) = &self.http_cache {
+            let mut cache = cache.lock().unwrap();
+            if let Some(cached) = cache.get(&key, slot) {
+                let headers = request.headers_mut();
+                if let Some(etag) = &cached.etag {
+                    headers.insert(IF_NONE_MATCH, etag.clone());
+                }
+                if let Some(last_modified) = &cached.last_modified {
+                    headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+                }
+            }
+        }
+
+        self.throttle().await;
+        let response = self.client.execute(request).await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED
+            && let Some(cache) = &self.http_cache
+            && let Some(cached) = cache.lock().unwrap().get(&key, slot)
+        {
+            return serde_json::from_slice(&cached.body)
+                .map_err(|e| JupiterClientError::DeserializationError(e.to_string()));
+        }
+
+        let response = handle_response(response).await?;
+        let etag = response.headers().get(ETAG).cloned();
+        let last_modified = response.headers().get(LAST_MODIFIED).cloned();
+        let body: Vec<u8> = response.bytes().await?.into();
+
+        let data = serde_json::from_slice(&body)
+            .map_err(|e| JupiterClientError::DeserializationError(e.to_string()))?;
+
+        if let Some(cache) = &self.http_cache
+            && (etag.is_some() || last_modified.is_some())
+        {
+            cache.lock().unwrap().insert(
+                key,
+                CachedResponse {
+                    etag,
+                    last_modified,
+                    body,
+                    last_used: 0,
+                },
+                slot,
+            );
+        }
+
+        Ok(data)
+    }
+}
@@ -0,0 +1,534 @@
+//! Decoding and verification of unsigned transactions returned by Jupiter.
+//!
+//! Lets callers see which programs a transaction invokes, which accounts it writes to, its
+//! compute budget and the SOL and token transfers it makes, and reject it before signing if
+//! it touches programs outside an allowlist. Requires the `solana` feature.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use solana_transaction::versioned::VersionedTransaction;
+
+use crate::{codec, error::JupiterClientError};
+
+/// Jupiter Aggregator v6 program.
+pub const JUPITER_V6_PROGRAM_ID: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+
+/// Jupiter Z (RFQ order engine) program used by Ultra.
+pub const JUPITER_ORDER_ENGINE_PROGRAM_ID: &str = "61DFfeTKM7trxYcPQCM78bJ794ddZprZpAwAnLiwTpYH";
+
+/// Jupiter Limit Order v2 program used by the Trigger API.
+pub const JUPITER_LIMIT_ORDER_PROGRAM_ID: &str = "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X";
+
+/// Jupiter DCA program used by the Recurring API.
+pub const JUPITER_DCA_PROGRAM_ID: &str = "DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M";
+
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+/// Accounts loaded from an address lookup table.
+///
+/// Only the indexes are known without fetching the table from an RPC node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LookupTableAccess {
+    pub account_key: String,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// Compute budget instructions of a transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeBudget {
+    /// `SetComputeUnitLimit`, in compute units.
+    pub unit_limit: Option<u32>,
+
+    /// `SetComputeUnitPrice`, in micro-lamports per compute unit.
+    pub unit_price_micro_lamports: Option<u64>,
+
+    /// `RequestHeapFrame`, in bytes.
+    pub heap_frame_bytes: Option<u32>,
+
+    /// `SetLoadedAccountsDataSizeLimit`, in bytes.
+    pub loaded_accounts_data_size_limit: Option<u32>,
+}
+
+impl ComputeBudget {
+    /// The priority fee paid on top of the base fee, in lamports, if both the unit limit and
+    /// price are set.
+    pub fn priority_fee_lamports(&self) -> Option<u64> {
+        let micro_lamports =
+            u128::from(self.unit_limit?) * u128::from(self.unit_price_micro_lamports?);
+        u64::try_from(micro_lamports.div_ceil(1_000_000)).ok()
+    }
+
+    fn apply(&mut self, data: &[u8]) {
+        match data.first() {
+            Some(1) => self.heap_frame_bytes = read_u32(data, 1),
+            Some(2) => self.unit_limit = read_u32(data, 1),
+            Some(3) => self.unit_price_micro_lamports = read_u64(data, 1),
+            Some(4) => self.loaded_accounts_data_size_limit = read_u32(data, 1),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransferKind {
+    /// System program `Transfer` of lamports.
+    Sol,
+    /// SPL Token or Token-2022 `Transfer` or `TransferChecked`.
+    Token,
+}
+
+/// A SOL or token transfer made by a top-level instruction.
+///
+/// Accounts loaded through an address lookup table cannot be resolved without an RPC node
+/// and are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    pub kind: TransferKind,
+    pub program_id: String,
+
+    /// The paying wallet for SOL, the source token account for tokens.
+    pub source: Option<String>,
+    pub destination: Option<String>,
+
+    /// The account signing the transfer, the owner or delegate of a token account.
+    pub authority: Option<String>,
+
+    /// Only known for `TransferChecked`.
+    pub mint: Option<String>,
+
+    /// Lamports for SOL, base units for tokens.
+    pub amount: u64,
+
+    /// Only known for `TransferChecked`.
+    pub decimals: Option<u8>,
+}
+
+/// What a transaction does, as far as can be told without an RPC node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSummary {
+    /// The account paying the transaction fee.
+    pub fee_payer: String,
+
+    /// Accounts that must sign, fee payer first.
+    pub signers: Vec<String>,
+
+    /// Distinct programs invoked by top-level instructions, in order of first use.
+    pub program_ids: Vec<String>,
+
+    /// Writable accounts listed in the message itself.
+    pub writable_accounts: Vec<String>,
+
+    /// Accounts loaded through address lookup tables (v0 transactions only).
+    pub lookup_tables: Vec<LookupTableAccess>,
+
+    #[serde(default)]
+    pub compute_budget: ComputeBudget,
+
+    /// SOL and token transfers made by top-level instructions, in order.
+    #[serde(default)]
+    pub transfers: Vec<Transfer>,
+}
+
+impl TransactionSummary {
+    /// Decodes a base64-encoded `VersionedTransaction`, as returned by `/swap` or `/ultra/v1/order`.
+    pub fn from_base64(transaction: &str) -> Result<Self, JupiterClientError> {
+        Ok(Self::from_transaction(&codec::decode_base64(transaction)?))
+    }
+
+    /// Summarizes an already decoded transaction.
+    pub fn from_transaction(tx: &VersionedTransaction) -> Self {
+        let message = &tx.message;
+        let header = message.header();
+        let keys = message.static_account_keys();
+
+        let num_signers = header.num_required_signatures as usize;
+        let writable_signers =
+            num_signers.saturating_sub(header.num_readonly_signed_accounts as usize);
+        let writable_unsigned = keys
+            .len()
+            .saturating_sub(header.num_readonly_unsigned_accounts as usize);
+        let is_writable = |index: usize| {
+            if index < num_signers {
+                index < writable_signers
+            } else {
+                index < writable_unsigned
+            }
+        };
+
+        let mut program_ids: Vec<String> = Vec::new();
+        let mut compute_budget = ComputeBudget::default();
+        let mut transfers = Vec::new();
+        for instruction in message.instructions() {
+            let Some(program_id) = keys.get(instruction.program_id_index as usize) else {
+                continue;
+            };
+            let program_id = program_id.to_string();
+
+            let account = |position: usize| {
+                let index = *instruction.accounts.get(position)? as usize;
+                keys.get(index).map(|key| key.to_string())
+            };
+            match program_id.as_str() {
+                COMPUTE_BUDGET_PROGRAM_ID => {
+                    compute_budget.apply(&instruction.data);
+                }
+                SYSTEM_PROGRAM_ID => {
+                    // Transfer { lamports: u64 }, accounts [from, to]
+                    if let Some(amount) = system_transfer_amount(&instruction.data) {
+                        transfers.push(Transfer {
+                            kind: TransferKind::Sol,
+                            program_id: program_id.clone(),
+                            source: account(0),
+                            destination: account(1),
+                            authority: account(0),
+                            mint: None,
+                            amount,
+                            decimals: None,
+                        });
+                    }
+                }
+                TOKEN_PROGRAM_ID | TOKEN_2022_PROGRAM_ID => {
+                    match (instruction.data.first(), read_u64(&instruction.data, 1)) {
+                        // Transfer { amount }, accounts [source, destination, authority]
+                        (Some(3), Some(amount)) => transfers.push(Transfer {
+                            kind: TransferKind::Token,
+                            program_id: program_id.clone(),
+                            source: account(0),
+                            destination: account(1),
+                            authority: account(2),
+                            mint: None,
+                            amount,
+                            decimals: None,
+                        }),
+                        // TransferChecked { amount, decimals }, accounts
+                        // [source, mint, destination, authority]
+                        (Some(12), Some(amount)) => transfers.push(Transfer {
+                            kind: TransferKind::Token,
+                            program_id: program_id.clone(),
+                            source: account(0),
+                            destination: account(2),
+                            authority: account(3),
+                            mint: account(1),
+                            amount,
+                            decimals: instruction.data.get(9).copied(),
+                        }),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+
+            if !program_ids.contains(&program_id) {
+                program_ids.push(program_id);
+            }
+        }
+
+        let lookup_tables = message
+            .address_table_lookups()
+            .unwrap_or_default()
+            .iter()
+            .map(|lookup| LookupTableAccess {
+                account_key: lookup.account_key.to_string(),
+                writable_indexes: lookup.writable_indexes.clone(),
+                readonly_indexes: lookup.readonly_indexes.clone(),
+            })
+            .collect();
+
+        Self {
+            fee_payer: keys.first().map(|key| key.to_string()).unwrap_or_default(),
+            signers: keys
+                .iter()
+                .take(num_signers)
+                .map(|key| key.to_string())
+                .collect(),
+            program_ids,
+            writable_accounts: keys
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| is_writable(*index))
+                .map(|(_, key)| key.to_string())
+                .collect(),
+            lookup_tables,
+            compute_budget,
+            transfers,
+        }
+    }
+
+    /// Total lamports sent by system transfers from `wallet`, saturating at `u64::MAX`.
+    pub fn sol_sent_by(&self, wallet: &str) -> u64 {
+        self.transfers
+            .iter()
+            .filter(|transfer| {
+                transfer.kind == TransferKind::Sol && transfer.source.as_deref() == Some(wallet)
+            })
+            .fold(0u64, |total, transfer| {
+                total.saturating_add(transfer.amount)
+            })
+    }
+}
+
+/// Lamports of a system program `Transfer`, whose instruction index is a `u32`.
+fn system_transfer_amount(data: &[u8]) -> Option<u64> {
+    (read_u32(data, 0)? == 2)
+        .then(|| read_u64(data, 4))
+        .flatten()
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// A set of program IDs a transaction is allowed to invoke.
+///
+/// # Example
+/// ```
+/// let allowlist = ProgramAllowlist::ultra_default().allow(MY_ROUTER_PROGRAM_ID);
+/// let summary = allowlist.verify_base64(&order.transaction.unwrap())?;
+///
+/// // or checked by every sign-and-execute helper of a client
+/// let api = api.with_transaction_policy(TransactionPolicy::new(allowlist));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgramAllowlist {
+    pub programs: HashSet<String>,
+}
+
+impl ProgramAllowlist {
+    /// Creates an allowlist from program IDs.
+    pub fn new<I, S>(programs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            programs: programs.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// The programs used by Jupiter-built swaps: the aggregator, the RFQ order engine and
+    /// the system, compute budget, token, associated token and memo programs.
+    ///
+    /// Ultra orders filled by third-party routers invoke their own programs, add them with
+    /// [`ProgramAllowlist::allow`].
+    pub fn ultra_default() -> Self {
+        Self::with_common_programs([JUPITER_V6_PROGRAM_ID, JUPITER_ORDER_ENGINE_PROGRAM_ID])
+    }
+
+    /// The programs used by Trigger API transactions: the limit order program and the
+    /// system, compute budget, token, associated token and memo programs.
+    pub fn trigger_default() -> Self {
+        Self::with_common_programs([JUPITER_LIMIT_ORDER_PROGRAM_ID])
+    }
+
+    /// The programs used by Recurring API transactions: the DCA program and the system,
+    /// compute budget, token, associated token and memo programs.
+    pub fn recurring_default() -> Self {
+        Self::with_common_programs([JUPITER_DCA_PROGRAM_ID])
+    }
+
+    /// The programs of [`ProgramAllowlist::ultra_default`],
+    /// [`ProgramAllowlist::trigger_default`] and [`ProgramAllowlist::recurring_default`]
+    /// together, for a client using several APIs.
+    pub fn jupiter_default() -> Self {
+        Self::with_common_programs([
+            JUPITER_V6_PROGRAM_ID,
+            JUPITER_ORDER_ENGINE_PROGRAM_ID,
+            JUPITER_LIMIT_ORDER_PROGRAM_ID,
+            JUPITER_DCA_PROGRAM_ID,
+        ])
+    }
+
+    fn with_common_programs<const N: usize>(programs: [&str; N]) -> Self {
+        Self::new(programs.into_iter().chain([
+            SYSTEM_PROGRAM_ID,
+            COMPUTE_BUDGET_PROGRAM_ID,
+            TOKEN_PROGRAM_ID,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+            MEMO_PROGRAM_ID,
+        ]))
+    }
+
+    /// Adds a program ID to the allowlist.
+    pub fn allow(mut self, program_id: impl Into<String>) -> Self {
+        self.programs.insert(program_id.into());
+        self
+    }
+
+    /// Returns `true` if the program ID is allowed.
+    pub fn contains(&self, program_id: &str) -> bool {
+        self.programs.contains(program_id)
+    }
+
+    /// Checks that every program invoked by the transaction is allowed.
+    ///
+    /// # Errors
+    /// `PolicyViolation` listing the programs outside the allowlist.
+    pub fn verify(&self, summary: &TransactionSummary) -> Result<(), JupiterClientError> {
+        let disallowed: Vec<&str> = summary
+            .program_ids
+            .iter()
+            .map(String::as_str)
+            .filter(|program_id| !self.contains(program_id))
+            .collect();
+
+        if disallowed.is_empty() {
+            Ok(())
+        } else {
+            Err(JupiterClientError::PolicyViolation(format!(
+                "transaction invokes programs outside the allowlist: {}",
+                disallowed.join(", ")
+            )))
+        }
+    }
+
+    /// Decodes a base64 transaction and verifies it, returning its summary.
+    pub fn verify_base64(
+        &self,
+        transaction: &str,
+    ) -> Result<TransactionSummary, JupiterClientError> {
+        let summary = TransactionSummary::from_base64(transaction)?;
+        self.verify(&summary)?;
+        Ok(summary)
+    }
+}
+
+/// Rules a transaction must satisfy before it is signed.
+///
+/// Every program invoked by a top-level instruction must be in `programs`. If
+/// `writable_accounts` is set, every writable account must also be in it or be a signer of
+/// the transaction; writable accounts loaded through address lookup tables cannot be
+/// checked and are rejected.
+///
+/// Set on a client with [`JupiterClient::with_transaction_policy`](crate::JupiterClient::with_transaction_policy),
+/// it is checked by every sign-and-execute helper. The default policy allows the programs
+/// of every Jupiter API; [`TransactionPolicy::ultra`], [`TransactionPolicy::trigger`] and
+/// [`TransactionPolicy::recurring`] narrow it to one.
+///
+/// # Example
+/// ```
+/// let policy = TransactionPolicy::default()
+///     .allow_writable(&treasury_usdc_account)
+///     .allow_writable(&treasury_sol_account);
+/// let api = JupiterClient::new("https://lite-api.jup.ag").with_transaction_policy(policy);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionPolicy {
+    pub programs: ProgramAllowlist,
+
+    /// Accounts, besides the signers, the transaction may write to. `None` allows any.
+    pub writable_accounts: Option<HashSet<String>>,
+}
+
+impl Default for TransactionPolicy {
+    /// Programs of [`ProgramAllowlist::jupiter_default`], any writable account.
+    fn default() -> Self {
+        Self::new(ProgramAllowlist::jupiter_default())
+    }
+}
+
+impl TransactionPolicy {
+    pub fn new(programs: ProgramAllowlist) -> Self {
+        Self {
+            programs,
+            writable_accounts: None,
+        }
+    }
+
+    /// Programs of [`ProgramAllowlist::ultra_default`], any writable account.
+    pub fn ultra() -> Self {
+        Self::new(ProgramAllowlist::ultra_default())
+    }
+
+    /// Programs of [`ProgramAllowlist::trigger_default`], any writable account.
+    pub fn trigger() -> Self {
+        Self::new(ProgramAllowlist::trigger_default())
+    }
+
+    /// Programs of [`ProgramAllowlist::recurring_default`], any writable account.
+    pub fn recurring() -> Self {
+        Self::new(ProgramAllowlist::recurring_default())
+    }
+
+    /// Adds a program ID to the program allowlist.
+    pub fn allow_program(mut self, program_id: impl Into<String>) -> Self {
+        self.programs = self.programs.allow(program_id);
+        self
+    }
+
+    /// Adds an account to the writable allowlist, enabling it if unset.
+    pub fn allow_writable(mut self, account: impl Into<String>) -> Self {
+        self.writable_accounts
+            .get_or_insert_with(HashSet::new)
+            .insert(account.into());
+        self
+    }
+
+    /// Checks the transaction against the policy.
+    ///
+    /// # Errors
+    /// `PolicyViolation` describing the first rule broken.
+    pub fn verify(&self, summary: &TransactionSummary) -> Result<(), JupiterClientError> {
+        self.programs.verify(summary)?;
+
+        let Some(allowed) = &self.writable_accounts else {
+            return Ok(());
+        };
+
+        let disallowed: Vec<&str> = summary
+            .writable_accounts
+            .iter()
+            .filter(|account| !summary.signers.contains(account) && !allowed.contains(*account))
+            .map(String::as_str)
+            .collect();
+        if !disallowed.is_empty() {
+            return Err(JupiterClientError::PolicyViolation(format!(
+                "transaction writes to accounts outside the allowlist: {}",
+                disallowed.join(", ")
+            )));
+        }
+
+        let unresolved: Vec<&str> = summary
+            .lookup_tables
+            .iter()
+            .filter(|lookup| !lookup.writable_indexes.is_empty())
+            .map(|lookup| lookup.account_key.as_str())
+            .collect();
+        if !unresolved.is_empty() {
+            return Err(JupiterClientError::PolicyViolation(format!(
+                "transaction writes to accounts of lookup tables that cannot be checked: {}",
+                unresolved.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Decodes a base64 transaction and verifies it, returning its summary.
+    pub fn verify_base64(
+        &self,
+        transaction: &str,
+    ) -> Result<TransactionSummary, JupiterClientError> {
+        let summary = TransactionSummary::from_base64(transaction)?;
+        self.verify(&summary)?;
+        Ok(summary)
+    }
+}
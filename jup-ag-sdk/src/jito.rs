@@ -0,0 +1,361 @@
+//! Jito bundle submission.
+//!
+//! A bundle is a list of up to five signed transactions executed atomically and in order by
+//! Jito-enabled validators, and kept out of the public mempool. Validators only consider
+//! bundles paying a tip to one of the Jito tip accounts. Requires the `jito` feature.
+
+use std::str::FromStr;
+
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_message::{Message, VersionedMessage, compiled_instruction::CompiledInstruction};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+
+use crate::{
+    codec::{self, MAX_TRANSACTION_SIZE, VersionedTransaction},
+    compute_budget::{message_parts, static_account_index},
+    error::JupiterClientError,
+    introspection::SYSTEM_PROGRAM_ID,
+    rpc::{RpcResponse, SolanaRpc},
+    signer::TransactionSigner,
+};
+
+/// Mainnet block engine, routing to the closest region.
+pub const JITO_MAINNET_BLOCK_ENGINE: &str = "https://mainnet.block-engine.jito.wtf";
+
+/// Maximum number of transactions in a bundle.
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Minimum tip accepted by the block engines, in lamports.
+pub const MIN_JITO_TIP_LAMPORTS: u64 = 1_000;
+
+/// Mainnet tip accounts, also returned by [`JitoClient::get_tip_accounts`].
+pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+
+/// Status of a recently submitted bundle, from `getInflightBundleStatuses`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InflightBundleStatus {
+    pub bundle_id: String,
+
+    /// `Invalid`, `Pending`, `Failed` or `Landed`.
+    pub status: String,
+
+    /// The slot the bundle landed in, if it did.
+    #[serde(default)]
+    pub landed_slot: Option<u64>,
+}
+
+impl InflightBundleStatus {
+    pub fn is_landed(&self) -> bool {
+        self.status == "Landed"
+    }
+}
+
+/// A client for the Jito block engine JSON-RPC API.
+///
+/// Bundles are sent to every configured block engine, e.g. several regions.
+///
+/// # Example
+///
+/// ```
+/// let jito = JitoClient::new(JITO_MAINNET_BLOCK_ENGINE);
+///
+/// let swap = api.get_swap_transaction(&payload).await?;
+/// let bundle_id = jito
+///     .send_tipped(&swap.swap_transaction, &keypair, 10_000)
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct JitoClient {
+    pub block_engines: Vec<SolanaRpc>,
+}
+
+impl JitoClient {
+    /// Creates a client for one block engine, e.g. [`JITO_MAINNET_BLOCK_ENGINE`].
+    pub fn new(block_engine_url: &str) -> Self {
+        Self::with_block_engines([block_engine_url])
+    }
+
+    /// Creates a client sending bundles to several block engines.
+    pub fn with_block_engines<I, S>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            block_engines: urls
+                .into_iter()
+                .map(|url| {
+                    SolanaRpc::new(&format!(
+                        "{}/api/v1/bundles",
+                        url.as_ref().trim_end_matches('/')
+                    ))
+                })
+                .collect(),
+        }
+    }
+
+    /// Submits signed, base64-encoded transactions as one bundle and returns its ID.
+    ///
+    /// The bundle is sent to every block engine; it is accepted if any of them accepts it.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` if there are no transactions, more than
+    ///   [`MAX_BUNDLE_TRANSACTIONS`], or no block engine.
+    /// * The error of the first block engine if every one of them refused the bundle.
+    pub async fn send_bundle(&self, transactions: &[String]) -> Result<String, JupiterClientError> {
+        if transactions.is_empty() || transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(JupiterClientError::InvalidRequest(format!(
+                "a bundle holds 1 to {MAX_BUNDLE_TRANSACTIONS} transactions, got {}",
+                transactions.len()
+            )));
+        }
+        if self.block_engines.is_empty() {
+            return Err(JupiterClientError::InvalidRequest(
+                "no block engine to send the bundle to".to_string(),
+            ));
+        }
+
+        let params = json!([transactions, { "encoding": "base64" }]);
+        let results = join_all(
+            self.block_engines
+                .iter()
+                .map(|engine| engine.call::<String>("sendBundle", params.clone())),
+        )
+        .await;
+
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok(bundle_id) => return Ok(bundle_id),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        Err(first_error.expect("at least one block engine"))
+    }
+
+    /// Adds a tip paid by `signer` to an unsigned, base64-encoded `transaction`, signs it
+    /// and submits it as a one-transaction bundle.
+    ///
+    /// The tip is an instruction of the transaction itself, see [`add_tip`], so it is only
+    /// paid if the transaction lands. Prefer this to [`JitoClient::send_with_tip`] for the
+    /// unsigned transactions returned by the Jupiter APIs.
+    ///
+    /// # Errors
+    ///
+    /// * Any error of [`add_tip`], of signing or of [`JitoClient::send_bundle`].
+    pub async fn send_tipped<S>(
+        &self,
+        transaction: &str,
+        signer: &S,
+        tip_lamports: u64,
+    ) -> Result<String, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let tipped = add_tip_base64(transaction, tip_lamports)?;
+        let signed = signer.sign_transaction(&tipped).await?;
+        self.send_bundle(&[signed.transaction]).await
+    }
+
+    /// Appends a tip transaction paid by `signer` to `transactions` and submits the bundle.
+    ///
+    /// For transactions that are already signed, which cannot take a tip instruction
+    /// without invalidating their signatures. The tip goes last, so it is only paid if
+    /// every other transaction succeeds, and uses the blockhash of the last transaction.
+    /// A bundle is not always kept whole, though: if a validator executes the tip
+    /// transaction on its own, the tip is paid without the rest. Use
+    /// [`JitoClient::send_tipped`] when the transaction is still unsigned.
+    pub async fn send_with_tip<S>(
+        &self,
+        transactions: &[String],
+        signer: &S,
+        tip_lamports: u64,
+    ) -> Result<String, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let Some(last) = transactions.last() else {
+            return Err(JupiterClientError::InvalidRequest(
+                "a bundle needs a transaction to tip for".to_string(),
+            ));
+        };
+        let tip = tip_transaction(signer, tip_lamports, &codec::decode_base64(last)?).await?;
+
+        let mut bundle = transactions.to_vec();
+        bundle.push(tip);
+        self.send_bundle(&bundle).await
+    }
+
+    /// Fetches the statuses of bundles submitted in the last five minutes.
+    ///
+    /// Only the first block engine is queried.
+    pub async fn get_inflight_bundle_statuses(
+        &self,
+        bundle_ids: &[String],
+    ) -> Result<Vec<InflightBundleStatus>, JupiterClientError> {
+        let response: RpcResponse<Vec<InflightBundleStatus>> = self
+            .first_engine()?
+            .call("getInflightBundleStatuses", json!([bundle_ids]))
+            .await?;
+        Ok(response.value)
+    }
+
+    /// Fetches the current tip accounts from the first block engine.
+    pub async fn get_tip_accounts(&self) -> Result<Vec<String>, JupiterClientError> {
+        self.first_engine()?.call("getTipAccounts", json!([])).await
+    }
+
+    fn first_engine(&self) -> Result<&SolanaRpc, JupiterClientError> {
+        self.block_engines.first().ok_or_else(|| {
+            JupiterClientError::InvalidRequest("no block engine configured".to_string())
+        })
+    }
+}
+
+/// Builds and signs a transaction transferring `tip_lamports` from `signer` to a Jito tip
+/// account, reusing the recent blockhash of `bundled`.
+///
+/// The tip account is picked from [`JITO_TIP_ACCOUNTS`] based on the blockhash, spreading
+/// tips across accounts.
+///
+/// # Errors
+///
+/// * `InvalidRequest` if `tip_lamports` is below [`MIN_JITO_TIP_LAMPORTS`].
+/// * `SigningError` if signing fails.
+pub async fn tip_transaction<S>(
+    signer: &S,
+    tip_lamports: u64,
+    bundled: &VersionedTransaction,
+) -> Result<String, JupiterClientError>
+where
+    S: TransactionSigner + ?Sized,
+{
+    check_tip(tip_lamports)?;
+
+    let blockhash = *bundled.message.recent_blockhash();
+    let tip_account = tip_account(blockhash.as_ref())?;
+    let system_program = system_program()?;
+
+    let payer = signer.address();
+    let transfer = Instruction::new_with_bytes(
+        system_program,
+        &transfer_data(tip_lamports),
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(tip_account, false),
+        ],
+    );
+
+    let message = Message::new_with_blockhash(&[transfer], Some(&payer), &blockhash);
+    let unsigned = VersionedTransaction {
+        signatures: vec![Default::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::Legacy(message),
+    };
+    let signed = signer
+        .sign_transaction(&codec::encode_base64(&unsigned)?)
+        .await?;
+    Ok(signed.transaction)
+}
+
+/// Adds a transfer of `tip_lamports` from the fee payer of an unsigned `transaction` to a
+/// Jito tip account, as its last instruction.
+///
+/// Unlike a separate [`tip_transaction`], the tip cannot be paid without the rest of the
+/// transaction. The tip account is picked from [`JITO_TIP_ACCOUNTS`] based on the
+/// blockhash and added to the transaction's accounts, with the system program, if needed.
+///
+/// # Errors
+///
+/// * `InvalidRequest` if `tip_lamports` is below [`MIN_JITO_TIP_LAMPORTS`], the
+///   transaction already carries signatures, has no room for the accounts, or would
+///   exceed [`MAX_TRANSACTION_SIZE`] with the tip. The transaction is left unchanged then.
+pub fn add_tip(
+    transaction: &mut VersionedTransaction,
+    tip_lamports: u64,
+) -> Result<(), JupiterClientError> {
+    check_tip(tip_lamports)?;
+    if transaction
+        .signatures
+        .iter()
+        .any(|signature| *signature != Signature::default())
+    {
+        return Err(JupiterClientError::InvalidRequest(
+            "cannot add a tip to a signed transaction".to_string(),
+        ));
+    }
+
+    let mut tipped = transaction.clone();
+    let tip_account = tip_account(tipped.message.recent_blockhash().as_ref())?;
+    let message = &mut tipped.message;
+    let tip_index = static_account_index(message, tip_account, true, "the tip account")?;
+    let system_index =
+        static_account_index(message, system_program()?, false, "the system program")?;
+    // the fee payer is always the first account
+    let (_, _, instructions) = message_parts(message);
+    instructions.push(CompiledInstruction::new_from_raw_parts(
+        system_index,
+        transfer_data(tip_lamports),
+        vec![0, tip_index],
+    ));
+
+    let size = codec::serialize(&tipped)?.len();
+    if size > MAX_TRANSACTION_SIZE {
+        return Err(JupiterClientError::InvalidRequest(format!(
+            "transaction would be {size} bytes with the tip, above the \
+             {MAX_TRANSACTION_SIZE} byte limit"
+        )));
+    }
+    *transaction = tipped;
+    Ok(())
+}
+
+/// Same as [`add_tip`] for a base64-encoded transaction, returning the tipped transaction.
+pub fn add_tip_base64(transaction: &str, tip_lamports: u64) -> Result<String, JupiterClientError> {
+    let mut decoded = codec::decode_base64(transaction)?;
+    add_tip(&mut decoded, tip_lamports)?;
+    codec::encode_base64(&decoded)
+}
+
+fn check_tip(tip_lamports: u64) -> Result<(), JupiterClientError> {
+    if tip_lamports < MIN_JITO_TIP_LAMPORTS {
+        return Err(JupiterClientError::InvalidRequest(format!(
+            "Jito tips must be at least {MIN_JITO_TIP_LAMPORTS} lamports, got {tip_lamports}"
+        )));
+    }
+    Ok(())
+}
+
+/// The tip account for a transaction with `blockhash`, spreading tips across accounts.
+fn tip_account(blockhash: &[u8]) -> Result<Pubkey, JupiterClientError> {
+    let index = blockhash[0] as usize % JITO_TIP_ACCOUNTS.len();
+    Pubkey::from_str(JITO_TIP_ACCOUNTS[index])
+        .map_err(|e| JupiterClientError::InvalidRequest(e.to_string()))
+}
+
+fn system_program() -> Result<Pubkey, JupiterClientError> {
+    Pubkey::from_str(SYSTEM_PROGRAM_ID)
+        .map_err(|e| JupiterClientError::InvalidRequest(e.to_string()))
+}
+
+/// System program `Transfer`: u32 instruction index 2, then the lamports.
+fn transfer_data(lamports: u64) -> Vec<u8> {
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend(lamports.to_le_bytes());
+    data
+}
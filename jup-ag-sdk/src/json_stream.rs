@@ -0,0 +1,178 @@
+//! Incremental decoding of large JSON array responses.
+
+use std::collections::VecDeque;
+
+use futures::{Stream, stream};
+use reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+#[cfg(feature = "runtime")]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    JupiterClient,
+    error::{JupiterClientError, handle_response},
+};
+
+/// Splits the bytes of a JSON array into the bytes of its elements, without buffering
+/// more than one element at a time.
+#[derive(Debug, Default)]
+struct ArraySplitter {
+    opened: bool,
+    closed: bool,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    element: Vec<u8>,
+}
+
+impl ArraySplitter {
+    /// Feeds `chunk`, returning the elements it completed.
+    fn push(&mut self, chunk: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+        let mut elements = Vec::new();
+
+        for &byte in chunk {
+            if self.closed {
+                if !byte.is_ascii_whitespace() {
+                    return Err("unexpected data after the end of the array".to_string());
+                }
+                continue;
+            }
+
+            if !self.opened {
+                match byte {
+                    b'[' => self.opened = true,
+                    byte if byte.is_ascii_whitespace() => {}
+                    _ => return Err("expected a JSON array".to_string()),
+                }
+                continue;
+            }
+
+            if self.in_string {
+                self.element.push(byte);
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+
+            match byte {
+                b',' | b']' if self.depth == 0 => {
+                    if !self.element.is_empty() {
+                        elements.push(std::mem::take(&mut self.element));
+                    }
+                    self.closed = byte == b']';
+                }
+                b'{' | b'[' => {
+                    self.depth += 1;
+                    self.element.push(byte);
+                }
+                b'}' | b']' => {
+                    self.depth = self
+                        .depth
+                        .checked_sub(1)
+                        .ok_or_else(|| "unbalanced brackets".to_string())?;
+                    self.element.push(byte);
+                }
+                b'"' => {
+                    self.in_string = true;
+                    self.element.push(byte);
+                }
+                byte if byte.is_ascii_whitespace() && self.depth == 0 => {}
+                byte => self.element.push(byte),
+            }
+        }
+
+        Ok(elements)
+    }
+}
+
+/// Sends `request` through `client` and yields the elements of the JSON array it returns
+/// as they arrive.
+pub(crate) fn json_array_stream<'a, T: DeserializeOwned + 'a>(
+    client: &'a JupiterClient,
+    request: RequestBuilder,
+) -> impl Stream<Item = Result<T, JupiterClientError>> + 'a {
+    struct State<T> {
+        request: Option<RequestBuilder>,
+        response: Option<Response>,
+        splitter: ArraySplitter,
+        pending: VecDeque<T>,
+    }
+
+    let state = State {
+        request: Some(request),
+        response: None,
+        splitter: ArraySplitter::default(),
+        pending: VecDeque::new(),
+    };
+
+    stream::try_unfold(state, move |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Ok(Some((item, state)));
+            }
+
+            if let Some(request) = state.request.take() {
+                client.throttle().await;
+                let response = request
+                    .send()
+                    .await
+                    .map_err(JupiterClientError::RequestError)?;
+                state.response = Some(handle_response(response).await?);
+            }
+
+            let Some(response) = state.response.as_mut() else {
+                return Ok(None);
+            };
+
+            match response.chunk().await? {
+                Some(chunk) => {
+                    for element in state
+                        .splitter
+                        .push(&chunk)
+                        .map_err(JupiterClientError::DeserializationError)?
+                    {
+                        let item = serde_json::from_slice(&element)
+                            .map_err(|e| JupiterClientError::DeserializationError(e.to_string()))?;
+                        state.pending.push_back(item);
+                    }
+                }
+                None if state.splitter.closed => state.response = None,
+                None => {
+                    return Err(JupiterClientError::DeserializationError(
+                        "response ended before the end of the array".to_string(),
+                    ));
+                }
+            }
+        }
+    })
+}
+
+/// Sends `request` through `client` and copies the response body into `writer` chunk by
+/// chunk, returning the number of bytes written.
+#[cfg(feature = "runtime")]
+pub(crate) async fn download_to<W: AsyncWrite + Unpin>(
+    client: &JupiterClient,
+    request: RequestBuilder,
+    mut writer: W,
+) -> Result<u64, JupiterClientError> {
+    client.throttle().await;
+    let response = request
+        .send()
+        .await
+        .map_err(JupiterClientError::RequestError)?;
+    let mut response = handle_response(response).await?;
+
+    let mut written = 0;
+    while let Some(chunk) = response.chunk().await? {
+        writer.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+    }
+    writer.flush().await?;
+
+    Ok(written)
+}
@@ -30,9 +30,54 @@
 //! }
 //! ```
 
-pub use client::JupiterClient;
-pub use error::JupiterClientError;
+pub use client::{JupiterClient, SwapApiMode};
+pub use error::{JupiterClientError, ParseEnumError, ValidationError};
 
+#[cfg(feature = "runtime")]
+pub mod arbitrage;
+#[cfg(feature = "solana")]
+pub mod broadcast;
+pub mod cache;
 pub mod client;
+#[cfg(feature = "solana")]
+pub mod codec;
+#[cfg(feature = "solana")]
+pub mod compute_budget;
+pub mod decimals;
 pub mod error;
+pub mod export;
+#[cfg(feature = "solana")]
+pub mod grid;
+mod http_cache;
+#[cfg(feature = "solana")]
+pub mod introspection;
+#[cfg(feature = "jito")]
+pub mod jito;
+mod json_stream;
+#[cfg(feature = "solana")]
+pub mod oco;
+#[cfg(feature = "solana")]
+pub mod order_manager;
+#[cfg(feature = "runtime")]
+pub mod price_watcher;
+#[cfg(feature = "solana")]
+pub mod priority_fee;
+#[cfg(feature = "solana")]
+pub mod pubkey;
+#[cfg(feature = "runtime")]
+pub mod rate_limit;
+#[cfg(feature = "runtime")]
+pub mod recurring_watcher;
+#[cfg(feature = "solana")]
+pub mod rpc;
+#[cfg(feature = "solana")]
+pub mod signer;
+#[cfg(feature = "solana")]
+pub mod stop_loss;
+pub mod token_index;
+#[cfg(feature = "solana")]
+pub mod twap;
 pub mod types;
+pub mod validation;
+#[cfg(feature = "solana")]
+pub mod wsol;
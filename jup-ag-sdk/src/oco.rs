@@ -0,0 +1,256 @@
+//! One-cancels-other emulation on top of the Trigger API.
+//!
+//! Jupiter has no native OCO orders. [`OcoManager`] places two trigger orders, polls the
+//! maker's orders until one of them leaves the active set, and cancels the other one if it
+//! filled. Requires the `solana` feature.
+
+use std::time::Duration;
+
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
+
+use crate::{
+    JupiterClient,
+    client::TriggerOrderAges,
+    error::JupiterClientError,
+    signer::TransactionSigner,
+    types::{CancelTriggerOrder, CreateTriggerOrder, PlacedTriggerOrder, TriggerOrderState},
+};
+
+/// How often [`OcoManager::run`] polls the maker's orders by default.
+pub const DEFAULT_OCO_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Something [`OcoManager::run`] observed or did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcoEvent {
+    /// `order` filled completely.
+    Filled { order: String },
+
+    /// `order` was cancelled by the manager because its sibling filled.
+    SiblingCancelled { order: String, signature: String },
+
+    /// `order` left the active set without filling, e.g. cancelled by the maker or
+    /// expired. Its sibling is left open.
+    Closed {
+        order: String,
+        status: TriggerOrderState,
+    },
+
+    /// Polling the Trigger API failed; the manager keeps polling.
+    PollFailed(String),
+}
+
+/// Watches two trigger orders and cancels one when the other fills.
+///
+/// Only complete fills count; a partially filled order is still active and keeps its
+/// sibling open.
+///
+/// Both orders are limit orders and each escrows its own input while open. To stop a loss
+/// below the market, use [`StopLossManager`](crate::stop_loss::StopLossManager) instead.
+///
+/// # Example
+///
+/// ```
+/// // sell 1 SOL at 250 USDC, or buy 1 SOL at 150 USDC, whichever fills first
+/// let sell = CreateTriggerOrder::from_price(SOL_MINT, USDC_MINT, maker, 1_000_000_000, 250.0, 9, 6)?;
+/// let buy = CreateTriggerOrder::from_price(USDC_MINT, SOL_MINT, maker, 150_000_000, 1.0 / 150.0, 6, 9)?;
+/// let (oco, mut events) = OcoManager::place(client.clone(), keypair, &sell, &buy).await?;
+///
+/// tokio::spawn(async move {
+///     while let Some(event) = events.next().await {
+///         println!("{event:?}");
+///     }
+/// });
+/// let last = oco.run().await?;
+/// ```
+pub struct OcoManager<S> {
+    client: JupiterClient,
+    signer: S,
+    maker: String,
+    orders: [String; 2],
+    poll_interval: Duration,
+    events: UnboundedSender<OcoEvent>,
+    ages: TriggerOrderAges,
+}
+
+impl<S> OcoManager<S>
+where
+    S: TransactionSigner,
+{
+    /// Places both orders with `signer` and returns a manager watching them, with the
+    /// receiving end of its event channel.
+    ///
+    /// If the second order fails to be placed, the first one stays open and the error is
+    /// returned.
+    pub async fn place(
+        client: JupiterClient,
+        signer: S,
+        first: &CreateTriggerOrder,
+        second: &CreateTriggerOrder,
+    ) -> Result<(Self, UnboundedReceiver<OcoEvent>), JupiterClientError> {
+        let first_placed = client
+            .create_and_execute_trigger_order(first, &signer)
+            .await?;
+        let second_placed = client
+            .create_and_execute_trigger_order(second, &signer)
+            .await?;
+
+        Ok(Self::watch(
+            client,
+            signer,
+            &first.maker,
+            first_placed,
+            second_placed,
+        ))
+    }
+
+    /// Returns a manager watching two orders of `maker` that are already placed.
+    pub fn watch(
+        client: JupiterClient,
+        signer: S,
+        maker: &str,
+        first: PlacedTriggerOrder,
+        second: PlacedTriggerOrder,
+    ) -> (Self, UnboundedReceiver<OcoEvent>) {
+        let (events, receiver) = unbounded();
+        let manager = Self {
+            client,
+            signer,
+            maker: maker.to_string(),
+            orders: [first.order, second.order],
+            poll_interval: DEFAULT_OCO_POLL_INTERVAL,
+            events,
+            ages: TriggerOrderAges::default(),
+        };
+        (manager, receiver)
+    }
+
+    /// Sets how often the maker's orders are polled.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// The two watched order accounts.
+    pub fn orders(&self) -> &[String; 2] {
+        &self.orders
+    }
+
+    /// Polls until one order leaves the active set, cancelling the other if it filled.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(OcoEvent::SiblingCancelled)` once an order filled and its sibling was cancelled.
+    /// * `Ok(OcoEvent::Closed)` if an order closed without filling.
+    /// * `Ok(OcoEvent::Filled)` with the second order if both filled between two polls;
+    ///   nothing is cancelled then.
+    /// * `Err` if cancelling the sibling fails; it may still be open.
+    pub async fn run(mut self) -> Result<OcoEvent, JupiterClientError> {
+        loop {
+            tokio::time::sleep(self.poll_interval).await;
+
+            let inactive = match self
+                .client
+                .inactive_trigger_orders(
+                    &self.maker,
+                    self.orders.iter().map(String::as_str),
+                    &mut self.ages,
+                )
+                .await
+            {
+                Ok(inactive) => inactive,
+                Err(e) => {
+                    self.emit(OcoEvent::PollFailed(e.to_string()));
+                    continue;
+                }
+            };
+            if inactive.is_empty() {
+                continue;
+            }
+
+            let closed = match self
+                .client
+                .closed_trigger_orders(&self.maker, &inactive, &mut self.ages)
+                .await
+            {
+                Ok(closed) => closed,
+                Err(e) => {
+                    self.emit(OcoEvent::PollFailed(e.to_string()));
+                    continue;
+                }
+            };
+
+            // not in history yet, the order may have just been closed; a fill wins over an
+            // order closed otherwise, which would leave its sibling open
+            let Some(index) = (0..2)
+                .find(|&index| {
+                    closed.get(&self.orders[index]) == Some(&TriggerOrderState::Completed)
+                })
+                .or_else(|| (0..2).find(|&index| closed.contains_key(&self.orders[index])))
+            else {
+                continue;
+            };
+            let status = closed[&self.orders[index]].clone();
+            let order = &self.orders[index];
+            let sibling = &self.orders[1 - index];
+
+            if status != TriggerOrderState::Completed {
+                let event = OcoEvent::Closed {
+                    order: order.clone(),
+                    status,
+                };
+                self.emit(event.clone());
+                return Ok(event);
+            }
+
+            // the sibling left the active set too, there is nothing left to cancel
+            if inactive.contains(sibling) {
+                let Some(sibling_status) = closed.get(sibling).cloned() else {
+                    // not in history yet, check it again on the next poll
+                    continue;
+                };
+                self.emit(OcoEvent::Filled {
+                    order: order.clone(),
+                });
+                let event = match sibling_status {
+                    TriggerOrderState::Completed => OcoEvent::Filled {
+                        order: sibling.clone(),
+                    },
+                    status => OcoEvent::Closed {
+                        order: sibling.clone(),
+                        status,
+                    },
+                };
+                self.emit(event.clone());
+                return Ok(event);
+            }
+
+            self.emit(OcoEvent::Filled {
+                order: order.clone(),
+            });
+
+            let signature = self.cancel(sibling).await?;
+            let event = OcoEvent::SiblingCancelled {
+                order: sibling.clone(),
+                signature,
+            };
+            self.emit(event.clone());
+            return Ok(event);
+        }
+    }
+
+    fn emit(&self, event: OcoEvent) {
+        // the receiver may have been dropped, the manager keeps working without it
+        let _ = self.events.unbounded_send(event);
+    }
+
+    async fn cancel(&self, order: &str) -> Result<String, JupiterClientError> {
+        let executed = self
+            .client
+            .cancel_and_execute_trigger_order(
+                &CancelTriggerOrder::new(&self.maker, order),
+                &self.signer,
+            )
+            .await?;
+        Ok(executed.signature)
+    }
+}
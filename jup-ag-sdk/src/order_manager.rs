@@ -0,0 +1,357 @@
+//! One view over a wallet's trigger and recurring orders.
+//!
+//! Listing everything a wallet has open on Jupiter takes separate, paginated calls to the
+//! Trigger and Recurring APIs, which return different order types. [`OrderManager`] merges
+//! them into [`OpenOrder`]s, cancels them in bulk and, through [`OrderWatcher`], polls them
+//! in a single loop. Requires the `solana` feature.
+
+use std::{collections::HashMap, time::Duration};
+
+use futures::{
+    TryStreamExt,
+    channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    signer::TransactionSigner,
+    types::{
+        CancelRecurringOrderRequest, CancelTriggerOrders, GetRecurringOrders, GetTriggerOrders,
+        OrderStatus, RecurringOrder, RecurringOrderType, TriggerOrder,
+    },
+};
+
+/// How often [`OrderWatcher::run`] polls the wallet's orders by default.
+pub const DEFAULT_ORDER_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// The API and type of an [`OpenOrder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderKind {
+    /// A limit order of the Trigger API.
+    Trigger,
+    /// A time-based (DCA) order of the Recurring API.
+    RecurringTime,
+    /// A price-based order of the Recurring API.
+    RecurringPrice,
+}
+
+/// An open trigger or recurring order.
+///
+/// Amounts are raw, `None` if the API returned an unparsable value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrder {
+    pub kind: OrderKind,
+
+    /// The order account.
+    pub order_key: String,
+    pub input_mint: String,
+    pub output_mint: String,
+
+    /// Input amount deposited into the order.
+    pub in_amount: Option<u64>,
+
+    /// Input amount not traded or withdrawn yet.
+    pub in_remaining: Option<u64>,
+
+    /// Output amount received so far.
+    pub out_received: Option<u64>,
+
+    /// Number of trades so far.
+    pub trades: usize,
+    pub created_at: String,
+}
+
+impl OpenOrder {
+    pub fn is_trigger(&self) -> bool {
+        self.kind == OrderKind::Trigger
+    }
+
+    pub fn is_recurring(&self) -> bool {
+        !self.is_trigger()
+    }
+}
+
+impl From<&TriggerOrder> for OpenOrder {
+    fn from(order: &TriggerOrder) -> Self {
+        let making: Option<u64> = order.raw_making_amount.parse().ok();
+        let remaining: Option<u64> = order.raw_remaining_making_amount.parse().ok();
+        let out_received = order.trades.iter().try_fold(0u64, |total, trade| {
+            total.checked_add(trade.raw_output_amount.parse().ok()?)
+        });
+
+        Self {
+            kind: OrderKind::Trigger,
+            order_key: order.order_key.clone(),
+            input_mint: order.input_mint.clone(),
+            output_mint: order.output_mint.clone(),
+            in_amount: making,
+            in_remaining: remaining,
+            out_received,
+            trades: order.trades.len(),
+            created_at: order.created_at.clone(),
+        }
+    }
+}
+
+impl From<&RecurringOrder> for OpenOrder {
+    fn from(order: &RecurringOrder) -> Self {
+        let (kind, created_at) = match order {
+            RecurringOrder::Time(order) => (OrderKind::RecurringTime, order.created_at.clone()),
+            RecurringOrder::Price(order) => (OrderKind::RecurringPrice, order.created_at.clone()),
+        };
+        let deposited = order.raw_in_deposited_u64();
+        let in_remaining = deposited.and_then(|deposited| {
+            deposited
+                .checked_sub(order.raw_in_used_u64()?)?
+                .checked_sub(order.raw_in_withdrawn_u64()?)
+        });
+
+        Self {
+            kind,
+            order_key: order.order_key().to_string(),
+            input_mint: order.input_mint().to_string(),
+            output_mint: order.output_mint().to_string(),
+            in_amount: deposited,
+            in_remaining,
+            out_received: order.raw_out_received_u64(),
+            trades: order.trades().len(),
+            created_at,
+        }
+    }
+}
+
+/// Lists, cancels and watches the trigger and recurring orders of one wallet.
+///
+/// # Example
+///
+/// ```
+/// let manager = OrderManager::new(client.clone(), user);
+/// for order in manager.open_orders().await? {
+///     println!("{:?} {} {} -> {}", order.kind, order.order_key, order.input_mint, order.output_mint);
+/// }
+///
+/// let signatures = manager.cancel_all(&keypair).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderManager {
+    client: JupiterClient,
+    user: String,
+}
+
+impl OrderManager {
+    pub fn new(client: JupiterClient, user: &str) -> Self {
+        Self {
+            client,
+            user: user.to_string(),
+        }
+    }
+
+    /// The wallet whose orders are managed.
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Fetches every open trigger and recurring order of the wallet, across all pages.
+    ///
+    /// Trigger orders come first, then recurring orders, each in API order.
+    pub async fn open_orders(&self) -> Result<Vec<OpenOrder>, JupiterClientError> {
+        let trigger_params = GetTriggerOrders::new(&self.user, OrderStatus::Active);
+        let recurring_params =
+            GetRecurringOrders::new(RecurringOrderType::All, OrderStatus::Active, &self.user);
+
+        let trigger: Vec<TriggerOrder> = self
+            .client
+            .get_trigger_orders_stream(&trigger_params)
+            .try_collect()
+            .await?;
+        let recurring: Vec<RecurringOrder> = self
+            .client
+            .get_recurring_orders_stream(&recurring_params)
+            .try_collect()
+            .await?;
+
+        Ok(trigger
+            .iter()
+            .map(OpenOrder::from)
+            .chain(recurring.iter().map(OpenOrder::from))
+            .collect())
+    }
+
+    /// Cancels `orders`, signing with `signer`, and returns the cancellation signatures.
+    ///
+    /// Trigger orders are cancelled in batches, recurring orders one by one. Stops at the
+    /// first failure: orders cancelled until then stay cancelled.
+    ///
+    /// # Errors
+    ///
+    /// * `TransactionFailed` if a cancellation is not executed successfully.
+    pub async fn cancel<S>(
+        &self,
+        orders: &[OpenOrder],
+        signer: &S,
+    ) -> Result<Vec<String>, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let mut signatures = Vec::new();
+
+        let trigger: Vec<String> = orders
+            .iter()
+            .filter(|order| order.is_trigger())
+            .map(|order| order.order_key.clone())
+            .collect();
+        if !trigger.is_empty() {
+            let batches = self
+                .client
+                .cancel_trigger_orders(&CancelTriggerOrders::new(&self.user, trigger))
+                .await?;
+            for batch in batches {
+                let transactions = match batch.transactions {
+                    Some(transactions) if !transactions.is_empty() => transactions,
+                    _ => vec![batch.transaction],
+                };
+                for transaction in transactions {
+                    let executed = self
+                        .client
+                        .sign_and_execute_trigger(&batch.request_id, &transaction, signer)
+                        .await?;
+                    signatures.push(executed.signature);
+                }
+            }
+        }
+
+        for order in orders {
+            let recurring_type = match order.kind {
+                OrderKind::Trigger => continue,
+                OrderKind::RecurringTime => RecurringOrderType::Time,
+                OrderKind::RecurringPrice => RecurringOrderType::Price,
+            };
+            let cancel =
+                CancelRecurringOrderRequest::new(&order.order_key, recurring_type, &self.user);
+            let executed = self
+                .client
+                .cancel_and_execute_recurring_order(&cancel, signer)
+                .await?;
+            signatures.push(executed.signature);
+        }
+
+        Ok(signatures)
+    }
+
+    /// Cancels every open order of the wallet. See [`OrderManager::cancel`].
+    pub async fn cancel_all<S>(&self, signer: &S) -> Result<Vec<String>, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let orders = self.open_orders().await?;
+        self.cancel(&orders, signer).await
+    }
+
+    /// Returns a watcher polling the wallet's open orders, with the receiving end of its
+    /// event channel.
+    pub fn watch(&self) -> (OrderWatcher, UnboundedReceiver<OrderEvent>) {
+        let (events, receiver) = unbounded();
+        let watcher = OrderWatcher {
+            manager: self.clone(),
+            known: None,
+            poll_interval: DEFAULT_ORDER_POLL_INTERVAL,
+            events,
+        };
+        (watcher, receiver)
+    }
+}
+
+/// Something [`OrderWatcher`] observed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderEvent {
+    /// The order is open. Reported for every open order on the first poll, then for new
+    /// orders.
+    Opened(OpenOrder),
+
+    /// The order traded `new_trades` times since the last poll.
+    Traded { order: OpenOrder, new_trades: usize },
+
+    /// The order is no longer open: it was fully executed, cancelled or expired. Carries
+    /// its last known state.
+    Closed(OpenOrder),
+
+    /// Polling failed; the watcher keeps polling.
+    PollFailed(String),
+}
+
+/// Polls the open trigger and recurring orders of a wallet and reports changes.
+///
+/// # Example
+///
+/// ```
+/// let (watcher, mut events) = OrderManager::new(client.clone(), user).watch();
+///
+/// tokio::spawn(watcher.run());
+/// while let Some(event) = events.next().await {
+///     println!("{event:?}");
+/// }
+/// ```
+pub struct OrderWatcher {
+    manager: OrderManager,
+    /// Open orders by order account, `None` until the first poll.
+    known: Option<HashMap<String, OpenOrder>>,
+    poll_interval: Duration,
+    events: UnboundedSender<OrderEvent>,
+}
+
+impl OrderWatcher {
+    /// Sets how often the wallet's orders are polled.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Polls until the event receiver is dropped, sending events to the channel.
+    pub async fn run(mut self) {
+        loop {
+            match self.poll().await {
+                Ok(events) => events.into_iter().for_each(|event| self.emit(event)),
+                Err(e) => self.emit(OrderEvent::PollFailed(e.to_string())),
+            }
+            if self.events.is_closed() {
+                return;
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Fetches the open orders once and returns what changed since the last poll.
+    pub async fn poll(&mut self) -> Result<Vec<OrderEvent>, JupiterClientError> {
+        let open = self.manager.open_orders().await?;
+        let mut known = self.known.take().unwrap_or_default();
+        let mut events = Vec::new();
+
+        let mut current = HashMap::with_capacity(open.len());
+        for order in open {
+            match known.remove(&order.order_key) {
+                None => events.push(OrderEvent::Opened(order.clone())),
+                Some(previous) if order.trades > previous.trades => {
+                    events.push(OrderEvent::Traded {
+                        new_trades: order.trades - previous.trades,
+                        order: order.clone(),
+                    });
+                }
+                Some(_) => {}
+            }
+            current.insert(order.order_key.clone(), order);
+        }
+        events.extend(known.into_values().map(OrderEvent::Closed));
+
+        self.known = Some(current);
+        Ok(events)
+    }
+
+    fn emit(&self, event: OrderEvent) {
+        // the receiver may have been dropped, `run` stops after this poll
+        let _ = self.events.unbounded_send(event);
+    }
+}
@@ -0,0 +1,228 @@
+//! Price threshold and movement alerts.
+//!
+//! [`PriceWatcher`] polls [`JupiterClient::get_tokens_price`] for a set of mints and reports
+//! threshold crossings and large moves over a time window. Requires the `runtime` feature.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
+
+use crate::{JupiterClient, error::JupiterClientError};
+
+/// How often [`PriceWatcher::run`] polls prices by default.
+pub const DEFAULT_PRICE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Something [`PriceWatcher`] observed. Prices are in USD.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceEvent {
+    /// The price of `mint` rose to or above `threshold`.
+    CrossedAbove {
+        mint: String,
+        threshold: f64,
+        price: f64,
+    },
+
+    /// The price of `mint` fell to or below `threshold`.
+    CrossedBelow {
+        mint: String,
+        threshold: f64,
+        price: f64,
+    },
+
+    /// The price of `mint` changed by `change_pct` percent within `window`.
+    Moved {
+        mint: String,
+        change_pct: f64,
+        window: Duration,
+        price: f64,
+    },
+
+    /// Fetching prices failed; the watcher keeps polling.
+    PollFailed(String),
+}
+
+/// Alerts when prices move by more than `pct` percent within `window`.
+#[derive(Debug, Clone, Copy)]
+struct MoveAlert {
+    pct: f64,
+    window: Duration,
+}
+
+#[derive(Debug, Default)]
+struct Tracked {
+    above: Vec<f64>,
+    below: Vec<f64>,
+    /// Prices seen within the move alert window, oldest first.
+    history: VecDeque<(Instant, f64)>,
+}
+
+/// Polls the prices of a set of mints and reports threshold crossings and large moves.
+///
+/// Thresholds are reported when crossed between two polls, not when a price is already
+/// past them at the first poll.
+///
+/// # Example
+///
+/// ```
+/// let (watcher, mut events) = PriceWatcher::new(client.clone());
+/// let watcher = watcher
+///     .above(SOL_MINT, 200.0)
+///     .below(SOL_MINT, 120.0)
+///     .move_alert(5.0, Duration::from_secs(15 * 60));
+///
+/// tokio::spawn(watcher.run());
+/// while let Some(event) = events.next().await {
+///     println!("{event:?}");
+/// }
+/// ```
+pub struct PriceWatcher {
+    client: JupiterClient,
+    mints: HashMap<String, Tracked>,
+    last_prices: HashMap<String, f64>,
+    move_alert: Option<MoveAlert>,
+    poll_interval: Duration,
+    events: UnboundedSender<PriceEvent>,
+}
+
+impl PriceWatcher {
+    /// Returns a watcher with no alerts, with the receiving end of its event channel.
+    pub fn new(client: JupiterClient) -> (Self, UnboundedReceiver<PriceEvent>) {
+        let (events, receiver) = unbounded();
+        let watcher = Self {
+            client,
+            mints: HashMap::new(),
+            last_prices: HashMap::new(),
+            move_alert: None,
+            poll_interval: DEFAULT_PRICE_POLL_INTERVAL,
+            events,
+        };
+        (watcher, receiver)
+    }
+
+    /// Alerts when the price of `mint` rises to or above `threshold`.
+    pub fn above(mut self, mint: &str, threshold: f64) -> Self {
+        self.mints
+            .entry(mint.to_string())
+            .or_default()
+            .above
+            .push(threshold);
+        self
+    }
+
+    /// Alerts when the price of `mint` falls to or below `threshold`.
+    pub fn below(mut self, mint: &str, threshold: f64) -> Self {
+        self.mints
+            .entry(mint.to_string())
+            .or_default()
+            .below
+            .push(threshold);
+        self
+    }
+
+    /// Watches `mint` for move alerts only.
+    pub fn watch(mut self, mint: &str) -> Self {
+        self.mints.entry(mint.to_string()).or_default();
+        self
+    }
+
+    /// Alerts when the price of any watched mint changes by `pct` percent or more within
+    /// `window`. After an alert, the mint's window starts over.
+    pub fn move_alert(mut self, pct: f64, window: Duration) -> Self {
+        self.move_alert = Some(MoveAlert { pct, window });
+        self
+    }
+
+    /// Sets how often prices are polled.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Mints watched.
+    pub fn mints(&self) -> impl Iterator<Item = &str> {
+        self.mints.keys().map(String::as_str)
+    }
+
+    /// Polls until the event receiver is dropped, sending events to the channel.
+    pub async fn run(mut self) {
+        while !self.events.is_closed() {
+            match self.poll().await {
+                Ok(events) => events.into_iter().for_each(|event| self.emit(event)),
+                Err(e) => self.emit(PriceEvent::PollFailed(e.to_string())),
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Fetches prices once and returns the alerts they trigger.
+    pub async fn poll(&mut self) -> Result<Vec<PriceEvent>, JupiterClientError> {
+        let mints: Vec<String> = self.mints.keys().cloned().collect();
+        let prices = self.client.get_tokens_price(&mints).await?;
+        let now = Instant::now();
+
+        let mut events = Vec::new();
+        for (mint, tracked) in &mut self.mints {
+            let Some(price) = prices.get(mint).map(|price| price.usd_price) else {
+                continue;
+            };
+
+            if let Some(last) = self.last_prices.insert(mint.clone(), price) {
+                for &threshold in &tracked.above {
+                    if last < threshold && price >= threshold {
+                        events.push(PriceEvent::CrossedAbove {
+                            mint: mint.clone(),
+                            threshold,
+                            price,
+                        });
+                    }
+                }
+                for &threshold in &tracked.below {
+                    if last > threshold && price <= threshold {
+                        events.push(PriceEvent::CrossedBelow {
+                            mint: mint.clone(),
+                            threshold,
+                            price,
+                        });
+                    }
+                }
+            }
+
+            let Some(alert) = self.move_alert else {
+                continue;
+            };
+            while tracked
+                .history
+                .front()
+                .is_some_and(|(at, _)| now.duration_since(*at) > alert.window)
+            {
+                tracked.history.pop_front();
+            }
+
+            if let Some(&(_, start)) = tracked.history.front()
+                && start > 0.0
+            {
+                let change_pct = (price - start) / start * 100.0;
+                if change_pct.abs() >= alert.pct {
+                    events.push(PriceEvent::Moved {
+                        mint: mint.clone(),
+                        change_pct,
+                        window: alert.window,
+                        price,
+                    });
+                    tracked.history.clear();
+                }
+            }
+            tracked.history.push_back((now, price));
+        }
+        Ok(events)
+    }
+
+    fn emit(&self, event: PriceEvent) {
+        // the receiver may have been dropped, `run` stops at the next poll
+        let _ = self.events.unbounded_send(event);
+    }
+}
@@ -0,0 +1,171 @@
+//! Priority fee estimation.
+//!
+//! A [`PriorityFeeProvider`] estimates the compute unit price needed to land a transaction.
+//! [`RecentFeeEstimator`] uses `getRecentPrioritizationFees`; implement the trait to use a
+//! provider-specific API instead. The estimate can be passed to
+//! `SwapRequest::compute_unit_price_micro_lamports`, the trigger order `compute_unit_price`
+//! or a [`ComputeBudgetOverride`]. Requires the `solana` feature.
+
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    compute_budget::ComputeBudgetOverride, error::JupiterClientError,
+    introspection::TransactionSummary, rpc::SolanaRpc, types::ComputeUnitPrice,
+};
+
+/// Maximum number of accounts accepted by `getRecentPrioritizationFees`.
+pub const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// Default percentile of recent fees used by [`RecentFeeEstimator`].
+pub const DEFAULT_FEE_PERCENTILE: u8 = 75;
+
+/// An estimated compute unit price.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityFeeEstimate {
+    /// In micro-lamports per compute unit.
+    pub unit_price_micro_lamports: u64,
+}
+
+impl PriorityFeeEstimate {
+    /// The priority fee paid for `unit_limit` compute units, in lamports, rounded up.
+    pub fn fee_lamports(&self, unit_limit: u32) -> u64 {
+        let micro_lamports = u128::from(unit_limit) * u128::from(self.unit_price_micro_lamports);
+        u64::try_from(micro_lamports.div_ceil(1_000_000)).unwrap_or(u64::MAX)
+    }
+
+    /// A [`ComputeBudgetOverride`] setting the estimated unit price.
+    pub fn compute_budget(&self) -> ComputeBudgetOverride {
+        ComputeBudgetOverride::new().unit_price(self.unit_price_micro_lamports)
+    }
+}
+
+impl From<PriorityFeeEstimate> for ComputeBudgetOverride {
+    fn from(estimate: PriorityFeeEstimate) -> Self {
+        estimate.compute_budget()
+    }
+}
+
+impl From<PriorityFeeEstimate> for ComputeUnitPrice {
+    fn from(estimate: PriorityFeeEstimate) -> Self {
+        ComputeUnitPrice::MicroLamports(estimate.unit_price_micro_lamports)
+    }
+}
+
+/// Estimates the compute unit price needed to land a transaction.
+pub trait PriorityFeeProvider: Send + Sync {
+    /// Estimates the price for a transaction writing to `accounts`.
+    fn estimate(
+        &self,
+        accounts: &[String],
+    ) -> impl Future<Output = Result<PriorityFeeEstimate, JupiterClientError>> + Send;
+
+    /// Estimates the price for a base64-encoded transaction, based on the accounts it writes
+    /// to.
+    ///
+    /// Accounts loaded from address lookup tables are not known without fetching the
+    /// tables and are left out.
+    fn estimate_for_transaction(
+        &self,
+        transaction: &str,
+    ) -> impl Future<Output = Result<PriorityFeeEstimate, JupiterClientError>> + Send {
+        async move {
+            let mut accounts = TransactionSummary::from_base64(transaction)?.writable_accounts;
+            accounts.truncate(MAX_PRIORITIZATION_FEE_ACCOUNTS);
+            self.estimate(&accounts).await
+        }
+    }
+}
+
+/// A [`PriorityFeeProvider`] taking a percentile of the fees paid in the last 150 slots, as
+/// returned by `getRecentPrioritizationFees`.
+///
+/// # Example
+///
+/// ```
+/// let estimator = RecentFeeEstimator::new(SolanaRpc::new("https://api.mainnet-beta.solana.com"))
+///     .percentile(90)
+///     .max_micro_lamports(1_000_000);
+///
+/// let fee = estimator.estimate(&[JUPITER_V6_PROGRAM_ID.to_string()]).await?;
+/// let payload = SwapRequest::new(user, user, quote)
+///     .compute_unit_price_micro_lamports(fee.unit_price_micro_lamports);
+///
+/// let fee = estimator.estimate_for_transaction(&transaction).await?;
+/// let transaction = fee.compute_budget().apply_base64(&transaction)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecentFeeEstimator {
+    pub rpc: SolanaRpc,
+
+    /// Percentile of the per-slot fees, 0 to 100. Default: [`DEFAULT_FEE_PERCENTILE`].
+    pub percentile: u8,
+
+    /// Lower bound of the estimate, in micro-lamports. Default: 0.
+    pub min_micro_lamports: u64,
+
+    /// Upper bound of the estimate, in micro-lamports, if any.
+    pub max_micro_lamports: Option<u64>,
+}
+
+impl RecentFeeEstimator {
+    pub fn new(rpc: SolanaRpc) -> Self {
+        Self {
+            rpc,
+            percentile: DEFAULT_FEE_PERCENTILE,
+            min_micro_lamports: 0,
+            max_micro_lamports: None,
+        }
+    }
+
+    /// Sets the percentile of recent fees to use, capped at 100.
+    pub fn percentile(mut self, percentile: u8) -> Self {
+        self.percentile = percentile.min(100);
+        self
+    }
+
+    /// Sets the lowest estimate returned, e.g. when recent slots paid no fee.
+    pub fn min_micro_lamports(mut self, micro_lamports: u64) -> Self {
+        self.min_micro_lamports = micro_lamports;
+        self
+    }
+
+    /// Caps the estimate.
+    pub fn max_micro_lamports(mut self, micro_lamports: u64) -> Self {
+        self.max_micro_lamports = Some(micro_lamports);
+        self
+    }
+}
+
+impl PriorityFeeProvider for RecentFeeEstimator {
+    async fn estimate(
+        &self,
+        accounts: &[String],
+    ) -> Result<PriorityFeeEstimate, JupiterClientError> {
+        let mut fees: Vec<u64> = self
+            .rpc
+            .get_recent_prioritization_fees(accounts)
+            .await?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+        fees.sort_unstable();
+
+        // nearest-rank percentile
+        let rank = (fees.len() * usize::from(self.percentile)).div_ceil(100);
+        let fee = fees
+            .get(rank.saturating_sub(1))
+            .copied()
+            .unwrap_or_default();
+
+        let mut unit_price_micro_lamports = fee.max(self.min_micro_lamports);
+        if let Some(max) = self.max_micro_lamports {
+            unit_price_micro_lamports = unit_price_micro_lamports.min(max);
+        }
+        Ok(PriorityFeeEstimate {
+            unit_price_micro_lamports,
+        })
+    }
+}
@@ -0,0 +1,141 @@
+//! Typed Solana addresses.
+//!
+//! Request and response types keep addresses as base58 strings. The constructors and
+//! accessors here take and return [`Pubkey`] instead, so a malformed address fails where it
+//! is parsed rather than as a `400` from the API. Requires the `solana` feature.
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub use solana_pubkey::Pubkey;
+
+use crate::{
+    error::ValidationError,
+    types::{CreateTriggerOrder, QuoteRequest, QuoteResponse, TokenInfo, UltraOrderRequest},
+};
+
+/// Parses the base58 address `value` of `field`.
+///
+/// # Example
+///
+/// ```
+/// let mint = parse_pubkey("input_mint", "So11111111111111111111111111111111111111112")?;
+/// assert!(parse_pubkey("input_mint", "not a mint").is_err());
+/// ```
+pub fn parse_pubkey(field: &'static str, value: &str) -> Result<Pubkey, ValidationError> {
+    value.parse().map_err(|_| ValidationError::InvalidAddress {
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// Serializes a [`Pubkey`] as a base58 string, for `#[serde(with = "...")]`.
+///
+/// `Pubkey`'s own serde implementation uses a byte array, which the Jupiter APIs and most
+/// config files don't expect.
+///
+/// # Example
+///
+/// ```
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "jup_ag_sdk::pubkey::base58")]
+///     wallet: Pubkey,
+///
+///     #[serde(with = "jup_ag_sdk::pubkey::base58::option", default)]
+///     referral_account: Option<Pubkey>,
+/// }
+/// ```
+pub mod base58 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(pubkey)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid base58 address: {value:?}")))
+    }
+
+    /// Same as [`base58`](super::base58) for `Option<Pubkey>`.
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(
+            pubkey: &Option<Pubkey>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match pubkey {
+                Some(pubkey) => serializer.collect_str(pubkey),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Pubkey>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] Pubkey);
+
+            Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(pubkey)| pubkey))
+        }
+    }
+}
+
+impl QuoteRequest {
+    /// Same as [`QuoteRequest::new`] with typed mints.
+    pub fn from_pubkeys(input_mint: &Pubkey, output_mint: &Pubkey, amount: u64) -> Self {
+        Self::new(&input_mint.to_string(), &output_mint.to_string(), amount)
+    }
+}
+
+impl UltraOrderRequest {
+    /// Same as [`UltraOrderRequest::new`] with typed mints.
+    pub fn from_pubkeys(input_mint: &Pubkey, output_mint: &Pubkey, amount: u64) -> Self {
+        Self::new(&input_mint.to_string(), &output_mint.to_string(), amount)
+    }
+
+    /// Same as [`UltraOrderRequest::taker`] with a typed wallet.
+    pub fn taker_pubkey(self, taker: &Pubkey) -> Self {
+        self.taker(&taker.to_string())
+    }
+}
+
+impl CreateTriggerOrder {
+    /// Same as [`CreateTriggerOrder::new`] with typed mints and wallets.
+    pub fn from_pubkeys(
+        input_mint: &Pubkey,
+        output_mint: &Pubkey,
+        maker: &Pubkey,
+        payer: &Pubkey,
+        making_amount: u64,
+        taking_amount: u64,
+    ) -> Self {
+        Self::new(
+            &input_mint.to_string(),
+            &output_mint.to_string(),
+            &maker.to_string(),
+            &payer.to_string(),
+            making_amount,
+            taking_amount,
+        )
+    }
+}
+
+impl QuoteResponse {
+    pub fn input_mint_pubkey(&self) -> Result<Pubkey, ValidationError> {
+        parse_pubkey("input_mint", &self.input_mint)
+    }
+
+    pub fn output_mint_pubkey(&self) -> Result<Pubkey, ValidationError> {
+        parse_pubkey("output_mint", &self.output_mint)
+    }
+}
+
+impl TokenInfo {
+    pub fn mint_pubkey(&self) -> Result<Pubkey, ValidationError> {
+        parse_pubkey("id", &self.id)
+    }
+}
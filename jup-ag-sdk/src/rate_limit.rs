@@ -0,0 +1,71 @@
+//! Client-side request rate limiting.
+//!
+//! Jupiter rate limits API keys per time window and answers `429 Too Many Requests` past
+//! the limit. [`RateLimiter`] spaces out the client's requests so bursts such as
+//! [`JupiterClient::get_quotes`](crate::JupiterClient::get_quotes) stay under it, instead
+//! of failing and being retried. Requires the `runtime` feature.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Allows `requests` requests per `per`, bursting up to `requests` at once.
+///
+/// Waiting callers are served in the order they asked: each reserves the next free slot
+/// before sleeping, so concurrent requests cannot overshoot the limit.
+///
+/// # Example
+///
+/// ```
+/// // 600 requests per minute, i.e. 10 per second on average
+/// let api = JupiterClient::new("https://api.jup.ag")
+///     .with_api_key("your-api-key")
+///     .with_rate_limiter(RateLimiter::new(600, Duration::from_secs(60)));
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Time between two requests at the sustained rate.
+    interval: Duration,
+
+    /// How far ahead of the sustained rate a burst may run.
+    burst: Duration,
+
+    /// When the next request is due at the sustained rate.
+    next: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// A limiter allowing `requests` requests per `per`. A `requests` of `0` is treated
+    /// as `1`.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        let requests = requests.max(1);
+        let interval = per / requests;
+        Self {
+            interval,
+            burst: interval * (requests - 1),
+            next: Mutex::new(None),
+        }
+    }
+
+    /// A limiter allowing `requests` requests per second.
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Waits until a request may be sent and counts it.
+    pub async fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Reserves the next slot at `now` and returns how long to wait for it.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut next = self.next.lock().unwrap();
+        let due = next.map_or(now, |next| next.max(now));
+        *next = Some(due + self.interval);
+        due.saturating_duration_since(now + self.burst)
+    }
+}
@@ -0,0 +1,227 @@
+//! Fill and completion notifications for recurring orders.
+//!
+//! The Recurring API has no push notifications. [`RecurringWatcher`] polls a user's orders
+//! and reports new trades, orders leaving the active set, and orders that have not traded
+//! for longer than their interval. Requires the `runtime` feature.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use futures::{
+    TryStreamExt,
+    channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+};
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    types::{GetRecurringOrders, OrderStatus, RecurringOrder, RecurringOrderType},
+};
+
+/// How often [`RecurringWatcher::run`] polls the user's orders by default.
+pub const DEFAULT_RECURRING_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Delay past an order's interval before it is reported as stalled, by default.
+pub const DEFAULT_STALL_GRACE: Duration = Duration::from_secs(300);
+
+/// Something [`RecurringWatcher`] observed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecurringEvent {
+    /// A trade of `order` landed in transaction `tx_id`.
+    Trade { order: String, tx_id: String },
+
+    /// `order` left the active set, because it was fully executed or cancelled. It is no
+    /// longer watched.
+    Completed { order: String },
+
+    /// `order` has not traded for `idle`, longer than its interval plus the stall grace.
+    /// Reported once per stall.
+    Stalled { order: String, idle: Duration },
+
+    /// Polling the Recurring API failed; the watcher keeps polling.
+    PollFailed(String),
+}
+
+#[derive(Debug)]
+struct Tracked {
+    /// Trades seen so far, `None` until the order is first observed.
+    trades: Option<usize>,
+    last_change: Instant,
+    stalled: bool,
+}
+
+/// Polls a set of recurring orders of one user and reports their progress.
+///
+/// # Example
+///
+/// ```
+/// let (watcher, mut events) = RecurringWatcher::new(client.clone(), user, [order_key]);
+///
+/// tokio::spawn(async move {
+///     while let Some(event) = events.next().await {
+///         println!("{event:?}");
+///     }
+/// });
+/// watcher.run().await;
+/// ```
+pub struct RecurringWatcher {
+    client: JupiterClient,
+    user: String,
+    orders: HashMap<String, Tracked>,
+    poll_interval: Duration,
+    stall_grace: Duration,
+    events: UnboundedSender<RecurringEvent>,
+}
+
+impl RecurringWatcher {
+    /// Returns a watcher for `orders` of `user`, with the receiving end of its event channel.
+    ///
+    /// Trades already executed when an order is first polled are not reported.
+    pub fn new<I, K>(
+        client: JupiterClient,
+        user: &str,
+        orders: I,
+    ) -> (Self, UnboundedReceiver<RecurringEvent>)
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        let now = Instant::now();
+        let orders = orders
+            .into_iter()
+            .map(|order| {
+                let tracked = Tracked {
+                    trades: None,
+                    last_change: now,
+                    stalled: false,
+                };
+                (order.into(), tracked)
+            })
+            .collect();
+
+        let (events, receiver) = unbounded();
+        let watcher = Self {
+            client,
+            user: user.to_string(),
+            orders,
+            poll_interval: DEFAULT_RECURRING_POLL_INTERVAL,
+            stall_grace: DEFAULT_STALL_GRACE,
+            events,
+        };
+        (watcher, receiver)
+    }
+
+    /// Sets how often the user's orders are polled.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets how long past its interval an order may go without trading before it is
+    /// reported as stalled.
+    pub fn stall_grace(mut self, stall_grace: Duration) -> Self {
+        self.stall_grace = stall_grace;
+        self
+    }
+
+    /// Order accounts still watched.
+    pub fn orders(&self) -> impl Iterator<Item = &str> {
+        self.orders.keys().map(String::as_str)
+    }
+
+    /// Polls until every watched order has completed, sending events to the channel.
+    pub async fn run(mut self) {
+        while !self.orders.is_empty() {
+            tokio::time::sleep(self.poll_interval).await;
+
+            match self.poll().await {
+                Ok(events) => events.into_iter().for_each(|event| self.emit(event)),
+                Err(e) => self.emit(RecurringEvent::PollFailed(e.to_string())),
+            }
+        }
+    }
+
+    /// Fetches the watched orders once and returns what changed since the last poll.
+    pub async fn poll(&mut self) -> Result<Vec<RecurringEvent>, JupiterClientError> {
+        let mut events = Vec::new();
+        let now = Instant::now();
+
+        let active = self.fetch(OrderStatus::Active).await?;
+        let mut seen = HashSet::new();
+        for order in &active {
+            seen.insert(order.order_key().to_string());
+            self.track(order, now, &mut events);
+        }
+
+        let missing: Vec<String> = self
+            .orders
+            .keys()
+            .filter(|order| !seen.contains(*order))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            return Ok(events);
+        }
+
+        for order in self.fetch(OrderStatus::History).await? {
+            let key = order.order_key();
+            if missing.iter().any(|missing| missing == key) {
+                self.track(&order, now, &mut events);
+                self.orders.remove(key);
+                events.push(RecurringEvent::Completed {
+                    order: key.to_string(),
+                });
+            }
+        }
+        Ok(events)
+    }
+
+    fn track(&mut self, order: &RecurringOrder, now: Instant, events: &mut Vec<RecurringEvent>) {
+        let Some(tracked) = self.orders.get_mut(order.order_key()) else {
+            return;
+        };
+        let trades = order.trades();
+
+        match tracked.trades {
+            None => tracked.trades = Some(trades.len()),
+            Some(count) if trades.len() > count => {
+                events.extend(trades[count..].iter().map(|trade| RecurringEvent::Trade {
+                    order: order.order_key().to_string(),
+                    tx_id: trade.tx_id.clone(),
+                }));
+                tracked.trades = Some(trades.len());
+                tracked.last_change = now;
+                tracked.stalled = false;
+            }
+            Some(_) => {
+                let idle = now.duration_since(tracked.last_change);
+                let stalled = order
+                    .interval()
+                    .is_some_and(|interval| idle >= interval + self.stall_grace);
+                if stalled && !tracked.stalled {
+                    tracked.stalled = true;
+                    events.push(RecurringEvent::Stalled {
+                        order: order.order_key().to_string(),
+                        idle,
+                    });
+                }
+            }
+        }
+    }
+
+    async fn fetch(&self, status: OrderStatus) -> Result<Vec<RecurringOrder>, JupiterClientError> {
+        let params = GetRecurringOrders::new(RecurringOrderType::All, status, &self.user);
+        self.client
+            .get_recurring_orders_stream(&params)
+            .try_filter(|order| futures::future::ready(self.orders.contains_key(order.order_key())))
+            .try_collect()
+            .await
+    }
+
+    fn emit(&self, event: RecurringEvent) {
+        // the receiver may have been dropped, the watcher keeps working without it
+        let _ = self.events.unbounded_send(event);
+    }
+}
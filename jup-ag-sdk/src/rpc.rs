@@ -0,0 +1,485 @@
+//! Minimal Solana JSON-RPC client used by the transaction helpers.
+//!
+//! Only the handful of methods the SDK needs are implemented. Requires the `solana` feature.
+
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+pub use solana_signer::Signer;
+pub use solana_transaction_error::TransactionError;
+
+use crate::error::{JupiterClientError, handle_response};
+
+/// A lightweight Solana RPC client.
+///
+/// # Example
+///
+/// ```
+/// let rpc = SolanaRpc::new("https://api.mainnet-beta.solana.com");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SolanaRpc {
+    pub client: Client,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// The `{ context, value }` envelope returned by most RPC methods.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcResponse<T> {
+    pub context: RpcContext,
+    pub value: T,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcContext {
+    pub slot: u64,
+}
+
+/// Error returned by `simulateTransaction`.
+///
+/// Falls back to the raw JSON when the RPC node returns an error variant this SDK does not know.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SimulationError {
+    Transaction(TransactionError),
+    Unknown(Value),
+}
+
+/// Result of simulating a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationResult {
+    /// The slot the simulation ran against.
+    #[serde(skip_deserializing)]
+    pub slot: u64,
+
+    /// `None` if the transaction would succeed.
+    #[serde(default)]
+    pub err: Option<SimulationError>,
+
+    /// Program logs emitted during simulation.
+    #[serde(default)]
+    pub logs: Option<Vec<String>>,
+
+    /// Compute units consumed by the transaction.
+    #[serde(default)]
+    pub units_consumed: Option<u64>,
+}
+
+impl SimulationResult {
+    /// Returns `true` if the simulation did not produce an error.
+    pub fn is_success(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
+/// Commitment level used when sending and confirming transactions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CommitmentLevel {
+    Processed,
+    #[default]
+    Confirmed,
+    Finalized,
+}
+
+/// Status of a submitted transaction as returned by `getSignatureStatuses`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureStatus {
+    /// The slot the transaction was processed in.
+    pub slot: u64,
+
+    /// Number of blocks since confirmation, `None` once rooted.
+    #[serde(default)]
+    pub confirmations: Option<u64>,
+
+    /// `None` if the transaction succeeded.
+    #[serde(default)]
+    pub err: Option<SimulationError>,
+
+    /// The cluster confirmation status, missing on very old nodes.
+    #[serde(default)]
+    pub confirmation_status: Option<CommitmentLevel>,
+}
+
+impl SignatureStatus {
+    /// Returns `true` if the transaction has reached at least `commitment`.
+    pub fn satisfies(&self, commitment: CommitmentLevel) -> bool {
+        match self.confirmation_status {
+            Some(status) => status >= commitment,
+            None => self.confirmations.is_none(),
+        }
+    }
+}
+
+/// Lowest priority fee paid in a recent slot, from `getRecentPrioritizationFees`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentPrioritizationFee {
+    pub slot: u64,
+
+    /// In micro-lamports per compute unit.
+    pub prioritization_fee: u64,
+}
+
+impl SolanaRpc {
+    /// Creates a new RPC client for the given endpoint.
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.to_string(),
+        }
+    }
+
+    /// Sends a raw JSON-RPC request and deserializes its `result`.
+    pub async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T, JupiterClientError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let response = match self.client.post(&self.url).json(&body).send().await {
+            Ok(resp) => resp,
+            Err(e) => return Err(JupiterClientError::RequestError(e)),
+        };
+
+        let response = handle_response(response).await?;
+
+        let rpc_response = match response.json::<JsonRpcResponse<T>>().await {
+            Ok(rpc_response) => rpc_response,
+            Err(e) => return Err(JupiterClientError::DeserializationError(e.to_string())),
+        };
+
+        match (rpc_response.result, rpc_response.error) {
+            (_, Some(error)) => Err(JupiterClientError::RpcError(format!(
+                "{} (code {})",
+                error.message, error.code
+            ))),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(JupiterClientError::RpcError(format!(
+                "{method} returned no result"
+            ))),
+        }
+    }
+
+    /// Simulates a base64-encoded transaction without verifying signatures.
+    ///
+    /// The recent blockhash is replaced by the node so unsigned Jupiter transactions can be
+    /// simulated as-is.
+    pub async fn simulate_transaction(
+        &self,
+        transaction: &str,
+    ) -> Result<SimulationResult, JupiterClientError> {
+        let params = json!([
+            transaction,
+            {
+                "encoding": "base64",
+                "sigVerify": false,
+                "replaceRecentBlockhash": true,
+                "commitment": "processed",
+            }
+        ]);
+
+        let response: RpcResponse<SimulationResult> =
+            self.call("simulateTransaction", params).await?;
+
+        Ok(SimulationResult {
+            slot: response.context.slot,
+            ..response.value
+        })
+    }
+
+    /// Submits a signed, base64-encoded transaction and returns its signature.
+    pub async fn send_transaction(
+        &self,
+        transaction: &str,
+        skip_preflight: bool,
+        commitment: CommitmentLevel,
+    ) -> Result<String, JupiterClientError> {
+        let params = json!([
+            transaction,
+            {
+                "encoding": "base64",
+                "skipPreflight": skip_preflight,
+                "preflightCommitment": commitment,
+            }
+        ]);
+
+        self.call("sendTransaction", params).await
+    }
+
+    /// Fetches the statuses of the given signatures, searching the full transaction history.
+    ///
+    /// Entries are `None` for signatures the node has not seen.
+    pub async fn get_signature_statuses(
+        &self,
+        signatures: &[String],
+    ) -> Result<Vec<Option<SignatureStatus>>, JupiterClientError> {
+        let params = json!([signatures, { "searchTransactionHistory": true }]);
+
+        let response: RpcResponse<Vec<Option<SignatureStatus>>> =
+            self.call("getSignatureStatuses", params).await?;
+
+        Ok(response.value)
+    }
+
+    /// Returns the per-slot priority fees of the last 150 slots, for transactions writing to
+    /// all of `accounts`, or any transaction if `accounts` is empty.
+    pub async fn get_recent_prioritization_fees(
+        &self,
+        accounts: &[String],
+    ) -> Result<Vec<RecentPrioritizationFee>, JupiterClientError> {
+        self.call("getRecentPrioritizationFees", json!([accounts]))
+            .await
+    }
+
+    /// Returns whether each of `accounts` exists, in the same order.
+    ///
+    /// Only account presence is fetched, not the account data.
+    pub async fn accounts_exist(
+        &self,
+        accounts: &[String],
+    ) -> Result<Vec<bool>, JupiterClientError> {
+        let params = json!([
+            accounts,
+            { "encoding": "base64", "dataSlice": { "offset": 0, "length": 0 } }
+        ]);
+        let response: RpcResponse<Vec<Option<Value>>> =
+            self.call("getMultipleAccounts", params).await?;
+        Ok(response.value.iter().map(Option::is_some).collect())
+    }
+
+    /// Returns the current block height at the given commitment.
+    pub async fn get_block_height(
+        &self,
+        commitment: CommitmentLevel,
+    ) -> Result<u64, JupiterClientError> {
+        self.call("getBlockHeight", json!([{ "commitment": commitment }]))
+            .await
+    }
+
+    /// Returns whether `blockhash` is still valid for new transactions at `commitment`.
+    pub async fn is_blockhash_valid(
+        &self,
+        blockhash: &str,
+        commitment: CommitmentLevel,
+    ) -> Result<bool, JupiterClientError> {
+        let params = json!([blockhash, { "commitment": commitment }]);
+        let response: RpcResponse<bool> = self.call("isBlockhashValid", params).await?;
+        Ok(response.value)
+    }
+
+    /// Returns whether the recent blockhash of a base64-encoded transaction is still valid,
+    /// so the transaction can be signed and landed.
+    ///
+    /// Checked at `processed` commitment, as Jupiter builds transactions on recent blockhashes
+    /// that may not be confirmed yet.
+    pub async fn is_transaction_fresh(
+        &self,
+        transaction: &str,
+    ) -> Result<bool, JupiterClientError> {
+        let tx = crate::codec::decode_base64(transaction)?;
+        self.is_blockhash_valid(
+            &tx.message.recent_blockhash().to_string(),
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Polls a signature with backoff until it reaches `commitment`, fails, is dropped or
+    /// `timeout` elapses.
+    ///
+    /// The delay between polls starts at `poll_interval` and doubles up to
+    /// [`MAX_CONFIRM_POLL_INTERVAL`]. An unseen transaction is dropped once the block height
+    /// passes `last_valid_block_height`, or, if unknown, [`MAX_BLOCKHASH_AGE`] blocks after
+    /// polling started.
+    ///
+    /// RPC errors are retried like an unconfirmed status. The last one is returned only if
+    /// no poll succeeded before `timeout`.
+    pub(crate) async fn poll_confirmation(
+        &self,
+        signature: &str,
+        commitment: CommitmentLevel,
+        timeout: Duration,
+        poll_interval: Duration,
+        last_valid_block_height: Option<u64>,
+    ) -> Result<Confirmation, JupiterClientError> {
+        let started = Instant::now();
+        let signatures = [signature.to_string()];
+        let mut last_valid_block_height = last_valid_block_height;
+        let mut interval = poll_interval;
+
+        let mut polled = false;
+        let mut last_error = None;
+
+        loop {
+            let poll = async {
+                let status = self
+                    .get_signature_statuses(&signatures)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .flatten();
+
+                if let Some(status) = status {
+                    if status.err.is_some() {
+                        return Ok(Some(Confirmation::Failed(status)));
+                    }
+                    if status.satisfies(CommitmentLevel::Finalized) {
+                        return Ok(Some(Confirmation::Finalized(status)));
+                    }
+                    if status.satisfies(commitment) {
+                        return Ok(Some(Confirmation::Confirmed(status)));
+                    }
+                } else {
+                    let block_height = self.get_block_height(commitment).await?;
+                    let last_valid =
+                        *last_valid_block_height.get_or_insert(block_height + MAX_BLOCKHASH_AGE);
+                    if block_height > last_valid {
+                        return Ok(Some(Confirmation::Dropped));
+                    }
+                }
+                Ok::<_, JupiterClientError>(None)
+            };
+
+            match poll.await {
+                Ok(Some(confirmation)) => return Ok(confirmation),
+                Ok(None) => polled = true,
+                // RPC nodes fail transiently; keep polling until the timeout
+                Err(e) => last_error = Some(e),
+            }
+
+            let elapsed = started.elapsed();
+            if elapsed >= timeout {
+                return match last_error {
+                    Some(e) if !polled => Err(e),
+                    _ => Ok(Confirmation::TimedOut),
+                };
+            }
+
+            tokio::time::sleep(interval.min(timeout - elapsed)).await;
+            interval = (interval * 2).min(MAX_CONFIRM_POLL_INTERVAL.max(poll_interval));
+        }
+    }
+}
+
+/// Delay before the first signature status poll of [`confirm_signature`].
+pub const DEFAULT_CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Longest delay between two signature status polls.
+pub const MAX_CONFIRM_POLL_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Number of blocks a blockhash stays valid for.
+pub const MAX_BLOCKHASH_AGE: u64 = 150;
+
+/// Outcome of [`confirm_signature`].
+#[derive(Debug, Clone)]
+pub enum Confirmation {
+    /// Reached the requested commitment, but not `finalized` yet.
+    Confirmed(SignatureStatus),
+
+    /// Reached `finalized`.
+    Finalized(SignatureStatus),
+
+    /// Landed, but failed on-chain; `err` is set.
+    Failed(SignatureStatus),
+
+    /// Never seen by the node and its blockhash has expired, so it can no longer land.
+    Dropped,
+
+    /// Still pending when the timeout elapsed. It may yet land.
+    TimedOut,
+}
+
+impl Confirmation {
+    /// Returns `true` if the transaction landed and succeeded.
+    pub fn is_success(&self) -> bool {
+        matches!(
+            self,
+            Confirmation::Confirmed(_) | Confirmation::Finalized(_)
+        )
+    }
+
+    /// The last status seen, if the transaction landed.
+    pub fn status(&self) -> Option<&SignatureStatus> {
+        match self {
+            Confirmation::Confirmed(status)
+            | Confirmation::Finalized(status)
+            | Confirmation::Failed(status) => Some(status),
+            Confirmation::Dropped | Confirmation::TimedOut => None,
+        }
+    }
+
+    /// Converts the outcome into the status of a successful transaction, or a
+    /// `TransactionFailed` error for `signature`.
+    pub fn into_result(self, signature: &str) -> Result<SignatureStatus, JupiterClientError> {
+        let failed = |reason: String| JupiterClientError::TransactionFailed {
+            signature: signature.to_string(),
+            reason,
+        };
+        match self {
+            Confirmation::Confirmed(status) | Confirmation::Finalized(status) => Ok(status),
+            Confirmation::Failed(status) => Err(failed(match status.err {
+                Some(err) => format!("transaction failed: {:?}", err),
+                None => "transaction failed".to_string(),
+            })),
+            Confirmation::Dropped => Err(failed("blockhash expired".to_string())),
+            Confirmation::TimedOut => Err(failed("timed out".to_string())),
+        }
+    }
+}
+
+/// Waits for a submitted transaction to reach `commitment`.
+///
+/// Polls `getSignatureStatuses` with backoff, starting at [`DEFAULT_CONFIRM_POLL_INTERVAL`]
+/// and doubling up to [`MAX_CONFIRM_POLL_INTERVAL`]. RPC errors are retried until `timeout`
+/// and returned as `Err` only if no poll succeeded; every other outcome, including on-chain
+/// failures, is a [`Confirmation`].
+///
+/// # Example
+///
+/// ```
+/// let signature = rpc.send_transaction(&signed, false, CommitmentLevel::Confirmed).await?;
+/// match confirm_signature(&rpc, &signature, CommitmentLevel::Confirmed, Duration::from_secs(60)).await? {
+///     Confirmation::Confirmed(status) | Confirmation::Finalized(status) => println!("landed in {}", status.slot),
+///     Confirmation::Failed(status) => eprintln!("failed: {:?}", status.err),
+///     Confirmation::Dropped => eprintln!("dropped, resend a fresh transaction"),
+///     Confirmation::TimedOut => eprintln!("still pending"),
+/// }
+/// ```
+pub async fn confirm_signature(
+    rpc: &SolanaRpc,
+    signature: &str,
+    commitment: CommitmentLevel,
+    timeout: Duration,
+) -> Result<Confirmation, JupiterClientError> {
+    rpc.poll_confirmation(
+        signature,
+        commitment,
+        timeout,
+        DEFAULT_CONFIRM_POLL_INTERVAL,
+        None,
+    )
+    .await
+}
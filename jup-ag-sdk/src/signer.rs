@@ -0,0 +1,309 @@
+//! Transaction signing.
+//!
+//! The Ultra, Trigger, Recurring and Swap APIs return unsigned base64 transactions.
+//! [`TransactionSigner`] signs them, placing the signature in the signer's slot, and
+//! [`PartialTransaction`] collects the signatures of several signers. Signing is
+//! async so it can be delegated to a hardware wallet, a remote signing service or a KMS.
+//!
+//! Every [`solana_signer::Signer`], such as a `Keypair`, is a [`TransactionSigner`].
+//! [`KeypairSigner`] adds keypair loading helpers and requires the `signer` feature.
+
+use std::future::Future;
+#[cfg(feature = "signer")]
+use std::path::Path;
+
+#[cfg(feature = "signer")]
+pub use solana_keypair::Keypair;
+pub use solana_pubkey::Pubkey;
+pub use solana_signature::Signature;
+
+use crate::{
+    codec::{self, VersionedTransaction},
+    error::JupiterClientError,
+};
+
+/// A transaction signed by a [`TransactionSigner`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTransaction {
+    /// The signed transaction, base64-encoded, ready for an execute endpoint.
+    pub transaction: String,
+
+    /// The signer's signature, base58-encoded.
+    pub signature: String,
+}
+
+/// Signs the transactions returned by the Jupiter APIs.
+///
+/// Implementors only sign raw messages; [`TransactionSigner::sign_transaction`] decodes the
+/// transaction, places the signature in the signer's slot among the required signers and
+/// re-encodes it. Use [`PartialTransaction`] when several signers are required. The method
+/// names differ from [`solana_signer::Signer`] so both traits can be imported together.
+///
+/// # Example
+///
+/// ```
+/// struct RemoteSigner {
+///     http: reqwest::Client,
+///     address: Pubkey,
+/// }
+///
+/// impl TransactionSigner for RemoteSigner {
+///     fn address(&self) -> Pubkey {
+///         self.address
+///     }
+///
+///     async fn sign(&self, message: &[u8]) -> Result<Signature, JupiterClientError> {
+///         let response: SignResponse = self
+///             .http
+///             .post("https://signer.internal/sign")
+///             .body(message.to_vec())
+///             .send()
+///             .await?
+///             .json()
+///             .await?;
+///         response.signature.parse().map_err(|e| JupiterClientError::SigningError(format!("{e}")))
+///     }
+/// }
+///
+/// let placed = client.create_and_execute_trigger_order(&order, &remote_signer).await?;
+/// ```
+pub trait TransactionSigner: Send + Sync {
+    /// The address signing transactions.
+    fn address(&self) -> Pubkey;
+
+    /// Signs a serialized transaction message.
+    fn sign(
+        &self,
+        message: &[u8],
+    ) -> impl Future<Output = Result<Signature, JupiterClientError>> + Send;
+
+    /// Signs a base64-encoded `VersionedTransaction`.
+    ///
+    /// # Errors
+    ///
+    /// * `DeserializationError` if `transaction` is not a base64 `VersionedTransaction`.
+    /// * `SigningError` if the signer is not a required signer of the transaction, or
+    ///   signing fails.
+    fn sign_transaction(
+        &self,
+        transaction: &str,
+    ) -> impl Future<Output = Result<SignedTransaction, JupiterClientError>> + Send {
+        async move {
+            let mut partial = PartialTransaction::from_base64(transaction)?;
+            let signature = partial.sign(self).await?;
+            Ok(SignedTransaction {
+                transaction: partial.to_base64()?,
+                signature: signature.to_string(),
+            })
+        }
+    }
+}
+
+impl<S> TransactionSigner for S
+where
+    S: solana_signer::Signer + Send + Sync + ?Sized,
+{
+    fn address(&self) -> Pubkey {
+        self.pubkey()
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Signature, JupiterClientError> {
+        self.try_sign_message(message)
+            .map_err(|e| JupiterClientError::SigningError(e.to_string()))
+    }
+}
+
+/// A decoded transaction collecting the signatures of its required signers.
+///
+/// Each signature goes in the slot matching the signer's position among the message's
+/// required signers, so a user and a separate fee payer or sponsor can sign the same
+/// transaction in any order without overwriting each other.
+///
+/// # Example
+///
+/// ```
+/// let mut tx = PartialTransaction::from_base64(&swap.swap_transaction)?;
+/// tx.sign(&user).await?;
+/// tx.sign(&fee_payer).await?;
+/// assert!(tx.is_fully_signed());
+/// rpc.send_transaction(&tx.to_base64()?, false, Commitment::Confirmed).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PartialTransaction {
+    tx: VersionedTransaction,
+    message: Vec<u8>,
+}
+
+impl PartialTransaction {
+    /// Decodes a base64-encoded `VersionedTransaction`, keeping any signatures it already has.
+    pub fn from_base64(transaction: &str) -> Result<Self, JupiterClientError> {
+        codec::decode_base64(transaction).map(Self::from_transaction)
+    }
+
+    /// Wraps an already decoded transaction, keeping any signatures it already has.
+    pub fn from_transaction(mut tx: VersionedTransaction) -> Self {
+        let required = tx.message.header().num_required_signatures as usize;
+        if tx.signatures.len() < required {
+            tx.signatures.resize(required, Signature::default());
+        }
+
+        Self {
+            message: tx.message.serialize(),
+            tx,
+        }
+    }
+
+    /// Returns the transaction with the signatures collected so far.
+    pub fn into_transaction(self) -> VersionedTransaction {
+        self.tx
+    }
+
+    /// The serialized message signers sign.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// The required signers, in slot order. The first one is the fee payer.
+    pub fn signers(&self) -> &[Pubkey] {
+        let required = self.tx.message.header().num_required_signatures as usize;
+        let keys = self.tx.message.static_account_keys();
+        &keys[..required.min(keys.len())]
+    }
+
+    /// The required signers whose slot is still empty.
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        self.signers()
+            .iter()
+            .zip(&self.tx.signatures)
+            .filter(|(_, signature)| **signature == Signature::default())
+            .map(|(signer, _)| *signer)
+            .collect()
+    }
+
+    /// Whether every required signer has signed.
+    pub fn is_fully_signed(&self) -> bool {
+        self.missing_signers().is_empty()
+    }
+
+    /// Signs with `signer` and places the signature in its slot.
+    ///
+    /// # Errors
+    ///
+    /// * `SigningError` if `signer` is not a required signer, or signing fails.
+    pub async fn sign<S>(&mut self, signer: &S) -> Result<Signature, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let index = self.slot(&signer.address())?;
+        let signature = signer.sign(&self.message).await?;
+        self.tx.signatures[index] = signature;
+        Ok(signature)
+    }
+
+    /// Places a signature produced elsewhere, e.g. by a sponsor, in the slot of `signer`.
+    ///
+    /// # Errors
+    ///
+    /// * `SigningError` if `signer` is not a required signer, or `signature` does not verify
+    ///   against the message.
+    pub fn add_signature(
+        &mut self,
+        signer: &Pubkey,
+        signature: Signature,
+    ) -> Result<(), JupiterClientError> {
+        let index = self.slot(signer)?;
+        if !signature.verify(signer.as_ref(), &self.message) {
+            return Err(JupiterClientError::SigningError(format!(
+                "invalid signature for {}",
+                signer
+            )));
+        }
+        self.tx.signatures[index] = signature;
+        Ok(())
+    }
+
+    /// Re-encodes the transaction as base64, ready for an execute endpoint or RPC node.
+    pub fn to_base64(&self) -> Result<String, JupiterClientError> {
+        codec::encode_base64(&self.tx)
+    }
+
+    fn slot(&self, signer: &Pubkey) -> Result<usize, JupiterClientError> {
+        self.signers()
+            .iter()
+            .position(|key| key == signer)
+            .ok_or_else(|| {
+                JupiterClientError::SigningError(format!(
+                    "{} is not a required signer of the transaction",
+                    signer
+                ))
+            })
+    }
+}
+
+/// A [`TransactionSigner`] backed by a local [`Keypair`].
+#[cfg(feature = "signer")]
+#[derive(Debug)]
+pub struct KeypairSigner {
+    keypair: Keypair,
+}
+
+#[cfg(feature = "signer")]
+impl KeypairSigner {
+    pub fn new(keypair: Keypair) -> Self {
+        Self { keypair }
+    }
+
+    /// Loads a keypair from its 64 secret and public key bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JupiterClientError> {
+        Keypair::from_bytes(bytes)
+            .map(Self::new)
+            .map_err(|e| JupiterClientError::SigningError(format!("invalid keypair: {e}")))
+    }
+
+    /// Loads a keypair from its base58 encoding, as exported by most wallets.
+    pub fn from_base58(key: &str) -> Result<Self, JupiterClientError> {
+        let bytes = bs58::decode(key.trim()).into_vec().map_err(|e| {
+            JupiterClientError::SigningError(format!("invalid base58 keypair: {e}"))
+        })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Loads a keypair file written by `solana-keygen`, a JSON array of bytes.
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<Self, JupiterClientError> {
+        let path = path.as_ref();
+        solana_keypair::read_keypair_file(path)
+            .map(Self::new)
+            .map_err(|e| {
+                JupiterClientError::SigningError(format!(
+                    "failed to read keypair {}: {e}",
+                    path.display()
+                ))
+            })
+    }
+
+    pub fn keypair(&self) -> &Keypair {
+        &self.keypair
+    }
+
+    pub fn into_keypair(self) -> Keypair {
+        self.keypair
+    }
+}
+
+#[cfg(feature = "signer")]
+impl From<Keypair> for KeypairSigner {
+    fn from(keypair: Keypair) -> Self {
+        Self::new(keypair)
+    }
+}
+
+#[cfg(feature = "signer")]
+impl TransactionSigner for KeypairSigner {
+    fn address(&self) -> Pubkey {
+        solana_signer::Signer::pubkey(&self.keypair)
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Signature, JupiterClientError> {
+        self.keypair.sign(message).await
+    }
+}
@@ -0,0 +1,279 @@
+//! Take-profit / stop-loss emulation on top of the Trigger and Ultra APIs.
+//!
+//! A trigger order fills as soon as the market pays its price, so it can take profit above
+//! the market but cannot stop a loss below it. [`StopLossManager`] places the take-profit as
+//! a trigger order and watches quotes for the stop: once the price falls to it, the
+//! take-profit order is cancelled and the remaining position is sold at market through
+//! Ultra. Requires the `solana` feature.
+
+use std::{collections::HashSet, time::Duration};
+
+use futures::{
+    TryStreamExt,
+    channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+};
+
+use crate::{
+    JupiterClient,
+    client::TriggerOrderAges,
+    error::JupiterClientError,
+    signer::TransactionSigner,
+    types::{
+        CancelTriggerOrder, GetTriggerOrders, OrderStatus, PlacedTriggerOrder, QuoteRequest,
+        Status, TakeProfitStopLoss, TriggerOrder, TriggerOrderState, UltraOrderRequest,
+    },
+};
+
+/// How often [`StopLossManager::run`] checks the order and the price by default.
+pub const DEFAULT_STOP_LOSS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Something [`StopLossManager`] observed or did.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopLossEvent {
+    /// The take-profit order filled completely.
+    TakeProfitFilled { order: String },
+
+    /// The take-profit order left the active set without filling, e.g. cancelled by the
+    /// maker or expired. The stop is no longer watched.
+    Closed {
+        order: String,
+        status: TriggerOrderState,
+    },
+
+    /// The price fell to the stop: the take-profit order was cancelled and the remaining
+    /// position sold at market. Amounts are raw.
+    StopLossExecuted {
+        /// Quoted price that triggered the stop, output tokens per input token.
+        price: f64,
+        cancel_signature: String,
+        signature: Option<String>,
+        in_amount: u64,
+        out_amount: Option<u64>,
+    },
+
+    /// Checking the order or the price failed; the manager keeps polling.
+    PollFailed(String),
+}
+
+/// Result of one check of the take-profit order and the price.
+enum Check {
+    /// Nothing to do yet.
+    Pending,
+
+    /// The take-profit order is gone, nothing left to watch.
+    Done(StopLossEvent),
+
+    /// The stop was hit while `remaining` input was still in the take-profit order.
+    Stop { price: f64, remaining: u64 },
+}
+
+/// Watches a take-profit trigger order and sells the position at market if the price
+/// falls to the stop-loss.
+///
+/// The stop is checked against a Swap API quote for the input left in the take-profit
+/// order, so the price includes the impact of selling it. Between two polls the price can
+/// fall further than the stop, and the market sale gets whatever the market pays then.
+///
+/// # Example
+///
+/// ```
+/// let pair = TakeProfitStopLoss::new(SOL_MINT, USDC_MINT, maker, 10_000_000_000, 250.0, 150.0, 9, 6)?;
+/// let (manager, mut events) = StopLossManager::place(client.clone(), keypair, pair).await?;
+///
+/// tokio::spawn(async move {
+///     while let Some(event) = events.next().await {
+///         println!("{event:?}");
+///     }
+/// });
+/// let last = manager.run().await?;
+/// ```
+pub struct StopLossManager<S> {
+    client: JupiterClient,
+    signer: S,
+    pair: TakeProfitStopLoss,
+    order: String,
+    poll_interval: Duration,
+    events: UnboundedSender<StopLossEvent>,
+    ages: TriggerOrderAges,
+}
+
+impl<S> StopLossManager<S>
+where
+    S: TransactionSigner,
+{
+    /// Places the take-profit order with `signer` and returns a manager watching it, with
+    /// the receiving end of its event channel.
+    pub async fn place(
+        client: JupiterClient,
+        signer: S,
+        pair: TakeProfitStopLoss,
+    ) -> Result<(Self, UnboundedReceiver<StopLossEvent>), JupiterClientError> {
+        let placed = client
+            .create_and_execute_trigger_order(&pair.take_profit, &signer)
+            .await?;
+        Ok(Self::watch(client, signer, pair, placed))
+    }
+
+    /// Returns a manager watching the take-profit order of `pair`, already placed as
+    /// `take_profit`.
+    pub fn watch(
+        client: JupiterClient,
+        signer: S,
+        pair: TakeProfitStopLoss,
+        take_profit: PlacedTriggerOrder,
+    ) -> (Self, UnboundedReceiver<StopLossEvent>) {
+        let (events, receiver) = unbounded();
+        let manager = Self {
+            client,
+            signer,
+            pair,
+            order: take_profit.order,
+            poll_interval: DEFAULT_STOP_LOSS_POLL_INTERVAL,
+            events,
+            ages: TriggerOrderAges::default(),
+        };
+        (manager, receiver)
+    }
+
+    /// Sets how often the order and the price are checked.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// The take-profit order account.
+    pub fn order(&self) -> &str {
+        &self.order
+    }
+
+    /// Polls until the take-profit order fills or closes, or the stop is hit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StopLossEvent::TakeProfitFilled)` or `Ok(StopLossEvent::Closed)` once the
+    ///   take-profit order left the active set.
+    /// * `Ok(StopLossEvent::StopLossExecuted)` once the position was sold at market.
+    /// * `Err` if cancelling the take-profit order or the market sale fails. If the
+    ///   cancellation went through, the maker holds the unsold position.
+    pub async fn run(mut self) -> Result<StopLossEvent, JupiterClientError> {
+        loop {
+            tokio::time::sleep(self.poll_interval).await;
+
+            let event = match self.check().await {
+                Ok(Check::Pending) => continue,
+                Ok(Check::Done(event)) => event,
+                Ok(Check::Stop { price, remaining }) => self.stop(price, remaining).await?,
+                Err(e) => {
+                    self.emit(StopLossEvent::PollFailed(e.to_string()));
+                    continue;
+                }
+            };
+            self.emit(event.clone());
+            return Ok(event);
+        }
+    }
+
+    async fn check(&mut self) -> Result<Check, JupiterClientError> {
+        let Some(order) = self.active_order().await? else {
+            let maker = &self.pair.take_profit.maker;
+            let watched = HashSet::from([self.order.clone()]);
+            let mut closed = self
+                .client
+                .closed_trigger_orders(maker, &watched, &mut self.ages)
+                .await?;
+            // not in history yet, the order may have just been closed
+            let Some(status) = closed.remove(&self.order) else {
+                return Ok(Check::Pending);
+            };
+            let event = match status {
+                TriggerOrderState::Completed => StopLossEvent::TakeProfitFilled {
+                    order: self.order.clone(),
+                },
+                status => StopLossEvent::Closed {
+                    order: self.order.clone(),
+                    status,
+                },
+            };
+            return Ok(Check::Done(event));
+        };
+        self.ages.record(&order);
+
+        let remaining: u64 = order.raw_remaining_making_amount.parse().map_err(|_| {
+            JupiterClientError::DeserializationError(format!(
+                "invalid rawRemainingMakingAmount: {}",
+                order.raw_remaining_making_amount
+            ))
+        })?;
+        if remaining == 0 {
+            return Ok(Check::Pending);
+        }
+
+        let take_profit = &self.pair.take_profit;
+        let quote = self
+            .client
+            .get_quote(&QuoteRequest::new(
+                &take_profit.input_mint,
+                &take_profit.output_mint,
+                remaining,
+            ))
+            .await?;
+        let out_amount = quote.out_amount_u64().ok_or_else(|| {
+            JupiterClientError::DeserializationError("invalid outAmount in quote".to_string())
+        })?;
+
+        match self.pair.price(remaining, out_amount) {
+            Some(price) if self.pair.is_stopped(remaining, out_amount) => {
+                Ok(Check::Stop { price, remaining })
+            }
+            _ => Ok(Check::Pending),
+        }
+    }
+
+    /// Cancels the take-profit order and sells `remaining` at market.
+    async fn stop(&self, price: f64, remaining: u64) -> Result<StopLossEvent, JupiterClientError> {
+        let take_profit = &self.pair.take_profit;
+        let cancelled = self
+            .client
+            .cancel_and_execute_trigger_order(
+                &CancelTriggerOrder::new(&take_profit.maker, &self.order),
+                &self.signer,
+            )
+            .await?;
+
+        let params =
+            UltraOrderRequest::new(&take_profit.input_mint, &take_profit.output_mint, remaining)
+                .taker(&take_profit.maker);
+        let executed = self.client.ultra_swap(&params, &self.signer).await?;
+        if executed.status != Status::Success {
+            return Err(JupiterClientError::TransactionFailed {
+                signature: executed.signature.clone().unwrap_or_default(),
+                reason: executed.to_string(),
+            });
+        }
+
+        Ok(StopLossEvent::StopLossExecuted {
+            price,
+            cancel_signature: cancelled.signature,
+            signature: executed.signature,
+            in_amount: remaining,
+            out_amount: executed
+                .output_amount_result
+                .as_deref()
+                .and_then(|amount| amount.parse().ok()),
+        })
+    }
+
+    async fn active_order(&self) -> Result<Option<TriggerOrder>, JupiterClientError> {
+        let params = GetTriggerOrders::new(&self.pair.take_profit.maker, OrderStatus::Active);
+        self.client
+            .get_trigger_orders_stream(&params)
+            .try_filter(|candidate| futures::future::ready(candidate.order_key == self.order))
+            .try_next()
+            .await
+    }
+
+    fn emit(&self, event: StopLossEvent) {
+        // the receiver may have been dropped, the manager keeps working without it
+        let _ = self.events.unbounded_send(event);
+    }
+}
@@ -0,0 +1,250 @@
+//! Offline token search for autocomplete.
+//!
+//! [`TokenIndex`] searches a fetched token list by mint prefix, symbol and name without a
+//! request per keystroke. [`LiveTokenIndex`] keeps one up to date from the Tokens API.
+
+use std::sync::{Arc, RwLock};
+
+use crate::{
+    JupiterClient,
+    error::JupiterClientError,
+    types::{TokenInfo, TokenTag},
+};
+
+/// Factor by which [`LiveTokenIndex::run`] stretches its interval at most after failed
+/// refreshes.
+pub const MAX_REFRESH_BACKOFF: u32 = 8;
+
+/// How well a token matches a query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchRank {
+    Mint,
+    Symbol,
+    MintPrefix,
+    SymbolPrefix,
+    NamePrefix,
+    Contains,
+    Fuzzy,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedToken {
+    token: TokenInfo,
+    symbol: String,
+    name: String,
+}
+
+impl IndexedToken {
+    /// `query` is lowercased, `raw_query` keeps the case of base58 mints.
+    fn rank(&self, query: &str, raw_query: &str) -> Option<MatchRank> {
+        let rank = if self.token.id == raw_query {
+            MatchRank::Mint
+        } else if self.symbol == query {
+            MatchRank::Symbol
+        } else if raw_query.len() >= 4 && self.token.id.starts_with(raw_query) {
+            MatchRank::MintPrefix
+        } else if self.symbol.starts_with(query) {
+            MatchRank::SymbolPrefix
+        } else if self.name.starts_with(query)
+            || self
+                .name
+                .split_whitespace()
+                .any(|word| word.starts_with(query))
+        {
+            MatchRank::NamePrefix
+        } else if self.symbol.contains(query) || self.name.contains(query) {
+            MatchRank::Contains
+        } else if is_subsequence(query, &self.symbol) || is_subsequence(query, &self.name) {
+            MatchRank::Fuzzy
+        } else {
+            return None;
+        };
+        Some(rank)
+    }
+}
+
+/// Returns `true` if the characters of `needle` appear in `haystack` in order.
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle
+        .chars()
+        .all(|c| haystack.by_ref().any(|other| other == c))
+}
+
+/// A searchable, in-memory token list.
+///
+/// Matches are ranked exact mint, exact symbol, mint prefix, symbol prefix, name or word
+/// prefix, substring, then fuzzy (the query's characters in order). Within a rank, verified
+/// tokens come first, then higher liquidity.
+///
+/// # Example
+///
+/// ```
+/// let index = TokenIndex::new(client.get_mints_by_tags(&[TokenTag::Verified]).await?);
+/// for token in index.search("jup", 5) {
+///     println!("{} {}", token.symbol, token.id);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TokenIndex {
+    tokens: Vec<IndexedToken>,
+}
+
+impl TokenIndex {
+    pub fn new(tokens: impl IntoIterator<Item = TokenInfo>) -> Self {
+        let tokens = tokens
+            .into_iter()
+            .map(|token| IndexedToken {
+                symbol: token.symbol.to_lowercase(),
+                name: token.name.to_lowercase(),
+                token,
+            })
+            .collect();
+        Self { tokens }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Returns the token with mint address `mint`.
+    pub fn get(&self, mint: &str) -> Option<&TokenInfo> {
+        self.tokens
+            .iter()
+            .map(|indexed| &indexed.token)
+            .find(|token| token.id == mint)
+    }
+
+    /// Returns up to `limit` tokens matching `query`, best match first.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&TokenInfo> {
+        let raw_query = query.trim();
+        let query = raw_query.to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<(MatchRank, &TokenInfo)> = self
+            .tokens
+            .iter()
+            .filter_map(|indexed| Some((indexed.rank(&query, raw_query)?, &indexed.token)))
+            .collect();
+
+        matches.sort_by(|(rank_a, a), (rank_b, b)| {
+            rank_a
+                .cmp(rank_b)
+                .then_with(|| (b.is_verified == Some(true)).cmp(&(a.is_verified == Some(true))))
+                .then_with(|| {
+                    b.liquidity
+                        .unwrap_or_default()
+                        .total_cmp(&a.liquidity.unwrap_or_default())
+                })
+        });
+
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, token)| token)
+            .collect()
+    }
+}
+
+/// A [`TokenIndex`] built from tokens with the given tags, refreshable in the background.
+///
+/// Clones share the same index. Combine with [`JupiterClient::with_http_cache`] so refreshes
+/// of an unchanged list cost a `304` instead of a full download.
+///
+/// # Example
+///
+/// ```
+/// let index = LiveTokenIndex::new(client.clone(), vec![TokenTag::Verified]);
+/// index.refresh().await?;
+/// tokio::spawn(index.clone().run(Duration::from_secs(600)));
+///
+/// let suggestions: Vec<String> = index
+///     .search("bon", 5)
+///     .into_iter()
+///     .map(|token| token.symbol)
+///     .collect();
+/// ```
+#[derive(Debug, Clone)]
+pub struct LiveTokenIndex {
+    client: JupiterClient,
+    tags: Vec<TokenTag>,
+    index: Arc<RwLock<TokenIndex>>,
+    /// Error of the last refresh, `None` once a refresh succeeds.
+    last_error: Arc<RwLock<Option<String>>>,
+}
+
+impl LiveTokenIndex {
+    /// Returns an empty index over tokens tagged with any of `tags`; call
+    /// [`LiveTokenIndex::refresh`] to fill it.
+    pub fn new(client: JupiterClient, tags: Vec<TokenTag>) -> Self {
+        Self {
+            client,
+            tags,
+            index: Arc::default(),
+            last_error: Arc::default(),
+        }
+    }
+
+    /// Fetches the token list and replaces the index, returning the number of tokens.
+    ///
+    /// On error the previous index is kept and the error is available from
+    /// [`LiveTokenIndex::last_error`] until a refresh succeeds.
+    pub async fn refresh(&self) -> Result<usize, JupiterClientError> {
+        let tokens = match self.client.get_mints_by_tags(&self.tags).await {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                *self.last_error.write().unwrap() = Some(e.to_string());
+                return Err(e);
+            }
+        };
+        let index = TokenIndex::new(tokens);
+        let len = index.len();
+        *self.index.write().unwrap() = index;
+        *self.last_error.write().unwrap() = None;
+        Ok(len)
+    }
+
+    /// Error of the last refresh, `None` if it succeeded or none ran yet.
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.read().unwrap().clone()
+    }
+
+    /// Refreshes the index every `interval` forever, keeping the previous index when a
+    /// refresh fails. Requires the `runtime` feature.
+    ///
+    /// After a failed refresh the delay doubles, up to [`MAX_REFRESH_BACKOFF`] times
+    /// `interval`, and goes back to `interval` after a successful one.
+    #[cfg(feature = "runtime")]
+    pub async fn run(self, interval: std::time::Duration) {
+        let mut delay = interval;
+        loop {
+            tokio::time::sleep(delay).await;
+            delay = match self.refresh().await {
+                Ok(_) => interval,
+                Err(_) => (delay * 2).min(interval * MAX_REFRESH_BACKOFF),
+            };
+        }
+    }
+
+    /// Same as [`TokenIndex::search`], returning owned tokens.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<TokenInfo> {
+        self.index
+            .read()
+            .unwrap()
+            .search(query, limit)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// A copy of the current index.
+    pub fn snapshot(&self) -> TokenIndex {
+        self.index.read().unwrap().clone()
+    }
+}
@@ -0,0 +1,365 @@
+//! Time-weighted average price (TWAP) execution.
+//!
+//! [`TwapExecutor`] splits a large order into equal slices spread over a time window and
+//! swaps them one by one, through Ultra or the Swap API, skipping slices whose quote moves
+//! the price too much. Requires the `solana` feature.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    JupiterClient,
+    error::{JupiterClientError, ValidationError},
+    rpc::SolanaRpc,
+    signer::TransactionSigner,
+    types::{QuoteRequest, Status, SwapAndSendOptions, UltraOrderRequest},
+    validation::{check_address, check_bps, check_non_zero},
+};
+
+/// Slippage applied to every slice unless set, in basis points.
+pub const DEFAULT_TWAP_SLIPPAGE_BPS: u16 = 50;
+
+/// How each slice is swapped.
+#[derive(Debug, Clone)]
+pub enum TwapVenue {
+    /// `/order` and `/execute` of the Ultra API, signed by the taker.
+    Ultra,
+
+    /// `/quote` and `/swap`, sent and confirmed through `rpc`.
+    Swap {
+        rpc: SolanaRpc,
+        options: SwapAndSendOptions,
+    },
+}
+
+/// What happened to a slice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SliceStatus {
+    Filled,
+
+    /// Not swapped because its quote broke a guard.
+    Skipped(String),
+
+    /// Quoting, signing or executing failed.
+    Failed(String),
+}
+
+/// One slice of a TWAP order. Amounts are raw.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapSlice {
+    /// Position of the slice, from 0.
+    pub index: u32,
+
+    /// Input amount the slice tried to swap.
+    pub in_amount: u64,
+
+    /// Input amount swapped, 0 unless filled.
+    pub in_filled: u64,
+
+    /// Output amount received, 0 unless filled.
+    pub out_filled: u64,
+
+    /// Price impact of the slice's quote, in percent.
+    pub price_impact_pct: Option<f64>,
+    pub signature: Option<String>,
+    pub status: SliceStatus,
+}
+
+impl TwapSlice {
+    pub fn is_filled(&self) -> bool {
+        self.status == SliceStatus::Filled
+    }
+}
+
+/// Outcome of [`TwapExecutor::run`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapReport {
+    pub slices: Vec<TwapSlice>,
+}
+
+impl TwapReport {
+    /// Input amount the order tried to swap.
+    pub fn in_amount(&self) -> u64 {
+        self.slices.iter().map(|slice| slice.in_amount).sum()
+    }
+
+    /// Input amount swapped.
+    pub fn in_filled(&self) -> u64 {
+        self.slices.iter().map(|slice| slice.in_filled).sum()
+    }
+
+    /// Output amount received.
+    pub fn out_filled(&self) -> u64 {
+        self.slices.iter().map(|slice| slice.out_filled).sum()
+    }
+
+    pub fn filled_slices(&self) -> usize {
+        self.slices.iter().filter(|slice| slice.is_filled()).count()
+    }
+
+    /// Share of the input amount swapped, from 0 to 1.
+    pub fn fill_ratio(&self) -> f64 {
+        match self.in_amount() {
+            0 => 0.0,
+            total => self.in_filled() as f64 / total as f64,
+        }
+    }
+
+    /// Raw output received per raw input swapped, `None` if nothing was filled.
+    pub fn average_price(&self) -> Option<f64> {
+        match self.in_filled() {
+            0 => None,
+            filled => Some(self.out_filled() as f64 / filled as f64),
+        }
+    }
+
+    /// Highest price impact among the filled slices, in percent.
+    pub fn max_price_impact_pct(&self) -> Option<f64> {
+        self.slices
+            .iter()
+            .filter(|slice| slice.is_filled())
+            .filter_map(|slice| slice.price_impact_pct)
+            .reduce(f64::max)
+    }
+}
+
+/// Swaps `total_amount` of `input_mint` in `slices` equal parts spread over `window`.
+///
+/// The first slice is swapped right away and slice `i` at `i * window / slices` after it,
+/// however long the previous slices took; the remainder of the division goes to the last
+/// slice. A slice running late is swapped as soon as the previous one finishes. A slice that fails or breaks a guard
+/// is reported and not retried, the next slices are still swapped.
+///
+/// # Example
+///
+/// ```
+/// let report = TwapExecutor::new(client.clone(), SOL_MINT, USDC_MINT, 100_000_000_000, 10)
+///     .window(Duration::from_secs(3600))
+///     .slippage_bps(30)
+///     .max_price_impact_pct(0.5)
+///     .run(&keypair)
+///     .await?;
+/// println!("filled {:.0}% at {:?}", report.fill_ratio() * 100.0, report.average_price());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TwapExecutor {
+    client: JupiterClient,
+    pub input_mint: String,
+    pub output_mint: String,
+
+    /// Raw input amount of the whole order.
+    pub total_amount: u64,
+    pub slices: u32,
+
+    /// Time the slices are spread over, slice `i` starting `i * window / slices` after the
+    /// first. Default: zero, every slice is swapped right after the previous one.
+    pub window: Duration,
+
+    /// Slippage of each slice. Default: [`DEFAULT_TWAP_SLIPPAGE_BPS`].
+    pub slippage_bps: u16,
+
+    /// Slices quoted with a higher price impact, in percent, are skipped.
+    pub max_price_impact_pct: Option<f64>,
+
+    /// Default: [`TwapVenue::Ultra`].
+    pub venue: TwapVenue,
+}
+
+impl TwapExecutor {
+    pub fn new(
+        client: JupiterClient,
+        input_mint: &str,
+        output_mint: &str,
+        total_amount: u64,
+        slices: u32,
+    ) -> Self {
+        Self {
+            client,
+            input_mint: input_mint.to_string(),
+            output_mint: output_mint.to_string(),
+            total_amount,
+            slices,
+            window: Duration::ZERO,
+            slippage_bps: DEFAULT_TWAP_SLIPPAGE_BPS,
+            max_price_impact_pct: None,
+            venue: TwapVenue::Ultra,
+        }
+    }
+
+    /// Sets the time window the slices are spread over.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets the slippage of each slice.
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = slippage_bps;
+        self
+    }
+
+    /// Skips slices quoted with a price impact above `pct` percent.
+    pub fn max_price_impact_pct(mut self, pct: f64) -> Self {
+        self.max_price_impact_pct = Some(pct);
+        self
+    }
+
+    /// Swaps the slices through `/quote` and `/swap` instead of Ultra.
+    pub fn via_swap(mut self, rpc: SolanaRpc, options: SwapAndSendOptions) -> Self {
+        self.venue = TwapVenue::Swap { rpc, options };
+        self
+    }
+
+    /// Checks the order before anything is quoted.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_address("input_mint", &self.input_mint)?;
+        check_address("output_mint", &self.output_mint)?;
+        if self.input_mint == self.output_mint {
+            return Err(ValidationError::SameMint);
+        }
+        check_non_zero("slices", u64::from(self.slices))?;
+        // every slice must swap something
+        check_non_zero("total_amount", self.total_amount / u64::from(self.slices))?;
+        check_bps("slippage_bps", u64::from(self.slippage_bps), 10_000)?;
+        if self
+            .max_price_impact_pct
+            .is_some_and(|pct| !pct.is_finite() || pct < 0.0)
+        {
+            return Err(ValidationError::InvalidPrice("max_price_impact_pct"));
+        }
+        Ok(())
+    }
+
+    /// Raw input amount of each slice.
+    pub fn slice_amounts(&self) -> Vec<u64> {
+        let slices = u64::from(self.slices);
+        if slices == 0 {
+            return Vec::new();
+        }
+        let mut amounts = vec![self.total_amount / slices; self.slices as usize];
+        if let Some(last) = amounts.last_mut() {
+            *last += self.total_amount % slices;
+        }
+        amounts
+    }
+
+    /// Swaps every slice, waiting between them, and reports the fills.
+    ///
+    /// # Errors
+    ///
+    /// * `ValidationError` if [`TwapExecutor::validate`] fails. Slice failures are
+    ///   reported in the [`TwapReport`] instead.
+    pub async fn run<S>(&self, signer: &S) -> Result<TwapReport, JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        self.validate()?;
+
+        let interval = self.window / self.slices;
+        let start = tokio::time::Instant::now();
+        let mut report = TwapReport::default();
+        for (index, in_amount) in (0..).zip(self.slice_amounts()) {
+            if index > 0 && !interval.is_zero() {
+                tokio::time::sleep_until(start + interval * index).await;
+            }
+            report
+                .slices
+                .push(self.execute_slice(index, in_amount, signer).await);
+        }
+        Ok(report)
+    }
+
+    async fn execute_slice<S>(&self, index: u32, in_amount: u64, signer: &S) -> TwapSlice
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        let mut slice = TwapSlice {
+            index,
+            in_amount,
+            in_filled: 0,
+            out_filled: 0,
+            price_impact_pct: None,
+            signature: None,
+            status: SliceStatus::Filled,
+        };
+        if let Err(e) = self.swap_slice(&mut slice, signer).await {
+            slice.status = SliceStatus::Failed(e.to_string());
+        }
+        slice
+    }
+
+    async fn swap_slice<S>(
+        &self,
+        slice: &mut TwapSlice,
+        signer: &S,
+    ) -> Result<(), JupiterClientError>
+    where
+        S: TransactionSigner + ?Sized,
+    {
+        match &self.venue {
+            TwapVenue::Ultra => {
+                let params =
+                    UltraOrderRequest::new(&self.input_mint, &self.output_mint, slice.in_amount)
+                        .taker(&signer.address().to_string())
+                        .slippage_bps(self.slippage_bps);
+                let order = self.client.get_ultra_order(&params).await?;
+                slice.price_impact_pct = order.price_impact_pct.parse().ok();
+                if let Some(reason) = self.check_impact(slice.price_impact_pct) {
+                    slice.status = SliceStatus::Skipped(reason);
+                    return Ok(());
+                }
+
+                let executed = self.client.sign_and_execute(&order, signer).await?;
+                slice.signature = executed.signature.clone();
+                if executed.status != Status::Success {
+                    slice.status = SliceStatus::Failed(executed.to_string());
+                    return Ok(());
+                }
+                let amount = |result: &Option<String>, quoted: &str| {
+                    result
+                        .as_deref()
+                        .and_then(|amount| amount.parse().ok())
+                        .or_else(|| quoted.parse().ok())
+                        .unwrap_or_default()
+                };
+                slice.in_filled = amount(&executed.input_amount_result, &order.in_amount);
+                slice.out_filled = amount(&executed.output_amount_result, &order.out_amount);
+            }
+            TwapVenue::Swap { rpc, options } => {
+                let params =
+                    QuoteRequest::new(&self.input_mint, &self.output_mint, slice.in_amount)
+                        .slippage_bps(self.slippage_bps);
+                let quote = self.client.get_quote(&params).await?;
+                slice.price_impact_pct = quote.price_impact_pct_f64();
+                if let Some(reason) = self.check_impact(slice.price_impact_pct) {
+                    slice.status = SliceStatus::Skipped(reason);
+                    return Ok(());
+                }
+
+                // confirmed swaps report the quoted amounts
+                let sent = self
+                    .client
+                    .swap_and_send_quote(quote, signer, rpc, options.clone())
+                    .await?;
+                slice.signature = Some(sent.signature);
+                slice.in_filled = sent.in_amount;
+                slice.out_filled = sent.out_amount;
+            }
+        }
+        Ok(())
+    }
+
+    /// Why a slice quoted with `impact` must be skipped, if it must.
+    fn check_impact(&self, impact: Option<f64>) -> Option<String> {
+        let max = self.max_price_impact_pct?;
+        match impact {
+            Some(impact) if impact <= max => None,
+            Some(impact) => Some(format!("price impact {impact}% above {max}%")),
+            None => Some("price impact unavailable".to_string()),
+        }
+    }
+}
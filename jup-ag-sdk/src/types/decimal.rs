@@ -0,0 +1,104 @@
+//! `Decimal` accessors for the amounts and percentages the APIs return as strings, so they
+//! can be used without going through `f64`. Requires the `decimal` feature.
+
+use rust_decimal::Decimal;
+
+use super::{
+    PlatformFee, PriceOrder, QuoteResponse, RecurringTrade, SwapInfo, TimeOrder, TriggerOrder,
+    TriggerTrade, UltraOrderResponse,
+};
+
+/// Parses a decimal string, also accepting scientific notation such as `"1.5e-7"`.
+pub(crate) fn parse_decimal(value: &str) -> Option<Decimal> {
+    value
+        .parse()
+        .ok()
+        .or_else(|| Decimal::from_scientific(value).ok())
+}
+
+/// Implements `$accessor(&self) -> Option<Decimal>` for each string `$field` of `$ty`.
+macro_rules! decimal_accessors {
+    ($ty:ty { $($field:ident => $accessor:ident),* $(,)? }) => {
+        impl $ty {
+            $(
+                #[doc = concat!("`", stringify!($field), "` as a `Decimal`, or `None` if it cannot be parsed.")]
+                pub fn $accessor(&self) -> Option<Decimal> {
+                    parse_decimal(&self.$field)
+                }
+            )*
+        }
+    };
+}
+
+decimal_accessors!(QuoteResponse {
+    in_amount => in_amount_decimal,
+    out_amount => out_amount_decimal,
+    other_amount_threshold => other_amount_threshold_decimal,
+    price_impact_pct => price_impact_pct_decimal,
+});
+
+impl QuoteResponse {
+    /// `swap_usd_value` as a `Decimal`, or `None` if missing or it cannot be parsed.
+    pub fn swap_usd_value_decimal(&self) -> Option<Decimal> {
+        self.swap_usd_value.as_deref().and_then(parse_decimal)
+    }
+}
+
+decimal_accessors!(PlatformFee {
+    amount => amount_decimal,
+});
+
+decimal_accessors!(SwapInfo {
+    in_amount => in_amount_decimal,
+    out_amount => out_amount_decimal,
+    fee_amount => fee_amount_decimal,
+});
+
+decimal_accessors!(UltraOrderResponse {
+    in_amount => in_amount_decimal,
+    out_amount => out_amount_decimal,
+    other_amount_threshold => other_amount_threshold_decimal,
+    price_impact_pct => price_impact_pct_decimal,
+});
+
+decimal_accessors!(TriggerOrder {
+    making_amount => making_amount_decimal,
+    taking_amount => taking_amount_decimal,
+    remaining_making_amount => remaining_making_amount_decimal,
+    remaining_taking_amount => remaining_taking_amount_decimal,
+});
+
+decimal_accessors!(TriggerTrade {
+    input_amount => input_amount_decimal,
+    output_amount => output_amount_decimal,
+    fee_amount => fee_amount_decimal,
+});
+
+decimal_accessors!(TimeOrder {
+    in_amount_per_cycle => in_amount_per_cycle_decimal,
+    in_deposited => in_deposited_decimal,
+    in_used => in_used_decimal,
+    in_withdrawn => in_withdrawn_decimal,
+    max_out_amount => max_out_amount_decimal,
+    min_out_amount => min_out_amount_decimal,
+    out_received => out_received_decimal,
+    out_withdrawn => out_withdrawn_decimal,
+});
+
+decimal_accessors!(PriceOrder {
+    estimated_usdc_value_spent => estimated_usdc_value_spent_decimal,
+    in_deposited => in_deposited_decimal,
+    in_left => in_left_decimal,
+    in_used => in_used_decimal,
+    in_withdrawn => in_withdrawn_decimal,
+    incremental_usd_value => incremental_usd_value_decimal,
+    out_received => out_received_decimal,
+    out_withdrawn => out_withdrawn_decimal,
+    supposed_usd_value => supposed_usd_value_decimal,
+});
+
+decimal_accessors!(RecurringTrade {
+    input_amount => input_amount_decimal,
+    output_amount => output_amount_decimal,
+    fee_amount => fee_amount_decimal,
+});
@@ -1,6 +1,13 @@
-use serde::{Deserialize, Serialize, Serializer};
+use std::{convert::Infallible, str::FromStr};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A DEX Jupiter can route through, identified by its Jupiter label.
+///
+/// Serializes to and parses from the label (e.g. `"Meteora DLMM"`). Labels this SDK
+/// version does not know are kept as [`DexEnum::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DexEnum {
     Woofi,
     PumpFun,
@@ -51,6 +58,8 @@ pub enum DexEnum {
     OpenBook,
     Invariant,
     Guacswap,
+    /// A DEX label not known to this SDK version.
+    Other(String),
 }
 
 impl std::fmt::Display for DexEnum {
@@ -105,11 +114,168 @@ impl std::fmt::Display for DexEnum {
             DexEnum::OpenBook => "Openbook",
             DexEnum::Invariant => "Invariant",
             DexEnum::Guacswap => "Guacswap",
+            DexEnum::Other(label) => label,
         };
         write!(f, "{label}")
     }
 }
 
+impl DexEnum {
+    /// Every known DEX, in declaration order.
+    pub const ALL: [DexEnum; 49] = [
+        DexEnum::Woofi,
+        DexEnum::PumpFun,
+        DexEnum::Whirlpool,
+        DexEnum::Virtuals,
+        DexEnum::DaosFun,
+        DexEnum::LifinityV2,
+        DexEnum::StabbleStableSwap,
+        DexEnum::TokenMill,
+        DexEnum::Meteora,
+        DexEnum::Oasis,
+        DexEnum::Aldrin,
+        DexEnum::GooseFxGamma,
+        DexEnum::Perps,
+        DexEnum::SolFi,
+        DexEnum::DexLab,
+        DexEnum::TokenSwap,
+        DexEnum::ZeroFi,
+        DexEnum::Cropper,
+        DexEnum::ObricV2,
+        DexEnum::StabbleWeightedSwap,
+        DexEnum::SanctumInfinity,
+        DexEnum::Moonit,
+        DexEnum::Sanctum,
+        DexEnum::RaydiumCp,
+        DexEnum::Phoenix,
+        DexEnum::PumpFunAmm,
+        DexEnum::Saber,
+        DexEnum::SaberDecimals,
+        DexEnum::RaydiumClmm,
+        DexEnum::Dex1,
+        DexEnum::Penguin,
+        DexEnum::OrcaV2,
+        DexEnum::FluxBeam,
+        DexEnum::Raydium,
+        DexEnum::MeteoraDlmm,
+        DexEnum::Bonkswap,
+        DexEnum::Solayer,
+        DexEnum::Stepn,
+        DexEnum::HeliumNetwork,
+        DexEnum::Mercurial,
+        DexEnum::Perena,
+        DexEnum::OrcaV1,
+        DexEnum::AldrinV2,
+        DexEnum::Saros,
+        DexEnum::OpenBookV2,
+        DexEnum::Crema,
+        DexEnum::OpenBook,
+        DexEnum::Invariant,
+        DexEnum::Guacswap,
+    ];
+
+    /// Looks up a DEX by the label Jupiter uses, e.g. `"Meteora DLMM"`.
+    ///
+    /// Returns `None` for labels this SDK version does not know. Use `str::parse` to get
+    /// [`DexEnum::Other`] instead.
+    pub fn from_label(label: &str) -> Option<DexEnum> {
+        Self::ALL.into_iter().find(|dex| dex.to_string() == label)
+    }
+
+    /// Looks up a known DEX by its on-chain program ID.
+    pub fn from_program_id(program_id: &str) -> Option<DexEnum> {
+        Self::ALL
+            .into_iter()
+            .find(|dex| dex.program_id() == Some(program_id))
+    }
+
+    /// The on-chain program ID of the DEX, or `None` for [`DexEnum::Other`].
+    ///
+    /// This is a snapshot; `JupiterClient::get_program_id_to_label` is authoritative.
+    pub fn program_id(&self) -> Option<&'static str> {
+        let program_id = match self {
+            DexEnum::Woofi => "WooFif76YGRNjk1pA8wCsN67aQsD9f9iLsz4NcJ1AVb",
+            DexEnum::PumpFun => "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P",
+            DexEnum::Whirlpool => "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+            DexEnum::Virtuals => "5U3EU2ubXtK84QcRjWVmYt9RaDyA8gKxdUrPFXmZyaki",
+            DexEnum::DaosFun => "4FqThZWv3QKWkSyXCDmATpWkpEiCHq5yhkdGWpSEDAZM",
+            DexEnum::LifinityV2 => "2wT8Yq49kHgDzXuPxZSaeLaH1qbmGXtEyPy64bL7aD3c",
+            DexEnum::StabbleStableSwap => "swapNyd8XiQwJ6ianp9snpu4brUqFxadzvHebnAXjJZ",
+            DexEnum::TokenMill => "JoeaRXgtME3jAoz5WuFXGEndfv4NPH9nBxsLq44hk9J",
+            DexEnum::Meteora => "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB",
+            DexEnum::Oasis => "9tKE7Mbmj4mxDjWatikzGAtkoWosiiZX9y6J4Hfm2R8H",
+            DexEnum::Aldrin => "AMM55ShdkoGRB5jVYPjWziwk8m5MpwyDgsMWHaMSQWH6",
+            DexEnum::GooseFxGamma => "GAMMA7meSFWaBXF25oSUgmGRwaW6sCMFLmBNiMSdbHVT",
+            DexEnum::Perps => "PERPHjGBqRHArX4DySjwM6UJHiR3sWAatqfdBS2qQJu",
+            DexEnum::SolFi => "SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe",
+            DexEnum::DexLab => "DSwpgjMvXhtGn6BsbqmacdBZyfLj6jSWf3HJpdJtmg6N",
+            DexEnum::TokenSwap => "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8",
+            DexEnum::ZeroFi => "ZERor4xhbUycZ6gb9ntrhqscUcZmAbQDjEAtCf4hbZY",
+            DexEnum::Cropper => "H8W3ctz92svYg6mkn1UtGfu2aQr2fnUFHM1RhScEtQDt",
+            DexEnum::ObricV2 => "obriQD1zbpyLz95G5n7nJe6a4DPjpFwa5XYPoNm113y",
+            DexEnum::StabbleWeightedSwap => "swapFpHZwjELNnjvThjajtiVmkz3yPQEHjLtka2fwHW",
+            DexEnum::SanctumInfinity => "5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx",
+            DexEnum::Moonit => "MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG",
+            DexEnum::Sanctum => "stkitrT1Uoy18Dk1fTrgPw8W6MVzoCfYoAFT4MLsmhq",
+            DexEnum::RaydiumCp => "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C",
+            DexEnum::Phoenix => "PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY",
+            DexEnum::PumpFunAmm => "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA",
+            DexEnum::Saber => "SSwpkEEcbUqx4vtoEByFjSkhKdCT862DNVb52nZg1UZ",
+            DexEnum::SaberDecimals => "DecZY86MU5Gj7kppfUCEmd4LbXXuyZH1yHaP2NTqdiZB",
+            DexEnum::RaydiumClmm => "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK",
+            DexEnum::Dex1 => "DEXYosS6oEGvk8uCDayvwEZz4qEyDJRf9nFgYCaqPMTm",
+            DexEnum::Penguin => "PSwapMdSai8tjrEXcxFeQth87xC4rRsa4VA5mhGhXkP",
+            DexEnum::OrcaV2 => "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP",
+            DexEnum::FluxBeam => "FLUXubRmkEi2q6K3Y9kBPg9248ggaZVsoSFhtJHSrm1X",
+            DexEnum::Raydium => "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
+            DexEnum::MeteoraDlmm => "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
+            DexEnum::Bonkswap => "BSwp6bEBihVLdqJRKGgzjcGLHkcTuzmSo1TQkHepzH8p",
+            DexEnum::Solayer => "endoLNCKTqDn8gSVnN2hDdpgACUPWHZTwoYnnMybpAT",
+            DexEnum::Stepn => "Dooar9JkhdZ7J3LHN3A7YCuoGRUggXhQaG4kijfLGU2j",
+            DexEnum::HeliumNetwork => "treaf4wWBBty3fHdyBpo35Mz84M8k3heKXmjmi9vFt5",
+            DexEnum::Mercurial => "MERLuDFBMmsHnsBPZw2sDQZHvXFMwp8EdjudcU2HKky",
+            DexEnum::Perena => "NUMERUNsFCP3kuNmWZuXtm1AaQCPj9uw6Guv2Ekoi5P",
+            DexEnum::OrcaV1 => "DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1",
+            DexEnum::AldrinV2 => "CURVGoZn8zycx6FXwwevgBTB2gVvdbGTEpvMJDbgs2t4",
+            DexEnum::Saros => "SSwapUtytfBdBn1b9NUGG6foMVPtcWgpRU32HToDUZr",
+            DexEnum::OpenBookV2 => "opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb",
+            DexEnum::Crema => "CLMM9tUoggJu2wagPkkqs9eFG4BWhVBZWkP1qv3Sp7tR",
+            DexEnum::OpenBook => "srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX",
+            DexEnum::Invariant => "HyaB3W9q6XdA5xwpU4XnSZV94htfmbmqJXZcEbRaJutt",
+            DexEnum::Guacswap => "Gswppe6ERWKpUTXvRPfXdzHhiCyJvLadVvXGfdpBqcE1",
+            DexEnum::Other(_) => return None,
+        };
+        Some(program_id)
+    }
+}
+
+impl FromStr for DexEnum {
+    type Err = Infallible;
+
+    /// Parses a Jupiter label, falling back to the variant name (e.g. `"MeteoraDlmm"`),
+    /// and to [`DexEnum::Other`] for anything else.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dex = Self::from_label(s)
+            .or_else(|| Self::ALL.into_iter().find(|dex| format!("{dex:?}") == s))
+            .unwrap_or_else(|| DexEnum::Other(s.to_string()));
+        Ok(dex)
+    }
+}
+
+impl Serialize for DexEnum {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DexEnum {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let label = String::deserialize(deserializer)?;
+        let Ok(dex) = label.parse();
+        Ok(dex)
+    }
+}
+
 pub fn dex_vec_to_comma_string<S>(
     vec: &Option<Vec<DexEnum>>,
     serializer: S,
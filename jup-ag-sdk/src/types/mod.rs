@@ -7,17 +7,48 @@ pub use dex_enum::*;
 pub mod quote_response;
 pub use quote_response::*;
 
+pub mod quote_analysis;
+pub use quote_analysis::*;
+
+pub mod slippage;
+pub use slippage::*;
+
 pub mod swap_transaction;
 pub use swap_transaction::*;
 
+#[cfg(feature = "solana")]
+pub mod swap_and_send;
+#[cfg(feature = "solana")]
+pub use swap_and_send::*;
+
 pub mod ultra;
 pub use ultra::*;
 
+#[cfg(feature = "chrono")]
+pub mod timestamp;
+#[cfg(feature = "chrono")]
+pub use timestamp::parse_timestamp;
+
+#[cfg(feature = "decimal")]
+mod decimal;
+
 pub mod token;
 pub use token::*;
 
+pub mod trade_cost;
+pub use trade_cost::*;
+
+pub mod token_policy;
+pub use token_policy::*;
+
 pub mod trigger;
+// the deprecated `Order` and `Trade` aliases of `trigger` and `recurring` collide here, use
+// `TriggerOrder`, `TriggerTrade`, `RecurringOrder` and `RecurringTrade` instead
+#[allow(ambiguous_glob_reexports)]
 pub use trigger::*;
 
 pub mod recurring;
 pub use recurring::*;
+
+pub mod recurring_analysis;
+pub use recurring_analysis::*;
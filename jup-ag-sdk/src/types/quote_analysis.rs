@@ -0,0 +1,462 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    DexEnum, PlatformFee, QuoteGetSwapModeEnum, QuoteRequest, QuoteResponse, RoutePlanItem,
+    UltraOrderResponse,
+};
+
+/// Side-by-side `ExactIn` and `ExactOut` quotes for the same pair.
+///
+/// Returned by `JupiterClient::compare_swap_modes`. Prices are implied from the raw
+/// amounts (output units per input unit, before decimals).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteComparison {
+    /// Quote fetched with `swapMode=ExactIn`.
+    pub exact_in: QuoteResponse,
+
+    /// Quote fetched with `swapMode=ExactOut`.
+    pub exact_out: QuoteResponse,
+}
+
+impl QuoteComparison {
+    /// Raw output per raw input implied by the `ExactIn` quote.
+    pub fn exact_in_price(&self) -> Option<f64> {
+        implied_price(&self.exact_in)
+    }
+
+    /// Raw output per raw input implied by the `ExactOut` quote.
+    pub fn exact_out_price(&self) -> Option<f64> {
+        implied_price(&self.exact_out)
+    }
+
+    /// Relative difference between the two implied prices, in percent.
+    ///
+    /// Positive when `ExactIn` gives a better rate than `ExactOut`.
+    pub fn price_difference_pct(&self) -> Option<f64> {
+        let exact_in = self.exact_in_price()?;
+        let exact_out = self.exact_out_price()?;
+        if exact_out == 0.0 {
+            return None;
+        }
+        Some((exact_in - exact_out) / exact_out * 100.0)
+    }
+
+    /// Price impact reported for the `ExactIn` quote, in percent.
+    pub fn exact_in_price_impact_pct(&self) -> Option<f64> {
+        self.exact_in.price_impact_pct_f64()
+    }
+
+    /// Price impact reported for the `ExactOut` quote, in percent.
+    pub fn exact_out_price_impact_pct(&self) -> Option<f64> {
+        self.exact_out.price_impact_pct_f64()
+    }
+
+    /// AMM labels used by the `ExactIn` route, in hop order.
+    pub fn exact_in_route(&self) -> Vec<&str> {
+        route_labels(&self.exact_in)
+    }
+
+    /// AMM labels used by the `ExactOut` route, in hop order.
+    pub fn exact_out_route(&self) -> Vec<&str> {
+        route_labels(&self.exact_out)
+    }
+
+    /// Returns `true` if both quotes route through the same AMMs in the same order.
+    pub fn same_route(&self) -> bool {
+        let amm_keys = |quote: &QuoteResponse| {
+            quote
+                .route_plan
+                .iter()
+                .map(|hop| hop.swap_info.amm_key.clone())
+                .collect::<Vec<_>>()
+        };
+        amm_keys(&self.exact_in) == amm_keys(&self.exact_out)
+    }
+}
+
+fn implied_price(quote: &QuoteResponse) -> Option<f64> {
+    quote.effective_price(0, 0)
+}
+
+fn route_labels(quote: &QuoteResponse) -> Vec<&str> {
+    quote
+        .route_plan
+        .iter()
+        .map(|hop| hop.swap_info.label.as_str())
+        .collect()
+}
+
+/// A single point on a [`QuoteLadder`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepthPoint {
+    /// Raw input amount quoted.
+    pub size: u64,
+
+    /// Raw output amount returned for `size`.
+    pub out_amount: u64,
+
+    /// Raw output per raw input at this size.
+    pub implied_price: f64,
+}
+
+/// Output amounts for increasing trade sizes on the same pair.
+///
+/// Returned by `JupiterClient::quote_ladder`. Points are sorted by ascending size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteLadder {
+    pub input_mint: String,
+    pub output_mint: String,
+    pub points: Vec<DepthPoint>,
+
+    /// Sizes whose quote request failed, in the order they were requested.
+    #[serde(default)]
+    pub failed_sizes: Vec<u64>,
+}
+
+impl QuoteLadder {
+    /// Builds a ladder from quotes, skipping quotes whose amounts cannot be parsed.
+    pub fn from_quotes(input_mint: &str, output_mint: &str, quotes: &[QuoteResponse]) -> Self {
+        let mut points: Vec<DepthPoint> = quotes
+            .iter()
+            .filter_map(|quote| {
+                let size = quote.in_amount_u64()?;
+                let out_amount = quote.out_amount_u64()?;
+                if size == 0 {
+                    return None;
+                }
+                Some(DepthPoint {
+                    size,
+                    out_amount,
+                    implied_price: out_amount as f64 / size as f64,
+                })
+            })
+            .collect();
+        points.sort_by_key(|point| point.size);
+
+        Self {
+            input_mint: input_mint.to_string(),
+            output_mint: output_mint.to_string(),
+            points,
+            failed_sizes: Vec::new(),
+        }
+    }
+
+    /// Implied price of the smallest size, used as the reference price.
+    pub fn best_price(&self) -> Option<f64> {
+        self.points.first().map(|point| point.implied_price)
+    }
+
+    /// Price degradation of each point relative to the smallest size, in percent.
+    ///
+    /// Returns `(size, impact_pct)` pairs; the first entry is always `0.0`.
+    pub fn impact_curve(&self) -> Vec<(u64, f64)> {
+        let Some(best) = self.best_price().filter(|best| *best > 0.0) else {
+            return Vec::new();
+        };
+        self.points
+            .iter()
+            .map(|point| (point.size, (best - point.implied_price) / best * 100.0))
+            .collect()
+    }
+
+    /// Largest quoted size whose impact relative to the smallest size stays within
+    /// `max_impact_pct`.
+    pub fn max_size_within_impact(&self, max_impact_pct: f64) -> Option<u64> {
+        self.impact_curve()
+            .into_iter()
+            .filter(|(_, impact)| *impact <= max_impact_pct)
+            .map(|(size, _)| size)
+            .max()
+    }
+}
+
+/// Fees charged by a single hop of a route.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HopFee {
+    /// AMM label, e.g. `"Whirlpool"`.
+    pub label: String,
+
+    /// AMM account the hop routes through.
+    pub amm_key: String,
+
+    /// Share of the previous hop's output routed through this AMM.
+    pub percent: i32,
+
+    /// Mint the LP fee is charged in.
+    pub fee_mint: String,
+
+    /// Raw LP fee amount.
+    pub fee_amount: u64,
+
+    /// LP fee relative to the hop's input amount, in basis points.
+    ///
+    /// Only meaningful when `fee_mint` is the hop's input mint.
+    pub fee_bps: Option<f64>,
+}
+
+/// Aggregated fees for a quote's route plan.
+///
+/// Build with [`FeeBreakdown::from_quote`] or `QuoteResponse::fee_breakdown`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    /// Per-hop LP fees in route order.
+    pub hops: Vec<HopFee>,
+
+    /// Total LP fees grouped by fee mint.
+    pub lp_fees_by_mint: HashMap<String, u64>,
+
+    /// Mint the platform fee is charged in, if a platform fee was applied.
+    pub platform_fee_mint: Option<String>,
+
+    /// Raw platform fee amount.
+    pub platform_fee_amount: Option<u64>,
+
+    /// Platform fee in basis points.
+    pub platform_fee_bps: Option<u16>,
+}
+
+impl FeeBreakdown {
+    /// Aggregates LP and platform fees from a quote.
+    ///
+    /// The platform fee is charged in the output mint for `ExactIn` quotes and in the
+    /// input mint for `ExactOut` quotes.
+    pub fn from_quote(quote: &QuoteResponse) -> Self {
+        Self::from_route(
+            &quote.route_plan,
+            quote.platform_fee.as_ref(),
+            &quote.swap_mode,
+            &quote.input_mint,
+            &quote.output_mint,
+        )
+    }
+
+    /// Aggregates LP and platform fees from an Ultra order, as for a quote.
+    pub fn from_ultra_order(order: &UltraOrderResponse) -> Self {
+        Self::from_route(
+            &order.route_plan,
+            order.platform_fee.as_ref(),
+            &order.swap_mode,
+            &order.input_mint,
+            &order.output_mint,
+        )
+    }
+
+    fn from_route(
+        route_plan: &[RoutePlanItem],
+        platform_fee: Option<&PlatformFee>,
+        swap_mode: &QuoteGetSwapModeEnum,
+        input_mint: &str,
+        output_mint: &str,
+    ) -> Self {
+        let mut lp_fees_by_mint: HashMap<String, u64> = HashMap::new();
+
+        let hops = route_plan
+            .iter()
+            .map(|hop| {
+                let info = &hop.swap_info;
+                let fee_amount = info.fee_amount.parse().unwrap_or(0);
+                *lp_fees_by_mint.entry(info.fee_mint.clone()).or_default() += fee_amount;
+
+                let in_amount: u64 = info.in_amount.parse().unwrap_or(0);
+                let fee_bps = (info.fee_mint == info.input_mint && in_amount > 0)
+                    .then(|| fee_amount as f64 * 10_000.0 / in_amount as f64);
+
+                HopFee {
+                    label: info.label.clone(),
+                    amm_key: info.amm_key.clone(),
+                    percent: hop.percent,
+                    fee_mint: info.fee_mint.clone(),
+                    fee_amount,
+                    fee_bps,
+                }
+            })
+            .collect();
+
+        let (platform_fee_mint, platform_fee_amount, platform_fee_bps) = match platform_fee {
+            Some(fee) => {
+                let mint = match swap_mode {
+                    QuoteGetSwapModeEnum::ExactIn => output_mint,
+                    QuoteGetSwapModeEnum::ExactOut => input_mint,
+                };
+                (
+                    Some(mint.to_string()),
+                    fee.amount.parse().ok(),
+                    Some(fee.fee_bps),
+                )
+            }
+            None => (None, None, None),
+        };
+
+        Self {
+            hops,
+            lp_fees_by_mint,
+            platform_fee_mint,
+            platform_fee_amount,
+            platform_fee_bps,
+        }
+    }
+
+    /// LP and platform fees combined, grouped by mint.
+    pub fn total_fees_by_mint(&self) -> HashMap<String, u64> {
+        let mut totals = self.lp_fees_by_mint.clone();
+        if let (Some(mint), Some(amount)) = (&self.platform_fee_mint, self.platform_fee_amount) {
+            *totals.entry(mint.clone()).or_default() += amount;
+        }
+        totals
+    }
+
+    /// Number of hops in the route.
+    pub fn hop_count(&self) -> usize {
+        self.hops.len()
+    }
+}
+
+impl QuoteResponse {
+    /// Aggregates the LP and platform fees of this quote. See [`FeeBreakdown`].
+    pub fn fee_breakdown(&self) -> FeeBreakdown {
+        FeeBreakdown::from_quote(self)
+    }
+}
+
+impl UltraOrderResponse {
+    /// Aggregates the LP and platform fees of this order. See [`FeeBreakdown`].
+    pub fn fee_breakdown(&self) -> FeeBreakdown {
+        FeeBreakdown::from_ultra_order(self)
+    }
+}
+
+/// A named set of quote parameters tried by `JupiterClient::best_quote`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteVariant {
+    /// Label used in the [`VariantReport`].
+    pub name: String,
+
+    /// The request to quote.
+    pub request: QuoteRequest,
+}
+
+impl QuoteVariant {
+    pub fn new(name: impl Into<String>, request: QuoteRequest) -> Self {
+        Self {
+            name: name.into(),
+            request,
+        }
+    }
+
+    /// Common variants of `base`: as-is, direct routes only, and intermediate token
+    /// restriction on and off.
+    pub fn defaults(base: &QuoteRequest) -> Vec<QuoteVariant> {
+        vec![
+            QuoteVariant::new("base", base.clone()),
+            QuoteVariant::new("direct-routes-only", base.clone().only_direct_routes(true)),
+            QuoteVariant::new(
+                "restricted-intermediates",
+                base.clone().restrict_intermediate_tokens(true),
+            ),
+            QuoteVariant::new(
+                "unrestricted-intermediates",
+                base.clone().restrict_intermediate_tokens(false),
+            ),
+        ]
+    }
+
+    /// A variant of `base` that only routes through `dexes`.
+    pub fn with_dexes(name: impl Into<String>, base: &QuoteRequest, dexes: Vec<DexEnum>) -> Self {
+        Self::new(name, base.clone().dexes(dexes))
+    }
+}
+
+/// Outcome of a single [`QuoteVariant`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariantReport {
+    pub name: String,
+
+    /// Raw input amount of the quote, `None` if the variant failed.
+    pub in_amount: Option<u64>,
+
+    /// Raw output amount of the quote, `None` if the variant failed.
+    pub out_amount: Option<u64>,
+
+    /// Number of hops in the route.
+    pub hops: usize,
+
+    /// Error message if the variant failed.
+    pub error: Option<String>,
+}
+
+/// The winning quote of `JupiterClient::best_quote` and a report for every variant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BestQuote {
+    /// Name of the winning variant.
+    pub variant: String,
+
+    /// The winning quote.
+    pub quote: QuoteResponse,
+
+    /// One report per variant, in the order they were given.
+    pub reports: Vec<VariantReport>,
+}
+
+impl BestQuote {
+    /// Picks the best quote: highest output for `ExactIn`, lowest input for `ExactOut`.
+    ///
+    /// Returns the reports as the error when no variant succeeded.
+    pub fn select<E: std::fmt::Display>(
+        variants: &[QuoteVariant],
+        results: Vec<Result<QuoteResponse, E>>,
+    ) -> Result<Self, Vec<VariantReport>> {
+        let mut reports = Vec::with_capacity(results.len());
+        let mut best: Option<(usize, QuoteResponse)> = None;
+
+        for (i, (variant, result)) in variants.iter().zip(results).enumerate() {
+            match result {
+                Ok(quote) => {
+                    reports.push(VariantReport {
+                        name: variant.name.clone(),
+                        in_amount: quote.in_amount_u64(),
+                        out_amount: quote.out_amount_u64(),
+                        hops: quote.route_plan.len(),
+                        error: None,
+                    });
+                    let better = match &best {
+                        None => true,
+                        Some((_, current)) => is_better(&quote, current),
+                    };
+                    if better {
+                        best = Some((i, quote));
+                    }
+                }
+                Err(e) => reports.push(VariantReport {
+                    name: variant.name.clone(),
+                    in_amount: None,
+                    out_amount: None,
+                    hops: 0,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        match best {
+            Some((i, quote)) => Ok(Self {
+                variant: variants[i].name.clone(),
+                quote,
+                reports,
+            }),
+            None => Err(reports),
+        }
+    }
+}
+
+fn is_better(candidate: &QuoteResponse, current: &QuoteResponse) -> bool {
+    match candidate.swap_mode {
+        QuoteGetSwapModeEnum::ExactIn => {
+            candidate.out_amount_u64().unwrap_or(0) > current.out_amount_u64().unwrap_or(0)
+        }
+        QuoteGetSwapModeEnum::ExactOut => {
+            candidate.in_amount_u64().unwrap_or(u64::MAX)
+                < current.in_amount_u64().unwrap_or(u64::MAX)
+        }
+    }
+}
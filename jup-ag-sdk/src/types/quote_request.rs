@@ -1,14 +1,23 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize, Serializer};
 
-use super::{DexEnum, dex_vec_to_comma_string};
+use super::{DexEnum, Slippage, dex_vec_to_comma_string};
+use crate::{
+    decimals::RawAmount,
+    error::{JupiterClientError, ValidationError},
+    validation::{check_address, check_bps, check_non_zero},
+};
 
 /// A request struct for fetching a quote from Jupiter's `/quote` endpoint.
 ///
 /// Use `QuoteRequest::new()` and the fluent setters to configure parameters.
 ///
 /// [Official API docs](https://docs.jup.ag/apis/quote)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct QuoteRequest {
     /// The mint address of the input token.
     ///
@@ -31,6 +40,7 @@ pub struct QuoteRequest {
     ///
     /// Example: `100` for 1% slippage.
     /// Optional; used only if `dyanmic_slippage` is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub slippage_bps: Option<u16>,
 
     /// Determines whether the amount is `ExactIn` or `ExactOut`.
@@ -39,54 +49,85 @@ pub struct QuoteRequest {
     /// - `ExactOut`: guarantees output amount, computes input.
     ///
     /// Default: `ExactIn`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub swap_mode: Option<QuoteGetSwapModeEnum>,
 
     /// A list of DEXes to exclusively include in routing.
     ///
     /// Example: `["Orca", "Meteora+DLMM"]`
-    #[serde(serialize_with = "dex_vec_to_comma_string")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "dex_vec_to_comma_string"
+    )]
     pub dexes: Option<Vec<DexEnum>>,
 
     /// A list of DEXes to exclude from routing.
     ///
     /// Example: `["Raydium", "Lifinity"]`
-    #[serde(serialize_with = "dex_vec_to_comma_string")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "dex_vec_to_comma_string"
+    )]
     pub exclude_dexes: Option<Vec<DexEnum>>,
 
+    /// DEX program IDs to exclude from routing.
+    ///
+    /// Not sent to the API; resolved into `exclude_dexes` via the program-id-to-label map.
+    #[serde(skip)]
+    pub exclude_program_ids: Option<Vec<String>>,
+
     /// If true, restricts intermediate tokens to a stable set.
     ///
     /// Reduces slippage risk. Default: `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub restrict_intermediate_tokens: Option<bool>,
 
     /// If true, only direct (single-hop) routes are allowed.
     ///
     /// May return suboptimal pricing. Default: `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub only_direct_routes: Option<bool>,
 
     /// If true, the quote is prepared for legacy (v0) transactions.
     ///
     /// Use this if you're building a legacy transaction manually.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub as_legacy_transaction: Option<bool>,
 
     /// Platform fee in basis points (bps).
     ///
     /// Used with `feeAccount` in `/swap` to apply affiliate/platform fees.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub platform_fee_bps: Option<u64>,
 
     /// Upper bound on the number of accounts used in the quote.
     ///
     /// Helps with resource budgeting. Default: `64`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_accounts: Option<u8>,
 
     /// Enables Jupiter's dynamic slippage estimation.
     ///
     /// If true, overrides `slippage_bps`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamic_slippage: Option<bool>,
+
+    /// Lets Jupiter compute the slippage for the quote.
+    ///
+    /// If true, overrides `slippage_bps`, capped by `max_auto_slippage_bps`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_slippage: Option<bool>,
+
+    /// Upper bound on the slippage Jupiter may pick when `auto_slippage` is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_auto_slippage_bps: Option<u16>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum QuoteGetSwapModeEnum {
+    #[default]
     ExactIn,
     ExactOut,
 }
@@ -97,7 +138,7 @@ impl QuoteRequest {
     /// # Arguments
     /// * `input_mint` - The mint address of the input token (e.g., SOL mint).
     /// * `output_mint` - The mint address of the output token (e.g., JUP mint).
-    /// * `amount` - The amount to swap (raw, before decimals). Meaning depends on `swap_mode`.
+    /// * `amount` - The amount to swap (raw `u64` or a `TokenAmount`). Meaning depends on `swap_mode`.
     ///
     /// # Returns
     /// A new `QuoteRequest` instance with None value for optional fields.
@@ -114,24 +155,45 @@ impl QuoteRequest {
     /// assert_eq!(request.output_mint, "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN");
     /// assert_eq!(request.amount, 1_000_000_000);
     /// ```
-    pub fn new(input_mint: &str, output_mint: &str, amount: u64) -> Self {
+    pub fn new(input_mint: &str, output_mint: &str, amount: impl RawAmount) -> Self {
         Self {
             input_mint: input_mint.to_string(),
             output_mint: output_mint.to_string(),
-            amount,
+            amount: amount.raw_amount(),
             slippage_bps: None,
             swap_mode: None,
             dexes: None,
             exclude_dexes: None,
+            exclude_program_ids: None,
             restrict_intermediate_tokens: None,
             only_direct_routes: None,
             as_legacy_transaction: None,
             platform_fee_bps: None,
             max_accounts: None,
             dynamic_slippage: None,
+            auto_slippage: None,
+            max_auto_slippage_bps: None,
         }
     }
 
+    /// Sets the mint address of the input token.
+    pub fn input_mint(mut self, input_mint: &str) -> Self {
+        self.input_mint = input_mint.to_string();
+        self
+    }
+
+    /// Sets the mint address of the output token.
+    pub fn output_mint(mut self, output_mint: &str) -> Self {
+        self.output_mint = output_mint.to_string();
+        self
+    }
+
+    /// Sets the amount to swap (raw `u64` or a `TokenAmount`).
+    pub fn amount(mut self, amount: impl RawAmount) -> Self {
+        self.amount = amount.raw_amount();
+        self
+    }
+
     /// Sets the slippage tolerance in basis points (bps).
     ///
     /// Only used if `dynamic_slippage` is `false`. 100 bps = 1% slippage.
@@ -158,6 +220,44 @@ impl QuoteRequest {
         self
     }
 
+    /// Sets the slippage tolerance from a validated [`Slippage`].
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::new(
+    ///     "So11111111111111111111111111111111111111112",
+    ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+    ///     1_000_000_000
+    /// )
+    /// .slippage(Slippage::from_percent(0.5)?);
+    /// assert_eq!(request.slippage_bps, Some(50));
+    /// ```
+    pub fn slippage(mut self, slippage: Slippage) -> Self {
+        self.slippage_bps = Some(slippage.bps());
+        self
+    }
+
+    /// Lets Jupiter compute the slippage, never exceeding `max`.
+    ///
+    /// Sets both `auto_slippage` and `max_auto_slippage_bps`.
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::new(
+    ///     "So11111111111111111111111111111111111111112",
+    ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+    ///     1_000_000_000
+    /// )
+    /// .auto_slippage(Slippage::from_bps(300)?);
+    /// assert_eq!(request.auto_slippage, Some(true));
+    /// assert_eq!(request.max_auto_slippage_bps, Some(300));
+    /// ```
+    pub fn auto_slippage(mut self, max: Slippage) -> Self {
+        self.auto_slippage = Some(true);
+        self.max_auto_slippage_bps = Some(max.bps());
+        self
+    }
+
     /// Sets the swap mode (`ExactIn` or `ExactOut`).
     ///
     /// - `ExactIn`: Specifies input amount, computes output.
@@ -236,6 +336,31 @@ impl QuoteRequest {
         self
     }
 
+    /// Sets DEX program IDs to exclude from routing.
+    ///
+    /// Program IDs are stable while labels change as Jupiter adds venues.
+    /// `JupiterClient::get_quote` resolves them to labels before sending, see
+    /// [`QuoteRequest::resolve_program_ids`].
+    ///
+    /// # Arguments
+    /// * `program_ids` - On-chain program IDs of the DEXes to exclude.
+    ///
+    /// # Returns
+    /// The modified `QuoteRequest` for chaining.
+    ///
+    /// # Example
+    /// ```
+    /// let request = QuoteRequest::new(
+    ///     "So11111111111111111111111111111111111111112",
+    ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+    ///     1_000_000_000
+    /// ).exclude_program_ids(vec!["675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8".to_string()]);
+    /// ```
+    pub fn exclude_program_ids(mut self, program_ids: Vec<String>) -> Self {
+        self.exclude_program_ids = Some(program_ids);
+        self
+    }
+
     /// Sets whether to restrict intermediate tokens to a stable set.
     ///
     /// Reduces slippage risk by limiting intermediate tokens. Default: `true`.
@@ -398,6 +523,85 @@ impl QuoteRequest {
     }
 }
 
+impl QuoteRequest {
+    /// Checks the request for mistakes the API would reject with a generic 400.
+    ///
+    /// Called by `JupiterClient::get_quote` before sending. Verifies that:
+    /// - both mints look like base58 addresses and differ
+    /// - `amount` is greater than 0
+    /// - slippage and platform fee are at most 10000 bps (100%)
+    /// - excluded program IDs look like base58 addresses
+    /// - no DEX is both included and excluded
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_address("input_mint", &self.input_mint)?;
+        check_address("output_mint", &self.output_mint)?;
+        if self.input_mint == self.output_mint {
+            return Err(ValidationError::SameMint);
+        }
+
+        check_non_zero("amount", self.amount)?;
+
+        for program_id in self.exclude_program_ids.iter().flatten() {
+            check_address("exclude_program_ids", program_id)?;
+        }
+
+        let max_bps = Slippage::MAX_BPS as u64;
+        if let Some(bps) = self.slippage_bps {
+            check_bps("slippage_bps", bps as u64, max_bps)?;
+        }
+        if let Some(bps) = self.max_auto_slippage_bps {
+            check_bps("max_auto_slippage_bps", bps as u64, max_bps)?;
+        }
+        if let Some(bps) = self.platform_fee_bps {
+            check_bps("platform_fee_bps", bps, max_bps)?;
+        }
+
+        if let (Some(dexes), Some(excluded)) = (&self.dexes, &self.exclude_dexes) {
+            let overlap: Vec<String> = dexes
+                .iter()
+                .filter(|dex| excluded.contains(dex))
+                .map(|dex| dex.to_string())
+                .collect();
+            if !overlap.is_empty() {
+                return Err(ValidationError::OverlappingDexes(overlap));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl QuoteRequest {
+    /// Moves `exclude_program_ids` into `exclude_dexes` using a program-id-to-label map.
+    ///
+    /// Fetch the map once with `JupiterClient::get_program_id_to_label` and reuse it to
+    /// avoid the extra request `get_quote` makes for unresolved program IDs.
+    ///
+    /// # Errors
+    /// `InvalidRequest` if a program ID is missing from the map.
+    pub fn resolve_program_ids(
+        mut self,
+        labels: &HashMap<String, String>,
+    ) -> Result<Self, JupiterClientError> {
+        let Some(program_ids) = self.exclude_program_ids.take() else {
+            return Ok(self);
+        };
+
+        let excluded = self.exclude_dexes.get_or_insert_with(Vec::new);
+        for program_id in program_ids {
+            let label = labels.get(&program_id).ok_or_else(|| {
+                JupiterClientError::InvalidRequest(format!("unknown DEX program id {program_id}"))
+            })?;
+            let Ok(dex) = label.parse::<DexEnum>();
+            if !excluded.contains(&dex) {
+                excluded.push(dex);
+            }
+        }
+
+        Ok(self)
+    }
+}
+
 pub fn vec_to_comma_string<S>(vec: &Option<Vec<String>>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -1,12 +1,18 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
 use serde::{Deserialize, Serialize};
 
-use super::QuoteGetSwapModeEnum;
+use super::{QuoteGetSwapModeEnum, QuoteRequest};
 
 /// A response returned by Jupiter’s `/quote` endpoint.
 ///
 /// Includes detailed routing, fee, and token swap info.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct QuoteResponse {
     /// The input token mint address.
     pub input_mint: String,
@@ -43,12 +49,17 @@ pub struct QuoteResponse {
 
     /// Optional: A diagnostic or scoring report from Jupiter.
     #[serde(default)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub score_report: Option<serde_json::Value>,
 
     /// The Solana slot number used for this quote.
+    #[serde(default)]
     pub context_slot: u64,
 
     /// Total time taken by Jupiter to generate this quote (in seconds).
+    ///
+    /// Not returned by every self-hosted `jupiter-swap-api` version, defaults to `0.0`.
+    #[serde(default)]
     pub time_taken: f64,
 
     /// Optional: Estimated USD value of the swap.
@@ -65,25 +76,180 @@ pub struct QuoteResponse {
 
     /// Optional: Slippage estimated by Jupiter’s internal engine.
     #[serde(default)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub use_incurred_slippage_for_quoting: Option<serde_json::Value>,
+
+    /// Fields this SDK version does not model, kept so the quote is sent back to `/swap`
+    /// unchanged. Always empty with the `strict-serde` feature, which rejects them instead.
+    #[cfg_attr(not(feature = "strict-serde"), serde(flatten))]
+    #[cfg_attr(feature = "strict-serde", serde(skip))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl QuoteResponse {
+    /// The raw input amount as a `u64`, or `None` if it cannot be parsed.
+    pub fn in_amount_u64(&self) -> Option<u64> {
+        self.in_amount.parse().ok()
+    }
+
+    /// The raw output amount as a `u64`, or `None` if it cannot be parsed.
+    pub fn out_amount_u64(&self) -> Option<u64> {
+        self.out_amount.parse().ok()
+    }
+
+    /// `other_amount_threshold` as a `u64`, or `None` if it cannot be parsed.
+    pub fn other_amount_threshold_u64(&self) -> Option<u64> {
+        self.other_amount_threshold.parse().ok()
+    }
+
+    /// The minimum raw output amount guaranteed after slippage.
+    ///
+    /// For `ExactIn` this is `other_amount_threshold`, for `ExactOut` the output is fixed.
+    pub fn min_out_amount_u64(&self) -> Option<u64> {
+        match self.swap_mode {
+            QuoteGetSwapModeEnum::ExactIn => self.other_amount_threshold_u64(),
+            QuoteGetSwapModeEnum::ExactOut => self.out_amount_u64(),
+        }
+    }
+
+    /// The maximum raw input amount that may be spent after slippage.
+    ///
+    /// For `ExactOut` this is `other_amount_threshold`, for `ExactIn` the input is fixed.
+    pub fn max_in_amount_u64(&self) -> Option<u64> {
+        match self.swap_mode {
+            QuoteGetSwapModeEnum::ExactIn => self.in_amount_u64(),
+            QuoteGetSwapModeEnum::ExactOut => self.other_amount_threshold_u64(),
+        }
+    }
+
+    /// `price_impact_pct` as an `f64`, or `None` if it cannot be parsed.
+    pub fn price_impact_pct_f64(&self) -> Option<f64> {
+        self.price_impact_pct.parse().ok()
+    }
+
+    /// Output tokens received per input token, adjusted for decimals.
+    ///
+    /// # Arguments
+    /// * `in_decimals` - Decimals of the input mint (e.g. `9` for SOL).
+    /// * `out_decimals` - Decimals of the output mint (e.g. `6` for USDC).
+    ///
+    /// # Example
+    /// ```
+    /// // 1 SOL -> 150 USDC
+    /// let price = quote.effective_price(9, 6); // Some(150.0)
+    /// ```
+    pub fn effective_price(&self, in_decimals: u8, out_decimals: u8) -> Option<f64> {
+        let in_amount = self.in_amount_u64()? as f64 / 10f64.powi(in_decimals as i32);
+        let out_amount = self.out_amount_u64()? as f64 / 10f64.powi(out_decimals as i32);
+        if in_amount == 0.0 {
+            return None;
+        }
+        Some(out_amount / in_amount)
+    }
+
+    /// Derives a [`QuoteRequest`] that re-quotes the same trade.
+    ///
+    /// Carries over the mints, the fixed amount for the quote's swap mode, the swap mode,
+    /// the slippage and the platform fee. Returns `None` if the amount cannot be parsed.
+    ///
+    /// # Example
+    /// ```
+    /// let request = quote.to_quote_request().expect("valid amounts");
+    /// let requote = client.get_quote(&request).await?;
+    /// ```
+    pub fn to_quote_request(&self) -> Option<QuoteRequest> {
+        let amount = match self.swap_mode {
+            QuoteGetSwapModeEnum::ExactIn => self.in_amount_u64()?,
+            QuoteGetSwapModeEnum::ExactOut => self.out_amount_u64()?,
+        };
+
+        let mut request = QuoteRequest::new(&self.input_mint, &self.output_mint, amount)
+            .swap_mode(self.swap_mode.clone())
+            .slippage_bps(self.slippage_bps);
+
+        if let Some(fee) = &self.platform_fee {
+            request = request.platform_fee_bps(fee.fee_bps as u64);
+        }
+
+        Some(request)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PlatformFee {
     pub amount: String,
     pub fee_bps: u16,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RoutePlanItem {
     pub swap_info: SwapInfo,
     pub percent: i32,
+
+    /// Fields this SDK version does not model, kept so the route is sent back to `/swap`
+    /// unchanged. Always empty with the `strict-serde` feature, which rejects them instead.
+    #[cfg_attr(not(feature = "strict-serde"), serde(flatten))]
+    #[cfg_attr(feature = "strict-serde", serde(skip))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// One-line summary of the quote, with raw amounts.
+///
+/// # Example
+/// ```
+/// println!("{quote}");
+/// // 1000000000 So11..1112 -> 400000000 JUPy..DvCN (min out 398000000), impact 0.0012%, slippage 50 bps, 2 hops
+/// ```
+impl fmt::Display for QuoteResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_swap_summary(
+            f,
+            (&self.input_mint, &self.in_amount),
+            (&self.output_mint, &self.out_amount),
+            &self.swap_mode,
+            &self.other_amount_threshold,
+        )?;
+        let hops = self.route_plan.len();
+        write!(
+            f,
+            ", impact {}%, slippage {} bps, {hops} hop{}",
+            self.price_impact_pct,
+            self.slippage_bps,
+            if hops == 1 { "" } else { "s" },
+        )
+    }
+}
+
+impl fmt::Display for RoutePlanItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let info = &self.swap_info;
+        write!(
+            f,
+            "{} {} -> {} ({}%) in: {} out: {} fee: {} {}",
+            info.label,
+            short_mint(&info.input_mint),
+            short_mint(&info.output_mint),
+            self.percent,
+            info.in_amount,
+            info.out_amount,
+            info.fee_amount,
+            short_mint(&info.fee_mint),
+        )
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SwapInfo {
     pub amm_key: String,
     pub label: String,
@@ -93,10 +259,197 @@ pub struct SwapInfo {
     pub out_amount: String,
     pub fee_amount: String,
     pub fee_mint: String,
+
+    /// Fields this SDK version does not model, kept so the route is sent back to `/swap`
+    /// unchanged. Always empty with the `strict-serde` feature, which rejects them instead.
+    #[cfg_attr(not(feature = "strict-serde"), serde(flatten))]
+    #[cfg_attr(feature = "strict-serde", serde(skip))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MostReliableAmmsQuoteReport {
-    pub info: std::collections::HashMap<String, String>,
+    pub info: HashMap<String, String>,
+}
+
+/// A [`QuoteResponse`] together with the [`QuoteRequest`] that produced it and the
+/// instant it was fetched.
+///
+/// Quotes go stale quickly; use [`FreshQuote::is_stale`] or
+/// `JupiterClient::refresh_if_stale` to re-quote before building a swap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreshQuote {
+    /// The request used to fetch the quote, reused when re-quoting.
+    pub request: QuoteRequest,
+
+    /// The quote returned by Jupiter.
+    pub quote: QuoteResponse,
+
+    /// When the quote was received.
+    pub fetched_at: Instant,
+}
+
+impl FreshQuote {
+    /// Wraps a quote, marking it as fetched now.
+    pub fn new(request: QuoteRequest, quote: QuoteResponse) -> Self {
+        Self {
+            request,
+            quote,
+            fetched_at: Instant::now(),
+        }
+    }
+
+    /// Time elapsed since the quote was fetched.
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
+
+    /// Returns `true` if the quote is older than `max_age`.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+
+    /// Unwraps the inner [`QuoteResponse`], e.g. to pass it to `SwapRequest::new`.
+    pub fn into_quote(self) -> QuoteResponse {
+        self.quote
+    }
+}
+
+impl QuoteResponse {
+    /// Formats the route plan as one numbered line per hop.
+    ///
+    /// # Example
+    /// ```
+    /// println!("{}", quote.format_route());
+    /// // 1. Whirlpool So11..1112 -> EPjF..Dt1v (100%) in: 1000000000 out: 150000000 fee: 300000 So11..1112
+    /// // 2. Meteora DLMM EPjF..Dt1v -> JUPy..DvCN (100%) in: 150000000 out: 400000000 fee: 45000 EPjF..Dt1v
+    /// ```
+    pub fn format_route(&self) -> String {
+        self.route_plan
+            .iter()
+            .enumerate()
+            .map(|(i, hop)| format!("{}. {}", i + 1, hop))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Exports the route plan as a Graphviz DOT digraph.
+    ///
+    /// Nodes are mints, edges are hops labelled with the venue and split percentage.
+    pub fn route_to_dot(&self) -> String {
+        let mut out = String::from("digraph route {\n    rankdir=LR;\n");
+        for mint in self.route_mints() {
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\"];\n",
+                dot_escape(mint),
+                dot_escape(&short_mint(mint))
+            ));
+        }
+        for (i, hop) in self.route_plan.iter().enumerate() {
+            let info = &hop.swap_info;
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}. {} ({}%)\"];\n",
+                dot_escape(&info.input_mint),
+                dot_escape(&info.output_mint),
+                i + 1,
+                dot_escape(&info.label),
+                hop.percent
+            ));
+        }
+        out.push('}');
+        out
+    }
+
+    /// Exports the route plan as a Mermaid `graph LR` diagram.
+    ///
+    /// Nodes are mints, edges are hops labelled with the venue and split percentage.
+    pub fn route_to_mermaid(&self) -> String {
+        let mints = self.route_mints();
+        let node_id = |mint: &str| {
+            let idx = mints.iter().position(|m| *m == mint).unwrap_or(0);
+            format!("m{}", idx)
+        };
+
+        let mut out = String::from("graph LR\n");
+        for mint in &mints {
+            out.push_str(&format!(
+                "    {}[\"{}\"]\n",
+                node_id(mint),
+                mermaid_escape(&short_mint(mint))
+            ));
+        }
+        for (i, hop) in self.route_plan.iter().enumerate() {
+            let info = &hop.swap_info;
+            out.push_str(&format!(
+                "    {} -->|\"{}. {} ({}%)\"| {}\n",
+                node_id(&info.input_mint),
+                i + 1,
+                mermaid_escape(&info.label),
+                hop.percent,
+                node_id(&info.output_mint)
+            ));
+        }
+        out
+    }
+
+    /// Distinct mints touched by the route, in order of first appearance.
+    fn route_mints(&self) -> Vec<&str> {
+        let mut mints: Vec<&str> = Vec::new();
+        for hop in &self.route_plan {
+            for mint in [&hop.swap_info.input_mint, &hop.swap_info.output_mint] {
+                if !mints.contains(&mint.as_str()) {
+                    mints.push(mint);
+                }
+            }
+        }
+        mints
+    }
+}
+
+/// Writes `in_amount input -> out_amount output (min out threshold)`, the common part of the
+/// quote and Ultra order summaries.
+pub(crate) fn write_swap_summary(
+    f: &mut fmt::Formatter<'_>,
+    (input_mint, in_amount): (&str, &str),
+    (output_mint, out_amount): (&str, &str),
+    swap_mode: &QuoteGetSwapModeEnum,
+    other_amount_threshold: &str,
+) -> fmt::Result {
+    let threshold = match swap_mode {
+        QuoteGetSwapModeEnum::ExactIn => "min out",
+        QuoteGetSwapModeEnum::ExactOut => "max in",
+    };
+    write!(
+        f,
+        "{in_amount} {} -> {out_amount} {} ({threshold} {other_amount_threshold})",
+        short_mint(input_mint),
+        short_mint(output_mint),
+    )
+}
+
+/// Shortens a mint address to `abcd..wxyz` for display.
+pub(crate) fn short_mint(mint: &str) -> String {
+    let len = mint.chars().count();
+    if len <= 10 {
+        return mint.to_string();
+    }
+    let head: String = mint.chars().take(4).collect();
+    let tail: String = mint.chars().skip(len - 4).collect();
+    format!("{head}..{tail}")
+}
+
+/// Escapes a string for a double-quoted DOT ID.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Escapes a string for a double-quoted Mermaid label, which has no backslash escapes.
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;").replace('\n', " ")
 }
@@ -1,9 +1,30 @@
-use super::OrderStatus;
+#[cfg(feature = "chrono")]
+use super::timestamp::parse_timestamp;
+use super::{OrderStatus, quote_response::short_mint};
+use crate::{
+    decimals::RawAmount,
+    error::ValidationError,
+    validation::{check_address, check_non_zero},
+};
 use serde::{Deserialize, Serialize};
 
+/// Minimum number of orders of a time-based recurring order.
+pub const MIN_RECURRING_ORDERS: u64 = 2;
+
+/// Minimum time between two executions of a recurring order, in seconds.
+pub const MIN_RECURRING_INTERVAL_SECS: u64 = 60;
+
+/// Minimum total value of a recurring order, in USD.
+pub const MIN_RECURRING_TOTAL_USD: u64 = 100;
+
+/// Minimum value of each execution of a time-based recurring order, in USD.
+pub const MIN_RECURRING_ORDER_USD: u64 = 50;
+
 /// Represents a request to create a recurring order, either time-based or price-based.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct CreateRecurringOrderRequest {
     /// The wallet address initiating the order.
     pub user: String,
@@ -18,8 +39,9 @@ pub struct CreateRecurringOrderRequest {
 /// Enum wrapper for the two types of recurring order strategies:
 /// - `TimeWrapper`: Splits funds over time.
 /// - `PriceWrapper`: Splits funds based on price increments.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum OrderParams {
     /// Time-based recurring order parameters.
     TimeWrapper { time: TimeParams },
@@ -27,9 +49,19 @@ pub enum OrderParams {
     PriceWrapper { price: PriceParams },
 }
 
+impl Default for OrderParams {
+    /// Time-based parameters with every field unset.
+    fn default() -> Self {
+        Self::TimeWrapper {
+            time: TimeParams::default(),
+        }
+    }
+}
+
 /// Parameters for creating a time-based recurring order.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TimeParams {
     /// Total input token amount to be split across orders.
     pub in_amount: u64,
@@ -38,16 +70,20 @@ pub struct TimeParams {
     /// Time interval (in seconds) between each order.
     pub interval: u64,
     /// Optional minimum price threshold for executing an order.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub min_price: Option<f64>,
     /// Optional maximum price threshold for executing an order.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_price: Option<f64>,
     /// Optional Unix timestamp to schedule when the order starts.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub start_at: Option<u64>,
 }
 
 /// Parameters for creating a price-based recurring order.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PriceParams {
     /// Total amount to be deposited for the strategy.
     pub deposit_amount: u64,
@@ -56,6 +92,7 @@ pub struct PriceParams {
     /// Minimum time interval (in seconds) between each order execution.
     pub interval: u64,
     /// Optional Unix timestamp to schedule when the order starts.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub start_at: Option<u64>,
 }
 
@@ -67,19 +104,19 @@ impl CreateRecurringOrderRequest {
     /// * `user` - The user wallet address.
     /// * `input_mint` - SPL token mint address for the input token.
     /// * `output_mint` - SPL token mint address for the output token.
-    /// * `in_amount` - Total input amount to be divided.
+    /// * `in_amount` - Total input amount to be divided (raw `u64` or a `TokenAmount`).
     /// * `number_of_orders` - How many times the order should be placed.
     /// * `interval` - Time gap between each order (in seconds).
     pub fn new_time_order(
         user: impl Into<String>,
         input_mint: impl Into<String>,
         output_mint: impl Into<String>,
-        in_amount: u64,
+        in_amount: impl RawAmount,
         number_of_orders: u64,
         interval: u64,
     ) -> Self {
         let params = TimeParams {
-            in_amount,
+            in_amount: in_amount.raw_amount(),
             number_of_orders,
             interval,
             min_price: None,
@@ -101,19 +138,19 @@ impl CreateRecurringOrderRequest {
     /// * `user` - The user wallet address.
     /// * `input_mint` - SPL token mint address for the input token.
     /// * `output_mint` - SPL token mint address for the output token.
-    /// * `deposit_amount` - Total amount to deposit.
+    /// * `deposit_amount` - Total amount to deposit (raw `u64` or a `TokenAmount`).
     /// * `increment_usdc_value` - Price increment in USDC for each order trigger.
     /// * `interval` - Minimum interval between executions (in seconds).
     pub fn new_price_order(
         user: impl Into<String>,
         input_mint: impl Into<String>,
         output_mint: impl Into<String>,
-        deposit_amount: u64,
+        deposit_amount: impl RawAmount,
         increment_usdc_value: u64,
         interval: u64,
     ) -> Self {
         let params = PriceParams {
-            deposit_amount,
+            deposit_amount: deposit_amount.raw_amount(),
             increment_usdc_value,
             interval,
             start_at: None,
@@ -127,8 +164,32 @@ impl CreateRecurringOrderRequest {
         }
     }
 
+    /// Sets the wallet address initiating the order.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = user.into();
+        self
+    }
+
+    /// Sets the mint address of the input token.
+    pub fn input_mint(mut self, input_mint: impl Into<String>) -> Self {
+        self.input_mint = input_mint.into();
+        self
+    }
+
+    /// Sets the mint address of the output token.
+    pub fn output_mint(mut self, output_mint: impl Into<String>) -> Self {
+        self.output_mint = output_mint.into();
+        self
+    }
+
+    /// Sets the time-based or price-based order parameters.
+    pub fn params(mut self, params: OrderParams) -> Self {
+        self.params = params;
+        self
+    }
+
     /// Sets the `start_at` Unix timestamp to delay the start of the recurring order.
-    pub fn with_start_at(mut self, start_at: u64) -> Self {
+    pub fn start_at(mut self, start_at: u64) -> Self {
         match &mut self.params {
             OrderParams::TimeWrapper { time } => time.start_at = Some(start_at),
             OrderParams::PriceWrapper { price } => price.start_at = Some(start_at),
@@ -136,25 +197,175 @@ impl CreateRecurringOrderRequest {
         self
     }
 
+    #[deprecated(note = "renamed to `start_at`")]
+    pub fn with_start_at(self, start_at: u64) -> Self {
+        self.start_at(start_at)
+    }
+
+    /// Sets the start of the recurring order from a `DateTime`.
+    ///
+    /// Times before the Unix epoch start the order immediately.
+    #[cfg(feature = "chrono")]
+    pub fn start_at_datetime(self, start_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.start_at(start_at.timestamp().max(0) as u64)
+    }
+
+    /// Delays the start of the recurring order by `delay` from now.
+    #[cfg(feature = "chrono")]
+    pub fn start_in(self, delay: std::time::Duration) -> Self {
+        self.start_at_datetime(chrono::Utc::now() + delay)
+    }
+
     /// Sets the optional `min_price` threshold for a time-based order.
-    pub fn with_min_price(mut self, price: f64) -> Self {
+    pub fn min_price(mut self, price: f64) -> Self {
         if let OrderParams::TimeWrapper { time } = &mut self.params {
             time.min_price = Some(price);
         }
         self
     }
 
+    #[deprecated(note = "renamed to `min_price`")]
+    pub fn with_min_price(self, price: f64) -> Self {
+        self.min_price(price)
+    }
+
     /// Sets the optional `max_price` threshold for a time-based order.
-    pub fn with_max_price(mut self, price: f64) -> Self {
+    pub fn max_price(mut self, price: f64) -> Self {
         if let OrderParams::TimeWrapper { time } = &mut self.params {
             time.max_price = Some(price);
         }
         self
     }
+
+    #[deprecated(note = "renamed to `max_price`")]
+    pub fn with_max_price(self, price: f64) -> Self {
+        self.max_price(price)
+    }
+
+    /// Checks the request for mistakes the API would reject with a generic 400.
+    ///
+    /// Called by `JupiterClient::create_recurring_order` before sending. Verifies that:
+    /// - the user and both mints look like base58 addresses, and the mints differ
+    /// - the amount is greater than 0
+    /// - a time-based order has at least [`MIN_RECURRING_ORDERS`] orders
+    /// - the interval is at least [`MIN_RECURRING_INTERVAL_SECS`]
+    /// - `min_price` and `max_price` are positive and `min_price` is below `max_price`
+    /// - `start_at` is in the future
+    ///
+    /// USD minimums depend on the input token price; check them with
+    /// [`CreateRecurringOrderRequest::check_min_notional`].
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        self.validate_at(now)
+    }
+
+    /// Same as [`CreateRecurringOrderRequest::validate`], with `now` in Unix seconds.
+    pub fn validate_at(&self, now: u64) -> Result<(), ValidationError> {
+        check_address("user", &self.user)?;
+        check_address("input_mint", &self.input_mint)?;
+        check_address("output_mint", &self.output_mint)?;
+        if self.input_mint == self.output_mint {
+            return Err(ValidationError::SameMint);
+        }
+
+        let (interval, start_at) = match &self.params {
+            OrderParams::TimeWrapper { time } => {
+                check_non_zero("in_amount", time.in_amount)?;
+                check_min(
+                    "number_of_orders",
+                    time.number_of_orders,
+                    MIN_RECURRING_ORDERS,
+                )?;
+
+                for (field, price) in [("min_price", time.min_price), ("max_price", time.max_price)]
+                {
+                    if price.is_some_and(|price| !price.is_finite() || price <= 0.0) {
+                        return Err(ValidationError::InvalidPrice(field));
+                    }
+                }
+                if let (Some(min), Some(max)) = (time.min_price, time.max_price)
+                    && min >= max
+                {
+                    return Err(ValidationError::InvertedPriceRange);
+                }
+                (time.interval, time.start_at)
+            }
+            OrderParams::PriceWrapper { price } => {
+                check_non_zero("deposit_amount", price.deposit_amount)?;
+                check_non_zero("increment_usdc_value", price.increment_usdc_value)?;
+                (price.interval, price.start_at)
+            }
+        };
+
+        check_min("interval", interval, MIN_RECURRING_INTERVAL_SECS)?;
+        if start_at.is_some_and(|start_at| start_at <= now) {
+            return Err(ValidationError::NotInFuture("start_at"));
+        }
+        Ok(())
+    }
+
+    /// Checks the USD minimums of the order: [`MIN_RECURRING_TOTAL_USD`] in total and, for a
+    /// time-based order, [`MIN_RECURRING_ORDER_USD`] per execution.
+    ///
+    /// # Arguments
+    /// * `input_price_usd` - USD price of one input token.
+    /// * `input_decimals` - Decimals of the input mint (e.g. `6` for USDC).
+    ///
+    /// # Example
+    /// ```
+    /// // 300 USDC over 3 orders of 100 USDC
+    /// let order = CreateRecurringOrderRequest::new_time_order(user, USDC_MINT, SOL_MINT, 300_000_000, 3, 86_400);
+    /// order.check_min_notional(1.0, 6)?;
+    /// ```
+    pub fn check_min_notional(
+        &self,
+        input_price_usd: f64,
+        input_decimals: u8,
+    ) -> Result<(), ValidationError> {
+        let usd = |amount: u64| amount as f64 / 10f64.powi(input_decimals as i32) * input_price_usd;
+
+        let (field, total) = match &self.params {
+            OrderParams::TimeWrapper { time } => {
+                let per_order = time
+                    .in_amount
+                    .checked_div(time.number_of_orders)
+                    .unwrap_or(0);
+                if usd(per_order) < MIN_RECURRING_ORDER_USD as f64 {
+                    return Err(ValidationError::BelowMinimumNotional {
+                        field: "in_amount_per_cycle",
+                        min_usd: MIN_RECURRING_ORDER_USD,
+                    });
+                }
+                ("in_amount", time.in_amount)
+            }
+            OrderParams::PriceWrapper { price } => ("deposit_amount", price.deposit_amount),
+        };
+
+        if usd(total) < MIN_RECURRING_TOTAL_USD as f64 {
+            return Err(ValidationError::BelowMinimumNotional {
+                field,
+                min_usd: MIN_RECURRING_TOTAL_USD,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn check_min(field: &'static str, value: u64, min: u64) -> Result<(), ValidationError> {
+    if value < min {
+        Err(ValidationError::BelowMinimum { field, value, min })
+    } else {
+        Ok(())
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct CancelRecurringOrderRequest {
     pub order: String,
 
@@ -175,10 +386,36 @@ impl CancelRecurringOrderRequest {
             user: user.into(),
         }
     }
+
+    /// Sets the recurring order account to cancel.
+    pub fn order(mut self, order: impl Into<String>) -> Self {
+        self.order = order.into();
+        self
+    }
+
+    /// Sets the type of the order to cancel.
+    pub fn recurring_type(mut self, recurring_type: RecurringOrderType) -> Self {
+        self.recurring_type = recurring_type;
+        self
+    }
+
+    /// Sets the wallet address owning the order.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = user.into();
+        self
+    }
+}
+
+impl Default for CancelRecurringOrderRequest {
+    /// A time-based cancellation without order or user.
+    fn default() -> Self {
+        Self::new("", RecurringOrderType::Time, "")
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum RecurringOrderType {
     Time,
     Price,
@@ -186,7 +423,9 @@ pub enum RecurringOrderType {
     All,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct PriceDeposit {
     pub amount: u64,
 
@@ -208,46 +447,123 @@ impl PriceDeposit {
             user: user.into(),
         }
     }
+
+    /// Sets the amount to deposit (raw `u64` or a `TokenAmount`).
+    pub fn amount(mut self, amount: impl RawAmount) -> Self {
+        self.amount = amount.raw_amount();
+        self
+    }
+
+    /// Sets the recurring order account address.
+    pub fn order(mut self, order: impl Into<String>) -> Self {
+        self.order = order.into();
+        self
+    }
+
+    /// Sets the user account address.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = user.into();
+        self
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Withdrawal from a price-based recurring order.
+///
+/// Time-based orders cannot be withdrawn from; cancel them with
+/// [`CancelRecurringOrderRequest`] to recover their funds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct PriceWithdraw {
     /// If no amount is provided, it will withdraw the entire amount
-    pub amount: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
 
     pub order: String,
 
     pub user: String,
 
-    /// Possible values: [In, Out]
-    pub input_or_output: String,
+    /// Which side of the order to withdraw
+    pub input_or_output: WithdrawSide,
+}
+
+/// Token withdrawn from a price-based recurring order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum WithdrawSide {
+    /// The unswapped input token.
+    In,
+    /// The output token received so far.
+    Out,
 }
 
 impl PriceWithdraw {
     /// # Arguments
     ///
-    /// * `amount` - The amount to withdraw
+    /// * `amount` - The amount to withdraw, `None` to withdraw everything
     /// * `order` - The recurring order account address
     /// * `user` - The user account address
-    /// * `input_or_output` - The withdrawal direction ("In" or "Out")
+    /// * `input_or_output` - The withdrawal direction
     pub fn new(
-        amount: u64,
+        amount: Option<u64>,
         order: impl Into<String>,
         user: impl Into<String>,
-        input_or_output: impl Into<String>,
+        input_or_output: WithdrawSide,
     ) -> Self {
         Self {
             amount,
             order: order.into(),
             user: user.into(),
-            input_or_output: input_or_output.into(),
+            input_or_output,
         }
     }
+
+    /// Withdraws the whole balance of `input_or_output`.
+    pub fn all(
+        order: impl Into<String>,
+        user: impl Into<String>,
+        input_or_output: WithdrawSide,
+    ) -> Self {
+        Self::new(None, order, user, input_or_output)
+    }
+
+    /// Sets the amount to withdraw (raw `u64` or a `TokenAmount`).
+    pub fn amount(mut self, amount: impl RawAmount) -> Self {
+        self.amount = Some(amount.raw_amount());
+        self
+    }
+
+    /// Sets the recurring order account address.
+    pub fn order(mut self, order: impl Into<String>) -> Self {
+        self.order = order.into();
+        self
+    }
+
+    /// Sets the user account address.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = user.into();
+        self
+    }
+
+    /// Sets the side of the order to withdraw.
+    pub fn input_or_output(mut self, input_or_output: WithdrawSide) -> Self {
+        self.input_or_output = input_or_output;
+        self
+    }
+}
+
+impl Default for PriceWithdraw {
+    /// Withdraws everything received so far, without order or user.
+    fn default() -> Self {
+        Self::all("", "", WithdrawSide::Out)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RecurringResponse {
     pub request_id: String,
 
@@ -255,8 +571,10 @@ pub struct RecurringResponse {
     pub transaction: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct ExecuteRecurringRequest {
     pub request_id: String,
 
@@ -270,27 +588,58 @@ impl ExecuteRecurringRequest {
             signed_transaction: signed_transaction.into(),
         }
     }
+
+    /// Sets the request ID from the create, cancel, deposit or withdraw response.
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = request_id.into();
+        self
+    }
+
+    /// Sets the signed transaction to execute.
+    pub fn signed_transaction(mut self, signed_transaction: impl Into<String>) -> Self {
+        self.signed_transaction = signed_transaction.into();
+        self
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ExecuteRecurringResponse {
     pub signature: String,
 
     pub status: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// `<status> <signature>`.
+impl std::fmt::Display for ExecuteRecurringResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.status, self.signature)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct GetRecurringOrders {
     pub recurring_type: RecurringOrderType,
     pub order_status: OrderStatus,
     pub user: String,
     pub page: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mint: Option<String>,
     pub include_failed_tx: bool,
 }
 
+impl Default for GetRecurringOrders {
+    /// The first page of active orders of every type, without a user.
+    fn default() -> Self {
+        Self::new(RecurringOrderType::All, OrderStatus::Active, "")
+    }
+}
+
 impl GetRecurringOrders {
     /// Basic constructor
     pub fn new(
@@ -308,18 +657,46 @@ impl GetRecurringOrders {
         }
     }
 
+    /// Sets the type of orders to list
+    pub fn recurring_type(mut self, recurring_type: RecurringOrderType) -> Self {
+        self.recurring_type = recurring_type;
+        self
+    }
+
+    /// Sets whether to list active or past orders
+    pub fn order_status(mut self, order_status: OrderStatus) -> Self {
+        self.order_status = order_status;
+        self
+    }
+
+    /// Sets the wallet address to list orders for
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = user.into();
+        self
+    }
+
     /// Customize page number
-    pub fn with_page(mut self, page: u64) -> Self {
+    pub fn page(mut self, page: u64) -> Self {
         self.page = page;
         self
     }
 
+    #[deprecated(note = "renamed to `page`")]
+    pub fn with_page(self, page: u64) -> Self {
+        self.page(page)
+    }
+
     /// Filter by a specific mint
-    pub fn with_mint(mut self, mint: impl Into<String>) -> Self {
+    pub fn mint(mut self, mint: impl Into<String>) -> Self {
         self.mint = Some(mint.into());
         self
     }
 
+    #[deprecated(note = "renamed to `mint`")]
+    pub fn with_mint(self, mint: impl Into<String>) -> Self {
+        self.mint(mint)
+    }
+
     /// Include failed transactions
     pub fn include_failed(mut self) -> Self {
         self.include_failed_tx = true;
@@ -327,8 +704,9 @@ impl GetRecurringOrders {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct RecurringOrders {
     pub order_status: OrderStatus,
     pub page: u64,
@@ -339,18 +717,145 @@ pub struct RecurringOrders {
     #[serde(default)]
     pub price: Option<Vec<PriceOrder>>,
     #[serde(default)]
-    pub all: Option<Vec<Order>>,
+    pub all: Option<Vec<RecurringOrder>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl RecurringOrders {
+    /// Every order of the page, whichever of `time`, `price` or `all` the API filled.
+    pub fn into_orders(self) -> Vec<RecurringOrder> {
+        let mut orders = self.all.unwrap_or_default();
+        orders.extend(self.time.into_iter().flatten().map(RecurringOrder::Time));
+        orders.extend(self.price.into_iter().flatten().map(RecurringOrder::Price));
+        orders
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
-pub enum Order {
+pub enum RecurringOrder {
     Time(TimeOrder),
     Price(PriceOrder),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// One-line summary of the order, with UI amounts.
+///
+/// # Example
+/// ```
+/// println!("{order}");
+/// // 8ZqE..3xYz: 200/1000 EPjF..Dt1v used -> 1.3 So11..1112 received, 2 trades
+/// ```
+impl std::fmt::Display for RecurringOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (in_used, in_deposited, out_received) = match self {
+            RecurringOrder::Time(order) => {
+                (&order.in_used, &order.in_deposited, &order.out_received)
+            }
+            RecurringOrder::Price(order) => {
+                (&order.in_used, &order.in_deposited, &order.out_received)
+            }
+        };
+        let trades = self.trades().len();
+        write!(
+            f,
+            "{}: {in_used}/{in_deposited} {} used -> {out_received} {} received, {trades} trade{}",
+            short_mint(self.order_key()),
+            short_mint(self.input_mint()),
+            short_mint(self.output_mint()),
+            if trades == 1 { "" } else { "s" },
+        )
+    }
+}
+
+#[deprecated(note = "renamed to `RecurringOrder`")]
+pub type Order = RecurringOrder;
+
+impl RecurringOrder {
+    /// The order account.
+    pub fn order_key(&self) -> &str {
+        match self {
+            RecurringOrder::Time(order) => &order.order_key,
+            RecurringOrder::Price(order) => &order.order_key,
+        }
+    }
+
+    /// The mint sold by the order.
+    pub fn input_mint(&self) -> &str {
+        match self {
+            RecurringOrder::Time(order) => &order.input_mint,
+            RecurringOrder::Price(order) => &order.input_mint,
+        }
+    }
+
+    /// The mint bought by the order.
+    pub fn output_mint(&self) -> &str {
+        match self {
+            RecurringOrder::Time(order) => &order.output_mint,
+            RecurringOrder::Price(order) => &order.output_mint,
+        }
+    }
+
+    /// Time between two executions: the cycle frequency of a time-based order, the
+    /// minimum interval of a price-based one. `None` if it cannot be parsed.
+    pub fn interval(&self) -> Option<std::time::Duration> {
+        let seconds = match self {
+            RecurringOrder::Time(order) => order.cycle_frequency_u64()?,
+            RecurringOrder::Price(order) => order.order_interval_u64()?,
+        };
+        Some(std::time::Duration::from_secs(seconds))
+    }
+
+    /// Executed trades, oldest first as returned by the API.
+    pub fn trades(&self) -> &[RecurringTrade] {
+        match self {
+            RecurringOrder::Time(order) => &order.trades,
+            RecurringOrder::Price(order) => &order.trades,
+        }
+    }
+
+    /// Total input deposited (raw, before decimals).
+    pub fn raw_in_deposited_u64(&self) -> Option<u64> {
+        match self {
+            RecurringOrder::Time(order) => order.raw_in_deposited_u64(),
+            RecurringOrder::Price(order) => order.raw_in_deposited_u64(),
+        }
+    }
+
+    /// Input already swapped (raw, before decimals).
+    pub fn raw_in_used_u64(&self) -> Option<u64> {
+        match self {
+            RecurringOrder::Time(order) => order.raw_in_used_u64(),
+            RecurringOrder::Price(order) => order.raw_in_used_u64(),
+        }
+    }
+
+    /// Input withdrawn by the user (raw, before decimals).
+    pub fn raw_in_withdrawn_u64(&self) -> Option<u64> {
+        match self {
+            RecurringOrder::Time(order) => order.raw_in_withdrawn_u64(),
+            RecurringOrder::Price(order) => order.raw_in_withdrawn_u64(),
+        }
+    }
+
+    /// Output received from swaps (raw, before decimals).
+    pub fn raw_out_received_u64(&self) -> Option<u64> {
+        match self {
+            RecurringOrder::Time(order) => order.raw_out_received_u64(),
+            RecurringOrder::Price(order) => order.raw_out_received_u64(),
+        }
+    }
+
+    /// Output withdrawn by the user (raw, before decimals).
+    pub fn raw_out_withdrawn_u64(&self) -> Option<u64> {
+        match self {
+            RecurringOrder::Time(order) => order.raw_out_withdrawn_u64(),
+            RecurringOrder::Price(order) => order.raw_out_withdrawn_u64(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct PriceOrder {
     pub close_tx: String,
     pub closed_by: String,
@@ -380,13 +885,132 @@ pub struct PriceOrder {
     pub start_at: String,
     pub status: String,
     pub supposed_usd_value: String,
-    pub trades: Vec<Trade>,
+    pub trades: Vec<RecurringTrade>,
     pub updated_at: String,
     pub user_pubkey: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl PriceOrder {
+    /// `created_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.created_at)
+    }
+
+    /// `start_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn start_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.start_at)
+    }
+
+    /// `updated_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn updated_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.updated_at)
+    }
+
+    /// `order_interval` as a `u64` (seconds), or `None` if it cannot be parsed.
+    pub fn order_interval_u64(&self) -> Option<u64> {
+        self.order_interval.parse().ok()
+    }
+
+    /// `estimated_usdc_value_spent` as an `f64`, or `None` if it cannot be parsed.
+    pub fn estimated_usdc_value_spent_f64(&self) -> Option<f64> {
+        self.estimated_usdc_value_spent.parse().ok()
+    }
+
+    /// `in_deposited` as an `f64`, or `None` if it cannot be parsed.
+    pub fn in_deposited_f64(&self) -> Option<f64> {
+        self.in_deposited.parse().ok()
+    }
+
+    /// `in_left` as an `f64`, or `None` if it cannot be parsed.
+    pub fn in_left_f64(&self) -> Option<f64> {
+        self.in_left.parse().ok()
+    }
+
+    /// `in_used` as an `f64`, or `None` if it cannot be parsed.
+    pub fn in_used_f64(&self) -> Option<f64> {
+        self.in_used.parse().ok()
+    }
+
+    /// `in_withdrawn` as an `f64`, or `None` if it cannot be parsed.
+    pub fn in_withdrawn_f64(&self) -> Option<f64> {
+        self.in_withdrawn.parse().ok()
+    }
+
+    /// `incremental_usd_value` as an `f64`, or `None` if it cannot be parsed.
+    pub fn incremental_usd_value_f64(&self) -> Option<f64> {
+        self.incremental_usd_value.parse().ok()
+    }
+
+    /// `out_received` as an `f64`, or `None` if it cannot be parsed.
+    pub fn out_received_f64(&self) -> Option<f64> {
+        self.out_received.parse().ok()
+    }
+
+    /// `out_withdrawn` as an `f64`, or `None` if it cannot be parsed.
+    pub fn out_withdrawn_f64(&self) -> Option<f64> {
+        self.out_withdrawn.parse().ok()
+    }
+
+    /// `supposed_usd_value` as an `f64`, or `None` if it cannot be parsed.
+    pub fn supposed_usd_value_f64(&self) -> Option<f64> {
+        self.supposed_usd_value.parse().ok()
+    }
+
+    /// `raw_estimated_usdc_value_spent` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_estimated_usdc_value_spent_u64(&self) -> Option<u64> {
+        self.raw_estimated_usdc_value_spent.parse().ok()
+    }
+
+    /// `raw_in_deposited` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_in_deposited_u64(&self) -> Option<u64> {
+        self.raw_in_deposited.parse().ok()
+    }
+
+    /// `raw_in_left` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_in_left_u64(&self) -> Option<u64> {
+        self.raw_in_left.parse().ok()
+    }
+
+    /// `raw_in_used` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_in_used_u64(&self) -> Option<u64> {
+        self.raw_in_used.parse().ok()
+    }
+
+    /// `raw_in_withdrawn` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_in_withdrawn_u64(&self) -> Option<u64> {
+        self.raw_in_withdrawn.parse().ok()
+    }
+
+    /// `raw_incremental_usd_value` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_incremental_usd_value_u64(&self) -> Option<u64> {
+        self.raw_incremental_usd_value.parse().ok()
+    }
+
+    /// `raw_out_received` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_out_received_u64(&self) -> Option<u64> {
+        self.raw_out_received.parse().ok()
+    }
+
+    /// `raw_out_withdrawn` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_out_withdrawn_u64(&self) -> Option<u64> {
+        self.raw_out_withdrawn.parse().ok()
+    }
+
+    /// `raw_supposed_usd_value` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_supposed_usd_value_u64(&self) -> Option<u64> {
+        self.raw_supposed_usd_value.parse().ok()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TimeOrder {
     pub close_tx: String,
     pub created_at: String,
@@ -411,15 +1035,117 @@ pub struct TimeOrder {
     pub raw_min_out_amount: String,
     pub raw_out_received: String,
     pub raw_out_withdrawn: String,
-    pub trades: Vec<Trade>,
+    pub trades: Vec<RecurringTrade>,
     pub updated_at: String,
     pub user_closed: bool,
     pub user_pubkey: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl TimeOrder {
+    /// `created_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.created_at)
+    }
+
+    /// `updated_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn updated_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.updated_at)
+    }
+
+    /// `cycle_frequency` as a `u64` (seconds), or `None` if it cannot be parsed.
+    pub fn cycle_frequency_u64(&self) -> Option<u64> {
+        self.cycle_frequency.parse().ok()
+    }
+
+    /// `in_amount_per_cycle` as an `f64`, or `None` if it cannot be parsed.
+    pub fn in_amount_per_cycle_f64(&self) -> Option<f64> {
+        self.in_amount_per_cycle.parse().ok()
+    }
+
+    /// `in_deposited` as an `f64`, or `None` if it cannot be parsed.
+    pub fn in_deposited_f64(&self) -> Option<f64> {
+        self.in_deposited.parse().ok()
+    }
+
+    /// `in_used` as an `f64`, or `None` if it cannot be parsed.
+    pub fn in_used_f64(&self) -> Option<f64> {
+        self.in_used.parse().ok()
+    }
+
+    /// `in_withdrawn` as an `f64`, or `None` if it cannot be parsed.
+    pub fn in_withdrawn_f64(&self) -> Option<f64> {
+        self.in_withdrawn.parse().ok()
+    }
+
+    /// `max_out_amount` as an `f64`, or `None` if it cannot be parsed.
+    pub fn max_out_amount_f64(&self) -> Option<f64> {
+        self.max_out_amount.parse().ok()
+    }
+
+    /// `min_out_amount` as an `f64`, or `None` if it cannot be parsed.
+    pub fn min_out_amount_f64(&self) -> Option<f64> {
+        self.min_out_amount.parse().ok()
+    }
+
+    /// `out_received` as an `f64`, or `None` if it cannot be parsed.
+    pub fn out_received_f64(&self) -> Option<f64> {
+        self.out_received.parse().ok()
+    }
+
+    /// `out_withdrawn` as an `f64`, or `None` if it cannot be parsed.
+    pub fn out_withdrawn_f64(&self) -> Option<f64> {
+        self.out_withdrawn.parse().ok()
+    }
+
+    /// `raw_in_amount_per_cycle` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_in_amount_per_cycle_u64(&self) -> Option<u64> {
+        self.raw_in_amount_per_cycle.parse().ok()
+    }
+
+    /// `raw_in_deposited` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_in_deposited_u64(&self) -> Option<u64> {
+        self.raw_in_deposited.parse().ok()
+    }
+
+    /// `raw_in_used` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_in_used_u64(&self) -> Option<u64> {
+        self.raw_in_used.parse().ok()
+    }
+
+    /// `raw_in_withdrawn` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_in_withdrawn_u64(&self) -> Option<u64> {
+        self.raw_in_withdrawn.parse().ok()
+    }
+
+    /// `raw_max_out_amount` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_max_out_amount_u64(&self) -> Option<u64> {
+        self.raw_max_out_amount.parse().ok()
+    }
+
+    /// `raw_min_out_amount` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_min_out_amount_u64(&self) -> Option<u64> {
+        self.raw_min_out_amount.parse().ok()
+    }
+
+    /// `raw_out_received` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_out_received_u64(&self) -> Option<u64> {
+        self.raw_out_received.parse().ok()
+    }
+
+    /// `raw_out_withdrawn` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_out_withdrawn_u64(&self) -> Option<u64> {
+        self.raw_out_withdrawn.parse().ok()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Trade {
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+pub struct RecurringTrade {
     pub action: String,
     pub confirmed_at: String,
     pub fee_amount: String,
@@ -438,8 +1164,51 @@ pub struct Trade {
     pub tx_id: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[deprecated(note = "renamed to `RecurringTrade`")]
+pub type Trade = RecurringTrade;
+
+impl RecurringTrade {
+    /// `confirmed_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn confirmed_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.confirmed_at)
+    }
+
+    /// `input_amount` as an `f64`, or `None` if it cannot be parsed.
+    pub fn input_amount_f64(&self) -> Option<f64> {
+        self.input_amount.parse().ok()
+    }
+
+    /// `output_amount` as an `f64`, or `None` if it cannot be parsed.
+    pub fn output_amount_f64(&self) -> Option<f64> {
+        self.output_amount.parse().ok()
+    }
+
+    /// `fee_amount` as an `f64`, or `None` if it cannot be parsed.
+    pub fn fee_amount_f64(&self) -> Option<f64> {
+        self.fee_amount.parse().ok()
+    }
+
+    /// `raw_input_amount` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_input_amount_u64(&self) -> Option<u64> {
+        self.raw_input_amount.parse().ok()
+    }
+
+    /// `raw_output_amount` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_output_amount_u64(&self) -> Option<u64> {
+        self.raw_output_amount.parse().ok()
+    }
+
+    /// `raw_fee_amount` as a `u64`, or `None` if it cannot be parsed.
+    pub fn raw_fee_amount_u64(&self) -> Option<u64> {
+        self.raw_fee_amount.parse().ok()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct ProductMeta {
     pub new_actual_usdc_value: String,
     pub value: String,
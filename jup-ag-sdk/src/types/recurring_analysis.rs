@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+use super::recurring::{
+    CreateRecurringOrderRequest, OrderParams, PriceOrder, RecurringOrder, TimeOrder, TimeParams,
+};
+
+/// Progress and profit of a recurring order, in tokens (adjusted for decimals).
+///
+/// Prices are input tokens per output token, e.g. USDC per SOL for a USDC -> SOL order,
+/// so they can be compared directly with the market price of the output token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecurringOrderReport {
+    pub order_key: String,
+
+    /// Number of trades executed so far.
+    pub fills: usize,
+
+    /// Input tokens deposited into the order.
+    pub deposited: f64,
+
+    /// Input tokens swapped so far.
+    pub invested: f64,
+
+    /// Input tokens still waiting to be swapped.
+    pub remaining: f64,
+
+    /// Output tokens received so far.
+    pub received: f64,
+
+    /// Input paid per output token received, `None` before the first fill.
+    pub average_fill_price: Option<f64>,
+
+    /// The price the report was computed with.
+    pub current_price: f64,
+
+    /// Received tokens valued at `current_price`, in input tokens.
+    pub current_value: f64,
+
+    /// `current_value` minus `invested`, in input tokens.
+    pub unrealized_pnl: f64,
+
+    /// `unrealized_pnl` relative to `invested`, in percent, `None` before the first fill.
+    pub unrealized_pnl_pct: Option<f64>,
+
+    /// Share of the deposit swapped so far, in percent, `None` if nothing was deposited.
+    pub completion_pct: Option<f64>,
+}
+
+impl RecurringOrderReport {
+    /// Builds the report of a time-based order, `None` if its amounts cannot be parsed.
+    ///
+    /// # Arguments
+    /// * `current_price` - Market price in input tokens per output token.
+    ///
+    /// # Example
+    /// ```
+    /// // USDC -> SOL order, SOL currently at 160 USDC
+    /// let report = RecurringOrderReport::from_time_order(&order, 160.0)?;
+    /// println!("avg {:?} pnl {}", report.average_fill_price, report.unrealized_pnl);
+    /// ```
+    pub fn from_time_order(order: &TimeOrder, current_price: f64) -> Option<Self> {
+        Some(Self::new(
+            &order.order_key,
+            order.trades.len(),
+            order.in_deposited_f64()?,
+            order.in_used_f64()?,
+            order.in_withdrawn_f64()?,
+            order.out_received_f64()?,
+            current_price,
+        ))
+    }
+
+    /// Builds the report of a price-based order, `None` if its amounts cannot be parsed.
+    ///
+    /// # Arguments
+    /// * `current_price` - Market price in input tokens per output token.
+    pub fn from_price_order(order: &PriceOrder, current_price: f64) -> Option<Self> {
+        Some(Self::new(
+            &order.order_key,
+            order.trades.len(),
+            order.in_deposited_f64()?,
+            order.in_used_f64()?,
+            order.in_withdrawn_f64()?,
+            order.out_received_f64()?,
+            current_price,
+        ))
+    }
+
+    /// Builds the report of either kind of order, `None` if its amounts cannot be parsed.
+    pub fn from_order(order: &RecurringOrder, current_price: f64) -> Option<Self> {
+        match order {
+            RecurringOrder::Time(order) => Self::from_time_order(order, current_price),
+            RecurringOrder::Price(order) => Self::from_price_order(order, current_price),
+        }
+    }
+
+    fn new(
+        order_key: &str,
+        fills: usize,
+        deposited: f64,
+        invested: f64,
+        withdrawn: f64,
+        received: f64,
+        current_price: f64,
+    ) -> Self {
+        let current_value = received * current_price;
+        let unrealized_pnl = current_value - invested;
+
+        Self {
+            order_key: order_key.to_string(),
+            fills,
+            deposited,
+            invested,
+            remaining: (deposited - invested - withdrawn).max(0.0),
+            received,
+            average_fill_price: (received > 0.0).then(|| invested / received),
+            current_price,
+            current_value,
+            unrealized_pnl,
+            unrealized_pnl_pct: (invested > 0.0).then(|| unrealized_pnl / invested * 100.0),
+            completion_pct: (deposited > 0.0).then(|| invested / deposited * 100.0),
+        }
+    }
+}
+
+/// Projected executions of a time-based recurring order.
+///
+/// Times are Unix seconds. Amounts are raw, before decimals; any remainder of the
+/// deposit that does not divide evenly into cycles is not included in `amount_per_cycle`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecurringSchedule {
+    /// Input swapped at each execution.
+    pub amount_per_cycle: u64,
+
+    /// Seconds between two executions.
+    pub interval: u64,
+
+    /// Time of every remaining execution, in order.
+    pub executions: Vec<u64>,
+}
+
+impl RecurringSchedule {
+    /// Schedule of a time-based order about to be created.
+    ///
+    /// An order without `start_at` starts at `now`.
+    ///
+    /// # Example
+    /// ```
+    /// // 1000 USDC over 10 daily orders
+    /// let order = CreateRecurringOrderRequest::new_time_order(user, USDC_MINT, SOL_MINT, 1_000_000_000, 10, 86_400);
+    /// let schedule = order.schedule(now).unwrap();
+    /// assert_eq!(schedule.amount_per_cycle, 100_000_000);
+    /// assert_eq!(schedule.end_at(), Some(now + 9 * 86_400));
+    /// ```
+    pub fn from_params(params: &TimeParams, now: u64) -> Self {
+        let start = params.start_at.unwrap_or(now);
+        Self {
+            amount_per_cycle: params
+                .in_amount
+                .checked_div(params.number_of_orders)
+                .unwrap_or(0),
+            interval: params.interval,
+            executions: (0..params.number_of_orders)
+                .map(|cycle| start.saturating_add(cycle.saturating_mul(params.interval)))
+                .collect(),
+        }
+    }
+
+    /// Remaining schedule of an existing time-based order, `None` if its amounts or
+    /// timestamps cannot be parsed. Requires the `chrono` feature.
+    ///
+    /// The next execution is one interval after the last trade, or at creation for an
+    /// order that has not traded yet.
+    #[cfg(feature = "chrono")]
+    pub fn from_time_order(order: &TimeOrder) -> Option<Self> {
+        let amount_per_cycle = order.raw_in_amount_per_cycle_u64()?;
+        let interval = order.cycle_frequency_u64()?;
+        let remaining = order
+            .raw_in_deposited_u64()?
+            .saturating_sub(order.raw_in_used_u64()?)
+            .saturating_sub(order.raw_in_withdrawn_u64()?);
+        let cycles = remaining.checked_div(amount_per_cycle).unwrap_or(0)
+            + u64::from(amount_per_cycle > 0 && remaining % amount_per_cycle > 0);
+
+        let confirmed = order
+            .trades
+            .iter()
+            .map(|trade| trade.confirmed_at_datetime())
+            .collect::<Option<Vec<_>>>()?;
+        let next = match confirmed.into_iter().max() {
+            Some(last) => u64::try_from(last.timestamp())
+                .ok()?
+                .saturating_add(interval),
+            None => u64::try_from(order.created_at_datetime()?.timestamp()).ok()?,
+        };
+
+        Some(Self {
+            amount_per_cycle,
+            interval,
+            executions: (0..cycles)
+                .map(|cycle| next.saturating_add(cycle.saturating_mul(interval)))
+                .collect(),
+        })
+    }
+
+    /// Time of the first remaining execution.
+    pub fn start_at(&self) -> Option<u64> {
+        self.executions.first().copied()
+    }
+
+    /// Time of the last execution.
+    pub fn end_at(&self) -> Option<u64> {
+        self.executions.last().copied()
+    }
+}
+
+impl CreateRecurringOrderRequest {
+    /// Projected schedule of a time-based order, `None` for a price-based one.
+    ///
+    /// See [`RecurringSchedule::from_params`].
+    pub fn schedule(&self, now: u64) -> Option<RecurringSchedule> {
+        match &self.params {
+            OrderParams::TimeWrapper { time } => Some(RecurringSchedule::from_params(time, now)),
+            OrderParams::PriceWrapper { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ValidationError;
+
+/// Slippage tolerance in basis points, validated to be at most 100%.
+///
+/// # Example
+/// ```
+/// let one_percent = Slippage::from_percent(1.0)?;
+/// assert_eq!(one_percent, Slippage::from_bps(100)?);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct Slippage(u16);
+
+impl Slippage {
+    /// 100% slippage.
+    pub const MAX_BPS: u16 = 10_000;
+
+    /// Creates a slippage from basis points. `100` bps = 1%.
+    ///
+    /// Fails if `bps` is greater than [`Slippage::MAX_BPS`].
+    pub fn from_bps(bps: u16) -> Result<Self, ValidationError> {
+        if bps > Self::MAX_BPS {
+            return Err(ValidationError::BpsOutOfRange {
+                field: "slippage",
+                value: bps.into(),
+                min: 0,
+                max: Self::MAX_BPS.into(),
+            });
+        }
+        Ok(Self(bps))
+    }
+
+    /// Creates a slippage from a percentage, rounded to the nearest basis point.
+    ///
+    /// Fails if `percent` is negative, not finite or greater than 100.
+    pub fn from_percent(percent: f64) -> Result<Self, ValidationError> {
+        if !percent.is_finite() || !(0.0..=100.0).contains(&percent) {
+            return Err(ValidationError::PercentOutOfRange {
+                field: "slippage",
+                value: percent.to_string(),
+            });
+        }
+        Ok(Self((percent * 100.0).round() as u16))
+    }
+
+    /// The slippage in basis points.
+    pub fn bps(&self) -> u16 {
+        self.0
+    }
+
+    /// The slippage as a percentage.
+    pub fn percent(&self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+}
+
+impl TryFrom<u16> for Slippage {
+    type Error = ValidationError;
+
+    fn try_from(bps: u16) -> Result<Self, Self::Error> {
+        Self::from_bps(bps)
+    }
+}
+
+impl From<Slippage> for u16 {
+    fn from(slippage: Slippage) -> Self {
+        slippage.0
+    }
+}
+
+impl fmt::Display for Slippage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bps", self.0)
+    }
+}
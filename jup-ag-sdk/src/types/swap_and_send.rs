@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use super::{PrioritizationFeeLamports, QuoteResponse};
+use crate::rpc::CommitmentLevel;
+
+/// Options for `JupiterClient::swap_and_send`.
+///
+/// Use `SwapAndSendOptions::default()` and the fluent setters to configure.
+///
+/// # Example
+/// ```
+/// let options = SwapAndSendOptions::default()
+///     .commitment(CommitmentLevel::Finalized)
+///     .timeout(Duration::from_secs(90));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapAndSendOptions {
+    /// Commitment the transaction must reach before returning. Default: `Confirmed`.
+    pub commitment: CommitmentLevel,
+
+    /// Skip the RPC node's preflight simulation when sending. Default: `false`.
+    pub skip_preflight: bool,
+
+    /// Maximum time to wait for confirmation. Default: 60 seconds.
+    pub timeout: Duration,
+
+    /// Delay before the first signature status poll, doubled after each poll up to
+    /// `MAX_CONFIRM_POLL_INTERVAL`. Default: 500 milliseconds.
+    pub poll_interval: Duration,
+
+    /// Let Jupiter simulate the swap to set the compute unit limit. Default: `true`.
+    pub dynamic_compute_unit_limit: bool,
+
+    /// Optional prioritization fee configuration passed to `/swap`.
+    pub prioritization_fee_lamports: Option<PrioritizationFeeLamports>,
+}
+
+impl Default for SwapAndSendOptions {
+    fn default() -> Self {
+        Self {
+            commitment: CommitmentLevel::Confirmed,
+            skip_preflight: false,
+            timeout: Duration::from_secs(60),
+            poll_interval: Duration::from_millis(500),
+            dynamic_compute_unit_limit: true,
+            prioritization_fee_lamports: None,
+        }
+    }
+}
+
+impl SwapAndSendOptions {
+    /// Sets the commitment to wait for.
+    pub fn commitment(mut self, commitment: CommitmentLevel) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Enables or disables the RPC preflight check.
+    pub fn skip_preflight(mut self, skip: bool) -> Self {
+        self.skip_preflight = skip;
+        self
+    }
+
+    /// Sets the confirmation timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the delay before the first status poll.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Enables or disables the dynamic compute unit limit.
+    pub fn dynamic_compute_unit_limit(mut self, enabled: bool) -> Self {
+        self.dynamic_compute_unit_limit = enabled;
+        self
+    }
+
+    /// Sets the prioritization fee configuration.
+    pub fn prioritization_fee_lamports(mut self, fee: PrioritizationFeeLamports) -> Self {
+        self.prioritization_fee_lamports = Some(fee);
+        self
+    }
+}
+
+/// Outcome of a confirmed `JupiterClient::swap_and_send` call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapAndSendResult {
+    /// The transaction signature (base58).
+    pub signature: String,
+
+    /// The slot the transaction was processed in.
+    pub slot: u64,
+
+    /// Quoted raw input amount.
+    pub in_amount: u64,
+
+    /// Quoted raw output amount.
+    pub out_amount: u64,
+
+    /// Worst-case amount after slippage, see `QuoteResponse::other_amount_threshold`.
+    pub other_amount_threshold: u64,
+
+    /// Prioritization fee paid, in lamports.
+    pub prioritization_fee_lamports: u64,
+
+    /// The quote the swap was built from.
+    pub quote: QuoteResponse,
+}
@@ -1,14 +1,20 @@
 use serde::{Deserialize, Serialize};
 
-use super::QuoteResponse;
+use super::{QuoteResponse, Slippage};
+use crate::{error::ValidationError, validation::check_address};
 
 /// SwapRequest is a struct that represents the request body for the swap transaction.
 ///
 /// user SwapRequest::new() and the fluent setters to configure parameters.
 ///
+/// `SwapRequest::default()` has no quote yet: attach one with
+/// [`quote_response`](SwapRequest::quote_response) before sending it.
+///
 /// [Official API docs](https://dev.jup.ag/docs/api/swap-api/swap)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct SwapRequest {
     /// Rquired. The public key of the user initiating the swap.
     pub user_public_key: String,
@@ -78,13 +84,16 @@ pub struct SwapRequest {
     /// Example: If you pass in 10 slots, the transaction will be valid for ~400ms * 10 = approximately 4 seconds before it expires
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockhash_slots_to_expiry: Option<u64>,
-    pub quote_response: QuoteResponse,
+
+    /// Required. The quote to build the swap from.
+    pub quote_response: Option<QuoteResponse>,
 }
 
 /// Only one of these fields should be set at a time.
 /// Use either `jito_tip_lamports` or `priority_level_with_max_lamports`, not both.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PrioritizationFeeLamports {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jito_tip_lamports: Option<u64>,
@@ -93,23 +102,27 @@ pub struct PrioritizationFeeLamports {
     pub priority_level_with_max_lamports: Option<PriorityLevelWithMaxLamports>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PriorityLevelWithMaxLamports {
     pub max_lamports: u32,
     pub priority_level: PriorityLevel,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum PriorityLevel {
     Medium,
     High,
     VeryHigh,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SwapResponse {
     pub swap_transaction: String,
     pub last_valid_block_height: u64,
@@ -151,10 +164,28 @@ impl SwapRequest {
             dynamic_slippage: None,
             compute_unit_price_micro_lamports: None,
             blockhash_slots_to_expiry: None,
-            quote_response: quote,
+            quote_response: Some(quote),
         }
     }
 
+    /// Sets the public key of the user initiating the swap.
+    pub fn user_public_key(mut self, user_public_key: impl Into<String>) -> Self {
+        self.user_public_key = user_public_key.into();
+        self
+    }
+
+    /// Sets the account paying for the transaction.
+    pub fn payer(mut self, payer: impl Into<String>) -> Self {
+        self.payer = payer.into();
+        self
+    }
+
+    /// Sets the quote to build the swap from.
+    pub fn quote_response(mut self, quote: QuoteResponse) -> Self {
+        self.quote_response = Some(quote);
+        self
+    }
+
     /// Sets whether to wrap or unwrap native SOL.
     ///
     /// If `true`, native SOL will be wrapped into WSOL before the swap
@@ -255,6 +286,17 @@ impl SwapRequest {
         self
     }
 
+    /// Overrides the slippage of the attached quote.
+    ///
+    /// `/swap` applies the `slippageBps` of the quote response, unless `dynamic_slippage` is set.
+    /// Has no effect before a quote is attached.
+    pub fn slippage(mut self, slippage: Slippage) -> Self {
+        if let Some(quote) = &mut self.quote_response {
+            quote.slippage_bps = slippage.bps();
+        }
+        self
+    }
+
     /// Sets a fixed compute unit price in micro-lamports for fee calculation.
     pub fn compute_unit_price_micro_lamports(mut self, price: u64) -> Self {
         self.compute_unit_price_micro_lamports = Some(price);
@@ -268,26 +310,47 @@ impl SwapRequest {
         self.blockhash_slots_to_expiry = Some(slots);
         self
     }
+
+    /// Checks the request for mistakes the API would reject.
+    ///
+    /// Called by `JupiterClient::get_swap_transaction` and `get_swap_instructions` before
+    /// sending. Verifies that:
+    /// - the user, payer and fee account look like base58 addresses
+    /// - a quote is attached
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_address("user_public_key", &self.user_public_key)?;
+        check_address("payer", &self.payer)?;
+        if let Some(account) = &self.fee_account {
+            check_address("fee_account", account)?;
+        }
+        if self.quote_response.is_none() {
+            return Err(ValidationError::MissingField("quote_response"));
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct AccountMeta {
     pub pubkey: String,
     pub is_signer: bool,
     pub is_writable: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Instruction {
     pub program_id: String,
     pub accounts: Vec<AccountMeta>,
     pub data: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct SwapInstructions {
     pub other_instructions: Option<Vec<Instruction>>,
     pub compute_budget_instructions: Option<Vec<Instruction>>,
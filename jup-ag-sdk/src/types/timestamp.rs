@@ -0,0 +1,27 @@
+//! Timestamps returned by the Trigger and Recurring APIs.
+//!
+//! Order and trade timestamps are kept as the strings sent by the API. With the `chrono`
+//! feature, their `*_datetime()` accessors parse them into `DateTime<Utc>`, accepting
+//! RFC 3339, ISO 8601 without an offset (read as UTC) and Unix seconds.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+/// Parses RFC 3339, ISO 8601 without offset (as UTC) or Unix seconds.
+///
+/// # Example
+/// ```
+/// let at = parse_timestamp("2025-05-30T16:22:51Z").unwrap();
+/// assert_eq!(parse_timestamp("1748622171"), Some(at));
+/// ```
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<i64>() {
+        return DateTime::from_timestamp(seconds, 0);
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
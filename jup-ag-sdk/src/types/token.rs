@@ -1,9 +1,15 @@
-use serde::{Deserialize, Serialize, Serializer};
+use super::TokenInfo;
+use crate::error::ParseEnumError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct TokenPriceRequest {
     /// Comma separate to pass in multiple
     /// Example: So11111111111111111111111111111111111111112,EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v
@@ -12,9 +18,11 @@ pub struct TokenPriceRequest {
     pub token_mints: Vec<String>,
 
     /// By default, prices are denominated by USD. To denominate price in SOL, use vsToken with SOL mint address
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub vs_token: Option<String>,
 
     /// To use, pass in showExtraInfo=true, cannot use vsToken with this parameter
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub show_extra_info: Option<bool>,
 }
 
@@ -27,22 +35,139 @@ impl TokenPriceRequest {
         }
     }
 
+    /// Sets the mints to price.
+    pub fn token_mints(mut self, token_mints: &[String]) -> Self {
+        self.token_mints = token_mints.to_vec();
+        self
+    }
+
     /// By default, prices are denominated by USD.
     /// For example: To denominate price in SOL, use vsToken with SOL mint address
-    pub fn with_vs_token(mut self, vs_token: &str) -> Self {
+    pub fn vs_token(mut self, vs_token: &str) -> Self {
         self.vs_token = Some(vs_token.to_string());
         self
     }
 
+    #[deprecated(note = "renamed to `vs_token`")]
+    pub fn with_vs_token(self, vs_token: &str) -> Self {
+        self.vs_token(vs_token)
+    }
+
     /// Boolean flag to show extra info
-    pub fn with_show_extra_info(mut self, show_extra_info: bool) -> Self {
+    pub fn show_extra_info(mut self, show_extra_info: bool) -> Self {
         self.show_extra_info = Some(show_extra_info);
         self
     }
+
+    #[deprecated(note = "renamed to `show_extra_info`")]
+    pub fn with_show_extra_info(self, show_extra_info: bool) -> Self {
+        self.show_extra_info(show_extra_info)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Filters and ordering applied to token search results.
+///
+/// The search endpoints take no limit, filter or sort parameters, so these are applied to
+/// the response before it is returned: filters first, then the sort, then the limit.
+///
+/// # Example
+/// ```
+/// // The real JUP, not the tokens reusing its ticker
+/// let options = SearchOptions::default()
+///     .exact_symbol("JUP")
+///     .verified_only(true)
+///     .sort_by(SearchSort::Liquidity)
+///     .limit(1);
+/// let tokens = client.token_search_with_options(&["JUP".to_string()], &options).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchOptions {
+    /// Maximum number of tokens to return.
+    pub limit: Option<usize>,
+
+    /// Only return tokens Jupiter marks as verified.
+    pub verified_only: bool,
+
+    /// Only return tokens with this symbol, compared case-insensitively.
+    pub exact_symbol: Option<String>,
+
+    /// Order of the results, the API's relevance order if `None`.
+    pub sort_by: Option<SearchSort>,
+}
+
+/// Order of token search results, highest first. Tokens missing the value come last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSort {
+    Liquidity,
+    MarketCap,
+    OrganicScore,
+}
+
+impl SearchSort {
+    fn key(&self, token: &TokenInfo) -> Option<f64> {
+        match self {
+            Self::Liquidity => token.liquidity,
+            Self::MarketCap => token.mcap,
+            Self::OrganicScore => Some(token.organic_score),
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Sets the maximum number of tokens to return.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets whether to drop unverified tokens.
+    pub fn verified_only(mut self, verified_only: bool) -> Self {
+        self.verified_only = verified_only;
+        self
+    }
+
+    /// Keeps only tokens whose symbol is `symbol`, ignoring case.
+    pub fn exact_symbol(mut self, symbol: &str) -> Self {
+        self.exact_symbol = Some(symbol.to_string());
+        self
+    }
+
+    /// Sorts the results by `sort`, highest first.
+    pub fn sort_by(mut self, sort: SearchSort) -> Self {
+        self.sort_by = Some(sort);
+        self
+    }
+
+    /// Applies the filters and sort to search results. Ties keep the API's ordering.
+    pub fn apply(&self, tokens: Vec<TokenInfo>) -> Vec<TokenInfo> {
+        let mut tokens: Vec<TokenInfo> = tokens
+            .into_iter()
+            .filter(|token| !self.verified_only || token.is_verified == Some(true))
+            .filter(|token| {
+                self.exact_symbol
+                    .as_ref()
+                    .is_none_or(|symbol| token.symbol.eq_ignore_ascii_case(symbol))
+            })
+            .collect();
+
+        if let Some(sort) = self.sort_by {
+            tokens.sort_by(|a, b| {
+                let key = |token| sort.key(token).filter(|value: &f64| value.is_finite());
+                match (key(a), key(b)) {
+                    (Some(a), Some(b)) => b.total_cmp(&a),
+                    (a, b) => b.is_some().cmp(&a.is_some()),
+                }
+            });
+        }
+
+        tokens.truncate(self.limit.unwrap_or(usize::MAX));
+        tokens
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TokenPrice {
     pub id: String,
 
@@ -55,8 +180,9 @@ pub struct TokenPrice {
     pub extra_info: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TokenPriceResponse {
     pub data: HashMap<String, TokenPrice>,
     pub time_taken: f64,
@@ -69,7 +195,8 @@ where
     serializer.serialize_str(&vec.join(","))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TokenInfoResponse {
     pub address: String,
     pub name: String,
@@ -87,7 +214,8 @@ pub struct TokenInfoResponse {
     pub extensions: HashMap<String, String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct NewTokens {
     pub mint: String,
     pub created_at: String,
@@ -101,7 +229,10 @@ pub struct NewTokens {
     pub freeze_authority: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A token ranking category of `/tokens/v2/{category}/{interval}`.
+///
+/// Parses from and serializes to the API identifiers, e.g. `"toptrending"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Category {
     TopOrganicScore,
     TopTraded,
@@ -119,7 +250,90 @@ impl fmt::Display for Category {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl FromStr for Category {
+    type Err = ParseEnumError;
+
+    /// Parses an API identifier, ignoring case.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "toporganicscore" => Ok(Self::TopOrganicScore),
+            "toptraded" => Ok(Self::TopTraded),
+            "toptrending" => Ok(Self::TopTrending),
+            _ => Err(ParseEnumError {
+                kind: "category",
+                value: value.to_string(),
+                expected: "toporganicscore, toptraded, toptrending",
+            }),
+        }
+    }
+}
+
+/// A token tag, as accepted by `/tokens/v2/tag` and listed in [`TokenInfo::tags`].
+///
+/// Tags the SDK does not know yet are kept verbatim in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TokenTag {
+    Verified,
+    Strict,
+    Community,
+    /// Liquid staking tokens.
+    Lst,
+    Token2022,
+    Moonshot,
+    Pump,
+    Other(String),
+}
+
+impl fmt::Display for TokenTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Verified => "verified",
+            Self::Strict => "strict",
+            Self::Community => "community",
+            Self::Lst => "lst",
+            Self::Token2022 => "token-2022",
+            Self::Moonshot => "moonshot",
+            Self::Pump => "pump",
+            Self::Other(tag) => tag,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for TokenTag {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "verified" => Self::Verified,
+            "strict" => Self::Strict,
+            "community" => Self::Community,
+            "lst" => Self::Lst,
+            "token-2022" => Self::Token2022,
+            "moonshot" => Self::Moonshot,
+            "pump" => Self::Pump,
+            other => Self::Other(other.to_string()),
+        })
+    }
+}
+
+impl Serialize for TokenTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tag = String::deserialize(deserializer)?;
+        Ok(tag.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+/// A time interval of `/tokens/v2/{category}/{interval}` and of token stats.
+///
+/// Parses from and serializes to the API identifiers, e.g. `"1h"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Interval {
     FiveMinutes,
     OneHour,
@@ -139,8 +353,48 @@ impl fmt::Display for Interval {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl FromStr for Interval {
+    type Err = ParseEnumError;
+
+    /// Parses an API identifier, ignoring case.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "5m" => Ok(Self::FiveMinutes),
+            "1h" => Ok(Self::OneHour),
+            "6h" => Ok(Self::SixHours),
+            "24h" => Ok(Self::TwentyFourHours),
+            _ => Err(ParseEnumError {
+                kind: "interval",
+                value: value.to_string(),
+                expected: "5m, 1h, 6h, 24h",
+            }),
+        }
+    }
+}
+
+macro_rules! serde_via_str {
+    ($($ty:ty),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    )*};
+}
+
+serde_via_str!(Category, Interval);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Price {
     pub usd_price: f64,
 
@@ -150,3 +404,33 @@ pub struct Price {
 
     pub price_change_24h: Option<f64>,
 }
+
+/// The price of a token in terms of another token, see
+/// `JupiterClient::get_prices_denominated`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DenominatedPrice {
+    pub denominator_mint: String,
+
+    /// Denominator tokens per whole token, e.g. JUP per BONK.
+    pub price: f64,
+
+    /// Decimals of the priced token.
+    pub decimals: u8,
+
+    /// Decimals of the denominator token.
+    pub denominator_decimals: u8,
+}
+
+impl DenominatedPrice {
+    /// Raw denominator units per raw unit of the priced token.
+    pub fn raw_price(&self) -> f64 {
+        self.price * 10f64.powi(self.denominator_decimals as i32 - self.decimals as i32)
+    }
+
+    /// Value of `raw_amount` of the priced token in raw denominator units, rounded down.
+    /// `None` if it does not fit in a `u64`.
+    pub fn convert_raw(&self, raw_amount: u64) -> Option<u64> {
+        let value = (raw_amount as f64 * self.raw_price()).floor();
+        (value.is_finite() && value >= 0.0 && value < u64::MAX as f64).then_some(value as u64)
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Severity, TokenInfo, Warning, WarningKind};
+
+/// Outcome of a [`TokenPolicy`] check, ordered from least to most severe.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum PolicyVerdict {
+    #[default]
+    Allow,
+
+    /// Allowed, but the user should be warned.
+    Flag,
+
+    Deny,
+}
+
+/// A check that did not pass, with the verdict it contributed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyReason {
+    pub verdict: PolicyVerdict,
+    pub message: String,
+}
+
+/// The verdict of a [`TokenPolicy`] for one mint: the most severe verdict of its reasons.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyDecision {
+    pub mint: String,
+    pub verdict: PolicyVerdict,
+
+    /// Every check that did not pass, empty if the token is allowed outright.
+    pub reasons: Vec<PolicyReason>,
+}
+
+impl PolicyDecision {
+    pub fn is_allowed(&self) -> bool {
+        self.verdict == PolicyVerdict::Allow
+    }
+
+    pub fn is_denied(&self) -> bool {
+        self.verdict == PolicyVerdict::Deny
+    }
+}
+
+/// A token-safety policy combining Shield warnings, token audit data, organic score and
+/// verification status.
+///
+/// Each check maps to a verdict; a token gets the most severe verdict of the checks it
+/// fails. The default policy denies critical warnings, suspicious tokens and unknown tokens,
+/// and flags everything else worth a warning.
+///
+/// # Example
+/// ```
+/// let policy = TokenPolicy::default()
+///     .warning(WarningKind::HasMintAuthority, PolicyVerdict::Deny)
+///     .min_organic_score(30.0, PolicyVerdict::Flag);
+///
+/// let decisions = client.evaluate_token_policy(&mints, &policy).await?;
+/// for decision in decisions.values().filter(|decision| decision.is_denied()) {
+///     println!("{} blocked: {:?}", decision.mint, decision.reasons);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenPolicy {
+    /// Verdict for Shield warnings with [`Severity::Critical`].
+    pub critical_warnings: PolicyVerdict,
+
+    /// Verdict for Shield warnings of any other severity.
+    pub other_warnings: PolicyVerdict,
+
+    /// Per-kind verdicts, taking precedence over the severity based ones.
+    pub warning_overrides: HashMap<WarningKind, PolicyVerdict>,
+
+    /// Verdict for tokens Jupiter has not verified.
+    pub unverified: PolicyVerdict,
+
+    /// Verdict for tokens whose audit marks them as suspicious.
+    pub suspicious: PolicyVerdict,
+
+    /// Verdict for tokens whose audit reports an enabled mint authority.
+    pub mint_authority: PolicyVerdict,
+
+    /// Verdict for tokens whose audit reports an enabled freeze authority.
+    pub freeze_authority: PolicyVerdict,
+
+    /// Minimum organic score and the verdict for tokens below it.
+    pub min_organic_score: Option<(f64, PolicyVerdict)>,
+
+    /// Maximum share of the supply held by top holders, in percent, and the verdict for
+    /// tokens above it.
+    pub max_top_holders_percentage: Option<(f64, PolicyVerdict)>,
+
+    /// Verdict for mints the token search does not know.
+    pub unknown_token: PolicyVerdict,
+}
+
+impl Default for TokenPolicy {
+    fn default() -> Self {
+        Self {
+            critical_warnings: PolicyVerdict::Deny,
+            other_warnings: PolicyVerdict::Flag,
+            warning_overrides: HashMap::new(),
+            unverified: PolicyVerdict::Flag,
+            suspicious: PolicyVerdict::Deny,
+            mint_authority: PolicyVerdict::Flag,
+            freeze_authority: PolicyVerdict::Flag,
+            min_organic_score: None,
+            max_top_holders_percentage: None,
+            unknown_token: PolicyVerdict::Deny,
+        }
+    }
+}
+
+impl TokenPolicy {
+    /// Sets the verdict for one kind of Shield warning.
+    pub fn warning(mut self, kind: WarningKind, verdict: PolicyVerdict) -> Self {
+        self.warning_overrides.insert(kind, verdict);
+        self
+    }
+
+    /// Sets the verdict for unverified tokens.
+    pub fn unverified(mut self, verdict: PolicyVerdict) -> Self {
+        self.unverified = verdict;
+        self
+    }
+
+    /// Sets the minimum organic score and the verdict for tokens below it.
+    pub fn min_organic_score(mut self, score: f64, verdict: PolicyVerdict) -> Self {
+        self.min_organic_score = Some((score, verdict));
+        self
+    }
+
+    /// Sets the maximum top holders percentage and the verdict for tokens above it.
+    pub fn max_top_holders_percentage(mut self, percentage: f64, verdict: PolicyVerdict) -> Self {
+        self.max_top_holders_percentage = Some((percentage, verdict));
+        self
+    }
+
+    /// Evaluates one mint from its token info (`None` if unknown) and Shield warnings.
+    pub fn evaluate(
+        &self,
+        mint: &str,
+        token: Option<&TokenInfo>,
+        warnings: &[Warning],
+    ) -> PolicyDecision {
+        let mut reasons = Vec::new();
+        let mut check = |failed: bool, verdict: PolicyVerdict, message: String| {
+            if failed && verdict != PolicyVerdict::Allow {
+                reasons.push(PolicyReason { verdict, message });
+            }
+        };
+
+        for warning in warnings {
+            let verdict = match self.warning_overrides.get(&warning.warning_type) {
+                Some(verdict) => *verdict,
+                None if warning.severity == Severity::Critical => self.critical_warnings,
+                None => self.other_warnings,
+            };
+            check(
+                true,
+                verdict,
+                format!("{}: {}", warning.warning_type, warning.message),
+            );
+        }
+
+        match token {
+            None => check(true, self.unknown_token, "unknown token".to_string()),
+            Some(token) => {
+                check(
+                    token.is_verified != Some(true),
+                    self.unverified,
+                    "not verified".to_string(),
+                );
+
+                let audit = token.audit.as_ref();
+                check(
+                    audit.and_then(|audit| audit.is_sus) == Some(true),
+                    self.suspicious,
+                    "marked as suspicious".to_string(),
+                );
+                check(
+                    audit.and_then(|audit| audit.mint_authority_disabled) == Some(false),
+                    self.mint_authority,
+                    "mint authority is enabled".to_string(),
+                );
+                check(
+                    audit.and_then(|audit| audit.freeze_authority_disabled) == Some(false),
+                    self.freeze_authority,
+                    "freeze authority is enabled".to_string(),
+                );
+
+                if let Some((min, verdict)) = self.min_organic_score {
+                    check(
+                        token.organic_score < min,
+                        verdict,
+                        format!("organic score {} is below {min}", token.organic_score),
+                    );
+                }
+
+                if let Some((max, verdict)) = self.max_top_holders_percentage
+                    && let Some(percentage) = audit.and_then(|audit| audit.top_holders_percentage)
+                {
+                    check(
+                        percentage > max,
+                        verdict,
+                        format!("top holders own {percentage}%, above {max}%"),
+                    );
+                }
+            }
+        }
+
+        PolicyDecision {
+            mint: mint.to_string(),
+            verdict: reasons
+                .iter()
+                .map(|reason| reason.verdict)
+                .max()
+                .unwrap_or_default(),
+            reasons,
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "solana")]
+use super::SwapResponse;
+use super::{FeeBreakdown, QuoteResponse, UltraOrderResponse, WRAPPED_SOL_MINT};
+#[cfg(feature = "solana")]
+use crate::{
+    codec,
+    error::JupiterClientError,
+    introspection::{
+        ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, TransactionSummary,
+    },
+    rpc::SolanaRpc,
+};
+
+/// Base fee per transaction signature, in lamports.
+pub const SIGNATURE_FEE_LAMPORTS: u64 = 5_000;
+
+/// Rent-exempt minimum of an SPL Token account, in lamports.
+pub const TOKEN_ACCOUNT_RENT_LAMPORTS: u64 = 2_039_280;
+
+/// Rent-exempt minimum of a Token-2022 associated token account with no extension besides
+/// `ImmutableOwner`, in lamports. Accounts of mints with extensions such as transfer fees
+/// are larger and cost more.
+pub const TOKEN_2022_ACCOUNT_RENT_LAMPORTS: u64 = 2_074_080;
+
+/// SPL Token `CloseAccount` instruction tag, the same in Token-2022.
+#[cfg(feature = "solana")]
+const CLOSE_ACCOUNT: u8 = 9;
+
+/// Everything a trade costs the user besides the swapped amount.
+///
+/// SOL costs are in lamports. Route LP fees and the platform fee, which includes any
+/// referral fee, are in their own mints; see [`FeeBreakdown`].
+///
+/// # Example
+///
+/// ```
+/// let order = api.get_ultra_order(&req).await?;
+/// let cost = order.trade_cost();
+/// println!("{} lamports in SOL fees", cost.sol_fees_lamports());
+/// for (mint, amount) in cost.total_by_mint() {
+///     println!("{amount} of {mint}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeCost {
+    /// Signature fees.
+    pub network_fee_lamports: u64,
+
+    pub priority_fee_lamports: u64,
+
+    /// Rent deposited into token accounts created by the trade.
+    pub rent_lamports: u64,
+
+    /// Token accounts the transaction creates and leaves open, if known. Their rent is
+    /// counted in `rent_lamports`.
+    #[serde(default)]
+    pub new_token_accounts: Vec<String>,
+
+    /// The Token-2022 accounts among `new_token_accounts`, whose rent is counted at
+    /// [`TOKEN_2022_ACCOUNT_RENT_LAMPORTS`].
+    #[serde(default)]
+    pub new_token_2022_accounts: Vec<String>,
+
+    /// LP and platform fees.
+    pub fees: FeeBreakdown,
+}
+
+impl TradeCost {
+    /// Estimates the cost of swapping a quote, before building the transaction.
+    ///
+    /// Assumes a single signature and no priority fee or new token accounts; use
+    /// [`TradeCost::from_swap`] once the transaction is built.
+    pub fn from_quote(quote: &QuoteResponse) -> Self {
+        Self {
+            network_fee_lamports: SIGNATURE_FEE_LAMPORTS,
+            priority_fee_lamports: 0,
+            rent_lamports: 0,
+            new_token_accounts: Vec::new(),
+            new_token_2022_accounts: Vec::new(),
+            fees: quote.fee_breakdown(),
+        }
+    }
+
+    /// The cost of an Ultra order to its taker, from the fees reported by `/order`.
+    ///
+    /// Fees paid by a sponsor, e.g. for gasless orders, are left out.
+    pub fn from_ultra_order(order: &UltraOrderResponse) -> Self {
+        let paid_by_taker = |payer: &Option<String>| {
+            payer
+                .as_deref()
+                .is_none_or(|payer| Some(payer) == order.taker.as_deref())
+        };
+
+        Self {
+            network_fee_lamports: if paid_by_taker(&order.signature_fee_payer) {
+                order
+                    .signature_fee_lamports
+                    .unwrap_or(SIGNATURE_FEE_LAMPORTS)
+            } else {
+                0
+            },
+            priority_fee_lamports: if paid_by_taker(&order.prioritization_fee_payer) {
+                order.prioritization_fee_lamports
+            } else {
+                0
+            },
+            rent_lamports: if paid_by_taker(&order.rent_fee_payer) {
+                order.rent_fee_lamports.unwrap_or_default()
+            } else {
+                0
+            },
+            new_token_accounts: Vec::new(),
+            new_token_2022_accounts: Vec::new(),
+            fees: order.fee_breakdown(),
+        }
+    }
+
+    /// Network, priority and rent costs combined, in lamports.
+    pub fn sol_fees_lamports(&self) -> u64 {
+        self.network_fee_lamports
+            .saturating_add(self.priority_fee_lamports)
+            .saturating_add(self.rent_lamports)
+    }
+
+    /// All costs grouped by mint, the SOL costs under [`WRAPPED_SOL_MINT`].
+    pub fn total_by_mint(&self) -> HashMap<String, u64> {
+        let mut totals = self.fees.total_fees_by_mint();
+        let sol = self.sol_fees_lamports();
+        if sol > 0 {
+            let total = totals.entry(WRAPPED_SOL_MINT.to_string()).or_default();
+            *total = total.saturating_add(sol);
+        }
+        totals
+    }
+}
+
+#[cfg(feature = "solana")]
+impl TradeCost {
+    /// The cost of a built swap transaction. Requires the `solana` feature.
+    ///
+    /// The priority fee is read from the transaction's compute budget instructions, falling
+    /// back to the one reported by `/swap`. Rent is counted for every associated token
+    /// account the transaction creates, including idempotent creations of accounts that
+    /// may already exist; call [`TradeCost::without_existing_accounts`] to check them.
+    /// Accounts closed by a later `CloseAccount` of the same transaction, such as a
+    /// temporary wrapped SOL account, get their rent back and are not counted.
+    ///
+    /// # Errors
+    ///
+    /// * `DeserializationError` if `swap_transaction` is not a base64 transaction.
+    pub fn from_swap(
+        quote: &QuoteResponse,
+        swap: &SwapResponse,
+    ) -> Result<Self, JupiterClientError> {
+        let transaction = codec::decode_base64(&swap.swap_transaction)?;
+        let summary = TransactionSummary::from_transaction(&transaction);
+        let signatures = u64::from(transaction.message.header().num_required_signatures);
+
+        let keys = transaction.message.static_account_keys();
+        let key = |index: Option<&u8>| {
+            index
+                .and_then(|index| keys.get(usize::from(*index)))
+                .map(ToString::to_string)
+        };
+
+        // (account, created by Token-2022) in order of creation
+        let mut created: Vec<(String, bool)> = Vec::new();
+        for instruction in transaction.message.instructions() {
+            let program = key(Some(&instruction.program_id_index)).unwrap_or_default();
+            let tag = instruction.data.first();
+            if program == ASSOCIATED_TOKEN_PROGRAM_ID && matches!(tag, None | Some(0) | Some(1)) {
+                // Create and CreateIdempotent: payer, account, owner, mint, system program,
+                // token program
+                if let Some(account) = key(instruction.accounts.get(1)) {
+                    let token_2022 =
+                        key(instruction.accounts.get(5)).as_deref() == Some(TOKEN_2022_PROGRAM_ID);
+                    created.push((account, token_2022));
+                }
+            } else if (program == TOKEN_PROGRAM_ID || program == TOKEN_2022_PROGRAM_ID)
+                && tag == Some(&CLOSE_ACCOUNT)
+                && let Some(closed) = key(instruction.accounts.first())
+            {
+                created.retain(|(account, _)| *account != closed);
+            }
+        }
+
+        let mut cost = Self {
+            network_fee_lamports: signatures * SIGNATURE_FEE_LAMPORTS,
+            priority_fee_lamports: summary
+                .compute_budget
+                .priority_fee_lamports()
+                .unwrap_or(swap.prioritization_fee_lamports),
+            rent_lamports: 0,
+            new_token_2022_accounts: created
+                .iter()
+                .filter(|(_, token_2022)| *token_2022)
+                .map(|(account, _)| account.clone())
+                .collect(),
+            new_token_accounts: created.into_iter().map(|(account, _)| account).collect(),
+            fees: quote.fee_breakdown(),
+        };
+        cost.rent_lamports = cost.new_accounts_rent();
+        Ok(cost)
+    }
+
+    /// Drops the rent of `new_token_accounts` that already exist. Requires the `solana`
+    /// feature.
+    pub async fn without_existing_accounts(
+        mut self,
+        rpc: &SolanaRpc,
+    ) -> Result<Self, JupiterClientError> {
+        if self.new_token_accounts.is_empty() {
+            return Ok(self);
+        }
+
+        let exists = rpc.accounts_exist(&self.new_token_accounts).await?;
+        let existing: Vec<String> = self
+            .new_token_accounts
+            .iter()
+            .zip(exists)
+            .filter(|(_, exists)| *exists)
+            .map(|(account, _)| account.clone())
+            .collect();
+        self.new_token_accounts
+            .retain(|account| !existing.contains(account));
+        self.new_token_2022_accounts
+            .retain(|account| !existing.contains(account));
+        self.rent_lamports = self.new_accounts_rent();
+        Ok(self)
+    }
+
+    fn new_accounts_rent(&self) -> u64 {
+        let token_2022 = self.new_token_2022_accounts.len() as u64;
+        let token = (self.new_token_accounts.len() as u64).saturating_sub(token_2022);
+        token * TOKEN_ACCOUNT_RENT_LAMPORTS + token_2022 * TOKEN_2022_ACCOUNT_RENT_LAMPORTS
+    }
+}
+
+impl QuoteResponse {
+    /// Estimates the cost of swapping this quote. See [`TradeCost::from_quote`].
+    pub fn trade_cost(&self) -> TradeCost {
+        TradeCost::from_quote(self)
+    }
+}
+
+impl UltraOrderResponse {
+    /// The cost of this order to its taker. See [`TradeCost::from_ultra_order`].
+    pub fn trade_cost(&self) -> TradeCost {
+        TradeCost::from_ultra_order(self)
+    }
+}
@@ -1,11 +1,23 @@
-use crate::types::to_comma_string;
-use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    decimals::RawAmount,
+    error::ValidationError,
+    types::{quote_response::short_mint, to_comma_string},
+    validation::{check_address, check_bps, check_non_zero},
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "chrono")]
+use crate::types::timestamp::parse_timestamp;
 
 /// Request for a base64-encoded unsigned trigger order creation transaction
 ///
 /// [Official API docs](https://dev.jup.ag/docs/api/trigger-api/create-order)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct CreateTriggerOrder {
     /// The mint address of the input token.
     ///
@@ -29,7 +41,7 @@ pub struct CreateTriggerOrder {
     /// In microlamports, defaults to 95th percentile of priority fees
     /// Default value: auto
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub compute_unit_price: Option<String>,
+    pub compute_unit_price: Option<ComputeUnitPrice>,
 
     /// A token account (via the Referral Program) that will receive the fees
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,26 +53,183 @@ pub struct CreateTriggerOrder {
     pub wrap_and_unwrap_sol: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct Params {
-    /// Amount of input mint to swap
-    pub making_amount: String,
+    /// Amount of input mint to swap (raw, before decimals)
+    #[serde(serialize_with = "num_to_string", deserialize_with = "num_from_string")]
+    pub making_amount: u64,
 
-    /// Amount of output mint to receive
-    pub taking_amount: String,
+    /// Amount of output mint to receive (raw, before decimals)
+    #[serde(serialize_with = "num_to_string", deserialize_with = "num_from_string")]
+    pub taking_amount: u64,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expired_at: Option<String>,
 
     /// Amount of slippage the order can be executed with
     /// Default value: 0
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub slippage_bps: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "option_num_to_string",
+        deserialize_with = "option_num_from_string"
+    )]
+    pub slippage_bps: Option<u16>,
 
     /// Requires the feeAccount parameter, the amount of fees in bps that will be sent to the fee account
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fee_bps: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "option_num_to_string",
+        deserialize_with = "option_num_from_string"
+    )]
+    pub fee_bps: Option<u16>,
+
+    /// Unparsable string given to a bps setter, reported by [`CreateTriggerOrder::validate`].
+    #[serde(skip)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub(crate) invalid_bps: Option<ValidationError>,
+}
+
+/// A slippage or fee in basis points: a `u16`, or a string for backward compatibility.
+///
+/// Strings are deprecated. One that is not an integer between 0 and 65535 leaves the value
+/// unset and makes [`CreateTriggerOrder::validate`] fail.
+pub trait TriggerBps {
+    fn bps(self, field: &'static str) -> Result<u16, ValidationError>;
+}
+
+impl TriggerBps for u16 {
+    fn bps(self, _field: &'static str) -> Result<u16, ValidationError> {
+        Ok(self)
+    }
+}
+
+/// Deprecated, pass a `u16`.
+impl TriggerBps for &str {
+    fn bps(self, field: &'static str) -> Result<u16, ValidationError> {
+        self.trim()
+            .parse()
+            .map_err(|_| ValidationError::InvalidBps {
+                field,
+                value: self.to_string(),
+            })
+    }
+}
+
+/// Deprecated, pass a `u16`.
+impl TriggerBps for &String {
+    fn bps(self, field: &'static str) -> Result<u16, ValidationError> {
+        self.as_str().bps(field)
+    }
+}
+
+/// Serializes a number as a string, the format the Trigger API expects.
+fn num_to_string<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn option_num_to_string<T: Display, S: Serializer>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(u64),
+}
+
+/// Deserializes a number sent either as a string or as a JSON number.
+fn num_from_string<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    let value = match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(value) => value,
+        StringOrNumber::Number(value) => value.to_string(),
+    };
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+fn option_num_from_string<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Wrapper<T: FromStr>(#[serde(deserialize_with = "num_from_string")] T)
+    where
+        T::Err: Display;
+
+    Ok(Option::<Wrapper<T>>::deserialize(deserializer)?.map(|Wrapper(value)| value))
+}
+
+/// Priority fee of a Trigger API transaction.
+///
+/// Serialized as `"auto"` or as the price in microlamports per compute unit, e.g. `"1000"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ComputeUnitPrice {
+    /// Let the API pick the 95th percentile of recent priority fees.
+    #[default]
+    Auto,
+
+    /// A fixed price in microlamports per compute unit.
+    MicroLamports(u64),
+}
+
+impl From<u64> for ComputeUnitPrice {
+    fn from(micro_lamports: u64) -> Self {
+        ComputeUnitPrice::MicroLamports(micro_lamports)
+    }
+}
+
+impl std::fmt::Display for ComputeUnitPrice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComputeUnitPrice::Auto => f.write_str("auto"),
+            ComputeUnitPrice::MicroLamports(price) => write!(f, "{price}"),
+        }
+    }
+}
+
+impl FromStr for ComputeUnitPrice {
+    type Err = std::num::ParseIntError;
+
+    /// Parses `"auto"` (case-insensitive) or a number of microlamports.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("auto") {
+            Ok(ComputeUnitPrice::Auto)
+        } else {
+            value.parse().map(ComputeUnitPrice::MicroLamports)
+        }
+    }
+}
+
+impl Serialize for ComputeUnitPrice {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ComputeUnitPrice {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        num_from_string(deserializer)
+    }
 }
 
 impl CreateTriggerOrder {
@@ -70,8 +239,8 @@ impl CreateTriggerOrder {
         output_mint: &str,
         maker: &str,
         payer: &str,
-        making_amount: u64,
-        taking_amount: u64,
+        making_amount: impl RawAmount,
+        taking_amount: impl RawAmount,
     ) -> Self {
         Self {
             input_mint: input_mint.to_string(),
@@ -85,10 +254,97 @@ impl CreateTriggerOrder {
         }
     }
 
-    /// Sets the compute unit price in microlamports
+    /// Creates a limit order selling `making_amount` of the input mint at `target_price`.
+    ///
+    /// The taking amount is derived from the price, so callers don't have to scale by
+    /// decimals themselves. The maker also pays the transaction fee.
+    ///
+    /// # Arguments
+    /// * `making_amount` - Amount of input mint to sell (raw `u64` or a `TokenAmount`).
+    /// * `target_price` - Output tokens per input token, e.g. `200.0` USDC per SOL.
+    /// * `in_decimals` - Decimals of the input mint (e.g. `9` for SOL).
+    /// * `out_decimals` - Decimals of the output mint (e.g. `6` for USDC).
+    ///
+    /// # Errors
+    /// * `InvalidPrice` if `target_price` is not positive and finite.
+    /// * `ZeroAmount` if `making_amount` or the derived taking amount is 0.
+    /// * `AmountOverflow` if the taking amount does not fit in a u64.
+    ///
+    /// # Example
+    /// ```
+    /// // sell 1 SOL for 200 USDC
+    /// let order = CreateTriggerOrder::from_price(SOL_MINT, USDC_MINT, maker, 1_000_000_000, 200.0, 9, 6)?;
+    /// assert_eq!(order.params.taking_amount, 200_000_000);
+    /// ```
+    pub fn from_price(
+        input_mint: &str,
+        output_mint: &str,
+        maker: &str,
+        making_amount: impl RawAmount,
+        target_price: f64,
+        in_decimals: u8,
+        out_decimals: u8,
+    ) -> Result<Self, ValidationError> {
+        let making_amount = making_amount.raw_amount();
+        check_non_zero("making_amount", making_amount)?;
+        if !target_price.is_finite() || target_price <= 0.0 {
+            return Err(ValidationError::InvalidPrice("target_price"));
+        }
+
+        let taking_amount = (making_amount as f64
+            * target_price
+            * 10f64.powi(out_decimals as i32 - in_decimals as i32))
+        .round();
+        if taking_amount >= u64::MAX as f64 {
+            return Err(ValidationError::AmountOverflow("taking_amount"));
+        }
+        let taking_amount = taking_amount as u64;
+        check_non_zero("taking_amount", taking_amount)?;
+
+        Ok(Self::new(
+            input_mint,
+            output_mint,
+            maker,
+            maker,
+            making_amount,
+            taking_amount,
+        ))
+    }
+
+    /// Sets the mint address of the input token.
+    pub fn input_mint(mut self, input_mint: &str) -> Self {
+        self.input_mint = input_mint.to_string();
+        self
+    }
+
+    /// Sets the mint address of the output token.
+    pub fn output_mint(mut self, output_mint: &str) -> Self {
+        self.output_mint = output_mint.to_string();
+        self
+    }
+
+    /// Sets the wallet address creating the order.
+    pub fn maker(mut self, maker: &str) -> Self {
+        self.maker = maker.to_string();
+        self
+    }
+
+    /// Sets the wallet address paying for the transaction.
+    pub fn payer(mut self, payer: &str) -> Self {
+        self.payer = payer.to_string();
+        self
+    }
+
+    /// Sets the order parameters.
+    pub fn params(mut self, params: Params) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Sets the priority fee of the order transaction
     /// Default value: auto
-    pub fn compute_unit_price(mut self, price: &str) -> Self {
-        self.compute_unit_price = Some(price.to_string());
+    pub fn compute_unit_price(mut self, price: ComputeUnitPrice) -> Self {
+        self.compute_unit_price = Some(price);
         self
     }
 
@@ -110,53 +366,233 @@ impl CreateTriggerOrder {
         self
     }
 
+    /// Sets the expiration time for the order from a `DateTime`
+    ///
+    /// # Example
+    /// ```
+    /// let order = CreateTriggerOrder::new(SOL_MINT, USDC_MINT, maker, payer, 1_000_000_000, 200_000_000)
+    ///     .expired_at_datetime(Utc::now() + TimeDelta::days(7));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn expired_at_datetime(self, expired_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.expired_at(&expired_at.timestamp().to_string())
+    }
+
+    /// Sets the order to expire `duration` from now
+    #[cfg(feature = "chrono")]
+    pub fn expires_in(self, duration: std::time::Duration) -> Self {
+        self.expired_at_datetime(chrono::Utc::now() + duration)
+    }
+
     /// Sets the slippage in basis points
     /// Default value: 0
-    pub fn slippage_bps(mut self, slippage: &str) -> Self {
-        self.params.slippage_bps = Some(slippage.to_string());
+    pub fn slippage_bps(mut self, slippage_bps: impl TriggerBps) -> Self {
+        self.params = self.params.slippage_bps(slippage_bps);
         self
     }
 
     /// Sets the fee in basis points (requires fee_account to be set)
-    pub fn fee_bps(mut self, fee: &str) -> Self {
-        self.params.fee_bps = Some(fee.to_string());
+    pub fn fee_bps(mut self, fee_bps: impl TriggerBps) -> Self {
+        self.params = self.params.fee_bps(fee_bps);
         self
     }
+
+    /// Checks the order for mistakes the API would reject.
+    ///
+    /// Called by `JupiterClient::create_trigger_order` before sending. Verifies that:
+    /// - mints, maker, payer and fee account look like base58 addresses, and the mints differ
+    /// - `making_amount` and `taking_amount` are greater than 0
+    /// - `slippage_bps` and `fee_bps` given as strings are integers
+    /// - `slippage_bps` is at most 10,000
+    /// - `fee_bps` is only set together with `fee_account`
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(e) = &self.params.invalid_bps {
+            return Err(e.clone());
+        }
+        check_address("input_mint", &self.input_mint)?;
+        check_address("output_mint", &self.output_mint)?;
+        if self.input_mint == self.output_mint {
+            return Err(ValidationError::SameMint);
+        }
+        check_address("maker", &self.maker)?;
+        check_address("payer", &self.payer)?;
+
+        check_non_zero("making_amount", self.params.making_amount)?;
+        check_non_zero("taking_amount", self.params.taking_amount)?;
+
+        if let Some(slippage_bps) = self.params.slippage_bps {
+            check_bps("slippage_bps", slippage_bps as u64, 10_000)?;
+        }
+        if let Some(account) = &self.fee_account {
+            check_address("fee_account", account)?;
+        }
+        if self.params.fee_bps.is_some() && self.fee_account.is_none() {
+            return Err(ValidationError::MissingField("fee_account"));
+        }
+
+        Ok(())
+    }
 }
 
 impl Params {
     /// Creates new parameters with required amounts
-    pub fn new(making_amount: u64, taking_amount: u64) -> Self {
+    pub fn new(making_amount: impl RawAmount, taking_amount: impl RawAmount) -> Self {
         Self {
-            making_amount: making_amount.to_string(),
-            taking_amount: taking_amount.to_string(),
+            making_amount: making_amount.raw_amount(),
+            taking_amount: taking_amount.raw_amount(),
             expired_at: None,
             slippage_bps: None,
             fee_bps: None,
+            invalid_bps: None,
         }
     }
 
+    /// Sets the amount of input mint to sell (raw `u64` or a `TokenAmount`).
+    pub fn making_amount(mut self, making_amount: impl RawAmount) -> Self {
+        self.making_amount = making_amount.raw_amount();
+        self
+    }
+
+    /// Sets the amount of output mint to receive (raw `u64` or a `TokenAmount`).
+    pub fn taking_amount(mut self, taking_amount: impl RawAmount) -> Self {
+        self.taking_amount = taking_amount.raw_amount();
+        self
+    }
+
     /// Sets expiration time (Unix timestamp or relative time)
     pub fn expired_at(mut self, expired_at: &str) -> Self {
         self.expired_at = Some(expired_at.to_string());
         self
     }
 
+    /// Sets expiration time from a `DateTime`
+    #[cfg(feature = "chrono")]
+    pub fn expired_at_datetime(self, expired_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.expired_at(&expired_at.timestamp().to_string())
+    }
+
     /// Sets slippage tolerance in basis points
-    pub fn slippage_bps(mut self, slippage: &str) -> Self {
-        self.slippage_bps = Some(slippage.to_string());
+    pub fn slippage_bps(mut self, slippage_bps: impl TriggerBps) -> Self {
+        self.slippage_bps = self.checked_bps("slippage_bps", slippage_bps);
         self
     }
 
     /// Sets fee in basis points
-    pub fn fee_bps(mut self, fee: &str) -> Self {
-        self.fee_bps = Some(fee.to_string());
+    pub fn fee_bps(mut self, fee_bps: impl TriggerBps) -> Self {
+        self.fee_bps = self.checked_bps("fee_bps", fee_bps);
         self
     }
+
+    fn checked_bps(&mut self, field: &'static str, bps: impl TriggerBps) -> Option<u16> {
+        bps.bps(field).map_err(|e| self.invalid_bps = Some(e)).ok()
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A take-profit trigger order and a stop-loss price closing the same position.
+///
+/// Trigger orders fill as soon as the market pays at least their price, so a stop-loss
+/// cannot be a trigger order: one priced below the market would fill right away. Only the
+/// take-profit is a trigger order; the stop-loss is a price that
+/// [`StopLossManager`](crate::stop_loss::StopLossManager) watches, selling the remaining
+/// position at market once a quote falls to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TakeProfitStopLoss {
+    /// Sells the position at the take-profit price.
+    pub take_profit: CreateTriggerOrder,
+
+    /// Output tokens per input token at or below which the position is sold at market.
+    pub stop_loss_price: f64,
+
+    /// Decimals of the input mint.
+    pub in_decimals: u8,
+
+    /// Decimals of the output mint.
+    pub out_decimals: u8,
+}
+
+impl TakeProfitStopLoss {
+    /// Builds the take-profit order for a position of `size` input tokens (raw, before
+    /// decimals) and records the stop-loss price.
+    ///
+    /// Prices are output tokens per input token, as in [`CreateTriggerOrder::from_price`].
+    ///
+    /// # Errors
+    /// * `InvalidPrice("stop_loss_price")` if the stop-loss is not positive, finite and below
+    ///   the take-profit.
+    /// * Any error from [`CreateTriggerOrder::from_price`].
+    ///
+    /// # Example
+    /// ```
+    /// // 10 SOL, take profit at 250 USDC, stop loss at 150 USDC
+    /// let pair = TakeProfitStopLoss::new(SOL_MINT, USDC_MINT, maker, 10_000_000_000, 250.0, 150.0, 9, 6)?;
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input_mint: &str,
+        output_mint: &str,
+        maker: &str,
+        size: impl RawAmount,
+        take_profit_price: f64,
+        stop_loss_price: f64,
+        in_decimals: u8,
+        out_decimals: u8,
+    ) -> Result<Self, ValidationError> {
+        let take_profit = CreateTriggerOrder::from_price(
+            input_mint,
+            output_mint,
+            maker,
+            size,
+            take_profit_price,
+            in_decimals,
+            out_decimals,
+        )?;
+
+        if !stop_loss_price.is_finite()
+            || stop_loss_price <= 0.0
+            || stop_loss_price >= take_profit_price
+        {
+            return Err(ValidationError::InvalidPrice("stop_loss_price"));
+        }
+
+        Ok(Self {
+            take_profit,
+            stop_loss_price,
+            in_decimals,
+            out_decimals,
+        })
+    }
+
+    /// Price of swapping `in_amount` for `out_amount` (both raw), in output tokens per
+    /// input token. `None` if `in_amount` is 0.
+    pub fn price(&self, in_amount: u64, out_amount: u64) -> Option<f64> {
+        if in_amount == 0 {
+            return None;
+        }
+        let scale = 10f64.powi(i32::from(self.in_decimals) - i32::from(self.out_decimals));
+        Some(out_amount as f64 / in_amount as f64 * scale)
+    }
+
+    /// Returns `true` if selling `in_amount` for `out_amount` is at or below the stop.
+    pub fn is_stopped(&self, in_amount: u64, out_amount: u64) -> bool {
+        self.price(in_amount, out_amount)
+            .is_some_and(|price| price <= self.stop_loss_price)
+    }
+}
+
+/// A trigger order created and submitted on chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct PlacedTriggerOrder {
+    /// solana PDA Trigger Order account, needed to cancel the order
+    pub order: String,
+
+    /// signature of the order creation transaction
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TriggerResponse {
     /// Required to make a request to /execute
     pub request_id: String,
@@ -176,8 +612,10 @@ pub struct TriggerResponse {
     pub code: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct ExecuteTriggerOrder {
     /// The request ID  
     pub request_id: String,
@@ -193,9 +631,22 @@ impl ExecuteTriggerOrder {
             signed_transaction: signed_transaction.to_string(),
         }
     }
+
+    /// Sets the request ID from the create or cancel response.
+    pub fn request_id(mut self, request_id: &str) -> Self {
+        self.request_id = request_id.to_string();
+        self
+    }
+
+    /// Sets the signed transaction to execute.
+    pub fn signed_transaction(mut self, signed_transaction: &str) -> Self {
+        self.signed_transaction = signed_transaction.to_string();
+        self
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct ExecuteTriggerOrderResponse {
     pub code: u8,
 
@@ -210,8 +661,21 @@ pub struct ExecuteTriggerOrderResponse {
     pub order: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// `<status> <signature>`, followed by the order account if known.
+impl std::fmt::Display for ExecuteTriggerOrderResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.status, self.signature)?;
+        if let Some(order) = &self.order {
+            write!(f, " order {order}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct CancelTriggerOrder {
     /// maker address
     pub maker: String,
@@ -222,7 +686,7 @@ pub struct CancelTriggerOrder {
     /// In microlamports, defaults to 95th percentile of priority fees
     /// Default value: auto
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub compute_unit_price: Option<String>,
+    pub compute_unit_price: Option<ComputeUnitPrice>,
 }
 
 impl CancelTriggerOrder {
@@ -236,10 +700,31 @@ impl CancelTriggerOrder {
             compute_unit_price: None,
         }
     }
+
+    /// Sets the maker's wallet address.
+    pub fn maker(mut self, maker: &str) -> Self {
+        self.maker = maker.to_string();
+        self
+    }
+
+    /// Sets the trigger order account to cancel.
+    pub fn order(mut self, order: &str) -> Self {
+        self.order = order.to_string();
+        self
+    }
+
+    /// Sets the priority fee of the cancellation transaction
+    /// Default value: auto
+    pub fn compute_unit_price(mut self, price: ComputeUnitPrice) -> Self {
+        self.compute_unit_price = Some(price);
+        self
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct CancelTriggerOrders {
     pub maker: String,
 
@@ -250,7 +735,7 @@ pub struct CancelTriggerOrders {
     /// In microlamports, defaults to 95th percentile of priority fees
     /// Default value: auto
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub compute_unit_price: Option<String>,
+    pub compute_unit_price: Option<ComputeUnitPrice>,
 }
 
 impl CancelTriggerOrders {
@@ -265,42 +750,109 @@ impl CancelTriggerOrders {
         }
     }
 
-    /// Sets the compute unit price in microlamports
-    pub fn compute_unit_price(mut self, price: &str) -> Self {
-        self.compute_unit_price = Some(price.to_string());
+    /// Sets the maker's wallet address.
+    pub fn maker(mut self, maker: &str) -> Self {
+        self.maker = maker.to_string();
+        self
+    }
+
+    /// Sets the trigger order accounts to cancel.
+    pub fn orders(mut self, orders: Vec<String>) -> Self {
+        self.order = orders;
+        self
+    }
+
+    /// Sets the priority fee of the cancellation transactions
+    /// Default value: auto
+    pub fn compute_unit_price(mut self, price: ComputeUnitPrice) -> Self {
+        self.compute_unit_price = Some(price);
         self
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct GetTriggerOrders {
     /// user wallet address to retrive orders for
     pub user: String,
 
+    /// Page number, starting at 1
     /// Default value: 1
-    pub page: Option<String>,
+    pub page: u64,
 
-    /// Whether to include failed transactions, expects 'true' or 'false'
-    /// Possible values: [true, false]
-    pub include_failed_tx: Option<String>,
+    /// Whether to include failed transactions
+    /// Default value: false
+    pub include_failed_tx: bool,
 
     /// The status of the orders to return
     /// Possible values: [active, history]
     pub order_status: OrderStatus,
 
     /// The input mint to filter by
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub input_mint: Option<String>,
 
     /// The output mint to filter by
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub output_mint: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// Which trigger orders to list: open ones or past ones.
+///
+/// Values are matched case-insensitively; values this SDK version does not know are kept
+/// as [`OrderStatus::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum OrderStatus {
     Active,
     History,
+    Other(String),
+}
+
+impl OrderStatus {
+    /// The identifier used by the API, e.g. `"active"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            OrderStatus::Active => "active",
+            OrderStatus::History => "history",
+            OrderStatus::Other(value) => value,
+        }
+    }
+}
+
+impl From<String> for OrderStatus {
+    fn from(value: String) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "active" => OrderStatus::Active,
+            "history" => OrderStatus::History,
+            _ => OrderStatus::Other(value),
+        }
+    }
+}
+
+impl From<OrderStatus> for String {
+    fn from(value: OrderStatus) -> Self {
+        match value {
+            OrderStatus::Other(value) => value,
+            value => value.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Default for GetTriggerOrders {
+    /// The first page of active orders, without a user.
+    fn default() -> Self {
+        Self::new("", OrderStatus::Active)
+    }
 }
 
 impl GetTriggerOrders {
@@ -308,23 +860,29 @@ impl GetTriggerOrders {
     pub fn new(user: &str, order_status: OrderStatus) -> Self {
         Self {
             user: user.to_string(),
-            page: None,
-            include_failed_tx: Some("false".to_string()),
+            page: 1,
+            include_failed_tx: false,
             order_status,
             input_mint: None,
             output_mint: None,
         }
     }
 
+    /// Sets the wallet address to list orders for
+    pub fn user(mut self, user: &str) -> Self {
+        self.user = user.to_string();
+        self
+    }
+
     /// Sets the page number for pagination
-    pub fn page(mut self, page: &str) -> Self {
-        self.page = Some(page.to_string());
+    pub fn page(mut self, page: u64) -> Self {
+        self.page = page;
         self
     }
 
     /// Sets whether to include failed transactions
     pub fn include_failed_tx(mut self, include: bool) -> Self {
-        self.include_failed_tx = Some(include.to_string());
+        self.include_failed_tx = include;
         self
     }
 
@@ -347,20 +905,63 @@ impl GetTriggerOrders {
     }
 }
 
+/// Selects active trigger orders by mint, e.g. for `cancel_all_trigger_orders`.
+///
+/// The default filter matches every order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TriggerOrderFilter {
+    pub input_mint: Option<String>,
+    pub output_mint: Option<String>,
+}
+
+impl TriggerOrderFilter {
+    /// Matches orders selling `input_mint` for `output_mint`.
+    pub fn pair(input_mint: &str, output_mint: &str) -> Self {
+        Self::default()
+            .input_mint(input_mint)
+            .output_mint(output_mint)
+    }
+
+    /// Sets the input mint to filter by
+    pub fn input_mint(mut self, mint: &str) -> Self {
+        self.input_mint = Some(mint.to_string());
+        self
+    }
+
+    /// Sets the output mint to filter by
+    pub fn output_mint(mut self, mint: &str) -> Self {
+        self.output_mint = Some(mint.to_string());
+        self
+    }
+
+    /// Builds the request listing the maker's active orders matching the filter.
+    pub fn active_orders(&self, maker: &str) -> GetTriggerOrders {
+        GetTriggerOrders {
+            input_mint: self.input_mint.clone(),
+            output_mint: self.output_mint.clone(),
+            ..GetTriggerOrders::new(maker, OrderStatus::Active)
+        }
+    }
+}
+
 /// orders associated to the provided user wallet address
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct OrderResponse {
     pub user: String,
-    pub order_status: String,
-    pub orders: Vec<Order>,
-    pub total_pages: u32,
-    pub page: u32,
+    pub order_status: OrderStatus,
+    pub orders: Vec<TriggerOrder>,
+    #[serde(deserialize_with = "num_from_string")]
+    pub total_pages: u64,
+    #[serde(deserialize_with = "num_from_string")]
+    pub page: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Order {
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+pub struct TriggerOrder {
     pub user_pubkey: String,
     pub order_key: String,
     pub input_mint: String,
@@ -378,16 +979,17 @@ pub struct Order {
     pub expired_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
-    pub status: String,
+    pub status: TriggerOrderState,
     pub open_tx: String,
     pub close_tx: String,
     pub program_version: String,
-    pub trades: Vec<Trade>,
+    pub trades: Vec<TriggerTrade>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Trade {
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+pub struct TriggerTrade {
     pub order_key: String,
     pub keeper: String,
     pub input_mint: String,
@@ -401,7 +1003,183 @@ pub struct Trade {
     pub raw_fee_amount: String,
     pub tx_id: String,
     pub confirmed_at: String,
-    pub action: String,
+    pub action: TradeAction,
     #[serde(default)]
     pub product_meta: Option<serde_json::Value>, // Flexible for null or arbitrary JSON
 }
+
+impl TriggerOrder {
+    /// `expired_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn expired_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.expired_at.as_deref().and_then(parse_timestamp)
+    }
+
+    /// `created_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.created_at)
+    }
+
+    /// `updated_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn updated_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.updated_at)
+    }
+}
+
+impl TriggerTrade {
+    /// `confirmed_at` as a `DateTime<Utc>`, or `None` if it cannot be parsed. Requires the
+    /// `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn confirmed_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_timestamp(&self.confirmed_at)
+    }
+}
+
+/// One-line summary of the order, with UI amounts.
+///
+/// # Example
+/// ```
+/// println!("{order}");
+/// // Open 8ZqE..3xYz: 1 So11..1112 -> 200 EPjF..Dt1v at 200, 1 left, 0 trades
+/// ```
+impl std::fmt::Display for TriggerOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}: {} {} -> {} {}",
+            self.status,
+            short_mint(&self.order_key),
+            self.making_amount,
+            short_mint(&self.input_mint),
+            self.taking_amount,
+            short_mint(&self.output_mint),
+        )?;
+        if let (Ok(making), Ok(taking)) = (
+            self.making_amount.parse::<f64>(),
+            self.taking_amount.parse::<f64>(),
+        ) && making > 0.0
+        {
+            write!(f, " at {}", taking / making)?;
+        }
+        write!(
+            f,
+            ", {} left, {} trade{}",
+            self.remaining_making_amount,
+            self.trades.len(),
+            if self.trades.len() == 1 { "" } else { "s" },
+        )
+    }
+}
+
+#[deprecated(note = "renamed to `TriggerOrder`")]
+pub type Order = TriggerOrder;
+
+#[deprecated(note = "renamed to `TriggerTrade`")]
+pub type Trade = TriggerTrade;
+
+/// Lifecycle state of a [`TriggerOrder`].
+///
+/// Values are matched case-insensitively; values this SDK version does not know are kept
+/// as [`TriggerOrderState::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum TriggerOrderState {
+    Open,
+    Completed,
+    Cancelled,
+    Expired,
+    Other(String),
+}
+
+impl TriggerOrderState {
+    /// The identifier used by the API, e.g. `"Completed"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            TriggerOrderState::Open => "Open",
+            TriggerOrderState::Completed => "Completed",
+            TriggerOrderState::Cancelled => "Cancelled",
+            TriggerOrderState::Expired => "Expired",
+            TriggerOrderState::Other(value) => value,
+        }
+    }
+}
+
+impl From<String> for TriggerOrderState {
+    fn from(value: String) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "open" => TriggerOrderState::Open,
+            "completed" => TriggerOrderState::Completed,
+            "cancelled" => TriggerOrderState::Cancelled,
+            "expired" => TriggerOrderState::Expired,
+            _ => TriggerOrderState::Other(value),
+        }
+    }
+}
+
+impl From<TriggerOrderState> for String {
+    fn from(value: TriggerOrderState) -> Self {
+        match value {
+            TriggerOrderState::Other(value) => value,
+            value => value.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for TriggerOrderState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a [`TriggerTrade`] did.
+///
+/// Values are matched case-insensitively; values this SDK version does not know are kept
+/// as [`TradeAction::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum TradeAction {
+    Fill,
+    Cancel,
+    Other(String),
+}
+
+impl TradeAction {
+    /// The identifier used by the API, e.g. `"Fill"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            TradeAction::Fill => "Fill",
+            TradeAction::Cancel => "Cancel",
+            TradeAction::Other(value) => value,
+        }
+    }
+}
+
+impl From<String> for TradeAction {
+    fn from(value: String) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "fill" => TradeAction::Fill,
+            "cancel" => TradeAction::Cancel,
+            _ => TradeAction::Other(value),
+        }
+    }
+}
+
+impl From<TradeAction> for String {
+    fn from(value: TradeAction) -> Self {
+        match value {
+            TradeAction::Other(value) => value,
+            value => value.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for TradeAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,12 +1,22 @@
-use super::{PlatformFee, QuoteGetSwapModeEnum, RoutePlanItem, vec_to_comma_string};
+use super::{
+    Interval, PlatformFee, Price, PriorityLevel, QuoteGetSwapModeEnum, RoutePlanItem,
+    quote_response::write_swap_summary, vec_to_comma_string,
+};
+use crate::{
+    decimals::RawAmount,
+    error::ValidationError,
+    validation::{check_address, check_bps, check_bps_range, check_non_zero},
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Request for a base64-encoded unsigned swap transaction to be used in POST
 ///
 /// [Official API docs](https://dev.jup.ag/docs/api/ultra-api/order)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct UltraOrderRequest {
     /// The mint address of the input token.
     ///
@@ -24,21 +34,42 @@ pub struct UltraOrderRequest {
     /// The user's wallet address
     ///
     /// Note: If the taker is not provided, there will still be an Order Response with no transaction field.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub taker: Option<String>,
 
     /// The referral account addres
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub referral_account: Option<String>,
 
     /// referral fee in basis points (bps)
     ///
     /// Possible values: >= 50 and <= 255
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub referral_fee: Option<u8>,
 
     /// A list of Routers to exclude from routing.
     ///
     /// Possible values: `[metis, jupiterz, hashflow, dflow, pyth, okx]`
-    #[serde(serialize_with = "vec_to_comma_string")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "vec_to_comma_string"
+    )]
     pub exclude_routers: Option<Vec<String>>,
+
+    /// Slippage tolerance in basis points, overriding Ultra's real-time slippage estimate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slippage_bps: Option<u16>,
+
+    /// How aggressively Ultra should price the transaction's priority fee.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_level: Option<PriorityLevel>,
+
+    /// Query parameters without a typed field yet, sent as is.
+    ///
+    /// Left empty by `Arbitrary`, as random keys could shadow the typed fields.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub extra_params: HashMap<String, String>,
 }
 
 impl UltraOrderRequest {
@@ -47,7 +78,7 @@ impl UltraOrderRequest {
     /// # Arguments
     /// * `input_mint` - The mint address of the input token (e.g., SOL mint).
     /// * `output_mint` - The mint address of the output token (e.g., JUP mint).
-    /// * `amount` - The amount to swap (raw, before decimals). Meaning depends on `swap_mode`.
+    /// * `amount` - The amount to swap (raw `u64` or a `TokenAmount`). Meaning depends on `swap_mode`.
     ///
     /// # Returns
     /// A new `QuoteRequest` instance with None value for optional fields.
@@ -59,19 +90,40 @@ impl UltraOrderRequest {
     ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", // JUP
     ///     1_000_000_000 // 1 SOL (9 decimals)
     /// );
-    pub fn new(input_mint: &str, output_mint: &str, amount: u64) -> Self {
+    pub fn new(input_mint: &str, output_mint: &str, amount: impl RawAmount) -> Self {
         UltraOrderRequest {
             input_mint: input_mint.to_string(),
             output_mint: output_mint.to_string(),
-            amount,
+            amount: amount.raw_amount(),
             taker: None,
             referral_account: None,
             referral_fee: None,
             exclude_routers: None,
+            slippage_bps: None,
+            priority_level: None,
+            extra_params: HashMap::new(),
         }
     }
 
-    /// add the taker account to the UltraOrder
+    /// Sets the mint address of the input token.
+    pub fn input_mint(mut self, input_mint: &str) -> Self {
+        self.input_mint = input_mint.to_string();
+        self
+    }
+
+    /// Sets the mint address of the output token.
+    pub fn output_mint(mut self, output_mint: &str) -> Self {
+        self.output_mint = output_mint.to_string();
+        self
+    }
+
+    /// Sets the amount to swap (raw `u64` or a `TokenAmount`).
+    pub fn amount(mut self, amount: impl RawAmount) -> Self {
+        self.amount = amount.raw_amount();
+        self
+    }
+
+    /// Sets the taker account of the UltraOrder
     ///
     /// # Arguments
     /// * `taker` - Taker wallet address
@@ -82,13 +134,18 @@ impl UltraOrderRequest {
     ///     "So11111111111111111111111111111111111111112", // SOL
     ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", // JUP
     ///     1_000_000_000 // 1 SOL (9 decimals)
-    /// ).add_taker("taker wallet address");
-    pub fn add_taker(mut self, taker: &str) -> Self {
+    /// ).taker("taker wallet address");
+    pub fn taker(mut self, taker: &str) -> Self {
         self.taker = Some(taker.to_string());
         self
     }
 
-    /// Add the referral account to the UltraOrder
+    #[deprecated(note = "renamed to `taker`")]
+    pub fn add_taker(self, taker: &str) -> Self {
+        self.taker(taker)
+    }
+
+    /// Sets the referral account of the UltraOrder
     ///
     /// # Arguments
     /// * `referral_account` - The referral account address
@@ -102,13 +159,18 @@ impl UltraOrderRequest {
     ///     "So11111111111111111111111111111111111111112", // SOL
     ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", // JUP
     ///     1_000_000_000 // 1 SOL (9 decimals)
-    /// ).add_referral_account("referral account address");
-    pub fn add_referral_account(mut self, referral_account: &str) -> Self {
+    /// ).referral_account("referral account address");
+    pub fn referral_account(mut self, referral_account: &str) -> Self {
         self.referral_account = Some(referral_account.to_string());
         self
     }
 
-    /// Add the referral fee to the UltraOrder
+    #[deprecated(note = "renamed to `referral_account`")]
+    pub fn add_referral_account(self, referral_account: &str) -> Self {
+        self.referral_account(referral_account)
+    }
+
+    /// Sets the referral fee of the UltraOrder
     ///
     /// # Arguments
     /// * `fee` - Referral fee in basis points (bps)
@@ -116,8 +178,8 @@ impl UltraOrderRequest {
     /// # Returns
     /// The updated UltraOrderRequest with referral fee set
     ///
-    /// # Panics
-    /// Panics if fee is less than 50 or greater than 255
+    /// The fee must be between 50 and 255 bps, checked by [`UltraOrderRequest::validate`]
+    /// when the order is requested.
     ///
     /// # Example
     /// ```
@@ -125,13 +187,17 @@ impl UltraOrderRequest {
     ///     "So11111111111111111111111111111111111111112", // SOL
     ///     "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", // JUP
     ///     1_000_000_000 // 1 SOL (9 decimals)
-    /// ).add_referral_fee(100); // 1% fee (100 bps)
-    pub fn add_referral_fee(mut self, fee: u8) -> Self {
-        assert!(fee >= 50, "Referral fee must be between 50 and 255 bps");
+    /// ).referral_fee(100); // 1% fee (100 bps)
+    pub fn referral_fee(mut self, fee: u8) -> Self {
         self.referral_fee = Some(fee);
         self
     }
 
+    #[deprecated(note = "renamed to `referral_fee`")]
+    pub fn add_referral_fee(self, fee: u8) -> Self {
+        self.referral_fee(fee)
+    }
+
     /// Sets the list of Routers to exclude from routing.
     ///
     ///
@@ -154,10 +220,99 @@ impl UltraOrderRequest {
         self.exclude_routers = Some(exclude_routers);
         self
     }
+
+    /// Sets the slippage tolerance, overriding Ultra's real-time slippage estimate.
+    ///
+    /// # Arguments
+    /// * `slippage_bps` - Slippage in basis points, at most 10,000.
+    ///
+    /// # Example
+    /// ```
+    /// let request = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000).slippage_bps(50);
+    /// ```
+    pub fn slippage_bps(mut self, slippage_bps: u16) -> Self {
+        self.slippage_bps = Some(slippage_bps);
+        self
+    }
+
+    /// Sets the priority fee level Ultra should use for the transaction.
+    ///
+    /// # Example
+    /// ```
+    /// let request = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000)
+    ///     .priority_level(PriorityLevel::High);
+    /// ```
+    pub fn priority_level(mut self, priority_level: PriorityLevel) -> Self {
+        self.priority_level = Some(priority_level);
+        self
+    }
+
+    /// Adds a raw query parameter, for order options the SDK does not type yet.
+    ///
+    /// Parameters with the same name as a typed field are sent twice; prefer the typed
+    /// builder when one exists.
+    ///
+    /// # Example
+    /// ```
+    /// let request = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 1_000_000_000)
+    ///     .param("receiver", "receiver wallet address");
+    /// ```
+    pub fn param(mut self, name: &str, value: &str) -> Self {
+        self.extra_params
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+impl UltraOrderRequest {
+    /// Minimum referral fee accepted by Ultra, in bps.
+    pub const MIN_REFERRAL_FEE_BPS: u8 = 50;
+
+    /// Checks the request for mistakes the API would reject.
+    ///
+    /// Called by `JupiterClient::get_ultra_order` before sending. Verifies that:
+    /// - mints, taker and referral account look like base58 addresses, and the mints differ
+    /// - `amount` is greater than 0
+    /// - the referral fee is between 50 and 255 bps and has a referral account
+    /// - `slippage_bps` is at most 10,000
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_address("input_mint", &self.input_mint)?;
+        check_address("output_mint", &self.output_mint)?;
+        if self.input_mint == self.output_mint {
+            return Err(ValidationError::SameMint);
+        }
+
+        check_non_zero("amount", self.amount)?;
+
+        if let Some(taker) = &self.taker {
+            check_address("taker", taker)?;
+        }
+        if let Some(account) = &self.referral_account {
+            check_address("referral_account", account)?;
+        }
+        if let Some(fee) = self.referral_fee {
+            check_bps_range(
+                "referral_fee",
+                fee as u64,
+                Self::MIN_REFERRAL_FEE_BPS as u64,
+                u8::MAX as u64,
+            )?;
+            if self.referral_account.is_none() {
+                return Err(ValidationError::MissingField("referral_account"));
+            }
+        }
+        if let Some(slippage_bps) = self.slippage_bps {
+            check_bps("slippage_bps", slippage_bps as u64, 10_000)?;
+        }
+
+        Ok(())
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UltraOrderResponse {
     pub mode: String,
     /// The input token mint address.
@@ -249,8 +404,68 @@ pub struct UltraOrderResponse {
     pub price_impact: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl UltraOrderResponse {
+    /// Returns `true` if the order's fees are paid by a sponsor rather than the taker.
+    pub fn is_gasless(&self) -> bool {
+        self.gasless
+    }
+
+    /// The account paying the transaction's signature fee, falling back to the taker.
+    pub fn fee_payer(&self) -> Option<&str> {
+        self.signature_fee_payer
+            .as_deref()
+            .or(self.taker.as_deref())
+    }
+
+    /// The account sponsoring a gasless order, `None` if the taker pays its own fees.
+    pub fn sponsor(&self) -> Option<&str> {
+        if !self.gasless {
+            return None;
+        }
+        self.fee_payer()
+            .filter(|payer| Some(*payer) != self.taker.as_deref())
+    }
+}
+
+/// One-line summary of the order, with raw amounts.
+///
+/// # Example
+/// ```
+/// println!("{order}");
+/// // 1000000000 So11..1112 -> 400000000 JUPy..DvCN (min out 398000000), impact 0.0012%, slippage 50 bps, via iris, request 01978a4e-1c2b-7d3e-9f10-2b3c4d5e6f70
+/// ```
+impl std::fmt::Display for UltraOrderResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_swap_summary(
+            f,
+            (&self.input_mint, &self.in_amount),
+            (&self.output_mint, &self.out_amount),
+            &self.swap_mode,
+            &self.other_amount_threshold,
+        )?;
+        write!(
+            f,
+            ", impact {}%, slippage {} bps",
+            self.price_impact_pct, self.slippage_bps
+        )?;
+        if let Some(router) = &self.router {
+            write!(f, ", via {router}")?;
+        }
+        if self.gasless {
+            f.write_str(", gasless")?;
+        }
+        write!(f, ", request {}", self.request_id)?;
+        if self.transaction.is_none() {
+            f.write_str(", no transaction")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub struct UltraExecuteOrderRequest {
     /// The signed transaction to execute
     pub signed_transaction: String,
@@ -271,10 +486,24 @@ impl UltraExecuteOrderRequest {
             request_id: request_id.to_string(),
         }
     }
+
+    /// Sets the signed transaction to execute.
+    pub fn signed_transaction(mut self, signed_transaction: &str) -> Self {
+        self.signed_transaction = signed_transaction.to_string();
+        self
+    }
+
+    /// Sets the request ID from the order response.
+    pub fn request_id(mut self, request_id: &str) -> Self {
+        self.request_id = request_id.to_string();
+        self
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UltraExecuteOrderResponse {
     pub status: Status,
 
@@ -287,7 +516,8 @@ pub struct UltraExecuteOrderResponse {
     #[serde(default)]
     pub error: Option<String>,
 
-    pub code: u32,
+    /// `0` on success, negative on failure, e.g. `-1005` for an expired order.
+    pub code: i32,
 
     #[serde(default)]
     pub total_input_amount: Option<String>,
@@ -305,14 +535,65 @@ pub struct UltraExecuteOrderResponse {
     pub swap_events: Option<Vec<SwapEvent>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Execute failure codes meaning the order can no longer land and must be fetched again:
+/// missing cached order, invalid block height and expired order.
+pub const ULTRA_EXPIRED_CODES: [i32; 3] = [-1, -1004, -1005];
+
+impl UltraExecuteOrderResponse {
+    /// Returns `true` if execution failed because the order or its blockhash expired.
+    ///
+    /// Such orders are safe to fetch again: the signed transaction can no longer land.
+    pub fn is_expired(&self) -> bool {
+        matches!(self.status, Status::Failed)
+            && (ULTRA_EXPIRED_CODES.contains(&self.code)
+                || self
+                    .error
+                    .as_deref()
+                    .is_some_and(|error| error.to_ascii_lowercase().contains("expired")))
+    }
+}
+
+/// `Success <signature> in: <amount> out: <amount>` or `Failed (<code>): <error>`.
+impl std::fmt::Display for UltraExecuteOrderResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.status {
+            Status::Success => {
+                f.write_str("Success")?;
+                if let Some(signature) = &self.signature {
+                    write!(f, " {signature}")?;
+                }
+                if let (Some(input), Some(output)) =
+                    (&self.input_amount_result, &self.output_amount_result)
+                {
+                    write!(f, " in: {input} out: {output}")?;
+                }
+                Ok(())
+            }
+            Status::Failed => {
+                write!(f, "Failed ({})", self.code)?;
+                if let Some(error) = &self.error {
+                    write!(f, ": {error}")?;
+                }
+                if let Some(signature) = &self.signature {
+                    write!(f, " {signature}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Status {
     Success,
     Failed,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SwapEvent {
     pub input_mint: Option<String>,
     pub input_amount: Option<String>,
@@ -320,8 +601,9 @@ pub struct SwapEvent {
     pub output_amount: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TokenBalance {
     pub amount: String,
     pub ui_amount: f64,
@@ -329,30 +611,333 @@ pub struct TokenBalance {
     pub is_frozen: bool,
 }
 
+impl TokenBalance {
+    /// The raw amount as a `u64`, or `None` if it cannot be parsed.
+    pub fn amount_u64(&self) -> Option<u64> {
+        self.amount.parse().ok()
+    }
+
+    /// Returns `true` if the balance is greater than zero.
+    pub fn is_non_zero(&self) -> bool {
+        self.amount_u64()
+            .map_or(self.ui_amount > 0.0, |amount| amount > 0)
+    }
+}
+
 pub type TokenBalancesResponse = HashMap<String, TokenBalance>;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Lookup and filtering helpers for [`TokenBalancesResponse`].
+///
+/// # Example
+/// ```
+/// let balances = api.get_token_balances(wallet).await?;
+/// let sol = balances.sol().map(|b| b.ui_amount).unwrap_or_default();
+/// for (mint, balance) in balances.spendable() {
+///     println!("{mint}: {}", balance.ui_amount);
+/// }
+/// ```
+pub trait TokenBalancesExt {
+    /// The native SOL balance.
+    fn sol(&self) -> Option<&TokenBalance>;
+
+    /// The balance of a token by mint address.
+    fn get_by_mint(&self, mint: &str) -> Option<&TokenBalance>;
+
+    /// Balances greater than zero.
+    fn non_zero(&self) -> impl Iterator<Item = (&String, &TokenBalance)>;
+
+    /// Balances greater than zero in accounts that are not frozen.
+    fn spendable(&self) -> impl Iterator<Item = (&String, &TokenBalance)>;
+}
+
+impl TokenBalancesExt for TokenBalancesResponse {
+    fn sol(&self) -> Option<&TokenBalance> {
+        self.get(NATIVE_SOL_KEY)
+    }
+
+    fn get_by_mint(&self, mint: &str) -> Option<&TokenBalance> {
+        self.get(mint)
+    }
+
+    fn non_zero(&self) -> impl Iterator<Item = (&String, &TokenBalance)> {
+        self.iter().filter(|(_, balance)| balance.is_non_zero())
+    }
+
+    fn spendable(&self) -> impl Iterator<Item = (&String, &TokenBalance)> {
+        self.non_zero().filter(|(_, balance)| !balance.is_frozen)
+    }
+}
+
+/// Key used by `/ultra/v1/balances` for native SOL.
+pub const NATIVE_SOL_KEY: &str = "SOL";
+
+/// Wrapped SOL mint, used to price native SOL balances.
+pub const WRAPPED_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// A token balance together with its USD price and value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValuedBalance {
+    /// The balance key, a mint address or `"SOL"` for native SOL.
+    pub mint: String,
+
+    pub balance: TokenBalance,
+
+    /// `None` if Jupiter has no price for the token.
+    pub usd_price: Option<f64>,
+
+    /// `ui_amount * usd_price`, `None` if the token has no price.
+    pub usd_value: Option<f64>,
+}
+
+/// A wallet's balances valued in USD, sorted by value descending.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValuedBalances {
+    pub tokens: Vec<ValuedBalance>,
+
+    /// Sum of all priced balances.
+    pub total_usd_value: f64,
+}
+
+impl ValuedBalances {
+    /// Joins balances with a price map keyed by mint.
+    ///
+    /// Native SOL is priced with the wrapped SOL mint. Tokens without a price are kept with
+    /// `usd_value: None` and sorted last.
+    pub fn from_prices(balances: TokenBalancesResponse, prices: &HashMap<String, Price>) -> Self {
+        let mut tokens: Vec<ValuedBalance> = balances
+            .into_iter()
+            .map(|(mint, balance)| {
+                let price_key = if mint == NATIVE_SOL_KEY {
+                    WRAPPED_SOL_MINT
+                } else {
+                    mint.as_str()
+                };
+                let usd_price = prices.get(price_key).map(|price| price.usd_price);
+                let usd_value = usd_price.map(|price| price * balance.ui_amount);
+                ValuedBalance {
+                    mint,
+                    balance,
+                    usd_price,
+                    usd_value,
+                }
+            })
+            .collect();
+
+        tokens.sort_by(|a, b| {
+            b.usd_value
+                .unwrap_or(f64::NEG_INFINITY)
+                .total_cmp(&a.usd_value.unwrap_or(f64::NEG_INFINITY))
+                .then_with(|| a.mint.cmp(&b.mint))
+        });
+
+        let total_usd_value = tokens.iter().filter_map(|token| token.usd_value).sum();
+
+        Self {
+            tokens,
+            total_usd_value,
+        }
+    }
+}
+
+/// Everything a wallet checks before placing an Ultra order, see `JupiterClient::ultra_pretrade`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreTrade {
+    pub order: UltraOrderResponse,
+
+    /// Shield warnings for the input mint.
+    pub input_warnings: Vec<Warning>,
+
+    /// Shield warnings for the output mint.
+    pub output_warnings: Vec<Warning>,
+
+    /// `None` if Jupiter has no price for the input mint.
+    pub input_price: Option<Price>,
+
+    /// `None` if Jupiter has no price for the output mint.
+    pub output_price: Option<Price>,
+}
+
+impl PreTrade {
+    /// All warnings for both mints.
+    pub fn warnings(&self) -> impl Iterator<Item = &Warning> {
+        self.input_warnings.iter().chain(&self.output_warnings)
+    }
+
+    /// Returns `true` if any warning has [`Severity::Critical`].
+    pub fn has_critical_warnings(&self) -> bool {
+        self.warnings()
+            .any(|warning| warning.severity == Severity::Critical)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Shield {
     pub warnings: HashMap<String, Vec<Warning>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A Shield warning about a token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Warning {
     #[serde(rename = "type")]
-    pub warning_type: String,
+    pub warning_type: WarningKind,
     pub message: String,
-    pub severity: String,
+    pub severity: Severity,
+}
+
+/// The kind of a Shield [`Warning`], e.g. `HAS_FREEZE_AUTHORITY`.
+///
+/// Kinds this SDK version does not know are kept as [`WarningKind::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum WarningKind {
+    NotVerified,
+    LowLiquidity,
+    NotSellable,
+    LowOrganicActivity,
+    HasMintAuthority,
+    HasFreezeAuthority,
+    HasPermanentDelegate,
+    NewListing,
+    VeryLowTradingActivity,
+    HighSupplyConcentration,
+    NonTransferable,
+    MutableTransferFees,
+    HighTransferFee,
+    SuspiciousDevActivity,
+    SuspiciousTopHolderActivity,
+    HighSingleOwnership,
+    Other(String),
+}
+
+impl WarningKind {
+    /// The identifier used by the API, e.g. `"HAS_FREEZE_AUTHORITY"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            WarningKind::NotVerified => "NOT_VERIFIED",
+            WarningKind::LowLiquidity => "LOW_LIQUIDITY",
+            WarningKind::NotSellable => "NOT_SELLABLE",
+            WarningKind::LowOrganicActivity => "LOW_ORGANIC_ACTIVITY",
+            WarningKind::HasMintAuthority => "HAS_MINT_AUTHORITY",
+            WarningKind::HasFreezeAuthority => "HAS_FREEZE_AUTHORITY",
+            WarningKind::HasPermanentDelegate => "HAS_PERMANENT_DELEGATE",
+            WarningKind::NewListing => "NEW_LISTING",
+            WarningKind::VeryLowTradingActivity => "VERY_LOW_TRADING_ACTIVITY",
+            WarningKind::HighSupplyConcentration => "HIGH_SUPPLY_CONCENTRATION",
+            WarningKind::NonTransferable => "NON_TRANSFERABLE",
+            WarningKind::MutableTransferFees => "MUTABLE_TRANSFER_FEES",
+            WarningKind::HighTransferFee => "HIGH_TRANSFER_FEE",
+            WarningKind::SuspiciousDevActivity => "SUSPICIOUS_DEV_ACTIVITY",
+            WarningKind::SuspiciousTopHolderActivity => "SUSPICIOUS_TOP_HOLDER_ACTIVITY",
+            WarningKind::HighSingleOwnership => "HIGH_SINGLE_OWNERSHIP",
+            WarningKind::Other(kind) => kind,
+        }
+    }
+}
+
+impl From<String> for WarningKind {
+    fn from(kind: String) -> Self {
+        match kind.as_str() {
+            "NOT_VERIFIED" => WarningKind::NotVerified,
+            "LOW_LIQUIDITY" => WarningKind::LowLiquidity,
+            "NOT_SELLABLE" => WarningKind::NotSellable,
+            "LOW_ORGANIC_ACTIVITY" => WarningKind::LowOrganicActivity,
+            "HAS_MINT_AUTHORITY" => WarningKind::HasMintAuthority,
+            "HAS_FREEZE_AUTHORITY" => WarningKind::HasFreezeAuthority,
+            "HAS_PERMANENT_DELEGATE" => WarningKind::HasPermanentDelegate,
+            "NEW_LISTING" => WarningKind::NewListing,
+            "VERY_LOW_TRADING_ACTIVITY" => WarningKind::VeryLowTradingActivity,
+            "HIGH_SUPPLY_CONCENTRATION" => WarningKind::HighSupplyConcentration,
+            "NON_TRANSFERABLE" => WarningKind::NonTransferable,
+            "MUTABLE_TRANSFER_FEES" => WarningKind::MutableTransferFees,
+            "HIGH_TRANSFER_FEE" => WarningKind::HighTransferFee,
+            "SUSPICIOUS_DEV_ACTIVITY" => WarningKind::SuspiciousDevActivity,
+            "SUSPICIOUS_TOP_HOLDER_ACTIVITY" => WarningKind::SuspiciousTopHolderActivity,
+            "HIGH_SINGLE_OWNERSHIP" => WarningKind::HighSingleOwnership,
+            _ => WarningKind::Other(kind),
+        }
+    }
+}
+
+impl From<WarningKind> for String {
+    fn from(kind: WarningKind) -> Self {
+        match kind {
+            WarningKind::Other(kind) => kind,
+            kind => kind.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Severity of a Shield [`Warning`].
+///
+/// Severities this SDK version does not know are kept as [`Severity::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+    Other(String),
+}
+
+impl Severity {
+    /// The identifier used by the API, e.g. `"warning"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+            Severity::Other(severity) => severity,
+        }
+    }
+}
+
+impl From<String> for Severity {
+    fn from(severity: String) -> Self {
+        match severity.as_str() {
+            "info" => Severity::Info,
+            "warning" => Severity::Warning,
+            "critical" => Severity::Critical,
+            _ => Severity::Other(severity),
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl From<Severity> for String {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Other(severity) => severity,
+            severity => severity.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Router {
     pub id: String,
     pub name: String,
     pub icon: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TokenStats {
     pub price_change: Option<f64>,
     pub holder_change: Option<f64>,
@@ -369,15 +954,46 @@ pub struct TokenStats {
     pub num_net_buyers: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl TokenStats {
+    /// Buy plus sell volume, `None` if either side is missing.
+    pub fn total_volume(&self) -> Option<f64> {
+        Some(self.buy_volume? + self.sell_volume?)
+    }
+
+    /// Buy minus sell volume; positive when buyers dominate.
+    pub fn net_buy_volume(&self) -> Option<f64> {
+        Some(self.buy_volume? - self.sell_volume?)
+    }
+
+    /// Buy volume per unit of sell volume, `None` without sells.
+    pub fn buy_sell_ratio(&self) -> Option<f64> {
+        let sell_volume = self.sell_volume.filter(|volume| *volume > 0.0)?;
+        Some(self.buy_volume? / sell_volume)
+    }
+
+    /// Organic buy plus organic sell volume.
+    pub fn organic_volume(&self) -> Option<f64> {
+        Some(self.buy_organic_volume? + self.sell_organic_volume?)
+    }
+
+    /// Share of the total volume that is organic, from 0 to 1, `None` without volume.
+    pub fn organic_volume_share(&self) -> Option<f64> {
+        let total_volume = self.total_volume().filter(|volume| *volume > 0.0)?;
+        Some(self.organic_volume()? / total_volume)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct FirstPool {
     pub id: String,
     pub created_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct Audit {
     pub is_sus: Option<bool>,
     pub mint_authority_disabled: Option<bool>,
@@ -387,8 +1003,9 @@ pub struct Audit {
     pub dev_migrations: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-serde", serde(deny_unknown_fields))]
 pub struct TokenInfo {
     pub id: String,
     pub name: String,
@@ -444,3 +1061,21 @@ pub struct TokenInfo {
     pub smart_ct_likes: Option<u64>,
     pub updated_at: Option<String>,
 }
+
+impl TokenInfo {
+    /// Trading stats over `interval`, if the API returned them.
+    ///
+    /// # Example
+    /// ```
+    /// let stats = token.stats(Interval::OneHour);
+    /// let organic = stats.and_then(TokenStats::organic_volume_share);
+    /// ```
+    pub fn stats(&self, interval: Interval) -> Option<&TokenStats> {
+        match interval {
+            Interval::FiveMinutes => self.stats5m.as_ref(),
+            Interval::OneHour => self.stats1h.as_ref(),
+            Interval::SixHours => self.stats6h.as_ref(),
+            Interval::TwentyFourHours => self.stats24h.as_ref(),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::error::ValidationError;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Returns `true` if `value` looks like a base58-encoded 32 byte address.
+///
+/// Only the length and alphabet are checked, the bytes are not decoded.
+pub fn is_base58_address(value: &str) -> bool {
+    (32..=44).contains(&value.len()) && value.chars().all(|c| BASE58_ALPHABET.contains(c))
+}
+
+pub(crate) fn check_address(field: &'static str, value: &str) -> Result<(), ValidationError> {
+    if is_base58_address(value) {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidAddress {
+            field,
+            value: value.to_string(),
+        })
+    }
+}
+
+pub(crate) fn check_non_zero(field: &'static str, value: u64) -> Result<(), ValidationError> {
+    if value == 0 {
+        Err(ValidationError::ZeroAmount(field))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn check_bps(field: &'static str, value: u64, max: u64) -> Result<(), ValidationError> {
+    check_bps_range(field, value, 0, max)
+}
+
+pub(crate) fn check_bps_range(
+    field: &'static str,
+    value: u64,
+    min: u64,
+    max: u64,
+) -> Result<(), ValidationError> {
+    if (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(ValidationError::BpsOutOfRange {
+            field,
+            value,
+            min,
+            max,
+        })
+    }
+}
@@ -0,0 +1,171 @@
+//! Wrapped SOL instructions.
+//!
+//! With `wrap_and_unwrap_sol` disabled, `get_swap_instructions` expects the user to hold
+//! wrapped SOL (WSOL) in a token account. These helpers build the instructions that wrap
+//! SOL before the swap and unwrap it after, in the [`Instruction`] format of
+//! [`SwapInstructions`](crate::types::SwapInstructions). Requires the `solana` feature.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    introspection::{ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    types::{AccountMeta, Instruction, WRAPPED_SOL_MINT},
+};
+
+pub use crate::types::TOKEN_ACCOUNT_RENT_LAMPORTS;
+
+/// Size of an SPL Token account, in bytes.
+pub const TOKEN_ACCOUNT_SIZE: u64 = 165;
+
+const SYSTEM_PROGRAM: Pubkey = Pubkey::from_str_const(SYSTEM_PROGRAM_ID);
+const TOKEN_PROGRAM: Pubkey = Pubkey::from_str_const(TOKEN_PROGRAM_ID);
+const ASSOCIATED_TOKEN_PROGRAM: Pubkey = Pubkey::from_str_const(ASSOCIATED_TOKEN_PROGRAM_ID);
+const WSOL_MINT: Pubkey = Pubkey::from_str_const(WRAPPED_SOL_MINT);
+
+/// The associated token account of `owner` for an SPL Token (not Token-2022) `mint`.
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM,
+    )
+    .0
+}
+
+/// Wraps `lamports` of SOL into the WSOL associated token account of `owner`.
+///
+/// Creates the account if it does not exist, transfers the lamports to it and syncs its
+/// token balance. Pair with [`unwrap_sol`] on the same account.
+///
+/// # Example
+///
+/// ```
+/// let swap = api.get_swap_instructions(&payload.wrap_and_unwrap_sol(false)).await?;
+/// let wsol = associated_token_address(&user, &WRAPPED_SOL_MINT.parse()?);
+///
+/// let mut instructions = wrap_sol(&user, amount);
+/// instructions.extend(swap.setup_instructions);
+/// instructions.push(swap.swap_instruction);
+/// instructions.push(unwrap_sol(&user, &wsol));
+/// ```
+pub fn wrap_sol(owner: &Pubkey, lamports: u64) -> Vec<Instruction> {
+    let account = associated_token_address(owner, &WSOL_MINT);
+    vec![
+        instruction(
+            ASSOCIATED_TOKEN_PROGRAM,
+            vec![
+                AccountMeta::new(owner, true, true),
+                AccountMeta::new(&account, false, true),
+                AccountMeta::new(owner, false, false),
+                AccountMeta::new(&WSOL_MINT, false, false),
+                AccountMeta::new(&SYSTEM_PROGRAM, false, false),
+                AccountMeta::new(&TOKEN_PROGRAM, false, false),
+            ],
+            // CreateIdempotent
+            vec![1],
+        ),
+        system_transfer(owner, &account, lamports),
+        sync_native(&account),
+    ]
+}
+
+/// Wraps `lamports` of SOL into a new token account at `temp_account`, owned by `owner`.
+///
+/// `temp_account` is usually a fresh keypair and must sign the transaction. The account is
+/// funded with `lamports` plus [`TOKEN_ACCOUNT_RENT_LAMPORTS`] when created, so it needs
+/// no separate transfer or sync. Close it with [`unwrap_sol`] to recover all of it.
+pub fn wrap_sol_with_temp_account(
+    owner: &Pubkey,
+    temp_account: &Pubkey,
+    lamports: u64,
+) -> Vec<Instruction> {
+    // system CreateAccount: u32 index 0, lamports, space, owner program
+    let mut create = 0u32.to_le_bytes().to_vec();
+    create.extend(
+        lamports
+            .saturating_add(TOKEN_ACCOUNT_RENT_LAMPORTS)
+            .to_le_bytes(),
+    );
+    create.extend(TOKEN_ACCOUNT_SIZE.to_le_bytes());
+    create.extend(TOKEN_PROGRAM.to_bytes());
+
+    // token InitializeAccount3: tag 18, owner
+    let mut initialize = vec![18];
+    initialize.extend(owner.to_bytes());
+
+    vec![
+        instruction(
+            SYSTEM_PROGRAM,
+            vec![
+                AccountMeta::new(owner, true, true),
+                AccountMeta::new(temp_account, true, true),
+            ],
+            create,
+        ),
+        instruction(
+            TOKEN_PROGRAM,
+            vec![
+                AccountMeta::new(temp_account, false, true),
+                AccountMeta::new(&WSOL_MINT, false, false),
+            ],
+            initialize,
+        ),
+    ]
+}
+
+/// Closes the WSOL token `account` of `owner`, returning its SOL, including the rent, to
+/// `owner`.
+pub fn unwrap_sol(owner: &Pubkey, account: &Pubkey) -> Instruction {
+    instruction(
+        TOKEN_PROGRAM,
+        vec![
+            AccountMeta::new(account, false, true),
+            AccountMeta::new(owner, false, true),
+            AccountMeta::new(owner, true, false),
+        ],
+        // CloseAccount
+        vec![9],
+    )
+}
+
+/// Updates the token balance of a WSOL `account` after lamports were sent to it.
+pub fn sync_native(account: &Pubkey) -> Instruction {
+    instruction(
+        TOKEN_PROGRAM,
+        vec![AccountMeta::new(account, false, true)],
+        // SyncNative
+        vec![17],
+    )
+}
+
+fn system_transfer(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    // system Transfer: u32 index 2, lamports
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend(lamports.to_le_bytes());
+    instruction(
+        SYSTEM_PROGRAM,
+        vec![
+            AccountMeta::new(from, true, true),
+            AccountMeta::new(to, false, true),
+        ],
+        data,
+    )
+}
+
+fn instruction(program_id: Pubkey, accounts: Vec<AccountMeta>, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: program_id.to_string(),
+        accounts,
+        data: STANDARD.encode(data),
+    }
+}
+
+impl AccountMeta {
+    fn new(pubkey: &Pubkey, is_signer: bool, is_writable: bool) -> Self {
+        Self {
+            pubkey: pubkey.to_string(),
+            is_signer,
+            is_writable,
+        }
+    }
+}
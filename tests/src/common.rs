@@ -20,3 +20,263 @@ pub const DEFAULT_SLIPPAGE_BPS: u16 = 100;
 pub fn create_test_client() -> JupiterClient {
     JupiterClient::new("https://lite-api.jup.ag")
 }
+
+/// A two-hop SOL -> USDC -> JUP quote used by offline tests.
+#[cfg(test)]
+pub const SAMPLE_QUOTE_JSON: &str = r#"{
+    "inputMint": "So11111111111111111111111111111111111111112",
+    "inAmount": "1000000000",
+    "outputMint": "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+    "outAmount": "400000000",
+    "otherAmountThreshold": "396000000",
+    "swapMode": "ExactIn",
+    "slippageBps": 100,
+    "platformFee": null,
+    "priceImpactPct": "0.0012",
+    "routePlan": [
+        {
+            "swapInfo": {
+                "ammKey": "AMMKey1111111111111111111111111111111111111",
+                "label": "Whirlpool",
+                "inputMint": "So11111111111111111111111111111111111111112",
+                "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "inAmount": "1000000000",
+                "outAmount": "150000000",
+                "feeAmount": "300000",
+                "feeMint": "So11111111111111111111111111111111111111112"
+            },
+            "percent": 100
+        },
+        {
+            "swapInfo": {
+                "ammKey": "AMMKey2222222222222222222222222222222222222",
+                "label": "Meteora DLMM",
+                "inputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "outputMint": "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+                "inAmount": "150000000",
+                "outAmount": "400000000",
+                "feeAmount": "45000",
+                "feeMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+            },
+            "percent": 100
+        }
+    ],
+    "contextSlot": 345000000,
+    "timeTaken": 0.012
+}"#;
+
+/// A time-based USDC -> SOL recurring order with two fills, used by offline tests.
+#[cfg(test)]
+pub const SAMPLE_TIME_ORDER_JSON: &str = r#"{
+    "closeTx": "",
+    "createdAt": "2025-05-30T16:00:00Z",
+    "cycleFrequency": "86400",
+    "inAmountPerCycle": "100",
+    "inDeposited": "1000",
+    "inUsed": "200",
+    "inWithdrawn": "0",
+    "inputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    "maxOutAmount": "0",
+    "minOutAmount": "0",
+    "openTx": "open-tx",
+    "orderKey": "time-order",
+    "outReceived": "1.25",
+    "outWithdrawn": "0",
+    "outputMint": "So11111111111111111111111111111111111111112",
+    "rawInAmountPerCycle": "100000000",
+    "rawInDeposited": "1000000000",
+    "rawInUsed": "200000000",
+    "rawInWithdrawn": "0",
+    "rawMaxOutAmount": "0",
+    "rawMinOutAmount": "0",
+    "rawOutReceived": "1250000000",
+    "rawOutWithdrawn": "0",
+    "trades": [
+        {
+            "action": "Fill",
+            "confirmedAt": "2025-05-30T16:00:10Z",
+            "feeAmount": "0.001",
+            "feeMint": "So11111111111111111111111111111111111111112",
+            "inputAmount": "100",
+            "inputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "keeper": "keeper",
+            "orderKey": "time-order",
+            "outputAmount": "0.5",
+            "outputMint": "So11111111111111111111111111111111111111112",
+            "rawFeeAmount": "1000000",
+            "rawInputAmount": "100000000",
+            "rawOutputAmount": "500000000",
+            "txId": "fill-1"
+        },
+        {
+            "action": "Fill",
+            "confirmedAt": "2025-05-31T16:00:10Z",
+            "feeAmount": "0.001",
+            "feeMint": "So11111111111111111111111111111111111111112",
+            "inputAmount": "100",
+            "inputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "keeper": "keeper",
+            "orderKey": "time-order",
+            "outputAmount": "0.75",
+            "outputMint": "So11111111111111111111111111111111111111112",
+            "rawFeeAmount": "1000000",
+            "rawInputAmount": "100000000",
+            "rawOutputAmount": "750000000",
+            "txId": "fill-2"
+        }
+    ],
+    "updatedAt": "2025-05-31T16:00:10Z",
+    "userClosed": false,
+    "userPubkey": "EXBdeRCdiNChKyD7akt64n9HgSXEpUtpPEhmbnm4L6iH"
+}"#;
+
+#[cfg(test)]
+pub fn sample_time_order() -> jup_ag_sdk::types::TimeOrder {
+    serde_json::from_str(SAMPLE_TIME_ORDER_JSON).expect("sample time order should deserialize")
+}
+
+#[cfg(test)]
+pub fn sample_quote_response() -> jup_ag_sdk::types::QuoteResponse {
+    serde_json::from_str(SAMPLE_QUOTE_JSON).expect("sample quote should deserialize")
+}
+
+/// Builds an unsigned, base64-encoded legacy transaction paid by `payer` that invokes each
+/// program once with a writable and a read-only account.
+#[cfg(test)]
+pub fn unsigned_transaction_base64(
+    payer: &solana_pubkey::Pubkey,
+    program_ids: &[solana_pubkey::Pubkey],
+) -> String {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_message::{Message, VersionedMessage};
+    use solana_pubkey::Pubkey;
+    use solana_transaction::versioned::VersionedTransaction;
+
+    let instructions: Vec<Instruction> = program_ids
+        .iter()
+        .map(|program_id| {
+            Instruction::new_with_bytes(
+                *program_id,
+                &[1, 2, 3],
+                vec![
+                    AccountMeta::new(Pubkey::new_unique(), false),
+                    AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                ],
+            )
+        })
+        .collect();
+
+    let message = VersionedMessage::Legacy(Message::new(&instructions, Some(payer)));
+    let tx = VersionedTransaction {
+        signatures: vec![Default::default(); message.header().num_required_signatures as usize],
+        message,
+    };
+
+    STANDARD.encode(bincode::serialize(&tx).expect("failed to serialize transaction"))
+}
+
+/// A local HTTP server answering every request with `body`, for offline client tests.
+///
+/// Returns the base URL to pass to `JupiterClient::new` and the bodies of the requests
+/// received so far, in arrival order.
+#[cfg(test)]
+pub async fn mock_server(
+    body: &'static str,
+) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+    mock_server_sequence(vec![body]).await
+}
+
+/// Same as [`mock_server`], answering the n-th request with `bodies[n]` and every request
+/// past the end with the last body.
+#[cfg(test)]
+pub async fn mock_server_sequence(
+    bodies: Vec<&'static str>,
+) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+    let responses = bodies
+        .into_iter()
+        .map(|body| {
+            format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        })
+        .collect();
+    let (url, requests, _) = mock_http_server(responses).await;
+    (url, requests)
+}
+
+/// Same as [`mock_server_sequence`], with raw HTTP `responses` (status line, headers and
+/// body) so tests can control status codes and headers.
+///
+/// Also returns the request lines and headers of the requests received so far.
+#[cfg(test)]
+pub async fn mock_http_server(
+    responses: Vec<String>,
+) -> (
+    String,
+    std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("failed to bind mock server");
+    let url = format!(
+        "http://{}",
+        listener.local_addr().expect("no local address")
+    );
+    let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let heads = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let (received, received_heads) = (requests.clone(), heads.clone());
+    let responses: Vec<std::sync::Arc<str>> = responses.into_iter().map(Into::into).collect();
+    tokio::spawn(async move {
+        let mut served = 0;
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let response = responses[served.min(responses.len() - 1)].clone();
+            served += 1;
+            let (received, received_heads) = (received.clone(), received_heads.clone());
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(header_end) = text.find("\r\n\r\n") {
+                        let content_length = text[..header_end]
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if request.len() >= header_end + 4 + content_length {
+                            received_heads
+                                .lock()
+                                .unwrap()
+                                .push(text[..header_end].to_string());
+                            received
+                                .lock()
+                                .unwrap()
+                                .push(text[header_end + 4..].to_string());
+                            break;
+                        }
+                    }
+                }
+
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+
+    (url, requests, heads)
+}
@@ -1,4 +1,5 @@
 mod common;
+mod properties;
 mod recurring;
 mod swap;
 mod token;
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod property_tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use jup_ag_sdk::types::{
+        CancelRecurringOrderRequest, CancelTriggerOrder, CreateRecurringOrderRequest,
+        CreateTriggerOrder, ExecuteRecurringRequest, ExecuteRecurringResponse, ExecuteTriggerOrder,
+        QuoteRequest, QuoteResponse, RecurringResponse, SwapRequest, SwapResponse,
+        UltraExecuteOrderRequest, UltraExecuteOrderResponse, UltraOrderRequest, UltraOrderResponse,
+    };
+    use serde::{Serialize, de::DeserializeOwned};
+
+    const CASES: usize = 256;
+
+    /// Builds `CASES` values of `T` from a fixed pseudo-random byte stream, so failures
+    /// reproduce.
+    fn samples<T: for<'a> Arbitrary<'a>>() -> Vec<T> {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        (0..CASES)
+            .filter_map(|_| {
+                let bytes: Vec<u8> = (0..512)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        state as u8
+                    })
+                    .collect();
+                T::arbitrary(&mut Unstructured::new(&bytes)).ok()
+            })
+            .collect()
+    }
+
+    /// Compares JSON values rather than `T`s: non-finite floats serialize as `null` and come
+    /// back as `None`.
+    fn assert_json_round_trip<T>(value: &T)
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let json = serde_json::to_value(value).expect("failed to serialize");
+        let back: T = serde_json::from_value(json.clone()).expect("failed to deserialize");
+        let again = serde_json::to_value(&back).expect("failed to serialize again");
+        assert_eq!(again, json);
+    }
+
+    #[test]
+    fn test_trigger_requests_round_trip() {
+        for order in samples::<CreateTriggerOrder>() {
+            assert_json_round_trip(&order);
+        }
+        for execute in samples::<ExecuteTriggerOrder>() {
+            assert_json_round_trip(&execute);
+        }
+        for cancel in samples::<CancelTriggerOrder>() {
+            assert_json_round_trip(&cancel);
+        }
+    }
+
+    #[test]
+    fn test_validation_never_panics() {
+        for quote in samples::<QuoteRequest>() {
+            let _ = quote.validate();
+        }
+        for order in samples::<UltraOrderRequest>() {
+            let _ = order.validate();
+        }
+        for order in samples::<CreateTriggerOrder>() {
+            let _ = order.validate();
+        }
+        for order in samples::<CreateRecurringOrderRequest>() {
+            let _ = order.validate_at(1_750_000_000);
+        }
+    }
+
+    /// Query-string requests join their lists with commas, so they only go one way.
+    #[test]
+    fn test_query_requests_encode() {
+        for quote in samples::<QuoteRequest>() {
+            serde_urlencoded::to_string(&quote).expect("failed to encode quote request");
+        }
+        for order in samples::<UltraOrderRequest>() {
+            serde_urlencoded::to_string(&order).expect("failed to encode ultra order request");
+        }
+    }
+
+    #[test]
+    fn test_ultra_types_round_trip() {
+        for order in samples::<UltraOrderResponse>() {
+            assert_json_round_trip(&order);
+        }
+        for execute in samples::<UltraExecuteOrderRequest>() {
+            assert_json_round_trip(&execute);
+        }
+        for execute in samples::<UltraExecuteOrderResponse>() {
+            assert_json_round_trip(&execute);
+        }
+    }
+
+    #[test]
+    fn test_swap_types_round_trip() {
+        for quote in samples::<QuoteResponse>() {
+            assert_json_round_trip(&quote);
+        }
+        for swap in samples::<SwapRequest>() {
+            assert_json_round_trip(&swap);
+        }
+        for swap in samples::<SwapResponse>() {
+            assert_json_round_trip(&swap);
+        }
+    }
+
+    #[test]
+    fn test_recurring_types_round_trip() {
+        for order in samples::<CreateRecurringOrderRequest>() {
+            assert_json_round_trip(&order);
+        }
+        for order in samples::<RecurringResponse>() {
+            assert_json_round_trip(&order);
+        }
+        for execute in samples::<ExecuteRecurringRequest>() {
+            assert_json_round_trip(&execute);
+        }
+        for execute in samples::<ExecuteRecurringResponse>() {
+            assert_json_round_trip(&execute);
+        }
+        for cancel in samples::<CancelRecurringOrderRequest>() {
+            assert_json_round_trip(&cancel);
+        }
+    }
+}
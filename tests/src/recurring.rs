@@ -1,10 +1,529 @@
 #[cfg(test)]
 mod recurring_tests {
-    use jup_ag_sdk::types::{
-        CreateRecurringOrderRequest, GetRecurringOrders, OrderStatus, RecurringOrderType,
+    use futures::{StreamExt, TryStreamExt};
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError, ValidationError,
+        order_manager::{OrderEvent, OrderKind, OrderManager},
+        recurring_watcher::{RecurringEvent, RecurringWatcher},
+        types::{
+            CancelRecurringOrderRequest, CreateRecurringOrderRequest, GetRecurringOrders,
+            OrderStatus, PriceWithdraw, RecurringOrder, RecurringOrderReport, RecurringOrderType,
+            RecurringSchedule, WithdrawSide,
+        },
     };
 
-    use crate::common::{SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client};
+    use solana_keypair::Keypair;
+    use solana_signer::Signer;
+
+    use crate::common::{
+        SAMPLE_TIME_ORDER_JSON, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client,
+        mock_server, mock_server_sequence, sample_time_order, unsigned_transaction_base64,
+    };
+
+    #[test]
+    fn test_recurring_order_parsed_amounts() {
+        let order = sample_time_order();
+        assert_eq!(order.cycle_frequency_u64(), Some(86_400));
+        assert_eq!(order.raw_in_deposited_u64(), Some(1_000_000_000));
+        assert_eq!(order.in_used_f64(), Some(200.0));
+        assert_eq!(order.raw_max_out_amount_u64(), Some(0));
+        assert_eq!(order.trades[1].raw_output_amount_u64(), Some(750_000_000));
+        assert_eq!(order.trades[1].output_amount_f64(), Some(0.75));
+
+        let order = RecurringOrder::Time(order);
+        assert_eq!(order.order_key(), "time-order");
+        assert_eq!(order.input_mint(), USDC_MINT);
+        assert_eq!(order.trades().len(), 2);
+        assert_eq!(order.raw_in_used_u64(), Some(200_000_000));
+        assert_eq!(order.raw_out_received_u64(), Some(1_250_000_000));
+    }
+
+    #[test]
+    fn test_recurring_requests_build_from_default() {
+        let get = GetRecurringOrders::default()
+            .recurring_type(RecurringOrderType::Time)
+            .user(TEST_USER_PUBKEY);
+        assert_eq!(
+            get,
+            GetRecurringOrders::new(
+                RecurringOrderType::Time,
+                OrderStatus::Active,
+                TEST_USER_PUBKEY
+            )
+        );
+
+        let cancel = CancelRecurringOrderRequest::default()
+            .order("order")
+            .user(TEST_USER_PUBKEY);
+        assert_eq!(
+            cancel,
+            CancelRecurringOrderRequest::new("order", RecurringOrderType::Time, TEST_USER_PUBKEY)
+        );
+
+        let withdraw = PriceWithdraw::default()
+            .order("order")
+            .user(TEST_USER_PUBKEY)
+            .input_or_output(WithdrawSide::In)
+            .amount(5);
+        assert_eq!(
+            withdraw,
+            PriceWithdraw::new(Some(5), "order", TEST_USER_PUBKEY, WithdrawSide::In)
+        );
+
+        let create = CreateRecurringOrderRequest::default()
+            .user(TEST_USER_PUBKEY)
+            .input_mint(USDC_MINT)
+            .output_mint(SOL_MINT);
+        assert_eq!(
+            create.params(
+                CreateRecurringOrderRequest::new_time_order("", "", "", 100, 2, 86_400).params
+            ),
+            CreateRecurringOrderRequest::new_time_order(
+                TEST_USER_PUBKEY,
+                USDC_MINT,
+                SOL_MINT,
+                100,
+                2,
+                86_400
+            )
+        );
+    }
+
+    #[test]
+    fn test_recurring_schedule() {
+        let now = 1_750_000_000;
+        let order = CreateRecurringOrderRequest::new_time_order(
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            SOL_MINT,
+            1_000_000_000,
+            10,
+            86_400,
+        );
+        let schedule = order.schedule(now).expect("time orders have a schedule");
+        assert_eq!(schedule.amount_per_cycle, 100_000_000);
+        assert_eq!(schedule.executions.len(), 10);
+        assert_eq!(schedule.start_at(), Some(now));
+        assert_eq!(schedule.end_at(), Some(now + 9 * 86_400));
+
+        let delayed = order.start_at(now + 60).schedule(now).unwrap();
+        assert_eq!(delayed.start_at(), Some(now + 60));
+
+        let price_order = CreateRecurringOrderRequest::new_price_order(
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            SOL_MINT,
+            1_000_000_000,
+            100_000_000,
+            86_400,
+        );
+        assert!(price_order.schedule(now).is_none());
+
+        // 800 USDC left at 100 per day, one day after the last fill
+        let remaining = RecurringSchedule::from_time_order(&sample_time_order())
+            .expect("sample amounts should parse");
+        let last_fill = 1_748_707_210;
+        assert_eq!(remaining.executions.len(), 8);
+        assert_eq!(remaining.start_at(), Some(last_fill + 86_400));
+        assert_eq!(remaining.end_at(), Some(last_fill + 8 * 86_400));
+    }
+
+    #[test]
+    fn test_recurring_order_omits_unset_options() {
+        let order = CreateRecurringOrderRequest::new_time_order(
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            SOL_MINT,
+            1_000_000_000,
+            10,
+            86_400,
+        );
+        let body = serde_json::to_value(&order).expect("failed to serialize order");
+        let time = &body["params"]["time"];
+        for key in ["minPrice", "maxPrice", "startAt"] {
+            assert!(time.get(key).is_none(), "{key} should be omitted: {time}");
+        }
+
+        let body = serde_json::to_value(order.min_price(100.0)).unwrap();
+        assert_eq!(body["params"]["time"]["minPrice"], 100.0);
+    }
+
+    #[test]
+    fn test_recurring_order_validation() {
+        let now = 1_750_000_000;
+        let time_order = |amount, orders, interval| {
+            CreateRecurringOrderRequest::new_time_order(
+                TEST_USER_PUBKEY,
+                USDC_MINT,
+                SOL_MINT,
+                amount,
+                orders,
+                interval,
+            )
+        };
+
+        let order = time_order(1_000_000_000, 10, 86_400);
+        assert_eq!(order.validate_at(now), Ok(()));
+        assert_eq!(order.check_min_notional(1.0, 6), Ok(()));
+
+        assert_eq!(
+            time_order(1_000_000_000, 1, 86_400).validate_at(now),
+            Err(ValidationError::BelowMinimum {
+                field: "number_of_orders",
+                value: 1,
+                min: 2,
+            })
+        );
+        assert!(matches!(
+            time_order(1_000_000_000, 10, 30).validate_at(now),
+            Err(ValidationError::BelowMinimum {
+                field: "interval",
+                ..
+            })
+        ));
+        assert_eq!(
+            time_order(1_000_000_000, 10, 86_400)
+                .min_price(200.0)
+                .max_price(100.0)
+                .validate_at(now),
+            Err(ValidationError::InvertedPriceRange)
+        );
+        assert_eq!(
+            time_order(1_000_000_000, 10, 86_400)
+                .start_at(now - 60)
+                .validate_at(now),
+            Err(ValidationError::NotInFuture("start_at"))
+        );
+        assert_eq!(
+            time_order(1_000_000_000, 10, 86_400)
+                .start_at(now + 60)
+                .validate_at(now),
+            Ok(())
+        );
+
+        // 10 orders of 20 USDC are below the 50 USD per order minimum
+        assert_eq!(
+            time_order(200_000_000, 10, 86_400).check_min_notional(1.0, 6),
+            Err(ValidationError::BelowMinimumNotional {
+                field: "in_amount_per_cycle",
+                min_usd: 50,
+            })
+        );
+        let price_order = CreateRecurringOrderRequest::new_price_order(
+            TEST_USER_PUBKEY,
+            USDC_MINT,
+            SOL_MINT,
+            50_000_000,
+            10_000_000,
+            86_400,
+        );
+        assert_eq!(
+            price_order.check_min_notional(1.0, 6),
+            Err(ValidationError::BelowMinimumNotional {
+                field: "deposit_amount",
+                min_usd: 100,
+            })
+        );
+    }
+
+    #[test]
+    fn test_price_withdraw_serialization() {
+        let partial =
+            PriceWithdraw::new(Some(5_000_000), "order", TEST_USER_PUBKEY, WithdrawSide::In);
+        let json = serde_json::to_value(&partial).expect("failed to serialize withdraw");
+        assert_eq!(json["amount"], 5_000_000);
+        assert_eq!(json["inputOrOutput"], "In");
+
+        let all = PriceWithdraw::all("order", TEST_USER_PUBKEY, WithdrawSide::Out);
+        let json = serde_json::to_value(&all).expect("failed to serialize withdraw");
+        assert!(
+            json.get("amount").is_none(),
+            "withdraw all should omit the amount"
+        );
+        assert_eq!(json["inputOrOutput"], "Out");
+    }
+
+    #[test]
+    fn test_recurring_order_report() {
+        // 200 USDC spent for 1.25 SOL, SOL now at 200 USDC
+        let report = RecurringOrderReport::from_time_order(&sample_time_order(), 200.0)
+            .expect("sample amounts should parse");
+        assert_eq!(report.fills, 2);
+        assert_eq!(report.invested, 200.0);
+        assert_eq!(report.remaining, 800.0);
+        assert_eq!(report.average_fill_price, Some(160.0));
+        assert_eq!(report.current_value, 250.0);
+        assert_eq!(report.unrealized_pnl, 50.0);
+        assert_eq!(report.unrealized_pnl_pct, Some(25.0));
+        assert_eq!(report.completion_pct, Some(20.0));
+
+        let mut fresh = sample_time_order();
+        fresh.in_used = "0".to_string();
+        fresh.out_received = "0".to_string();
+        fresh.trades.clear();
+        let report = RecurringOrderReport::from_order(&RecurringOrder::Time(fresh), 200.0)
+            .expect("amounts should parse");
+        assert_eq!(report.average_fill_price, None);
+        assert_eq!(report.unrealized_pnl_pct, None);
+        assert_eq!(report.completion_pct, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_recurring_orders_stream() {
+        let body = format!(
+            r#"{{"orderStatus":"history","page":1,"totalPages":1,"user":"{TEST_USER_PUBKEY}","time":[{SAMPLE_TIME_ORDER_JSON}]}}"#
+        );
+        let (url, requests) = mock_server(Box::leak(body.into_boxed_str())).await;
+        let client = JupiterClient::new(&url);
+        let params = GetRecurringOrders::new(
+            RecurringOrderType::Time,
+            OrderStatus::History,
+            TEST_USER_PUBKEY,
+        );
+
+        let orders: Vec<RecurringOrder> = client
+            .get_recurring_orders_stream(&params)
+            .try_collect()
+            .await
+            .expect("failed to stream recurring orders");
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_key(), "time-order");
+        assert_eq!(
+            requests.lock().unwrap().len(),
+            1,
+            "a single page was reported"
+        );
+
+        let unreachable = JupiterClient::new("http://127.0.0.1:1");
+        let results: Vec<_> = unreachable
+            .get_recurring_orders_stream(&params)
+            .collect()
+            .await;
+        assert_eq!(results.len(), 1, "stream should stop after the failed page");
+        assert!(matches!(
+            results[0],
+            Err(JupiterClientError::RequestError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_execute_recurring_flows() {
+        let keypair = Keypair::new();
+        let user = keypair.pubkey().to_string();
+        let tx =
+            unsigned_transaction_base64(&keypair.pubkey(), &[solana_pubkey::Pubkey::new_unique()]);
+        // answers the create or cancel request, its execution, then any further request
+        let mock = |status: &str| {
+            let transaction: &'static str = Box::leak(
+                format!(r#"{{"requestId":"req","transaction":"{tx}"}}"#).into_boxed_str(),
+            );
+            let executed = format!(r#"{{"signature":"sig","status":"{status}"}}"#);
+            mock_server_sequence(vec![
+                transaction,
+                Box::leak(executed.into_boxed_str()),
+                transaction,
+            ])
+        };
+
+        let (url, requests) = mock("Success").await;
+        let client = JupiterClient::new(&url);
+        let cancel = CancelRecurringOrderRequest::new("order", RecurringOrderType::Time, &user);
+        let executed = client
+            .cancel_and_execute_recurring_order(&cancel, &keypair)
+            .await
+            .expect("cancellation should execute");
+        assert_eq!(executed.signature, "sig");
+
+        let execute: serde_json::Value = serde_json::from_str(&requests.lock().unwrap()[1])
+            .expect("execute request should be json");
+        assert_eq!(execute["requestId"], "req");
+        assert_ne!(execute["signedTransaction"], tx.as_str());
+
+        let (url, _) = mock("Failed").await;
+        let client = JupiterClient::new(&url);
+        let order = CreateRecurringOrderRequest::new_time_order(
+            &user,
+            USDC_MINT,
+            SOL_MINT,
+            1_000_000_000,
+            10,
+            86_400,
+        );
+        let failed = client
+            .create_and_execute_recurring_order(&order, &keypair)
+            .await;
+        assert!(matches!(
+            failed,
+            Err(JupiterClientError::TransactionFailed { .. })
+        ));
+
+        let stranger = Keypair::new();
+        let unsigned = client
+            .cancel_and_execute_recurring_order(&cancel, &stranger)
+            .await;
+        assert!(matches!(unsigned, Err(JupiterClientError::SigningError(_))));
+    }
+
+    /// A `getRecurringOrders` page holding `orders`, leaked for the mock server.
+    fn orders_page(orders: &[serde_json::Value]) -> &'static str {
+        let page = serde_json::json!({
+            "orderStatus": "active",
+            "page": 1,
+            "totalPages": 1,
+            "user": TEST_USER_PUBKEY,
+            "all": orders,
+        });
+        Box::leak(page.to_string().into_boxed_str())
+    }
+
+    #[tokio::test]
+    async fn test_recurring_watcher_reports_trades_and_completion() {
+        let order: serde_json::Value =
+            serde_json::from_str(SAMPLE_TIME_ORDER_JSON).expect("sample should be json");
+        let mut first_fill = order.clone();
+        first_fill["trades"].as_array_mut().unwrap().truncate(1);
+
+        let (url, _) = mock_server_sequence(vec![
+            orders_page(std::slice::from_ref(&first_fill)),
+            orders_page(std::slice::from_ref(&order)),
+            orders_page(&[]),
+            orders_page(std::slice::from_ref(&order)),
+        ])
+        .await;
+        let (mut watcher, _events) =
+            RecurringWatcher::new(JupiterClient::new(&url), TEST_USER_PUBKEY, ["time-order"]);
+
+        let seeded = watcher.poll().await.expect("first poll should succeed");
+        assert!(seeded.is_empty(), "existing trades should not be reported");
+
+        let filled = watcher.poll().await.expect("second poll should succeed");
+        assert_eq!(
+            filled,
+            vec![RecurringEvent::Trade {
+                order: "time-order".to_string(),
+                tx_id: "fill-2".to_string(),
+            }]
+        );
+
+        let completed = watcher.poll().await.expect("third poll should succeed");
+        assert_eq!(
+            completed,
+            vec![RecurringEvent::Completed {
+                order: "time-order".to_string(),
+            }]
+        );
+        assert_eq!(watcher.orders().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_recurring_watcher_reports_stalls_once() {
+        let mut order: serde_json::Value =
+            serde_json::from_str(SAMPLE_TIME_ORDER_JSON).expect("sample should be json");
+        order["cycleFrequency"] = "0".into();
+
+        let (url, _) = mock_server(orders_page(std::slice::from_ref(&order))).await;
+        let (watcher, _events) =
+            RecurringWatcher::new(JupiterClient::new(&url), TEST_USER_PUBKEY, ["time-order"]);
+        let mut watcher = watcher.stall_grace(std::time::Duration::ZERO);
+
+        assert!(
+            watcher
+                .poll()
+                .await
+                .expect("poll should succeed")
+                .is_empty()
+        );
+        let stalled = watcher.poll().await.expect("poll should succeed");
+        assert!(matches!(
+            stalled.as_slice(),
+            [RecurringEvent::Stalled { order, .. }] if order == "time-order"
+        ));
+        assert!(
+            watcher
+                .poll()
+                .await
+                .expect("poll should succeed")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_manager_lists_and_watches_open_orders() {
+        let trigger_page = serde_json::json!({
+            "user": TEST_USER_PUBKEY,
+            "orderStatus": "active",
+            "orders": [{
+                "userPubkey": TEST_USER_PUBKEY,
+                "orderKey": "trigger-order",
+                "inputMint": SOL_MINT,
+                "outputMint": USDC_MINT,
+                "makingAmount": "1",
+                "takingAmount": "200",
+                "remainingMakingAmount": "1",
+                "remainingTakingAmount": "200",
+                "rawMakingAmount": "1000000000",
+                "rawTakingAmount": "200000000",
+                "rawRemainingMakingAmount": "1000000000",
+                "rawRemainingTakingAmount": "200000000",
+                "slippageBps": "0",
+                "createdAt": "2025-05-30T16:22:51Z",
+                "updatedAt": "2025-05-30T16:22:51Z",
+                "status": "Open",
+                "openTx": "tx",
+                "closeTx": "",
+                "programVersion": "v1",
+                "trades": []
+            }],
+            "totalPages": 1,
+            "page": 1
+        });
+        let no_trigger_orders = serde_json::json!({
+            "user": TEST_USER_PUBKEY,
+            "orderStatus": "active",
+            "orders": [],
+            "totalPages": 1,
+            "page": 1
+        });
+        let time_order: serde_json::Value =
+            serde_json::from_str(SAMPLE_TIME_ORDER_JSON).expect("sample should be json");
+        let mut first_fill = time_order.clone();
+        first_fill["trades"].as_array_mut().unwrap().truncate(1);
+
+        let (url, _) = mock_server_sequence(vec![
+            Box::leak(trigger_page.to_string().into_boxed_str()),
+            orders_page(std::slice::from_ref(&first_fill)),
+            Box::leak(no_trigger_orders.to_string().into_boxed_str()),
+            orders_page(std::slice::from_ref(&time_order)),
+        ])
+        .await;
+        let manager = OrderManager::new(JupiterClient::new(&url), TEST_USER_PUBKEY);
+        let (mut watcher, _events) = manager.watch();
+
+        let opened = watcher.poll().await.expect("first poll should succeed");
+        let orders: Vec<_> = opened
+            .iter()
+            .map(|event| match event {
+                OrderEvent::Opened(order) => order,
+                other => panic!("expected Opened, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].kind, OrderKind::Trigger);
+        assert_eq!(orders[0].in_remaining, Some(1_000_000_000));
+        assert_eq!(orders[0].out_received, Some(0));
+        assert_eq!(orders[1].kind, OrderKind::RecurringTime);
+        assert_eq!(orders[1].in_remaining, Some(800_000_000));
+        assert_eq!(orders[1].trades, 1);
+
+        let changed = watcher.poll().await.expect("second poll should succeed");
+        assert!(matches!(
+            changed.as_slice(),
+            [
+                OrderEvent::Traded { order, new_trades: 1 },
+                OrderEvent::Closed(closed),
+            ] if order.order_key == "time-order" && closed.order_key == "trigger-order"
+        ));
+    }
 
     #[tokio::test]
     async fn test_create_recurring_time_order() {
@@ -1,13 +1,38 @@
 #[cfg(test)]
 mod swap_tests {
+    use std::{
+        collections::HashMap,
+        time::{Duration, Instant},
+    };
+
     use jup_ag_sdk::{
-        JupiterClient,
-        types::{DexEnum, QuoteGetSwapModeEnum, QuoteRequest, SwapRequest},
+        JupiterClient, JupiterClientError, SwapApiMode, ValidationError,
+        arbitrage::{ArbitrageEvent, ArbitrageScanner},
+        broadcast::Broadcaster,
+        jito::{JITO_TIP_ACCOUNTS, JitoClient, add_tip_base64},
+        priority_fee::{PriorityFeeEstimate, PriorityFeeProvider, RecentFeeEstimator},
+        rate_limit::RateLimiter,
+        rpc::{
+            CommitmentLevel, Confirmation, RpcResponse, SignatureStatus, SimulationError,
+            SimulationResult, SolanaRpc, TransactionError, confirm_signature,
+        },
+        types::{
+            BestQuote, DexEnum, FreshQuote, PlatformFee, QuoteComparison, QuoteGetSwapModeEnum,
+            QuoteLadder, QuoteRequest, QuoteResponse, QuoteVariant, Slippage, SwapAndSendOptions,
+            SwapRequest, SwapResponse,
+        },
+        wsol::{
+            TOKEN_ACCOUNT_RENT_LAMPORTS, associated_token_address, unwrap_sol, wrap_sol,
+            wrap_sol_with_temp_account,
+        },
     };
 
+    use solana_keypair::Keypair;
+
     use crate::common::{
-        BASE_URL, DEFAULT_SLIPPAGE_BPS, JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY,
-        create_test_client,
+        BASE_URL, DEFAULT_SLIPPAGE_BPS, JUP_MINT, SAMPLE_QUOTE_JSON, SOL_MINT, TEST_AMOUNT,
+        TEST_USER_PUBKEY, USDC_MINT, create_test_client, mock_http_server, mock_server,
+        mock_server_sequence, sample_quote_response, unsigned_transaction_base64,
     };
 
     fn create_default_quote_request() -> QuoteRequest {
@@ -22,6 +47,22 @@ mod swap_tests {
         assert_eq!(client.base_url, BASE_URL);
     }
 
+    #[test]
+    fn test_self_hosted_swap_api_mode() {
+        let client = create_test_client();
+        assert_eq!(client.swap_api_mode, SwapApiMode::Hosted);
+        assert_eq!(client.swap_api_mode.path_prefix(), "/swap/v1");
+
+        let self_hosted = JupiterClient::self_hosted("http://127.0.0.1:8080").with_api_key("key");
+        assert_eq!(self_hosted.base_url, "http://127.0.0.1:8080");
+        assert_eq!(
+            self_hosted.swap_api_mode,
+            SwapApiMode::SelfHosted,
+            "mode should survive with_api_key"
+        );
+        assert_eq!(self_hosted.swap_api_mode.path_prefix(), "");
+    }
+
     #[test]
     fn test_quote_request_builder_methods() {
         let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
@@ -77,6 +118,350 @@ mod swap_tests {
         );
     }
 
+    #[test]
+    fn test_slippage_validation() {
+        assert_eq!(Slippage::from_bps(50).unwrap().percent(), 0.5);
+        assert_eq!(Slippage::from_percent(1.25).unwrap().bps(), 125);
+        assert_eq!(
+            Slippage::from_bps(10_001),
+            Err(ValidationError::BpsOutOfRange {
+                field: "slippage",
+                value: 10_001,
+                min: 0,
+                max: 10_000,
+            }),
+            "over 100% should fail"
+        );
+        assert_eq!(
+            Slippage::from_percent(-1.0),
+            Err(ValidationError::PercentOutOfRange {
+                field: "slippage",
+                value: "-1".to_string(),
+            }),
+            "negative should fail"
+        );
+        assert!(Slippage::from_percent(f64::NAN).is_err(), "NaN should fail");
+
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .slippage(Slippage::from_percent(0.5).unwrap())
+            .auto_slippage(Slippage::from_bps(300).unwrap());
+        assert_eq!(request.slippage_bps, Some(50));
+        assert_eq!(request.auto_slippage, Some(true));
+        assert_eq!(request.max_auto_slippage_bps, Some(300));
+
+        let swap = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response())
+            .slippage(Slippage::from_bps(75).unwrap());
+        assert_eq!(
+            swap.quote_response
+                .expect("quote should be attached")
+                .slippage_bps,
+            75
+        );
+    }
+
+    #[test]
+    fn test_swap_request_default() {
+        let swap = SwapRequest::default()
+            .user_public_key(TEST_USER_PUBKEY)
+            .payer(TEST_USER_PUBKEY);
+        assert_eq!(
+            swap.validate(),
+            Err(ValidationError::MissingField("quote_response"))
+        );
+
+        let swap = swap.quote_response(sample_quote_response());
+        assert_eq!(swap.validate(), Ok(()));
+        assert_eq!(
+            swap,
+            SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response())
+        );
+    }
+
+    #[test]
+    fn test_fresh_quote_staleness() {
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+        let mut quote = FreshQuote::new(request, sample_quote_response());
+
+        assert!(
+            !quote.is_stale(Duration::from_secs(10)),
+            "a just-fetched quote should be fresh"
+        );
+
+        quote.fetched_at = Instant::now() - Duration::from_secs(30);
+        assert!(
+            quote.is_stale(Duration::from_secs(10)),
+            "a 30s old quote should be stale"
+        );
+        assert_eq!(quote.request.amount, TEST_AMOUNT, "request should be kept");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_if_stale_keeps_fresh_quote() {
+        // the client points to an invalid endpoint, so any re-quote would fail
+        let client = JupiterClient::new("https://lite-api.jup.ag/invalid");
+        let request = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+        let quote = FreshQuote::new(request, sample_quote_response());
+
+        let refreshed = client
+            .refresh_if_stale(quote, Duration::from_secs(60))
+            .await
+            .expect("fresh quote should be returned without a request");
+
+        assert_eq!(refreshed.quote.out_amount, "400000000");
+    }
+
+    #[test]
+    fn test_quote_response_numeric_helpers() {
+        let quote = sample_quote_response();
+
+        assert_eq!(quote.in_amount_u64(), Some(TEST_AMOUNT));
+        assert_eq!(quote.out_amount_u64(), Some(400_000_000));
+        assert_eq!(quote.min_out_amount_u64(), Some(396_000_000));
+        assert_eq!(quote.max_in_amount_u64(), Some(TEST_AMOUNT));
+        assert_eq!(quote.price_impact_pct_f64(), Some(0.0012));
+
+        // 1 SOL (9 decimals) -> 400 JUP (6 decimals)
+        assert_eq!(quote.effective_price(9, 6), Some(400.0));
+    }
+
+    #[test]
+    fn test_fee_breakdown() {
+        let mut quote = sample_quote_response();
+        quote.platform_fee = Some(PlatformFee {
+            amount: "200000".to_string(),
+            fee_bps: 5,
+        });
+
+        let fees = quote.fee_breakdown();
+
+        assert_eq!(fees.hop_count(), 2);
+        assert_eq!(fees.hops[0].label, "Whirlpool");
+        assert_eq!(fees.hops[0].fee_amount, 300_000);
+        assert_eq!(fees.hops[0].fee_bps, Some(3.0));
+        assert_eq!(fees.lp_fees_by_mint.get(SOL_MINT), Some(&300_000));
+        assert_eq!(fees.platform_fee_mint.as_deref(), Some(JUP_MINT));
+
+        let totals = fees.total_fees_by_mint();
+        assert_eq!(totals.get(JUP_MINT), Some(&200_000));
+        assert_eq!(totals.len(), 3, "SOL, USDC and JUP fees expected");
+    }
+
+    #[test]
+    fn test_route_visualization() {
+        let quote = sample_quote_response();
+
+        let route = quote.format_route();
+        assert_eq!(route.lines().count(), 2);
+        assert!(route.starts_with("1. Whirlpool So11..1112 -> EPjF..Dt1v (100%)"));
+
+        let dot = quote.route_to_dot();
+        assert!(dot.starts_with("digraph route {"));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [label=\"2. Meteora DLMM (100%)\"];",
+            USDC_MINT, JUP_MINT
+        )));
+
+        let mermaid = quote.route_to_mermaid();
+        assert!(mermaid.starts_with("graph LR"));
+        assert!(mermaid.contains("m0 -->|\"1. Whirlpool (100%)\"| m1"));
+        assert!(mermaid.contains("m1 -->|\"2. Meteora DLMM (100%)\"| m2"));
+
+        let mut quote = quote;
+        quote.route_plan[1].swap_info.label = "Pool \"X\"".to_string();
+        assert!(
+            quote
+                .route_to_dot()
+                .contains("[label=\"2. Pool \\\"X\\\" (100%)\"]")
+        );
+        assert!(
+            quote
+                .route_to_mermaid()
+                .contains("|\"2. Pool #quot;X#quot; (100%)\"|")
+        );
+
+        quote.route_plan[1].swap_info.output_mint = "ééééééééééééé".to_string();
+        assert!(quote.format_route().contains("-> éééé..éééé (100%)"));
+    }
+
+    #[test]
+    fn test_quote_response_to_quote_request() {
+        let mut quote = sample_quote_response();
+        quote.platform_fee = Some(PlatformFee {
+            amount: "200000".to_string(),
+            fee_bps: 5,
+        });
+
+        let request = quote
+            .to_quote_request()
+            .expect("sample quote amounts should parse");
+
+        assert_eq!(request.input_mint, SOL_MINT);
+        assert_eq!(request.output_mint, JUP_MINT);
+        assert_eq!(request.amount, TEST_AMOUNT);
+        assert_eq!(request.slippage_bps, Some(DEFAULT_SLIPPAGE_BPS));
+        assert_eq!(request.platform_fee_bps, Some(5));
+        assert!(matches!(
+            request.swap_mode,
+            Some(QuoteGetSwapModeEnum::ExactIn)
+        ));
+    }
+
+    #[test]
+    fn test_quote_response_display() {
+        let mut quote = sample_quote_response();
+        assert_eq!(
+            quote.to_string(),
+            "1000000000 So11..1112 -> 400000000 JUPy..DvCN (min out 396000000), impact 0.0012%, slippage 100 bps, 2 hops"
+        );
+
+        quote.swap_mode = QuoteGetSwapModeEnum::ExactOut;
+        quote.route_plan.truncate(1);
+        assert!(quote.to_string().contains("(max in 396000000)"), "{quote}");
+        assert!(quote.to_string().ends_with(", 1 hop"), "{quote}");
+    }
+
+    #[test]
+    fn test_quote_types_clone_and_compare() {
+        let quote = sample_quote_response();
+        let mut snapshot = quote.clone();
+        assert_eq!(snapshot, quote);
+
+        snapshot.out_amount = "1".to_string();
+        assert_ne!(snapshot, quote);
+
+        let request = create_default_quote_request();
+        assert_eq!(request.clone(), request);
+        assert_eq!(
+            quote.to_quote_request().unwrap().swap_mode,
+            Some(QuoteGetSwapModeEnum::ExactIn)
+        );
+    }
+
+    #[test]
+    fn test_pubkey_typed_constructors() {
+        use jup_ag_sdk::pubkey::{Pubkey, base58, parse_pubkey};
+
+        let sol: Pubkey = parse_pubkey("input_mint", SOL_MINT).expect("valid mint");
+        let jup: Pubkey = JUP_MINT.parse().unwrap();
+        assert_eq!(
+            parse_pubkey("input_mint", "not-a-mint"),
+            Err(ValidationError::InvalidAddress {
+                field: "input_mint",
+                value: "not-a-mint".to_string(),
+            })
+        );
+
+        assert_eq!(
+            QuoteRequest::from_pubkeys(&sol, &jup, TEST_AMOUNT),
+            QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+        );
+
+        let quote = sample_quote_response();
+        assert_eq!(quote.input_mint_pubkey(), Ok(sol));
+        assert_eq!(quote.output_mint_pubkey(), Ok(jup));
+
+        let json = base58::serialize(&sol, serde_json::value::Serializer).unwrap();
+        assert_eq!(json, serde_json::json!(SOL_MINT));
+        assert_eq!(base58::deserialize(json).unwrap(), sol);
+        assert!(base58::deserialize(serde_json::json!("0OIl")).is_err());
+        assert_eq!(
+            base58::option::deserialize(serde_json::Value::Null).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_quote_response_unknown_fields() {
+        let mut json: serde_json::Value = serde_json::from_str(SAMPLE_QUOTE_JSON).unwrap();
+        json["futureField"] = serde_json::json!({"nested": true});
+        json["routePlan"][0]["swapInfo"]["ammVersion"] = serde_json::json!(2);
+
+        let quote = serde_json::from_value::<QuoteResponse>(json.clone());
+        if cfg!(feature = "strict-serde") {
+            assert!(quote.is_err(), "strict mode should reject unknown fields");
+            return;
+        }
+
+        let quote = quote.expect("unknown fields should be tolerated");
+        assert_eq!(quote.extra["futureField"], json["futureField"]);
+        assert_eq!(
+            quote.route_plan[0].swap_info.extra["ammVersion"],
+            serde_json::json!(2)
+        );
+
+        let sent = serde_json::to_value(&quote).unwrap();
+        assert_eq!(sent["futureField"], json["futureField"]);
+        assert_eq!(sent["routePlan"][0]["swapInfo"]["ammVersion"], 2);
+    }
+
+    #[test]
+    fn test_best_quote_selection() {
+        let base = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT);
+        let variants = QuoteVariant::defaults(&base);
+        assert_eq!(variants.len(), 4);
+        assert_eq!(variants[1].request.only_direct_routes, Some(true));
+
+        let mut better = sample_quote_response();
+        better.out_amount = "410000000".to_string();
+        let results: Vec<Result<QuoteResponse, String>> = vec![
+            Ok(sample_quote_response()),
+            Ok(better),
+            Err("no route found".to_string()),
+            Ok(sample_quote_response()),
+        ];
+
+        let best = BestQuote::select(&variants, results).expect("a variant succeeded");
+        assert_eq!(best.variant, "direct-routes-only");
+        assert_eq!(best.quote.out_amount, "410000000");
+        assert_eq!(best.reports.len(), 4);
+        assert_eq!(best.reports[2].error.as_deref(), Some("no route found"));
+        assert_eq!(best.reports[0].out_amount, Some(400_000_000));
+    }
+
+    #[test]
+    fn test_quote_comparison() {
+        let mut exact_out = sample_quote_response();
+        exact_out.in_amount = "1010000000".to_string();
+        exact_out.swap_mode = QuoteGetSwapModeEnum::ExactOut;
+
+        let cmp = QuoteComparison {
+            exact_in: sample_quote_response(),
+            exact_out,
+        };
+
+        assert_eq!(cmp.exact_in_price(), Some(0.4));
+        let diff = cmp.price_difference_pct().expect("prices should parse");
+        assert!((diff - 1.0).abs() < 1e-9, "exact in should be 1% better");
+        assert_eq!(cmp.exact_in_price_impact_pct(), Some(0.0012));
+        assert_eq!(cmp.exact_in_route(), vec!["Whirlpool", "Meteora DLMM"]);
+        assert!(cmp.same_route(), "routes use the same amm keys");
+    }
+
+    #[test]
+    fn test_quote_ladder_impact() {
+        let mut large = sample_quote_response();
+        large.in_amount = "10000000000".to_string();
+        large.out_amount = "3960000000".to_string();
+
+        let ladder =
+            QuoteLadder::from_quotes(SOL_MINT, JUP_MINT, &[large, sample_quote_response()]);
+
+        assert_eq!(ladder.points.len(), 2);
+        assert_eq!(
+            ladder.points[0].size, TEST_AMOUNT,
+            "points should be sorted"
+        );
+        assert_eq!(ladder.best_price(), Some(0.4));
+
+        let curve = ladder.impact_curve();
+        assert!(
+            (curve[1].1 - 1.0).abs() < 1e-9,
+            "10 SOL should have 1% impact"
+        );
+        assert_eq!(ladder.max_size_within_impact(0.5), Some(TEST_AMOUNT));
+        assert_eq!(ladder.max_size_within_impact(2.0), Some(10_000_000_000));
+    }
+
     #[tokio::test]
     async fn test_get_quote_successful() {
         let client = create_test_client();
@@ -115,6 +500,122 @@ mod swap_tests {
         assert!(result.is_err(), "Quote with invalid endpoint should fail");
     }
 
+    #[tokio::test]
+    async fn test_get_quotes_returns_per_request_results() {
+        let invalid_client = JupiterClient::new("https://lite-api.jup.ag/invalid");
+        let requests = vec![
+            create_default_quote_request(),
+            QuoteRequest::new(SOL_MINT, USDC_MINT, TEST_AMOUNT),
+            QuoteRequest::new(USDC_MINT, JUP_MINT, TEST_AMOUNT),
+        ];
+
+        let results = invalid_client
+            .get_quotes_with_concurrency(&requests, 2)
+            .await;
+
+        assert_eq!(results.len(), requests.len(), "one result per request");
+        assert!(results.iter().all(|res| res.is_err()));
+    }
+
+    #[tokio::test]
+    async fn test_get_quotes_respects_rate_limiter() {
+        let (url, requests) = mock_server_sequence(vec![SAMPLE_QUOTE_JSON]).await;
+        let client = JupiterClient::new(&url)
+            .with_rate_limiter(RateLimiter::new(2, Duration::from_millis(200)));
+        let quotes = vec![QuoteRequest::new(SOL_MINT, USDC_MINT, TEST_AMOUNT); 4];
+
+        let started = Instant::now();
+        let results = client.get_quotes(&quotes).await;
+
+        assert!(results.iter().all(|res| res.is_ok()));
+        assert_eq!(requests.lock().unwrap().len(), 4);
+        // two requests right away, then one every 100ms
+        assert!(started.elapsed() >= Duration::from_millis(190));
+    }
+
+    #[tokio::test]
+    async fn test_quote_ladder_keeps_successful_sizes() {
+        const BAD_REQUEST: &str =
+            "HTTP/1.1 400 Bad Request\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+        let quote = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            SAMPLE_QUOTE_JSON.len(),
+            SAMPLE_QUOTE_JSON
+        );
+        let sizes = [TEST_AMOUNT, 2 * TEST_AMOUNT, 3 * TEST_AMOUNT];
+
+        // the first request to arrive fails, the others get a quote
+        let (url, requests, _) = mock_http_server(vec![BAD_REQUEST.to_string(), quote]).await;
+        let ladder = JupiterClient::new(&url)
+            .quote_ladder(SOL_MINT, JUP_MINT, &sizes)
+            .await
+            .expect("one failed size should not fail the ladder");
+        assert_eq!(requests.lock().unwrap().len(), 3);
+        assert_eq!(ladder.points.len(), 2);
+        assert_eq!(ladder.failed_sizes.len(), 1);
+        assert!(sizes.contains(&ladder.failed_sizes[0]));
+
+        let (url, _, _) = mock_http_server(vec![BAD_REQUEST.to_string()]).await;
+        let result = JupiterClient::new(&url)
+            .quote_ladder(SOL_MINT, JUP_MINT, &sizes)
+            .await;
+        assert!(
+            matches!(result, Err(JupiterClientError::ApiError(_, status)) if status == 400),
+            "a ladder with no quote should fail"
+        );
+    }
+
+    #[test]
+    fn test_quote_request_validation() {
+        assert!(create_default_quote_request().validate().is_ok());
+
+        let same_mint = QuoteRequest::new(SOL_MINT, SOL_MINT, TEST_AMOUNT);
+        assert_eq!(same_mint.validate(), Err(ValidationError::SameMint));
+
+        let zero = QuoteRequest::new(SOL_MINT, JUP_MINT, 0);
+        assert_eq!(zero.validate(), Err(ValidationError::ZeroAmount("amount")));
+
+        let bad_mint = QuoteRequest::new(SOL_MINT, "not-a-mint", TEST_AMOUNT);
+        assert!(matches!(
+            bad_mint.validate(),
+            Err(ValidationError::InvalidAddress {
+                field: "output_mint",
+                ..
+            })
+        ));
+
+        let slippage = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).slippage_bps(20_000);
+        assert!(matches!(
+            slippage.validate(),
+            Err(ValidationError::BpsOutOfRange {
+                field: "slippage_bps",
+                ..
+            })
+        ));
+
+        let overlap = QuoteRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .dexes(vec![DexEnum::Whirlpool, DexEnum::Raydium])
+            .exclude_dexes(vec![DexEnum::Raydium]);
+        assert_eq!(
+            overlap.validate(),
+            Err(ValidationError::OverlappingDexes(vec![
+                "Raydium".to_string()
+            ]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_quote_rejects_invalid_request() {
+        let client = create_test_client();
+        let invalid_quote = QuoteRequest::new(SOL_MINT, JUP_MINT, 0);
+
+        let result = client.get_quote(&invalid_quote).await;
+        assert!(
+            matches!(result, Err(JupiterClientError::ValidationError(_))),
+            "zero amount should be rejected before sending"
+        );
+    }
+
     #[tokio::test]
     async fn test_get_quote_with_invalid_params() {
         let client = create_test_client();
@@ -137,12 +638,10 @@ mod swap_tests {
                     swap.user_public_key, TEST_USER_PUBKEY,
                     "user public key should match"
                 );
+                let quote = swap.quote_response.expect("quote should be attached");
+                assert_eq!(quote.input_mint, SOL_MINT, "input mint should match");
                 assert_eq!(
-                    swap.quote_response.input_mint, SOL_MINT,
-                    "input mint should match"
-                );
-                assert_eq!(
-                    swap.quote_response.out_amount,
+                    quote.out_amount,
                     TEST_AMOUNT.to_string(),
                     "output amount should match"
                 );
@@ -175,4 +674,845 @@ mod swap_tests {
             Err(err) => panic!("Failed to get swap transaction: {:?}", err),
         }
     }
+
+    #[test]
+    fn test_simulation_result_deserialization() {
+        let json = r#"{
+            "context": { "slot": 345000001 },
+            "value": {
+                "err": { "InstructionError": [3, { "Custom": 6001 }] },
+                "logs": ["Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed"],
+                "unitsConsumed": 182345
+            }
+        }"#;
+
+        let response: RpcResponse<SimulationResult> =
+            serde_json::from_str(json).expect("simulation response should deserialize");
+        let simulation = response.value;
+
+        assert!(!simulation.is_success());
+        assert_eq!(simulation.units_consumed, Some(182_345));
+        assert!(matches!(
+            simulation.err,
+            Some(SimulationError::Transaction(
+                TransactionError::InstructionError(3, _)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_swap_rejects_invalid_transaction() {
+        let client = create_test_client();
+        let rpc = SolanaRpc::new("http://127.0.0.1:8899");
+        let swap = SwapResponse {
+            swap_transaction: "not a transaction".to_string(),
+            last_valid_block_height: 0,
+            prioritization_fee_lamports: 0,
+        };
+
+        let res = client.simulate_swap(&swap, &rpc).await;
+        assert!(
+            matches!(res, Err(JupiterClientError::DeserializationError(_))),
+            "invalid base64 should fail before calling the rpc"
+        );
+    }
+
+    #[test]
+    fn test_signature_status_satisfies_commitment() {
+        let json = r#"{
+            "context": { "slot": 360 },
+            "value": [
+                { "slot": 350, "confirmations": 4, "err": null, "confirmationStatus": "confirmed" },
+                null
+            ]
+        }"#;
+
+        let response: RpcResponse<Vec<Option<SignatureStatus>>> =
+            serde_json::from_str(json).expect("failed to deserialize statuses");
+        let status = response.value[0].as_ref().expect("status present");
+
+        assert_eq!(status.slot, 350);
+        assert!(status.satisfies(CommitmentLevel::Processed));
+        assert!(status.satisfies(CommitmentLevel::Confirmed));
+        assert!(!status.satisfies(CommitmentLevel::Finalized));
+        assert!(response.value[1].is_none());
+    }
+
+    #[test]
+    fn test_swap_and_send_options_defaults() {
+        let options = SwapAndSendOptions::default();
+        assert_eq!(options.commitment, CommitmentLevel::Confirmed);
+        assert!(!options.skip_preflight);
+        assert!(options.dynamic_compute_unit_limit);
+
+        let options = options
+            .commitment(CommitmentLevel::Finalized)
+            .skip_preflight(true)
+            .timeout(Duration::from_secs(5));
+        assert_eq!(options.commitment, CommitmentLevel::Finalized);
+        assert!(options.skip_preflight);
+        assert_eq!(options.timeout, Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_swap_and_send_rejects_invalid_request() {
+        let client = create_test_client();
+        let rpc = SolanaRpc::new("http://127.0.0.1:8899");
+        let keypair = Keypair::new();
+        let quote = QuoteRequest::new(SOL_MINT, SOL_MINT, TEST_AMOUNT);
+
+        let res = client
+            .swap_and_send(&quote, &keypair, &rpc, SwapAndSendOptions::default())
+            .await;
+        assert!(
+            matches!(
+                res,
+                Err(JupiterClientError::ValidationError(
+                    ValidationError::SameMint
+                ))
+            ),
+            "invalid request should fail before quoting"
+        );
+    }
+
+    #[test]
+    fn test_resolve_program_ids_into_exclude_dexes() {
+        let raydium = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+        let dlmm = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+        let labels: HashMap<String, String> = [
+            (raydium.to_string(), "Raydium".to_string()),
+            (dlmm.to_string(), "Meteora DLMM".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        let request = create_default_quote_request()
+            .exclude_dexes(vec![DexEnum::Raydium])
+            .exclude_program_ids(vec![raydium.to_string(), dlmm.to_string()]);
+        assert!(request.validate().is_ok());
+
+        let query = serde_json::to_value(&request).expect("failed to serialize request");
+        assert!(query.get("excludeProgramIds").is_none());
+
+        let resolved = request
+            .resolve_program_ids(&labels)
+            .expect("known program ids should resolve");
+        assert!(resolved.exclude_program_ids.is_none());
+        assert_eq!(
+            resolved.exclude_dexes,
+            Some(vec![DexEnum::Raydium, DexEnum::MeteoraDlmm])
+        );
+
+        let labels_with_new_dex: HashMap<String, String> =
+            [(JUP_MINT.to_string(), "Brand New AMM".to_string())]
+                .into_iter()
+                .collect();
+        let resolved = create_default_quote_request()
+            .exclude_program_ids(vec![JUP_MINT.to_string()])
+            .resolve_program_ids(&labels_with_new_dex)
+            .expect("unknown labels should resolve to DexEnum::Other");
+        assert_eq!(
+            resolved.exclude_dexes,
+            Some(vec![DexEnum::Other("Brand New AMM".to_string())])
+        );
+
+        let unknown = create_default_quote_request()
+            .exclude_program_ids(vec![SOL_MINT.to_string()])
+            .resolve_program_ids(&labels);
+        assert!(matches!(
+            unknown,
+            Err(JupiterClientError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_dex_from_label() {
+        assert_eq!(
+            DexEnum::from_label("Meteora DLMM"),
+            Some(DexEnum::MeteoraDlmm)
+        );
+        assert_eq!(
+            DexEnum::from_label("Pump.fun Amm"),
+            Some(DexEnum::PumpFunAmm)
+        );
+        assert_eq!(DexEnum::from_label("Not A Dex"), None);
+        assert!(
+            DexEnum::ALL
+                .iter()
+                .all(|dex| DexEnum::from_label(&dex.to_string()).as_ref() == Some(dex))
+        );
+    }
+
+    #[test]
+    fn test_dex_enum_parsing_and_program_ids() {
+        let dex: DexEnum = "Raydium CLMM".parse().unwrap();
+        assert_eq!(dex, DexEnum::RaydiumClmm);
+        let dex: DexEnum = "RaydiumClmm".parse().unwrap();
+        assert_eq!(dex, DexEnum::RaydiumClmm);
+
+        let dexes: Vec<DexEnum> =
+            serde_json::from_str(r#"["Meteora DLMM", "Brand New AMM"]"#).unwrap();
+        assert_eq!(
+            dexes,
+            vec![
+                DexEnum::MeteoraDlmm,
+                DexEnum::Other("Brand New AMM".to_string())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&dexes).unwrap(),
+            r#"["Meteora DLMM","Brand New AMM"]"#
+        );
+
+        let request = create_default_quote_request()
+            .exclude_dexes(vec![DexEnum::Other("Brand New AMM".to_string())]);
+        let query = serde_json::to_value(&request).unwrap();
+        assert_eq!(query["excludeDexes"], "Brand New AMM");
+
+        assert_eq!(
+            DexEnum::Whirlpool.program_id(),
+            Some("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc")
+        );
+        assert_eq!(
+            DexEnum::from_program_id("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8"),
+            Some(DexEnum::Raydium)
+        );
+        assert_eq!(DexEnum::Other("x".to_string()).program_id(), None);
+        assert!(
+            DexEnum::ALL
+                .iter()
+                .all(
+                    |dex| DexEnum::from_program_id(dex.program_id().unwrap()).as_ref() == Some(dex)
+                )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_program_id_to_label() {
+        let client = create_test_client();
+        let labels = client
+            .get_program_id_to_label()
+            .await
+            .expect("Failed to get program id to label map");
+
+        assert!(!labels.is_empty(), "map should not be empty");
+        assert!(
+            labels.values().any(|label| label == "Whirlpool"),
+            "map should contain Whirlpool"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_quote_reuses_cached_program_labels() {
+        const LABELS: &str = r#"{"675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8":"Raydium"}"#;
+        let request = create_default_quote_request().exclude_program_ids(vec![
+            "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8".to_string(),
+        ]);
+
+        let (url, requests) = mock_server_sequence(vec![LABELS, SAMPLE_QUOTE_JSON]).await;
+        let client = JupiterClient::new(&url);
+        for _ in 0..2 {
+            client
+                .get_quote(&request)
+                .await
+                .expect("quote should succeed");
+        }
+        // one label map, then one request per quote
+        assert_eq!(requests.lock().unwrap().len(), 3);
+
+        let (url, requests) =
+            mock_server_sequence(vec![LABELS, SAMPLE_QUOTE_JSON, LABELS, SAMPLE_QUOTE_JSON]).await;
+        let client = JupiterClient::new(&url).with_program_labels_cache_ttl(Duration::ZERO);
+        for _ in 0..2 {
+            client
+                .get_quote(&request)
+                .await
+                .expect("quote should succeed");
+        }
+        assert_eq!(requests.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_signature_outcomes() {
+        const UNSEEN: &str =
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":[null]}}"#;
+        const CONFIRMED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":9},"value":[{"slot":8,"confirmations":1,"err":null,"confirmationStatus":"confirmed"}]}}"#;
+        const FINALIZED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":9},"value":[{"slot":8,"confirmations":null,"err":null,"confirmationStatus":"finalized"}]}}"#;
+        const FAILED: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":9},"value":[{"slot":8,"confirmations":1,"err":{"InstructionError":[0,{"Custom":1}]},"confirmationStatus":"confirmed"}]}}"#;
+        const HEIGHT: &str = r#"{"jsonrpc":"2.0","id":1,"result":100}"#;
+        const EXPIRED_HEIGHT: &str = r#"{"jsonrpc":"2.0","id":1,"result":251}"#;
+        const RPC_ERROR: &str =
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"Node is behind"}}"#;
+        let timeout = Duration::from_secs(30);
+
+        let confirm = |responses: Vec<&'static str>, commitment, timeout| async move {
+            let (url, requests) = mock_server_sequence(responses).await;
+            let outcome = confirm_signature(&SolanaRpc::new(&url), "sig", commitment, timeout)
+                .await
+                .expect("rpc should answer");
+            (outcome, requests.lock().unwrap().len())
+        };
+
+        let (outcome, calls) = confirm(
+            vec![UNSEEN, HEIGHT, CONFIRMED],
+            CommitmentLevel::Confirmed,
+            timeout,
+        )
+        .await;
+        assert!(matches!(&outcome, Confirmation::Confirmed(status) if status.slot == 8));
+        assert!(outcome.is_success());
+        assert_eq!(calls, 3);
+
+        let (outcome, _) = confirm(vec![FINALIZED], CommitmentLevel::Confirmed, timeout).await;
+        assert!(matches!(outcome, Confirmation::Finalized(_)));
+
+        let (outcome, _) = confirm(vec![FAILED], CommitmentLevel::Confirmed, timeout).await;
+        assert!(!outcome.is_success());
+        assert!(matches!(
+            outcome.into_result("sig"),
+            Err(JupiterClientError::TransactionFailed { signature, reason })
+                if signature == "sig" && reason.contains("InstructionError")
+        ));
+
+        // 150 blocks past the first poll without being seen
+        let (outcome, _) = confirm(
+            vec![UNSEEN, HEIGHT, UNSEEN, EXPIRED_HEIGHT],
+            CommitmentLevel::Confirmed,
+            timeout,
+        )
+        .await;
+        assert!(matches!(outcome, Confirmation::Dropped));
+        assert!(outcome.status().is_none());
+
+        let (outcome, _) =
+            confirm(vec![CONFIRMED], CommitmentLevel::Finalized, Duration::ZERO).await;
+        assert!(matches!(outcome, Confirmation::TimedOut));
+
+        // a transient RPC error is polled through
+        let (outcome, calls) = confirm(
+            vec![RPC_ERROR, UNSEEN, RPC_ERROR, CONFIRMED],
+            CommitmentLevel::Confirmed,
+            timeout,
+        )
+        .await;
+        assert!(matches!(outcome, Confirmation::Confirmed(_)));
+        assert_eq!(calls, 4);
+
+        let (url, _) = mock_server_sequence(vec![RPC_ERROR]).await;
+        let result = confirm_signature(
+            &SolanaRpc::new(&url),
+            "sig",
+            CommitmentLevel::Confirmed,
+            Duration::from_millis(600),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(JupiterClientError::RpcError(_))),
+            "the last error is returned when no poll succeeds"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_broadcaster_first_success() {
+        let (accepting, accepted) =
+            mock_server_sequence(vec![r#"{"jsonrpc":"2.0","id":1,"result":"sig"}"#]).await;
+        let (refusing, _) = mock_server_sequence(vec![
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32002,"message":"Blockhash not found"}}"#,
+        ])
+        .await;
+
+        let broadcaster = Broadcaster::from_urls([&refusing, &accepting, "http://127.0.0.1:1"])
+            .skip_preflight(true);
+        let sent = broadcaster
+            .send("dHg=")
+            .await
+            .expect("one endpoint accepts");
+        assert_eq!(sent.signature, "sig");
+        assert_eq!(sent.first_endpoint, accepting);
+        assert_eq!(sent.results.len(), 3);
+        assert_eq!(sent.accepted(), 1);
+        assert!(sent.results.iter().any(|r| matches!(
+            &r.result,
+            Err(JupiterClientError::RpcError(msg)) if msg.contains("Blockhash not found")
+        )));
+
+        let request: serde_json::Value =
+            serde_json::from_str(&accepted.lock().unwrap()[0]).unwrap();
+        assert_eq!(request["method"], "sendTransaction");
+        assert_eq!(request["params"][0], "dHg=");
+        assert_eq!(request["params"][1]["skipPreflight"], true);
+
+        let first = broadcaster.send_first("dHg=").await.unwrap();
+        assert_eq!(first.signature, "sig");
+        assert!(first.results.last().unwrap().result.is_ok());
+
+        let failing = Broadcaster::from_urls([&refusing, "http://127.0.0.1:1"]);
+        let Err(JupiterClientError::BroadcastFailed(results)) = failing.send("dHg=").await else {
+            panic!("every endpoint refused the transaction");
+        };
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().any(
+            |r| r.url == refusing && matches!(&r.result, Err(JupiterClientError::RpcError(_)))
+        ));
+        assert!(results.iter().any(|r| r.url == "http://127.0.0.1:1"
+            && matches!(&r.result, Err(JupiterClientError::RequestError(_)))));
+        assert!(matches!(
+            Broadcaster::new([]).send("dHg=").await,
+            Err(JupiterClientError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_jito_bundle_with_tip() {
+        use jup_ag_sdk::{codec, introspection::TransactionSummary};
+        use solana_signer::Signer;
+
+        let keypair = Keypair::new();
+        let swap = crate::common::unsigned_transaction_base64(
+            &keypair.pubkey(),
+            &[solana_pubkey::Pubkey::new_unique()],
+        );
+        let (refusing, _) = mock_server_sequence(vec![
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"bundle rejected"}}"#,
+        ])
+        .await;
+        let (accepting, requests) =
+            mock_server_sequence(vec![r#"{"jsonrpc":"2.0","id":1,"result":"bundle-id"}"#]).await;
+
+        let jito = JitoClient::with_block_engines([&refusing, &accepting]);
+        assert!(jito.block_engines[1].url.ends_with("/api/v1/bundles"));
+        let bundle_id = jito
+            .send_with_tip(std::slice::from_ref(&swap), &keypair, 10_000)
+            .await
+            .expect("one block engine accepts");
+        assert_eq!(bundle_id, "bundle-id");
+
+        let request: serde_json::Value =
+            serde_json::from_str(&requests.lock().unwrap()[0]).unwrap();
+        assert_eq!(request["method"], "sendBundle");
+        assert_eq!(request["params"][1]["encoding"], "base64");
+        let bundle = request["params"][0].as_array().unwrap();
+        assert_eq!(bundle.len(), 2);
+        assert_eq!(bundle[0], swap.as_str());
+
+        let tip = codec::decode_base64(bundle[1].as_str().unwrap()).unwrap();
+        assert_eq!(
+            tip.message.recent_blockhash(),
+            codec::decode_base64(&swap)
+                .unwrap()
+                .message
+                .recent_blockhash()
+        );
+        assert!(tip.signatures[0].verify(keypair.pubkey().as_ref(), &tip.message.serialize()));
+        let summary = TransactionSummary::from_transaction(&tip);
+        assert_eq!(summary.sol_sent_by(&keypair.pubkey().to_string()), 10_000);
+        let recipient = summary.transfers[0].destination.clone().unwrap();
+        assert!(JITO_TIP_ACCOUNTS.contains(&recipient.as_str()));
+
+        assert!(matches!(
+            jito.send_with_tip(std::slice::from_ref(&swap), &keypair, 10)
+                .await,
+            Err(JupiterClientError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            jito.send_bundle(&vec![swap; 6]).await,
+            Err(JupiterClientError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            JitoClient::new(&refusing).send_bundle(&[bundle[1].as_str().unwrap().to_string()]).await,
+            Err(JupiterClientError::RpcError(msg)) if msg.contains("bundle rejected")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_jito_tip_inside_transaction() {
+        use jup_ag_sdk::{codec, introspection::TransactionSummary};
+        use solana_signer::Signer;
+
+        let keypair = Keypair::new();
+        let program = solana_pubkey::Pubkey::new_unique();
+        let swap = crate::common::unsigned_transaction_base64(&keypair.pubkey(), &[program]);
+        let original = codec::decode_base64(&swap).unwrap();
+
+        let tipped = codec::decode_base64(&add_tip_base64(&swap, 10_000).unwrap()).unwrap();
+        let instructions = tipped.message.instructions();
+        assert_eq!(instructions.len(), 2, "the tip is appended to the swap");
+        let keys = |tx: &codec::VersionedTransaction, index: usize| {
+            let instruction = &tx.message.instructions()[index];
+            let static_keys = tx.message.static_account_keys();
+            std::iter::once(instruction.program_id_index)
+                .chain(instruction.accounts.iter().copied())
+                .map(|account| static_keys[usize::from(account)])
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keys(&tipped, 0),
+            keys(&original, 0),
+            "swap accounts are kept"
+        );
+        for (index, key) in tipped.message.static_account_keys().iter().enumerate() {
+            let was_writable = original
+                .message
+                .static_account_keys()
+                .iter()
+                .position(|original_key| original_key == key)
+                .map(|original_index| original.message.is_maybe_writable(original_index, None));
+            if let Some(was_writable) = was_writable {
+                assert_eq!(tipped.message.is_maybe_writable(index, None), was_writable);
+            }
+        }
+
+        let summary = TransactionSummary::from_transaction(&tipped);
+        assert_eq!(summary.sol_sent_by(&keypair.pubkey().to_string()), 10_000);
+        let recipient = summary.transfers[0].destination.clone().unwrap();
+        assert!(JITO_TIP_ACCOUNTS.contains(&recipient.as_str()));
+        assert!(summary.writable_accounts.contains(&recipient));
+
+        let (url, requests) =
+            mock_server_sequence(vec![r#"{"jsonrpc":"2.0","id":1,"result":"bundle-id"}"#]).await;
+        let bundle_id = JitoClient::new(&url)
+            .send_tipped(&swap, &keypair, 10_000)
+            .await
+            .expect("block engine accepts");
+        assert_eq!(bundle_id, "bundle-id");
+        let request: serde_json::Value =
+            serde_json::from_str(&requests.lock().unwrap()[0]).unwrap();
+        let bundle = request["params"][0].as_array().unwrap();
+        assert_eq!(bundle.len(), 1, "no separate tip transaction");
+        let sent = codec::decode_base64(bundle[0].as_str().unwrap()).unwrap();
+        assert!(sent.signatures[0].verify(keypair.pubkey().as_ref(), &sent.message.serialize()));
+        assert_eq!(sent.message, tipped.message);
+
+        assert!(matches!(
+            add_tip_base64(&swap, 10),
+            Err(JupiterClientError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            add_tip_base64(bundle[0].as_str().unwrap(), 10_000),
+            Err(JupiterClientError::InvalidRequest(msg)) if msg.contains("signed")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recent_fee_estimator() {
+        const FEES: &str = r#"{"jsonrpc":"2.0","id":1,"result":[
+            {"slot":1,"prioritizationFee":0},{"slot":2,"prioritizationFee":400},
+            {"slot":3,"prioritizationFee":100},{"slot":4,"prioritizationFee":300},
+            {"slot":5,"prioritizationFee":200}]}"#;
+        let (url, requests) = mock_server(FEES).await;
+        let estimator = RecentFeeEstimator::new(SolanaRpc::new(&url));
+
+        // 75th percentile of [0, 100, 200, 300, 400]
+        let fee = estimator.estimate(&[]).await.unwrap();
+        assert_eq!(fee.unit_price_micro_lamports, 300);
+        assert_eq!(
+            estimator.clone().percentile(0).estimate(&[]).await.unwrap(),
+            PriorityFeeEstimate::default()
+        );
+        let capped = estimator
+            .clone()
+            .percentile(100)
+            .max_micro_lamports(250)
+            .estimate(&[])
+            .await
+            .unwrap();
+        assert_eq!(capped.unit_price_micro_lamports, 250);
+        let floored = estimator
+            .clone()
+            .percentile(0)
+            .min_micro_lamports(50)
+            .estimate(&[])
+            .await
+            .unwrap();
+        assert_eq!(floored.unit_price_micro_lamports, 50);
+
+        // 1_400_000 CU * 300 micro-lamports
+        assert_eq!(fee.fee_lamports(1_400_000), 420);
+        let payload = SwapRequest::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY, sample_quote_response())
+            .compute_unit_price_micro_lamports(fee.unit_price_micro_lamports);
+        assert_eq!(payload.compute_unit_price_micro_lamports, Some(300));
+
+        // the transaction's writable accounts are sent to the RPC node
+        let payer = solana_pubkey::Pubkey::new_unique();
+        let tx = unsigned_transaction_base64(&payer, &[solana_pubkey::Pubkey::new_unique()]);
+        requests.lock().unwrap().clear();
+        let fee = estimator.estimate_for_transaction(&tx).await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&requests.lock().unwrap()[0]).unwrap();
+        assert_eq!(body["method"], "getRecentPrioritizationFees");
+        assert!(
+            body["params"][0]
+                .as_array()
+                .unwrap()
+                .contains(&payer.to_string().into())
+        );
+
+        let rewritten = fee.compute_budget().apply_base64(&tx).unwrap();
+        let summary =
+            jup_ag_sdk::introspection::TransactionSummary::from_base64(&rewritten).unwrap();
+        assert_eq!(summary.compute_budget.unit_price_micro_lamports, Some(300));
+    }
+
+    #[test]
+    fn test_wsol_wrap_and_unwrap_instructions() {
+        use base64::{Engine, engine::general_purpose::STANDARD};
+        use jup_ag_sdk::{
+            introspection::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID,
+                TransactionSummary, TransferKind,
+            },
+            types::{Instruction, WRAPPED_SOL_MINT},
+        };
+        use solana_message::{Message, VersionedMessage};
+        use solana_pubkey::Pubkey;
+        use solana_transaction::versioned::VersionedTransaction;
+
+        fn summarize(instructions: &[Instruction], payer: &Pubkey) -> TransactionSummary {
+            let instructions: Vec<_> = instructions
+                .iter()
+                .map(|ix| solana_instruction::Instruction {
+                    program_id: ix.program_id.parse().unwrap(),
+                    accounts: ix
+                        .accounts
+                        .iter()
+                        .map(|meta| solana_instruction::AccountMeta {
+                            pubkey: meta.pubkey.parse().unwrap(),
+                            is_signer: meta.is_signer,
+                            is_writable: meta.is_writable,
+                        })
+                        .collect(),
+                    data: STANDARD.decode(&ix.data).unwrap(),
+                })
+                .collect();
+            let message = VersionedMessage::Legacy(Message::new(&instructions, Some(payer)));
+            TransactionSummary::from_transaction(&VersionedTransaction {
+                signatures: vec![
+                    Default::default();
+                    message.header().num_required_signatures as usize
+                ],
+                message,
+            })
+        }
+
+        let owner = Pubkey::new_unique();
+        let wsol_mint: Pubkey = WRAPPED_SOL_MINT.parse().unwrap();
+        let ata = associated_token_address(&owner, &wsol_mint);
+        assert!(!ata.is_on_curve(), "an associated token account is a PDA");
+        let token_program: Pubkey = TOKEN_PROGRAM_ID.parse().unwrap();
+        let expected = Pubkey::find_program_address(
+            &[owner.as_ref(), token_program.as_ref(), wsol_mint.as_ref()],
+            &ASSOCIATED_TOKEN_PROGRAM_ID.parse().unwrap(),
+        )
+        .0;
+        assert_eq!(ata, expected);
+
+        // associated token account: create, transfer, sync native ... close
+        let mut instructions = wrap_sol(&owner, 1_000_000);
+        let unwrap = unwrap_sol(&owner, &ata);
+        assert_eq!(STANDARD.decode(&unwrap.data).unwrap(), vec![9]);
+        instructions.push(unwrap);
+        let summary = summarize(&instructions, &owner);
+        assert_eq!(
+            summary.program_ids,
+            vec![
+                ASSOCIATED_TOKEN_PROGRAM_ID.to_string(),
+                SYSTEM_PROGRAM_ID.to_string(),
+                TOKEN_PROGRAM_ID.to_string(),
+            ]
+        );
+        assert_eq!(summary.signers, vec![owner.to_string()]);
+        assert_eq!(summary.transfers.len(), 1);
+        assert_eq!(summary.transfers[0].kind, TransferKind::Sol);
+        assert_eq!(summary.transfers[0].destination, Some(ata.to_string()));
+        assert_eq!(summary.sol_sent_by(&owner.to_string()), 1_000_000);
+        assert_eq!(
+            STANDARD.decode(&instructions[2].data).unwrap(),
+            vec![17],
+            "sync native"
+        );
+
+        // temporary account: funded with the amount and rent when created
+        let temp = Pubkey::new_unique();
+        let instructions = wrap_sol_with_temp_account(&owner, &temp, 1_000_000);
+        let create = STANDARD.decode(&instructions[0].data).unwrap();
+        assert_eq!(&create[..4], &0u32.to_le_bytes());
+        assert_eq!(
+            u64::from_le_bytes(create[4..12].try_into().unwrap()),
+            1_000_000 + TOKEN_ACCOUNT_RENT_LAMPORTS
+        );
+        assert_eq!(&create[20..], token_program.as_ref());
+        let summary = summarize(&instructions, &owner);
+        assert_eq!(summary.signers, vec![owner.to_string(), temp.to_string()]);
+        assert!(summary.writable_accounts.contains(&temp.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_swap_trade_cost() {
+        use base64::{Engine, engine::general_purpose::STANDARD};
+        use jup_ag_sdk::{
+            introspection::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, COMPUTE_BUDGET_PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+            },
+            types::{SIGNATURE_FEE_LAMPORTS, TOKEN_2022_ACCOUNT_RENT_LAMPORTS, TradeCost},
+        };
+        use solana_instruction::{AccountMeta, Instruction};
+        use solana_message::{Message, VersionedMessage};
+        use solana_pubkey::Pubkey;
+        use solana_transaction::versioned::VersionedTransaction;
+
+        let quote = sample_quote_response();
+        let estimate = quote.trade_cost();
+        assert_eq!(estimate.sol_fees_lamports(), SIGNATURE_FEE_LAMPORTS);
+        // LP fees in SOL and USDC, plus the signature fee in SOL
+        let totals = estimate.total_by_mint();
+        assert_eq!(totals[SOL_MINT], 300_000 + SIGNATURE_FEE_LAMPORTS);
+        assert_eq!(totals[USDC_MINT], 45_000);
+
+        let payer = Pubkey::new_unique();
+        let (existing, missing) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (token_2022, temporary) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ata_program: Pubkey = ASSOCIATED_TOKEN_PROGRAM_ID.parse().unwrap();
+        let token_program: Pubkey = TOKEN_PROGRAM_ID.parse().unwrap();
+        let create_with = |account: Pubkey, program: Pubkey| {
+            Instruction::new_with_bytes(
+                ata_program,
+                &[1],
+                vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(account, false),
+                    AccountMeta::new_readonly(payer, false),
+                    AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID.parse().unwrap(), false),
+                    AccountMeta::new_readonly(program, false),
+                ],
+            )
+        };
+        let create = |account: Pubkey| create_with(account, token_program);
+        // CloseAccount: account, destination, owner
+        let close = Instruction::new_with_bytes(
+            token_program,
+            &[9],
+            vec![
+                AccountMeta::new(temporary, false),
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(payer, true),
+            ],
+        );
+        let mut unit_limit = vec![2];
+        unit_limit.extend(200_000u32.to_le_bytes());
+        let mut unit_price = vec![3];
+        unit_price.extend(50_000u64.to_le_bytes());
+        let instructions = [
+            Instruction::new_with_bytes(
+                COMPUTE_BUDGET_PROGRAM_ID.parse().unwrap(),
+                &unit_limit,
+                vec![],
+            ),
+            Instruction::new_with_bytes(
+                COMPUTE_BUDGET_PROGRAM_ID.parse().unwrap(),
+                &unit_price,
+                vec![],
+            ),
+            create(existing),
+            create(missing),
+            create_with(token_2022, TOKEN_2022_PROGRAM_ID.parse().unwrap()),
+            create(temporary),
+            close,
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&payer)));
+        let tx = VersionedTransaction {
+            signatures: vec![Default::default()],
+            message,
+        };
+        let swap = SwapResponse {
+            swap_transaction: STANDARD.encode(bincode::serialize(&tx).unwrap()),
+            last_valid_block_height: 1,
+            prioritization_fee_lamports: 0,
+        };
+
+        let cost = TradeCost::from_swap(&quote, &swap).unwrap();
+        assert_eq!(cost.network_fee_lamports, SIGNATURE_FEE_LAMPORTS);
+        // 200_000 CU * 50_000 micro-lamports
+        assert_eq!(cost.priority_fee_lamports, 10_000);
+        // the temporary account is closed again, its rent refunded
+        assert_eq!(
+            cost.new_token_accounts,
+            vec![
+                existing.to_string(),
+                missing.to_string(),
+                token_2022.to_string()
+            ]
+        );
+        assert_eq!(cost.new_token_2022_accounts, vec![token_2022.to_string()]);
+        assert_eq!(
+            cost.rent_lamports,
+            2 * TOKEN_ACCOUNT_RENT_LAMPORTS + TOKEN_2022_ACCOUNT_RENT_LAMPORTS
+        );
+
+        let (url, requests) = mock_server(
+            r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":[{"lamports":2039280,"data":["","base64"],"owner":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","executable":false,"rentEpoch":0},null,null]}}"#,
+        )
+        .await;
+        let cost = cost
+            .without_existing_accounts(&SolanaRpc::new(&url))
+            .await
+            .unwrap();
+        assert!(requests.lock().unwrap()[0].contains("getMultipleAccounts"));
+        assert_eq!(
+            cost.new_token_accounts,
+            vec![missing.to_string(), token_2022.to_string()]
+        );
+        assert_eq!(cost.new_token_2022_accounts, vec![token_2022.to_string()]);
+        assert_eq!(
+            cost.rent_lamports,
+            TOKEN_ACCOUNT_RENT_LAMPORTS + TOKEN_2022_ACCOUNT_RENT_LAMPORTS
+        );
+        assert_eq!(
+            cost.sol_fees_lamports(),
+            SIGNATURE_FEE_LAMPORTS
+                + 10_000
+                + TOKEN_ACCOUNT_RENT_LAMPORTS
+                + TOKEN_2022_ACCOUNT_RENT_LAMPORTS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_arbitrage_scanner_reports_net_edge() {
+        let quote = |out_amount: &str| -> &'static str {
+            let mut json: serde_json::Value =
+                serde_json::from_str(SAMPLE_QUOTE_JSON).expect("sample should be json");
+            json["outAmount"] = out_amount.into();
+            Box::leak(json.to_string().into_boxed_str())
+        };
+        // first legs of JUP and USDC, then their second legs back to SOL
+        let (url, _) = mock_server_sequence(vec![
+            quote("400000000"),
+            quote("150000000"),
+            quote("1002000000"),
+            quote("999000000"),
+        ])
+        .await;
+
+        let (scanner, _events) =
+            ArbitrageScanner::new(JupiterClient::new(&url), SOL_MINT, 1_000_000_000);
+        let scanner = scanner
+            .mints([SOL_MINT, JUP_MINT, USDC_MINT])
+            .min_edge_bps(10.0)
+            .concurrency(1);
+        assert_eq!(
+            scanner.mints_scanned().collect::<Vec<_>>(),
+            vec![JUP_MINT, USDC_MINT]
+        );
+
+        let events = scanner.scan().await;
+        let [ArbitrageEvent::Opportunity(found)] = events.as_slice() else {
+            panic!("expected one opportunity, got {events:?}");
+        };
+        assert_eq!(found.mint, JUP_MINT);
+        assert_eq!(found.out_amount, 1_002_000_000);
+        // default fixed cost of two signatures
+        assert_eq!(found.net_profit, 1_990_000);
+        assert!((found.edge_bps - 19.9).abs() < 1e-9);
+        assert_eq!(found.second_leg.out_amount, "1002000000");
+    }
 }
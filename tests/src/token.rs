@@ -1,6 +1,26 @@
 #[cfg(test)]
 mod token_tests {
-    use crate::common::{JUP_MINT, SOL_MINT, USDC_MINT, create_test_client};
+    use std::{str::FromStr, time::Duration};
+
+    use futures::TryStreamExt;
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError, ValidationError,
+        cache::{InMemoryTokenCache, TokenCache},
+        client::{DEFAULT_HTTP_CACHE_CAPACITY, MAX_PRICE_IDS},
+        decimals::{
+            Decimal, DecimalsResolver, TokenAmount, parse_ui_amount, to_raw_amount, to_ui_amount,
+        },
+        price_watcher::{PriceEvent, PriceWatcher},
+        token_index::{LiveTokenIndex, TokenIndex},
+        types::{
+            Category, Interval, QuoteRequest, SearchOptions, SearchSort, TokenInfo, TokenTag,
+            UltraOrderRequest,
+        },
+    };
+
+    use crate::common::{
+        JUP_MINT, SOL_MINT, USDC_MINT, create_test_client, mock_http_server, mock_server_sequence,
+    };
 
     #[tokio::test]
     async fn test_get_token_prices() {
@@ -21,6 +41,436 @@ mod token_tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_tokens_price_splits_large_requests() {
+        let (url, requests) = mock_server_sequence(vec![
+            r#"{"mint-a":{"usdPrice":1.5,"blockId":1,"decimals":6,"priceChange24h":null}}"#,
+            r#"{"mint-b":{"usdPrice":2.5,"blockId":1,"decimals":9,"priceChange24h":0.1}}"#,
+        ])
+        .await;
+        let client = JupiterClient::new(&url);
+        let mints: Vec<String> = (0..MAX_PRICE_IDS + 10)
+            .map(|i| format!("mint-{i}"))
+            .collect();
+
+        let prices = client
+            .get_tokens_price(&mints)
+            .await
+            .expect("failed to get token prices");
+
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices["mint-a"].usd_price, 1.5);
+        assert_eq!(prices["mint-b"].decimals, 9);
+
+        let empty = client
+            .get_tokens_price(&[])
+            .await
+            .expect("no mints should need no request");
+        assert!(empty.is_empty());
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_price_watcher_alerts() {
+        let (url, _) = mock_server_sequence(vec![
+            r#"{"So11111111111111111111111111111111111111112":{"usdPrice":150.0,"blockId":1,"decimals":9,"priceChange24h":null}}"#,
+            r#"{"So11111111111111111111111111111111111111112":{"usdPrice":210.0,"blockId":2,"decimals":9,"priceChange24h":null}}"#,
+            r#"{"So11111111111111111111111111111111111111112":{"usdPrice":100.0,"blockId":3,"decimals":9,"priceChange24h":null}}"#,
+        ])
+        .await;
+        let window = Duration::from_secs(3600);
+        let (watcher, _events) = PriceWatcher::new(JupiterClient::new(&url));
+        let mut watcher = watcher
+            .above(SOL_MINT, 200.0)
+            .below(SOL_MINT, 120.0)
+            .move_alert(20.0, window);
+
+        assert!(
+            watcher.poll().await.unwrap().is_empty(),
+            "first poll only records"
+        );
+
+        let events = watcher.poll().await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0],
+            PriceEvent::CrossedAbove {
+                mint: SOL_MINT.to_string(),
+                threshold: 200.0,
+                price: 210.0,
+            }
+        );
+        assert!(matches!(
+            events[1],
+            PriceEvent::Moved { change_pct, .. } if (change_pct - 40.0).abs() < 1e-9
+        ));
+
+        let events = watcher.poll().await.unwrap();
+        assert!(matches!(
+            events[0],
+            PriceEvent::CrossedBelow { threshold, .. } if threshold == 120.0
+        ));
+        assert!(matches!(
+            events[1],
+            PriceEvent::Moved { change_pct, .. } if change_pct < -50.0
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_tokens_price_in_quote_mint() {
+        let (url, _) = mock_server_sequence(vec![
+            r#"{
+                "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN":{"usdPrice":0.5,"blockId":1,"decimals":6,"priceChange24h":null},
+                "So11111111111111111111111111111111111111112":{"usdPrice":200.0,"blockId":1,"decimals":9,"priceChange24h":null}
+            }"#,
+            r#"{}"#,
+        ])
+        .await;
+        let client = JupiterClient::new(&url);
+        let mints = vec![JUP_MINT.to_string(), USDC_MINT.to_string()];
+
+        let prices = client
+            .get_tokens_price_in(&mints, SOL_MINT)
+            .await
+            .expect("failed to get prices in SOL");
+        assert_eq!(prices.len(), 1, "unpriced USDC should be left out");
+        assert_eq!(prices[JUP_MINT], 0.0025);
+
+        let missing = client.get_tokens_price_in(&mints, SOL_MINT).await;
+        assert!(matches!(
+            missing,
+            Err(JupiterClientError::PriceUnavailable(mint)) if mint == SOL_MINT
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_prices_denominated() {
+        let (url, _) = mock_server_sequence(vec![
+            r#"{
+                "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN":{"usdPrice":0.5,"blockId":1,"decimals":6,"priceChange24h":null},
+                "So11111111111111111111111111111111111111112":{"usdPrice":200.0,"blockId":1,"decimals":9,"priceChange24h":null}
+            }"#,
+        ])
+        .await;
+        let client = JupiterClient::new(&url);
+
+        let prices = client
+            .get_prices_denominated(&[JUP_MINT.to_string()], SOL_MINT)
+            .await
+            .expect("failed to get prices in SOL");
+        let jup = &prices[JUP_MINT];
+        assert_eq!(jup.price, 0.0025);
+        assert_eq!((jup.decimals, jup.denominator_decimals), (6, 9));
+        assert_eq!(jup.raw_price(), 2.5);
+        // 1 JUP is 0.0025 SOL
+        assert_eq!(jup.convert_raw(1_000_000), Some(2_500_000));
+        assert_eq!(jup.convert_raw(u64::MAX), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_tokens_info_uses_cache() {
+        let (url, requests) = mock_server_sequence(vec![
+            r#"[
+                {"id":"EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v","name":"USD Coin","symbol":"USDC","decimals":6,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":0.0,"organicScoreLabel":"low"},
+                {"id":"unrelated","name":"USDC fake","symbol":"USDC","decimals":9,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":0.0,"organicScoreLabel":"low"}
+            ]"#,
+        ])
+        .await;
+        let client = JupiterClient::new(&url).with_token_cache(InMemoryTokenCache::default());
+        let mints = vec![USDC_MINT.to_string()];
+
+        let tokens = client
+            .get_tokens_info(&mints)
+            .await
+            .expect("failed to get token info");
+        assert_eq!(tokens.len(), 1, "only requested mints should be returned");
+        assert_eq!(tokens[USDC_MINT].decimals, 6);
+
+        let cached = client
+            .clone()
+            .get_tokens_info(&mints)
+            .await
+            .expect("failed to get cached token info");
+        assert_eq!(cached[USDC_MINT].symbol, "USDC");
+        assert_eq!(
+            requests.lock().unwrap().len(),
+            1,
+            "second lookup should hit the cache"
+        );
+
+        let expiring = InMemoryTokenCache::new(Duration::ZERO);
+        expiring.insert(token(USDC_MINT, true)).await;
+        assert!(expiring.get(USDC_MINT).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tradable_mints_stream() {
+        let body = r#"[ "mint-1", "with,comma", "with]\"quote" ,"mint-4"]"#;
+        let (url, _) = mock_server_sequence(vec![body, r#"["mint-1","#]).await;
+        let client = JupiterClient::new(&url);
+
+        let mints: Vec<String> = client
+            .get_tradable_mints_stream()
+            .try_collect()
+            .await
+            .expect("failed to stream tradable mints");
+        assert_eq!(mints, ["mint-1", "with,comma", "with]\"quote", "mint-4"]);
+
+        let mut truncated = std::pin::pin!(client.get_tradable_mints_stream());
+        assert_eq!(
+            truncated.try_next().await.unwrap().as_deref(),
+            Some("mint-1")
+        );
+        assert!(matches!(
+            truncated.try_next().await,
+            Err(JupiterClientError::DeserializationError(_))
+        ));
+
+        let mut file = Vec::new();
+        let written = client
+            .download_tradable_mints(&mut file)
+            .await
+            .expect("failed to download tradable mints");
+        assert_eq!(written, 10);
+        assert_eq!(file, br#"["mint-1","#);
+    }
+
+    #[tokio::test]
+    async fn test_http_cache_revalidates_with_etag() {
+        let body = r#"[{"id":"lst-mint","name":"Staked SOL","symbol":"sSOL","decimals":9,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":0.0,"organicScoreLabel":"low"}]"#;
+        let (url, _, heads) = mock_http_server(vec![
+            format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\netag: \"v1\"\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            ),
+            "HTTP/1.1 304 Not Modified\r\netag: \"v1\"\r\nconnection: close\r\n\r\n".to_string(),
+        ])
+        .await;
+        let client = JupiterClient::new(&url).with_http_cache(DEFAULT_HTTP_CACHE_CAPACITY);
+
+        let fresh = client
+            .get_mints_by_tags(&[TokenTag::Lst])
+            .await
+            .expect("failed to get tagged mints");
+        let revalidated = client
+            .get_mints_by_tags(&[TokenTag::Lst])
+            .await
+            .expect("304 should reuse the cached list");
+
+        assert_eq!(revalidated.len(), 1);
+        assert_eq!(revalidated[0].id, fresh[0].id);
+
+        let heads = heads.lock().unwrap();
+        assert!(!heads[0].to_lowercase().contains("if-none-match"));
+        assert!(heads[1].to_lowercase().contains("if-none-match: \"v1\""));
+    }
+
+    #[tokio::test]
+    async fn test_http_cache_stays_within_capacity() {
+        let body = r#"[{"id":"lst-mint","name":"Staked SOL","symbol":"sSOL","decimals":9,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":0.0,"organicScoreLabel":"low"}]"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\netag: \"v1\"\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let (url, _, heads) = mock_http_server(vec![response]).await;
+
+        // room for a single body: caching the second list drops the first
+        let client = JupiterClient::new(&url).with_http_cache(body.len());
+        for tag in [
+            TokenTag::Lst,
+            TokenTag::Verified,
+            TokenTag::Verified,
+            TokenTag::Lst,
+        ] {
+            client
+                .get_mints_by_tags(&[tag])
+                .await
+                .expect("failed to get tagged mints");
+        }
+        // too small for any body: nothing is kept
+        let client = JupiterClient::new(&url).with_http_cache(body.len() - 1);
+        for _ in 0..2 {
+            client
+                .get_mints_by_tags(&[TokenTag::Lst])
+                .await
+                .expect("failed to get tagged mints");
+        }
+
+        let revalidated: Vec<bool> = heads
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|head| head.to_lowercase().contains("if-none-match"))
+            .collect();
+        assert_eq!(revalidated, [false, false, true, false, false, false]);
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_http_cache_keeps_bulk_lists_apart() {
+        let body = format!(r#"["{SOL_MINT}","{USDC_MINT}"]"#);
+        let (url, _, heads) = mock_http_server(vec![
+            format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\netag: \"v1\"\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            ),
+            "HTTP/1.1 304 Not Modified\r\netag: \"v1\"\r\nconnection: close\r\n\r\n".to_string(),
+        ])
+        .await;
+
+        // far too small for the list, which is still kept
+        let client = JupiterClient::new(&url).with_http_cache(1);
+        let fresh = client
+            .get_tradable_mints()
+            .await
+            .expect("failed to get tradable mints");
+        let reused = client
+            .get_tradable_mints()
+            .await
+            .expect("304 should reuse the kept list");
+        assert_eq!(fresh, [SOL_MINT, USDC_MINT]);
+        assert_eq!(reused, fresh);
+
+        let heads = heads.lock().unwrap();
+        assert!(!heads[0].to_lowercase().contains("if-none-match"));
+        assert!(heads[1].to_lowercase().contains("if-none-match: \"v1\""));
+    }
+
+    #[test]
+    fn test_amount_conversions() {
+        assert_eq!(to_raw_amount(1.5, 6), Ok(1_500_000));
+        assert_eq!(to_raw_amount(0.1 + 0.2, 6), Ok(300_000));
+        assert_eq!(
+            to_raw_amount(-1.0, 6),
+            Err(ValidationError::InvalidAmount("amount"))
+        );
+        assert_eq!(
+            to_raw_amount(1e30, 9),
+            Err(ValidationError::AmountOverflow("amount"))
+        );
+        assert_eq!(to_ui_amount(1_500_000, 6), 1.5);
+
+        assert_eq!(parse_ui_amount("1.5", 6), Ok(1_500_000));
+        assert_eq!(parse_ui_amount("0.000001", 6), Ok(1));
+        assert_eq!(parse_ui_amount("2.10", 1), Ok(21));
+        assert_eq!(parse_ui_amount(".5", 9), Ok(500_000_000));
+        assert_eq!(
+            parse_ui_amount("0.0000001", 6),
+            Err(ValidationError::ExcessPrecision {
+                field: "amount",
+                decimals: 6
+            })
+        );
+        assert_eq!(
+            parse_ui_amount("1e3", 6),
+            Err(ValidationError::InvalidAmount("amount"))
+        );
+    }
+
+    #[test]
+    fn test_token_amount() {
+        let usdc = TokenAmount::parse("1.5", 6).unwrap();
+        assert_eq!(usdc, TokenAmount::new(1_500_000, 6));
+        assert_eq!(TokenAmount::from_ui(1.5, 6), Ok(usdc));
+        assert_eq!(usdc.to_ui(), 1.5);
+
+        assert_eq!(usdc.to_string(), "1.5");
+        assert_eq!(TokenAmount::new(1, 9).to_string(), "0.000000001");
+        assert_eq!(TokenAmount::new(2_000_000_000, 9).to_string(), "2");
+        assert_eq!(TokenAmount::new(42, 0).to_string(), "42");
+
+        let sol = TokenAmount::new(1, 9);
+        assert_eq!(usdc.checked_add(usdc), Some(TokenAmount::new(3_000_000, 6)));
+        assert_eq!(usdc.checked_add(sol), None);
+        assert_eq!(usdc.checked_sub(usdc.checked_mul(2).unwrap()), None);
+        assert_eq!(usdc.checked_div(3), Some(TokenAmount::new(500_000, 6)));
+        assert_eq!(usdc.checked_div(0), None);
+        assert_eq!(TokenAmount::new(u64::MAX, 6).checked_mul(2), None);
+
+        let request = QuoteRequest::new(USDC_MINT, SOL_MINT, usdc);
+        assert_eq!(request, QuoteRequest::new(USDC_MINT, SOL_MINT, 1_500_000));
+        assert_eq!(
+            UltraOrderRequest::new(USDC_MINT, SOL_MINT, usdc).amount,
+            1_500_000
+        );
+    }
+
+    #[test]
+    fn test_decimal_accessors() {
+        let quote = crate::common::sample_quote_response();
+        assert_eq!(
+            quote.in_amount_decimal(),
+            Some(Decimal::from(1_000_000_000u64))
+        );
+        assert_eq!(
+            quote.price_impact_pct_decimal(),
+            Some(Decimal::from_str("0.0012").unwrap())
+        );
+        assert_eq!(quote.swap_usd_value_decimal(), None);
+
+        let mut quote = quote;
+        quote.price_impact_pct = "1.5e-7".to_string();
+        assert_eq!(
+            quote.price_impact_pct_decimal(),
+            Some(Decimal::from_str("0.00000015").unwrap())
+        );
+        quote.in_amount = "not a number".to_string();
+        assert_eq!(quote.in_amount_decimal(), None);
+
+        let usdc = TokenAmount::new(1_500_000, 6);
+        assert_eq!(usdc.to_decimal(), Decimal::from_str("1.5").unwrap());
+        assert_eq!(TokenAmount::from_decimal(usdc.to_decimal(), 6), Ok(usdc));
+        assert_eq!(
+            TokenAmount::from_decimal(Decimal::from_str("0.1234567").unwrap(), 6),
+            Err(ValidationError::ExcessPrecision {
+                field: "amount",
+                decimals: 6
+            })
+        );
+        assert_eq!(
+            TokenAmount::from_decimal(Decimal::from_str("-1").unwrap(), 6),
+            Err(ValidationError::InvalidAmount("amount"))
+        );
+        assert_eq!(
+            TokenAmount::from_decimal(Decimal::from(u64::MAX), 6),
+            Err(ValidationError::AmountOverflow("amount"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decimals_resolver() {
+        let (url, requests) = mock_server_sequence(vec![
+            r#"[{"id":"JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN","name":"Jupiter","symbol":"JUP","decimals":6,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":0.0,"organicScoreLabel":"low"}]"#,
+            "[]",
+        ])
+        .await;
+        let resolver = DecimalsResolver::new(JupiterClient::new(&url)).with_known(SOL_MINT, 9);
+
+        assert_eq!(resolver.to_raw(SOL_MINT, 0.5).await.unwrap(), 500_000_000);
+        assert!(
+            requests.lock().unwrap().is_empty(),
+            "known mints are not searched"
+        );
+
+        assert_eq!(resolver.to_ui(JUP_MINT, 2_500_000).await.unwrap(), 2.5);
+        assert_eq!(resolver.parse(JUP_MINT, "2.5").await.unwrap(), 2_500_000);
+        assert_eq!(
+            requests.lock().unwrap().len(),
+            1,
+            "decimals are looked up once"
+        );
+
+        let unknown = resolver.decimals("unknown-mint").await;
+        assert!(matches!(
+            unknown,
+            Err(JupiterClientError::TokenNotFound(mint)) if mint == "unknown-mint"
+        ));
+    }
+
     #[tokio::test]
     pub async fn test_token_info() {
         let client = create_test_client();
@@ -39,13 +489,258 @@ mod token_tests {
     pub async fn test_get_mints_by_tags() {
         let client = create_test_client();
 
-        let tags = vec![String::from("lst")];
-
         let mints = client
-            .get_mints_by_tags(&tags)
+            .get_mints_by_tags(&[TokenTag::Lst])
             .await
             .expect("failed to get mints by tags");
 
         assert!(mints.len() > 1000, "there are more that 1000 lst");
     }
+
+    #[test]
+    fn test_token_tag_round_trip() {
+        assert_eq!(TokenTag::Token2022.to_string(), "token-2022");
+        assert_eq!("lst".parse::<TokenTag>(), Ok(TokenTag::Lst));
+
+        let tags: Vec<TokenTag> =
+            serde_json::from_str(r#"["verified","birdeye-trending"]"#).expect("tags should parse");
+        assert_eq!(
+            tags,
+            vec![
+                TokenTag::Verified,
+                TokenTag::Other("birdeye-trending".to_string())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&tags).unwrap(),
+            r#"["verified","birdeye-trending"]"#
+        );
+    }
+
+    fn token(id: &str, verified: bool) -> TokenInfo {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "symbol": "USDC",
+            "decimals": 6,
+            "tokenProgram": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "organicScore": 0.0,
+            "organicScoreLabel": "low",
+            "isVerified": verified,
+        }))
+        .expect("failed to build token info")
+    }
+
+    #[test]
+    fn test_token_stats_helpers() {
+        let token: TokenInfo = serde_json::from_value(serde_json::json!({
+            "id": JUP_MINT,
+            "name": "Jupiter",
+            "symbol": "JUP",
+            "decimals": 6,
+            "tokenProgram": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "organicScore": 90.0,
+            "organicScoreLabel": "high",
+            "stats1h": {
+                "buyVolume": 300.0,
+                "sellVolume": 100.0,
+                "buyOrganicVolume": 150.0,
+                "sellOrganicVolume": 50.0,
+            },
+            "stats24h": { "buyVolume": 10.0, "sellVolume": 0.0 },
+        }))
+        .expect("failed to build token info");
+
+        assert!(token.stats(Interval::FiveMinutes).is_none());
+
+        let hour = token.stats(Interval::OneHour).expect("1h stats");
+        assert_eq!(hour.total_volume(), Some(400.0));
+        assert_eq!(hour.net_buy_volume(), Some(200.0));
+        assert_eq!(hour.buy_sell_ratio(), Some(3.0));
+        assert_eq!(hour.organic_volume_share(), Some(0.5));
+
+        let day = token.stats(Interval::TwentyFourHours).expect("24h stats");
+        assert_eq!(day.buy_sell_ratio(), None);
+        assert_eq!(day.organic_volume_share(), None);
+    }
+
+    #[test]
+    fn test_search_options_apply() {
+        let tokens = || {
+            vec![
+                token("fake-1", false),
+                token(USDC_MINT, true),
+                token("fake-2", false),
+                token("bridged", true),
+            ]
+        };
+
+        let all = SearchOptions::default().apply(tokens());
+        assert_eq!(all.len(), 4);
+
+        let verified = SearchOptions::default().verified_only(true).apply(tokens());
+        let ids: Vec<&str> = verified.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec![USDC_MINT, "bridged"]);
+
+        let limited = SearchOptions::default()
+            .verified_only(true)
+            .limit(1)
+            .apply(tokens());
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].id, USDC_MINT);
+    }
+
+    #[test]
+    fn test_search_options_exact_symbol_and_sort() {
+        let with = |id: &str, symbol: &str, liquidity: Option<f64>| {
+            let mut token = token(id, true);
+            token.symbol = symbol.to_string();
+            token.liquidity = liquidity;
+            token
+        };
+        let tokens = || {
+            vec![
+                with("jupiter-perps", "JUPSOL", Some(9e9)),
+                with("copycat", "jup", Some(1e3)),
+                with("no-liquidity", "JUP", None),
+                with(JUP_MINT, "JUP", Some(5e8)),
+            ]
+        };
+
+        let ids = |options: SearchOptions| -> Vec<String> {
+            options.apply(tokens()).into_iter().map(|t| t.id).collect()
+        };
+
+        assert_eq!(
+            ids(SearchOptions::default()
+                .exact_symbol("JUP")
+                .sort_by(SearchSort::Liquidity)),
+            vec![JUP_MINT, "copycat", "no-liquidity"]
+        );
+        assert_eq!(
+            ids(SearchOptions::default()
+                .sort_by(SearchSort::Liquidity)
+                .limit(1)),
+            vec!["jupiter-perps"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_tokens_by_categories_merges_and_dedups() {
+        let (url, requests) = mock_server_sequence(vec![
+            r#"[
+                {"id":"a","name":"A","symbol":"A","decimals":6,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":10.0,"organicScoreLabel":"low"},
+                {"id":"b","name":"B","symbol":"B","decimals":6,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":30.0,"organicScoreLabel":"low"}
+            ]"#,
+            r#"[
+                {"id":"b","name":"B","symbol":"B","decimals":6,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":30.0,"organicScoreLabel":"low"},
+                {"id":"c","name":"C","symbol":"C","decimals":6,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":20.0,"organicScoreLabel":"low"}
+            ]"#,
+        ])
+        .await;
+        let client = JupiterClient::new(&url);
+
+        let tokens = client
+            .get_tokens_by_categories(
+                &[
+                    (Category::TopTrending, Interval::OneHour),
+                    (Category::TopTraded, Interval::TwentyFourHours),
+                ],
+                Some(10),
+                &SearchOptions::default().sort_by(SearchSort::OrganicScore),
+            )
+            .await
+            .expect("failed to get tokens by categories");
+
+        let ids: Vec<&str> = tokens.iter().map(|token| token.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "a"]);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_token_index_search() {
+        let with = |id: &str, symbol: &str, name: &str, verified: bool| {
+            let mut token = token(id, verified);
+            token.symbol = symbol.to_string();
+            token.name = name.to_string();
+            token
+        };
+        let index = TokenIndex::new(vec![
+            with("fakeJup111", "JUP", "Jupiter Inu", false),
+            with("jupsol111", "JupSOL", "Jupiter Staked SOL", true),
+            with(JUP_MINT, "JUP", "Jupiter", true),
+            with("bonk111", "Bonk", "Bonk", true),
+        ]);
+
+        let found = |query: &str| -> Vec<String> {
+            index
+                .search(query, 10)
+                .into_iter()
+                .map(|token| token.id.clone())
+                .collect()
+        };
+
+        assert_eq!(found("jup"), vec![JUP_MINT, "fakeJup111", "jupsol111"]);
+        assert_eq!(found("staked"), vec!["jupsol111"]);
+        assert_eq!(found("bnk"), vec!["bonk111"], "fuzzy match");
+        assert_eq!(found(&JUP_MINT[..6]), vec![JUP_MINT]);
+        assert!(found("   ").is_empty());
+        assert_eq!(
+            index.get(JUP_MINT).map(|token| token.name.as_str()),
+            Some("Jupiter")
+        );
+
+        let (url, requests) = mock_server_sequence(vec![
+            r#"[{"id":"bonk111","name":"Bonk","symbol":"Bonk","decimals":5,"tokenProgram":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA","organicScore":0.0,"organicScoreLabel":"low"}]"#,
+        ])
+        .await;
+        let live = LiveTokenIndex::new(JupiterClient::new(&url), vec![TokenTag::Verified]);
+        assert!(live.search("bonk", 5).is_empty());
+        assert_eq!(live.refresh().await.unwrap(), 1);
+        assert_eq!(live.clone().search("bon", 5)[0].id, "bonk111");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert_eq!(live.last_error(), None);
+
+        let (url, _) = mock_server_sequence(vec![r#"{"error":"down"}"#]).await;
+        let failing = LiveTokenIndex::new(JupiterClient::new(&url), vec![TokenTag::Verified]);
+        assert!(failing.refresh().await.is_err());
+        assert!(failing.last_error().is_some());
+    }
+
+    #[test]
+    fn test_category_and_interval_parse() {
+        assert_eq!("toptrending".parse(), Ok(Category::TopTrending));
+        assert_eq!("TopOrganicScore".parse(), Ok(Category::TopOrganicScore));
+        assert_eq!(" 24H ".parse(), Ok(Interval::TwentyFourHours));
+
+        let err = "1d".parse::<Interval>().unwrap_err();
+        assert_eq!(err.value, "1d");
+        assert!(err.to_string().contains("5m, 1h, 6h, 24h"));
+        assert!("hot".parse::<Category>().is_err());
+
+        for interval in [
+            Interval::FiveMinutes,
+            Interval::OneHour,
+            Interval::SixHours,
+            Interval::TwentyFourHours,
+        ] {
+            assert_eq!(interval.to_string().parse(), Ok(interval));
+        }
+
+        assert_eq!(
+            serde_json::to_string(&(Category::TopTraded, Interval::SixHours)).unwrap(),
+            r#"["toptraded","6h"]"#
+        );
+
+        let config: Vec<(Category, Interval)> =
+            serde_json::from_str(r#"[["toptrending", "1h"], ["TOPTRADED", "5m"]]"#).unwrap();
+        assert_eq!(
+            config,
+            vec![
+                (Category::TopTrending, Interval::OneHour),
+                (Category::TopTraded, Interval::FiveMinutes),
+            ]
+        );
+        assert!(serde_json::from_str::<Interval>(r#""OneHour""#).is_err());
+    }
 }
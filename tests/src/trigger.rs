@@ -1,10 +1,37 @@
 #[cfg(test)]
 mod trigger_tests {
-    use jup_ag_sdk::types::{
-        CreateTriggerOrder, ExecuteTriggerOrder, GetTriggerOrders, OrderStatus,
+    use std::time::Duration;
+
+    use futures::{StreamExt, TryStreamExt};
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError, ValidationError,
+        client::{Executable, MAX_TRIGGER_ORDERS_PER_CANCEL},
+        export::{ExportRecord, FillRecord, OrderRecord, write_csv, write_json_lines},
+        grid::{GridEvent, GridManager, GridSide, GridStrategy, PlacedGridOrder},
+        introspection::{
+            JUPITER_DCA_PROGRAM_ID, JUPITER_LIMIT_ORDER_PROGRAM_ID, ProgramAllowlist,
+            TransactionPolicy, TransactionSummary,
+        },
+        oco::{OcoEvent, OcoManager},
+        rpc::{CommitmentLevel, SolanaRpc},
+        signer::{Pubkey, Signature, TransactionSigner},
+        stop_loss::{StopLossEvent, StopLossManager},
+        types::{
+            CancelTriggerOrder, CancelTriggerOrders, ComputeUnitPrice, CreateTriggerOrder,
+            ExecuteTriggerOrder, ExecuteTriggerOrderResponse, GetTriggerOrders, OrderResponse,
+            OrderStatus, Params, PlacedTriggerOrder, RecurringResponse, TakeProfitStopLoss,
+            TradeAction, TriggerOrder, TriggerOrderFilter, TriggerOrderState, TriggerResponse,
+            parse_timestamp,
+        },
     };
 
-    use crate::common::{SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client};
+    use solana_keypair::Keypair;
+    use solana_signer::Signer;
+
+    use crate::common::{
+        SAMPLE_QUOTE_JSON, SOL_MINT, TEST_USER_PUBKEY, USDC_MINT, create_test_client, mock_server,
+        mock_server_sequence, unsigned_transaction_base64,
+    };
 
     #[test]
     fn test_trigger_create_order_builder() {
@@ -37,6 +64,1178 @@ mod trigger_tests {
         );
     }
 
+    #[test]
+    fn test_trigger_requests_build_from_default() {
+        let order = CreateTriggerOrder::default()
+            .input_mint(SOL_MINT)
+            .output_mint(USDC_MINT)
+            .maker(TEST_USER_PUBKEY)
+            .payer(TEST_USER_PUBKEY)
+            .params(Params::default().making_amount(1_000).taking_amount(2_000));
+        assert_eq!(
+            order,
+            CreateTriggerOrder::new(
+                SOL_MINT,
+                USDC_MINT,
+                TEST_USER_PUBKEY,
+                TEST_USER_PUBKEY,
+                1_000,
+                2_000
+            )
+        );
+
+        assert_eq!(
+            GetTriggerOrders::default().user(TEST_USER_PUBKEY),
+            GetTriggerOrders::new(TEST_USER_PUBKEY, OrderStatus::Active)
+        );
+        assert_eq!(
+            CancelTriggerOrders::default()
+                .maker(TEST_USER_PUBKEY)
+                .orders(vec!["order".to_string()]),
+            CancelTriggerOrders::new(TEST_USER_PUBKEY, vec!["order".to_string()])
+        );
+        assert_eq!(
+            CancelTriggerOrder::default()
+                .maker(TEST_USER_PUBKEY)
+                .order("order"),
+            CancelTriggerOrder::new(TEST_USER_PUBKEY, "order")
+        );
+        assert_eq!(
+            ExecuteTriggerOrder::default()
+                .request_id("id")
+                .signed_transaction("tx"),
+            ExecuteTriggerOrder::new("id", "tx")
+        );
+    }
+
+    #[test]
+    fn test_trigger_create_order_numeric_params() {
+        let create_order = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000_000_000,
+            200_000_000,
+        )
+        .slippage_bps(50)
+        .fee_bps(10);
+
+        assert_eq!(create_order.params.making_amount, 1_000_000_000);
+        assert_eq!(create_order.params.slippage_bps, Some(50));
+
+        let json = serde_json::to_value(&create_order).expect("failed to serialize order");
+        assert_eq!(json["params"]["makingAmount"], "1000000000");
+        assert_eq!(json["params"]["takingAmount"], "200000000");
+        assert_eq!(json["params"]["slippageBps"], "50");
+        assert_eq!(json["params"]["feeBps"], "10");
+        assert!(json["params"].get("expiredAt").is_none());
+
+        let params: Params = serde_json::from_value(serde_json::json!({
+            "makingAmount": "5",
+            "takingAmount": 7,
+            "slippageBps": "25"
+        }))
+        .expect("failed to deserialize params");
+        assert_eq!(params.making_amount, 5);
+        assert_eq!(params.taking_amount, 7);
+        assert_eq!(params.slippage_bps, Some(25));
+        assert_eq!(params.fee_bps, None);
+
+        let legacy = Params::new(1, 2).slippage_bps("30").fee_bps(" 5 ");
+        assert_eq!(legacy.slippage_bps, Some(30));
+        assert_eq!(legacy.fee_bps, Some(5));
+
+        let invalid = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000_000_000,
+            200_000_000,
+        )
+        .slippage_bps("0.5%");
+        assert_eq!(invalid.params.slippage_bps, None);
+        assert!(matches!(
+            invalid.validate(),
+            Err(ValidationError::InvalidBps {
+                field: "slippage_bps",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_trigger_compute_unit_price() {
+        let order = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000_000_000,
+            200_000_000,
+        )
+        .compute_unit_price(ComputeUnitPrice::MicroLamports(1000));
+        let json = serde_json::to_value(&order).expect("failed to serialize order");
+        assert_eq!(json["computeUnitPrice"], "1000");
+
+        let cancel = CancelTriggerOrder::new(TEST_USER_PUBKEY, "order")
+            .compute_unit_price(ComputeUnitPrice::Auto);
+        let json = serde_json::to_value(&cancel).expect("failed to serialize cancel");
+        assert_eq!(json["computeUnitPrice"], "auto");
+
+        let parsed: ComputeUnitPrice =
+            serde_json::from_value(serde_json::json!(250)).expect("numeric price");
+        assert_eq!(parsed, ComputeUnitPrice::MicroLamports(250));
+        assert_eq!("AUTO".parse(), Ok(ComputeUnitPrice::Auto));
+        assert!("fast".parse::<ComputeUnitPrice>().is_err());
+    }
+
+    #[test]
+    fn test_trigger_order_from_price() {
+        // 1.5 SOL at 200 USDC per SOL
+        let order = CreateTriggerOrder::from_price(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            1_500_000_000,
+            200.0,
+            9,
+            6,
+        )
+        .expect("valid limit order");
+        assert_eq!(order.params.making_amount, 1_500_000_000);
+        assert_eq!(order.params.taking_amount, 300_000_000);
+        assert_eq!(order.payer, TEST_USER_PUBKEY);
+
+        // 100 USDC at 0.005 SOL per USDC
+        let order = CreateTriggerOrder::from_price(
+            USDC_MINT,
+            SOL_MINT,
+            TEST_USER_PUBKEY,
+            100_000_000,
+            0.005,
+            6,
+            9,
+        )
+        .expect("valid limit order");
+        assert_eq!(order.params.taking_amount, 500_000_000);
+
+        for price in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let res = CreateTriggerOrder::from_price(
+                SOL_MINT,
+                USDC_MINT,
+                TEST_USER_PUBKEY,
+                1,
+                price,
+                9,
+                6,
+            );
+            assert_eq!(
+                res.err(),
+                Some(ValidationError::InvalidPrice("target_price"))
+            );
+        }
+
+        let dust =
+            CreateTriggerOrder::from_price(SOL_MINT, USDC_MINT, TEST_USER_PUBKEY, 1, 200.0, 9, 6);
+        assert_eq!(
+            dust.err(),
+            Some(ValidationError::ZeroAmount("taking_amount"))
+        );
+
+        let huge = CreateTriggerOrder::from_price(
+            USDC_MINT,
+            SOL_MINT,
+            TEST_USER_PUBKEY,
+            u64::MAX,
+            1.0,
+            6,
+            9,
+        );
+        assert_eq!(
+            huge.err(),
+            Some(ValidationError::AmountOverflow("taking_amount"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_trigger_order_validation() {
+        let order = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000_000_000,
+            200_000_000,
+        );
+        assert_eq!(order.validate(), Ok(()));
+
+        assert_eq!(
+            order.clone().fee_bps(20).validate(),
+            Err(ValidationError::MissingField("fee_account"))
+        );
+        assert_eq!(
+            order
+                .clone()
+                .fee_bps(20)
+                .fee_account(TEST_USER_PUBKEY)
+                .validate(),
+            Ok(())
+        );
+        assert!(matches!(
+            order.clone().fee_account("not an account").validate(),
+            Err(ValidationError::InvalidAddress {
+                field: "fee_account",
+                ..
+            })
+        ));
+        assert_eq!(
+            order.clone().output_mint(SOL_MINT).validate(),
+            Err(ValidationError::SameMint)
+        );
+        assert!(matches!(
+            order.clone().slippage_bps(10_001).validate(),
+            Err(ValidationError::BpsOutOfRange {
+                field: "slippage_bps",
+                ..
+            })
+        ));
+
+        // rejected before any request is sent
+        let client = JupiterClient::new("http://127.0.0.1:1");
+        let res = client.create_trigger_order(&order.fee_bps(20)).await;
+        assert!(matches!(
+            res,
+            Err(JupiterClientError::ValidationError(
+                ValidationError::MissingField("fee_account")
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_take_profit_stop_loss_pair() {
+        let pair = TakeProfitStopLoss::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            2_000_000_000,
+            250.0,
+            150.0,
+            9,
+            6,
+        )
+        .expect("valid pair");
+        assert_eq!(pair.take_profit.params.making_amount, 2_000_000_000);
+        assert_eq!(pair.take_profit.params.taking_amount, 500_000_000);
+        assert_eq!(pair.stop_loss_price, 150.0);
+
+        // 2 SOL quoted at 310 USDC is 155 USDC per SOL, above the stop
+        assert_eq!(pair.price(2_000_000_000, 310_000_000), Some(155.0));
+        assert!(!pair.is_stopped(2_000_000_000, 310_000_000));
+        assert!(pair.is_stopped(2_000_000_000, 300_000_000));
+        assert!(pair.is_stopped(2_000_000_000, 200_000_000));
+        assert_eq!(pair.price(0, 200_000_000), None);
+
+        let inverted = TakeProfitStopLoss::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            2_000_000_000,
+            150.0,
+            250.0,
+            9,
+            6,
+        );
+        assert_eq!(
+            inverted.err(),
+            Some(ValidationError::InvalidPrice("stop_loss_price"))
+        );
+    }
+
+    #[test]
+    fn test_trigger_order_display() {
+        let order: TriggerOrder = serde_json::from_value(serde_json::json!({
+            "userPubkey": TEST_USER_PUBKEY,
+            "orderKey": "order",
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "makingAmount": "2",
+            "takingAmount": "300",
+            "remainingMakingAmount": "1.5",
+            "remainingTakingAmount": "225",
+            "rawMakingAmount": "2000000000",
+            "rawTakingAmount": "300000000",
+            "rawRemainingMakingAmount": "1500000000",
+            "rawRemainingTakingAmount": "225000000",
+            "slippageBps": "0",
+            "createdAt": "2025-05-30T16:22:51Z",
+            "updatedAt": "2025-05-30T16:22:51Z",
+            "status": "Open",
+            "openTx": "tx",
+            "closeTx": "",
+            "programVersion": "v1",
+            "trades": []
+        }))
+        .expect("failed to deserialize order");
+        assert_eq!(
+            order.to_string(),
+            "Open order: 2 So11..1112 -> 300 EPjF..Dt1v at 150, 1.5 left, 0 trades"
+        );
+
+        let executed = ExecuteTriggerOrderResponse {
+            code: 0,
+            signature: "sig".to_string(),
+            status: "Success".to_string(),
+            order: Some("order".to_string()),
+        };
+        assert_eq!(executed.to_string(), "Success sig order order");
+    }
+
+    #[test]
+    fn test_trigger_order_timestamps() {
+        let order: TriggerOrder = serde_json::from_value(serde_json::json!({
+            "userPubkey": TEST_USER_PUBKEY,
+            "orderKey": "order",
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "makingAmount": "1",
+            "takingAmount": "200",
+            "remainingMakingAmount": "1",
+            "remainingTakingAmount": "200",
+            "rawMakingAmount": "1000000000",
+            "rawTakingAmount": "200000000",
+            "rawRemainingMakingAmount": "1000000000",
+            "rawRemainingTakingAmount": "200000000",
+            "slippageBps": "0",
+            "expiredAt": "1848622171",
+            "createdAt": "2025-05-30T16:22:51Z",
+            "updatedAt": "2025-05-30T16:22:51.123",
+            "status": "open",
+            "openTx": "tx",
+            "closeTx": "",
+            "programVersion": "v1",
+            "trades": []
+        }))
+        .expect("failed to deserialize order");
+
+        let created = parse_timestamp("1748622171").expect("valid timestamp");
+        assert_eq!(order.created_at, "2025-05-30T16:22:51Z");
+        assert_eq!(order.created_at_datetime(), Some(created));
+        assert_eq!(
+            order.updated_at_datetime().map(|at| at.timestamp()),
+            Some(1748622171)
+        );
+        assert_eq!(order.status, TriggerOrderState::Open);
+        assert_eq!(
+            order.expired_at_datetime().map(|at| at.timestamp()),
+            Some(1848622171)
+        );
+
+        let json = serde_json::to_value(&order).expect("failed to serialize order");
+        assert_eq!(
+            json["createdAt"], "2025-05-30T16:22:51Z",
+            "sent back as received"
+        );
+
+        let create_order = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000_000_000,
+            200_000_000,
+        )
+        .expired_at_datetime(created);
+        assert_eq!(
+            create_order.params.expired_at.as_deref(),
+            Some("1748622171")
+        );
+
+        let in_a_day = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000_000_000,
+            200_000_000,
+        )
+        .expires_in(std::time::Duration::from_secs(86_400));
+        let expires: i64 = in_a_day
+            .params
+            .expired_at
+            .expect("expiry should be set")
+            .parse()
+            .expect("expiry should be unix seconds");
+        assert!(expires > created.timestamp());
+    }
+
+    #[tokio::test]
+    async fn test_trigger_orders_stream_ends_on_error() {
+        let client = JupiterClient::new("http://127.0.0.1:1");
+        let params = GetTriggerOrders::new(TEST_USER_PUBKEY, OrderStatus::History);
+
+        let results: Vec<_> = client.get_trigger_orders_stream(&params).collect().await;
+        assert_eq!(results.len(), 1, "stream should stop after the failed page");
+        assert!(matches!(
+            results[0],
+            Err(JupiterClientError::RequestError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_orders_stream() {
+        let client = create_test_client();
+        let params = GetTriggerOrders::new(TEST_USER_PUBKEY, OrderStatus::History);
+
+        let first_page = client
+            .get_trigger_orders(&params)
+            .await
+            .expect("failed to get trigger orders");
+        let orders: Vec<_> = client
+            .get_trigger_orders_stream(&params)
+            .try_collect()
+            .await
+            .expect("failed to stream trigger orders");
+        assert!(
+            orders.len() >= first_page.orders.len(),
+            "stream should include at least the first page"
+        );
+    }
+
+    /// A page of `maker`'s trigger orders, each given as its key, status, creation and close
+    /// time.
+    fn orders_page(
+        maker: &str,
+        orders: &[(&str, &str, &str, &str)],
+        total_pages: u32,
+    ) -> &'static str {
+        let orders: Vec<_> = orders
+            .iter()
+            .map(|(order_key, status, created_at, updated_at)| {
+                serde_json::json!({
+                    "userPubkey": maker,
+                    "orderKey": order_key,
+                    "inputMint": SOL_MINT,
+                    "outputMint": USDC_MINT,
+                    "makingAmount": "1",
+                    "takingAmount": "200",
+                    "remainingMakingAmount": "1",
+                    "remainingTakingAmount": "200",
+                    "rawMakingAmount": "1000000000",
+                    "rawTakingAmount": "200000000",
+                    "rawRemainingMakingAmount": "1000000000",
+                    "rawRemainingTakingAmount": "200000000",
+                    "slippageBps": "0",
+                    "createdAt": created_at,
+                    "updatedAt": updated_at,
+                    "status": status,
+                    "openTx": "tx",
+                    "closeTx": "",
+                    "programVersion": "v1",
+                    "trades": []
+                })
+            })
+            .collect();
+        let page = serde_json::json!({
+            "user": maker,
+            "orderStatus": "active",
+            "orders": orders,
+            "totalPages": total_pages,
+            "page": 1
+        });
+        Box::leak(page.to_string().into_boxed_str())
+    }
+
+    #[tokio::test]
+    async fn test_oco_manager_reports_poll_failures() {
+        let placed = |order: &str| PlacedTriggerOrder {
+            order: order.to_string(),
+            signature: "sig".to_string(),
+        };
+        let (oco, mut events) = OcoManager::watch(
+            JupiterClient::new("http://127.0.0.1:1"),
+            Keypair::new(),
+            TEST_USER_PUBKEY,
+            placed("take-profit"),
+            placed("stop-loss"),
+        );
+        let oco = oco.poll_interval(Duration::from_millis(10));
+        assert_eq!(
+            oco.orders(),
+            &["take-profit".to_string(), "stop-loss".to_string()]
+        );
+
+        let run = tokio::spawn(oco.run());
+        let event = tokio::time::timeout(Duration::from_secs(10), events.next())
+            .await
+            .expect("no event before timeout");
+        assert!(
+            matches!(event, Some(OcoEvent::PollFailed(_))),
+            "unreachable API should be reported, got {event:?}"
+        );
+        assert!(
+            !run.is_finished(),
+            "manager should keep polling after a failure"
+        );
+        run.abort();
+    }
+
+    #[tokio::test]
+    async fn test_oco_manager_history_not_ordered_by_close_time() {
+        let keypair = Keypair::new();
+        let maker = keypair.pubkey().to_string();
+        let tx = unsigned_transaction_base64(&keypair.pubkey(), &[Pubkey::new_unique()]);
+        let cancel: &'static str = Box::leak(
+            format!(r#"{{"requestId":"req","transaction":"{tx}","code":0}}"#).into_boxed_str(),
+        );
+        let created_at = "2025-05-30T16:00:00Z";
+        let open = |order_key| (order_key, "Open", created_at, created_at);
+        let completed =
+            |order_key, created_at, updated_at| (order_key, "Completed", created_at, updated_at);
+        // closed after the watched orders were created
+        let newer = completed("newer", "2025-05-30T17:00:00Z", "2025-05-30T17:10:00Z");
+        // closed before the watched orders were created
+        let old = completed("old", "2025-05-30T15:00:00Z", "2025-05-30T15:10:00Z");
+        let take = completed("take", created_at, "2025-05-30T17:30:00Z");
+        let (url, requests) = mock_server_sequence(vec![
+            // first poll, both orders are open
+            orders_page(&maker, &[open("take"), open("stop")], 1),
+            // second poll, "take" is not in history yet and the lookup stops at "old"
+            // without reading the second history page
+            orders_page(&maker, &[open("stop")], 1),
+            orders_page(&maker, &[newer, old], 2),
+            // third poll, "take" closed last but is listed by creation time, below "newer"
+            orders_page(&maker, &[open("stop")], 1),
+            orders_page(&maker, &[newer, take, old], 1),
+            cancel,
+            r#"{"code":0,"signature":"cancel-sig","status":"Success"}"#,
+        ])
+        .await;
+
+        let placed = |order: &str| PlacedTriggerOrder {
+            order: order.to_string(),
+            signature: "sig".to_string(),
+        };
+        let (oco, _events) = OcoManager::watch(
+            JupiterClient::new(&url),
+            keypair,
+            &maker,
+            placed("take"),
+            placed("stop"),
+        );
+        let last = tokio::time::timeout(
+            Duration::from_secs(10),
+            oco.poll_interval(Duration::from_millis(10)).run(),
+        )
+        .await
+        .expect("manager should stop before timeout")
+        .expect("sibling should be cancelled");
+        assert_eq!(
+            last,
+            OcoEvent::SiblingCancelled {
+                order: "stop".to_string(),
+                signature: "cancel-sig".to_string(),
+            }
+        );
+        assert_eq!(requests.lock().unwrap().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_oco_manager_both_filled() {
+        let keypair = Keypair::new();
+        let maker = keypair.pubkey().to_string();
+        let created_at = "2025-05-30T16:00:00Z";
+        let open = |order_key| (order_key, "Open", created_at, created_at);
+        let completed = |order_key| (order_key, "Completed", created_at, "2025-05-30T17:00:00Z");
+        // a cancel would be answered with the last page and fail
+        let (url, requests) = mock_server_sequence(vec![
+            orders_page(&maker, &[open("take"), open("stop")], 1),
+            orders_page(&maker, &[], 1),
+            orders_page(&maker, &[completed("stop"), completed("take")], 1),
+        ])
+        .await;
+
+        let placed = |order: &str| PlacedTriggerOrder {
+            order: order.to_string(),
+            signature: "sig".to_string(),
+        };
+        let (oco, events) = OcoManager::watch(
+            JupiterClient::new(&url),
+            keypair,
+            &maker,
+            placed("take"),
+            placed("stop"),
+        );
+        let last = tokio::time::timeout(
+            Duration::from_secs(10),
+            oco.poll_interval(Duration::from_millis(10)).run(),
+        )
+        .await
+        .expect("manager should stop before timeout")
+        .expect("both fills should be reported");
+        assert_eq!(
+            last,
+            OcoEvent::Filled {
+                order: "stop".to_string()
+            }
+        );
+        assert_eq!(
+            events.collect::<Vec<_>>().await,
+            vec![
+                OcoEvent::Filled {
+                    order: "take".to_string()
+                },
+                last
+            ]
+        );
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stop_loss_manager_sells_at_market() {
+        let keypair = Keypair::new();
+        let maker = keypair.pubkey().to_string();
+        let leak = |body: String| -> &'static str { Box::leak(body.into_boxed_str()) };
+
+        let active = serde_json::json!({
+            "user": maker,
+            "orderStatus": "active",
+            "orders": [{
+                "userPubkey": maker,
+                "orderKey": "take-profit",
+                "inputMint": SOL_MINT,
+                "outputMint": USDC_MINT,
+                "makingAmount": "2",
+                "takingAmount": "500",
+                "remainingMakingAmount": "2",
+                "remainingTakingAmount": "500",
+                "rawMakingAmount": "2000000000",
+                "rawTakingAmount": "500000000",
+                "rawRemainingMakingAmount": "2000000000",
+                "rawRemainingTakingAmount": "500000000",
+                "slippageBps": "0",
+                "createdAt": "2025-05-30T16:22:51Z",
+                "updatedAt": "2025-05-30T16:22:51Z",
+                "status": "Open",
+                "openTx": "tx",
+                "closeTx": "",
+                "programVersion": "v1",
+                "trades": []
+            }],
+            "totalPages": 1,
+            "page": 1
+        });
+        // 2 SOL quoted at 200 USDC, 100 USDC per SOL
+        let quote = SAMPLE_QUOTE_JSON.replacen(
+            r#""outAmount": "400000000""#,
+            r#""outAmount": "200000000""#,
+            1,
+        );
+        let tx = unsigned_transaction_base64(&keypair.pubkey(), &[Pubkey::new_unique()]);
+        let ultra_order = serde_json::json!({
+            "mode": "ultra",
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "inAmount": "2000000000",
+            "outAmount": "200000000",
+            "otherAmountThreshold": "199000000",
+            "swapMode": "ExactIn",
+            "slippageBps": 50,
+            "priceImpactPct": "0",
+            "routePlan": [],
+            "feeBps": 10,
+            "prioritizationFeeLamports": 0,
+            "swapType": "aggregator",
+            "transaction": tx,
+            "gasless": false,
+            "requestId": "ultra-req",
+            "totalTime": 120,
+            "taker": maker,
+        });
+        let (url, requests) = mock_server_sequence(vec![
+            leak(active.to_string()),
+            leak(quote),
+            leak(format!(r#"{{"requestId":"cancel-req","transaction":"{tx}","code":0}}"#)),
+            r#"{"code":0,"signature":"cancel-sig","status":"Success"}"#,
+            leak(ultra_order.to_string()),
+            r#"{"status":"Success","signature":"sell-sig","code":0,"outputAmountResult":"199500000"}"#,
+        ])
+        .await;
+
+        let pair = TakeProfitStopLoss::new(
+            SOL_MINT,
+            USDC_MINT,
+            &maker,
+            2_000_000_000,
+            250.0,
+            150.0,
+            9,
+            6,
+        )
+        .expect("valid pair");
+        let placed = PlacedTriggerOrder {
+            order: "take-profit".to_string(),
+            signature: "sig".to_string(),
+        };
+        let (manager, mut events) =
+            StopLossManager::watch(JupiterClient::new(&url), keypair, pair, placed);
+        let manager = manager.poll_interval(Duration::from_millis(10));
+        assert_eq!(manager.order(), "take-profit");
+
+        let expected = StopLossEvent::StopLossExecuted {
+            price: 100.0,
+            cancel_signature: "cancel-sig".to_string(),
+            signature: Some("sell-sig".to_string()),
+            in_amount: 2_000_000_000,
+            out_amount: Some(199_500_000),
+        };
+        let last = tokio::time::timeout(Duration::from_secs(10), manager.run())
+            .await
+            .expect("manager should stop before timeout")
+            .expect("stop-loss should execute");
+        assert_eq!(last, expected);
+        assert_eq!(events.next().await, Some(expected));
+        assert_eq!(requests.lock().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_grid_strategy_orders() {
+        let grid = GridStrategy::new(SOL_MINT, USDC_MINT, TEST_USER_PUBKEY, 9, 6)
+            .range(140.0, 180.0)
+            .step(5.0)
+            .size(500_000_000);
+        assert_eq!(grid.levels().len(), 9);
+
+        let orders = grid.orders(162.5).expect("grid should be valid");
+        let sides: Vec<GridSide> = orders.iter().map(|order| order.side).collect();
+        assert_eq!(sides[..4], [GridSide::Buy; 4]);
+        assert_eq!(sides[4..], [GridSide::Sell; 4]);
+        assert!(
+            orders.iter().all(|order| order.level != 4),
+            "the level nearest the price stays empty"
+        );
+        let levels: Vec<usize> = grid
+            .orders(165.0)
+            .expect("grid should be valid")
+            .iter()
+            .map(|order| order.level)
+            .collect();
+        assert_eq!(levels, [0, 1, 2, 3, 4, 6, 7, 8]);
+
+        let buy = &orders[0].order;
+        assert_eq!(
+            (buy.input_mint.as_str(), buy.output_mint.as_str()),
+            (USDC_MINT, SOL_MINT)
+        );
+        assert_eq!(buy.params.making_amount, 70_000_000);
+        assert_eq!(buy.params.taking_amount, 500_000_000);
+        let sell = &orders[4].order;
+        assert_eq!(sell.input_mint, SOL_MINT);
+        assert_eq!(sell.params.making_amount, 500_000_000);
+        assert_eq!(sell.params.taking_amount, 82_500_000);
+
+        assert!(matches!(
+            grid.clone().step(0.1).orders(162.5),
+            Err(ValidationError::AboveMaximum {
+                field: "levels",
+                ..
+            })
+        ));
+        assert_eq!(
+            grid.clone().range(180.0, 140.0).validate(),
+            Err(ValidationError::InvalidPrice("upper_price"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_grid_manager_replaces_filled_orders() {
+        let keypair = Keypair::new();
+        let maker = keypair.pubkey().to_string();
+        let tx = unsigned_transaction_base64(&keypair.pubkey(), &[Pubkey::new_unique()]);
+        let created: &'static str = Box::leak(
+            format!(r#"{{"requestId":"req","transaction":"{tx}","order":"buy-2b","code":0}}"#)
+                .into_boxed_str(),
+        );
+        let created_at = "2025-05-30T16:00:00Z";
+        let open = |order_key| (order_key, "Open", created_at, created_at);
+        let completed =
+            |order_key, created_at, updated_at| (order_key, "Completed", created_at, updated_at);
+        let (url, requests) = mock_server_sequence(vec![
+            // buy-2 fills, level 3 already holds sell-3
+            orders_page(&maker, &[open("sell-3"), open("sell-5")], 1),
+            orders_page(
+                &maker,
+                &[completed("buy-2", created_at, "2025-05-30T16:10:00Z")],
+                1,
+            ),
+            // sell-3 fills, level 2 is free again
+            orders_page(&maker, &[open("sell-5")], 1),
+            orders_page(
+                &maker,
+                &[
+                    completed("sell-3", created_at, "2025-05-30T16:20:00Z"),
+                    completed("buy-2", created_at, "2025-05-30T16:10:00Z"),
+                ],
+                1,
+            ),
+            created,
+            r#"{"code":0,"signature":"sig","status":"Success"}"#,
+            // sell-5 is not in history yet, the lookup stops at an order closed before it
+            // was created
+            orders_page(&maker, &[open("buy-2b")], 1),
+            orders_page(
+                &maker,
+                &[
+                    completed("sell-3", created_at, "2025-05-30T16:20:00Z"),
+                    completed("old", "2025-05-30T15:00:00Z", "2025-05-30T15:10:00Z"),
+                ],
+                2,
+            ),
+        ])
+        .await;
+
+        let strategy = GridStrategy::new(SOL_MINT, USDC_MINT, &maker, 9, 6)
+            .range(140.0, 180.0)
+            .step(5.0)
+            .size(500_000_000);
+        let placed = |level: usize, side: GridSide, order: &str| PlacedGridOrder {
+            level,
+            side,
+            order: order.to_string(),
+        };
+        let (mut grid, _events) = GridManager::watch(
+            JupiterClient::new(&url),
+            keypair,
+            strategy,
+            vec![
+                placed(2, GridSide::Buy, "buy-2"),
+                placed(3, GridSide::Sell, "sell-3"),
+                placed(5, GridSide::Sell, "sell-5"),
+            ],
+        );
+
+        let events = grid.poll().await.expect("poll should succeed");
+        assert_eq!(
+            events,
+            vec![GridEvent::Filled(placed(2, GridSide::Buy, "buy-2"))],
+            "no second order at an occupied level"
+        );
+
+        let events = grid.poll().await.expect("poll should succeed");
+        assert_eq!(
+            events,
+            vec![
+                GridEvent::Filled(placed(3, GridSide::Sell, "sell-3")),
+                GridEvent::Placed(placed(2, GridSide::Buy, "buy-2b")),
+            ]
+        );
+
+        let events = grid.poll().await.expect("poll should succeed");
+        assert!(events.is_empty());
+        assert_eq!(
+            grid.orders(),
+            &[
+                placed(5, GridSide::Sell, "sell-5"),
+                placed(2, GridSide::Buy, "buy-2b"),
+            ]
+        );
+        assert_eq!(
+            requests.lock().unwrap().len(),
+            8,
+            "the second history page is never read"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_trigger_orders_filter() {
+        let filter = TriggerOrderFilter::pair(SOL_MINT, USDC_MINT);
+        let params = filter.active_orders(TEST_USER_PUBKEY);
+        assert_eq!(params.user, TEST_USER_PUBKEY);
+        assert!(matches!(params.order_status, OrderStatus::Active));
+        assert_eq!(params.input_mint.as_deref(), Some(SOL_MINT));
+        assert_eq!(params.output_mint.as_deref(), Some(USDC_MINT));
+
+        let everything = TriggerOrderFilter::default().active_orders(TEST_USER_PUBKEY);
+        assert!(everything.input_mint.is_none() && everything.output_mint.is_none());
+
+        let client = JupiterClient::new("http://127.0.0.1:1");
+        let res = client
+            .cancel_all_trigger_orders(TEST_USER_PUBKEY, &filter)
+            .await;
+        assert!(
+            matches!(res, Err(JupiterClientError::RequestError(_))),
+            "listing failures should abort before cancelling"
+        );
+    }
+
+    #[test]
+    fn test_trigger_status_enums() {
+        assert_eq!(
+            TriggerOrderState::from("completed".to_string()),
+            TriggerOrderState::Completed
+        );
+        assert_eq!(
+            TriggerOrderState::from("Cancelled".to_string()),
+            TriggerOrderState::Cancelled
+        );
+        assert_eq!(
+            TriggerOrderState::from("Paused".to_string()),
+            TriggerOrderState::Other("Paused".to_string())
+        );
+        assert_eq!(TriggerOrderState::Completed.to_string(), "Completed");
+
+        assert_eq!(TradeAction::from("FILL".to_string()), TradeAction::Fill);
+        assert_eq!(
+            OrderStatus::from("History".to_string()),
+            OrderStatus::History
+        );
+
+        let status: OrderStatus =
+            serde_json::from_value(serde_json::json!("active")).expect("valid status");
+        assert_eq!(status, OrderStatus::Active);
+        assert_eq!(
+            serde_json::to_value(OrderStatus::History).expect("serializable"),
+            "history"
+        );
+    }
+
+    #[test]
+    fn test_trigger_orders_pagination_fields() {
+        let params = GetTriggerOrders::new(TEST_USER_PUBKEY, OrderStatus::History)
+            .page(3)
+            .include_failed_tx(true);
+        let json = serde_json::to_value(&params).expect("failed to serialize params");
+        assert_eq!(json["page"], 3);
+        assert_eq!(json["includeFailedTx"], true);
+
+        let response: OrderResponse = serde_json::from_value(serde_json::json!({
+            "user": TEST_USER_PUBKEY,
+            "orderStatus": "history",
+            "orders": [],
+            "totalPages": "4",
+            "page": 2
+        }))
+        .expect("failed to deserialize order response");
+        assert_eq!(response.total_pages, 4);
+        assert_eq!(response.page, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_execute_trigger_flows_surface_request_errors() {
+        let client = JupiterClient::new("http://127.0.0.1:1");
+        let keypair = Keypair::new();
+
+        let order = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            TEST_USER_PUBKEY,
+            TEST_USER_PUBKEY,
+            1_000_000_000,
+            200_000_000,
+        );
+        let created = client
+            .create_and_execute_trigger_order(&order, &keypair)
+            .await;
+        assert!(matches!(created, Err(JupiterClientError::RequestError(_))));
+
+        let cancel = CancelTriggerOrder::new(TEST_USER_PUBKEY, TEST_USER_PUBKEY);
+        let cancelled = client
+            .cancel_and_execute_trigger_order(&cancel, &keypair)
+            .await;
+        assert!(matches!(
+            cancelled,
+            Err(JupiterClientError::RequestError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_amend_trigger_order_reports_failed_replacement() {
+        let keypair = Keypair::new();
+        let order: TriggerOrder = serde_json::from_value(serde_json::json!({
+            "userPubkey": keypair.pubkey().to_string(),
+            "orderKey": "order",
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "makingAmount": "1",
+            "takingAmount": "200",
+            "remainingMakingAmount": "1",
+            "remainingTakingAmount": "200",
+            "rawMakingAmount": "1000000000",
+            "rawTakingAmount": "200000000",
+            "rawRemainingMakingAmount": "1000000000",
+            "rawRemainingTakingAmount": "200000000",
+            "slippageBps": "0",
+            "createdAt": "2025-05-30T16:22:51Z",
+            "updatedAt": "2025-05-30T16:22:51Z",
+            "status": "Open",
+            "openTx": "tx",
+            "closeTx": "",
+            "programVersion": "v1",
+            "trades": []
+        }))
+        .expect("failed to deserialize order");
+
+        let unreachable = JupiterClient::new("http://127.0.0.1:1");
+        let zero = unreachable
+            .amend_trigger_order(&order, Params::new(1_000_000_000, 0), &keypair)
+            .await;
+        assert!(matches!(
+            zero,
+            Err(JupiterClientError::ValidationError(
+                ValidationError::ZeroAmount("taking_amount")
+            ))
+        ));
+
+        // the mock answers both steps without an order account, so only the cancel succeeds
+        let tx =
+            unsigned_transaction_base64(&keypair.pubkey(), &[solana_pubkey::Pubkey::new_unique()]);
+        let transaction: &'static str = Box::leak(
+            format!(r#"{{"requestId":"req","transaction":"{tx}","code":0}}"#).into_boxed_str(),
+        );
+        let executed = r#"{"code":0,"signature":"cancel-sig","status":"Success"}"#;
+        let (url, requests) = mock_server_sequence(vec![transaction, executed, transaction]).await;
+        let client = JupiterClient::new(&url);
+
+        let res = client
+            .amend_trigger_order(&order, Params::new(1_000_000_000, 210_000_000), &keypair)
+            .await;
+        match res {
+            Err(JupiterClientError::ReplacementFailed {
+                order,
+                cancel_signature,
+                source,
+            }) => {
+                assert_eq!(order, "order");
+                assert_eq!(cancel_signature, "cancel-sig");
+                assert!(matches!(*source, JupiterClientError::InvalidRequest(_)));
+            }
+            other => panic!("expected ReplacementFailed, got {other:?}"),
+        }
+
+        let requests = requests.lock().unwrap();
+        let created: serde_json::Value = requests
+            .iter()
+            .filter_map(|body| serde_json::from_str::<serde_json::Value>(body).ok())
+            .find(|body| body.get("params").is_some())
+            .expect("replacement order should be requested");
+        assert_eq!(created["inputMint"], SOL_MINT);
+        assert_eq!(created["params"]["takingAmount"], "210000000");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_trigger_orders_chunks() {
+        let (url, requests) =
+            mock_server(r#"{"requestId":"req","transactions":["tx"],"code":0}"#).await;
+        let client = JupiterClient::new(&url);
+
+        let orders: Vec<String> = (0..12).map(|i| format!("order{i}")).collect();
+        let batches = client
+            .cancel_trigger_orders(&CancelTriggerOrders::new(TEST_USER_PUBKEY, orders))
+            .await
+            .expect("failed to cancel orders");
+        assert_eq!(batches.len(), 3, "12 orders should need 3 requests");
+
+        let mut sent: Vec<Vec<String>> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|body| {
+                let json: serde_json::Value =
+                    serde_json::from_str(body).expect("request body should be json");
+                json["order"]
+                    .as_str()
+                    .expect("orders should be comma separated")
+                    .split(',')
+                    .map(str::to_string)
+                    .collect()
+            })
+            .collect();
+        sent.sort_by_key(|chunk| chunk.len());
+        assert_eq!(
+            sent.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![
+                2,
+                MAX_TRIGGER_ORDERS_PER_CANCEL,
+                MAX_TRIGGER_ORDERS_PER_CANCEL
+            ]
+        );
+        assert_eq!(sent[0], vec!["order10", "order11"]);
+    }
+
+    #[test]
+    fn test_export_trigger_history() {
+        let order: TriggerOrder = serde_json::from_value(serde_json::json!({
+            "userPubkey": TEST_USER_PUBKEY,
+            "orderKey": "order",
+            "inputMint": SOL_MINT,
+            "outputMint": USDC_MINT,
+            "makingAmount": "1",
+            "takingAmount": "200",
+            "remainingMakingAmount": "0",
+            "remainingTakingAmount": "0",
+            "rawMakingAmount": "1000000000",
+            "rawTakingAmount": "200000000",
+            "rawRemainingMakingAmount": "0",
+            "rawRemainingTakingAmount": "0",
+            "slippageBps": "0",
+            "createdAt": "2025-05-30T16:22:51Z",
+            "updatedAt": "2025-05-30T16:22:51Z",
+            "status": "Completed",
+            "openTx": "tx",
+            "closeTx": "",
+            "programVersion": "v1",
+            "trades": [{
+                "orderKey": "order",
+                "keeper": "keeper",
+                "inputMint": SOL_MINT,
+                "outputMint": USDC_MINT,
+                "inputAmount": "1",
+                "outputAmount": "200.5",
+                "rawInputAmount": "1000000000",
+                "rawOutputAmount": "200500000",
+                "feeMint": USDC_MINT,
+                "feeAmount": "0.2",
+                "rawFeeAmount": "200000",
+                "txId": "fill,tx",
+                "confirmedAt": "2025-05-30T16:22:51Z",
+                "action": "Fill"
+            }]
+        }))
+        .expect("failed to deserialize order");
+
+        let fills = FillRecord::from_trigger_orders(std::slice::from_ref(&order));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].output_amount, "200.5");
+        assert_eq!(fills[0].raw_fee_amount, Some(200_000));
+
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &fills).expect("failed to write csv");
+        let csv = String::from_utf8(csv).expect("csv should be utf-8");
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], FillRecord::COLUMNS.join(","));
+        assert!(
+            lines[1].starts_with("trigger,order,\"fill,tx\",2025-05-30T16:22:51Z,Fill,"),
+            "unexpected row: {}",
+            lines[1]
+        );
+        assert!(
+            lines[1].ends_with(",1,200.5,0.2,1000000000,200500000,200000"),
+            "amounts should be written as sent: {}",
+            lines[1]
+        );
+
+        let mut jsonl = Vec::new();
+        write_json_lines(&mut jsonl, &[OrderRecord::from(&order)])
+            .expect("failed to write json lines");
+        let line: serde_json::Value =
+            serde_json::from_slice(&jsonl).expect("line should be a json object");
+        assert_eq!(line["status"], "Completed");
+        assert_eq!(line["raw_making_amount"], 1_000_000_000u64);
+        assert_eq!(line["taking_amount"], "200");
+        assert_eq!(line["expired_at"], serde_json::Value::Null);
+    }
+
     #[tokio::test]
     async fn test_create_order() {
         let client = create_test_client();
@@ -73,10 +1272,7 @@ mod trigger_tests {
     async fn test_execute_order_fail() {
         let client = create_test_client();
 
-        let invalid_order = ExecuteTriggerOrder {
-            request_id: "".to_string(),
-            signed_transaction: "".to_string(),
-        };
+        let invalid_order = ExecuteTriggerOrder::new("", "");
 
         let result = client.execute_trigger_order(&invalid_order).await;
         assert!(result.is_err(), "Executing an invalid order should fail");
@@ -104,8 +1300,332 @@ mod trigger_tests {
         );
 
         assert_eq!(
-            data.order_status, "history",
+            data.order_status,
+            OrderStatus::History,
             "order status should be history"
         );
     }
+
+    /// Signs after an await point, like a hardware wallet or remote signing service.
+    struct RemoteSigner {
+        keypair: Keypair,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl TransactionSigner for RemoteSigner {
+        fn address(&self) -> Pubkey {
+            self.keypair.pubkey()
+        }
+
+        async fn sign(&self, message: &[u8]) -> Result<Signature, JupiterClientError> {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(self.keypair.sign_message(message))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_execute_with_async_signer() {
+        use base64::{Engine, engine::general_purpose::STANDARD};
+        use solana_transaction::versioned::VersionedTransaction;
+
+        let signer = std::sync::Arc::new(RemoteSigner {
+            keypair: Keypair::new(),
+            calls: Default::default(),
+        });
+        let tx = unsigned_transaction_base64(&signer.address(), &[Pubkey::new_unique()]);
+        let cancel: &'static str = Box::leak(
+            format!(r#"{{"requestId":"req","transaction":"{tx}","code":0}}"#).into_boxed_str(),
+        );
+        let executed = r#"{"code":0,"signature":"cancel-sig","status":"Success"}"#;
+        let (url, requests) = mock_server_sequence(vec![cancel, executed]).await;
+        let client = JupiterClient::new(&url);
+
+        // the flow future must stay Send to be spawned
+        let task = tokio::spawn({
+            let signer = signer.clone();
+            async move {
+                client
+                    .cancel_and_execute_trigger_order(
+                        &CancelTriggerOrder::new(&signer.address().to_string(), "order"),
+                        signer.as_ref(),
+                    )
+                    .await
+            }
+        });
+        let res = task.await.unwrap().expect("failed to cancel");
+        assert_eq!(res.signature, "cancel-sig");
+        assert_eq!(signer.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let requests = requests.lock().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&requests[1]).unwrap();
+        let signed = body["signedTransaction"].as_str().unwrap();
+        let decoded: VersionedTransaction =
+            bincode::deserialize(&STANDARD.decode(signed).unwrap()).unwrap();
+        assert!(
+            decoded.signatures[0].verify(signer.address().as_ref(), &decoded.message.serialize())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_policy_checked_before_signing() {
+        let keypair = Keypair::new();
+        let program = solana_pubkey::Pubkey::new_unique();
+        let tx = unsigned_transaction_base64(&keypair.pubkey(), &[program]);
+        let summary = TransactionSummary::from_base64(&tx).unwrap();
+        let cancel: &'static str = Box::leak(
+            format!(r#"{{"requestId":"req","transaction":"{tx}","code":0}}"#).into_boxed_str(),
+        );
+        let executed = r#"{"code":0,"signature":"cancel-sig","status":"Success"}"#;
+        let request = CancelTriggerOrder::new(&keypair.pubkey().to_string(), "order");
+
+        // unknown program: rejected before anything is signed or executed
+        let (url, requests) = mock_server_sequence(vec![cancel, executed]).await;
+        let client = JupiterClient::new(&url).with_transaction_policy(TransactionPolicy::default());
+        let res = client
+            .cancel_and_execute_trigger_order(&request, &keypair)
+            .await;
+        assert!(
+            matches!(&res, Err(JupiterClientError::PolicyViolation(msg)) if msg.contains(&program.to_string())),
+            "unknown program should be rejected: {res:?}"
+        );
+        assert_eq!(
+            requests.lock().unwrap().len(),
+            1,
+            "nothing should be executed"
+        );
+
+        // the default policy covers every Jupiter API, the per-API ones only their programs
+        let limit_order: solana_pubkey::Pubkey = JUPITER_LIMIT_ORDER_PROGRAM_ID.parse().unwrap();
+        let dca: solana_pubkey::Pubkey = JUPITER_DCA_PROGRAM_ID.parse().unwrap();
+        let trigger_tx = TransactionSummary::from_base64(&unsigned_transaction_base64(
+            &keypair.pubkey(),
+            &[limit_order],
+        ))
+        .unwrap();
+        let recurring_tx = TransactionSummary::from_base64(&unsigned_transaction_base64(
+            &keypair.pubkey(),
+            &[dca],
+        ))
+        .unwrap();
+        let default = TransactionPolicy::default();
+        assert!(default.verify(&trigger_tx).is_ok());
+        assert!(default.verify(&recurring_tx).is_ok());
+        assert!(TransactionPolicy::trigger().verify(&trigger_tx).is_ok());
+        assert!(TransactionPolicy::recurring().verify(&recurring_tx).is_ok());
+        assert!(TransactionPolicy::trigger().verify(&recurring_tx).is_err());
+        assert!(TransactionPolicy::ultra().verify(&trigger_tx).is_err());
+
+        // writable allowlist: the signer is always allowed, other writable accounts are not
+        let programs = ProgramAllowlist::new([program.to_string()]);
+        let writable: Vec<&String> = summary
+            .writable_accounts
+            .iter()
+            .filter(|account| **account != keypair.pubkey().to_string())
+            .collect();
+        let strict = TransactionPolicy::new(programs.clone()).allow_writable("other");
+        assert!(matches!(
+            strict.verify(&summary),
+            Err(JupiterClientError::PolicyViolation(msg)) if msg.contains(writable[0].as_str())
+        ));
+        let policy = writable
+            .iter()
+            .fold(TransactionPolicy::new(programs), |policy, account| {
+                policy.allow_writable(account.as_str())
+            });
+        assert!(policy.verify(&summary).is_ok());
+
+        let (url, _) = mock_server_sequence(vec![cancel, executed]).await;
+        let client = JupiterClient::new(&url).with_transaction_policy(policy);
+        let res = client
+            .cancel_and_execute_trigger_order(&request, &keypair)
+            .await
+            .expect("allowed transaction should be executed");
+        assert_eq!(res.signature, "cancel-sig");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_trigger_order_when_blockhash_expired() {
+        let maker = Keypair::new().pubkey();
+        let tx = unsigned_transaction_base64(&maker, &[solana_pubkey::Pubkey::new_unique()]);
+        let created: TriggerResponse = serde_json::from_str(&format!(
+            r#"{{"requestId":"old","transaction":"{tx}","code":0}}"#
+        ))
+        .unwrap();
+        let order = CreateTriggerOrder::new(
+            SOL_MINT,
+            USDC_MINT,
+            &maker.to_string(),
+            &maker.to_string(),
+            1_000_000_000,
+            200_000_000,
+        );
+        let valid = |valid: bool| -> &'static str {
+            Box::leak(
+                format!(r#"{{"jsonrpc":"2.0","id":1,"result":{{"context":{{"slot":1}},"value":{valid}}}}}"#)
+                    .into_boxed_str(),
+            )
+        };
+
+        let (api_url, api_requests) =
+            mock_server(r#"{"requestId":"new","transaction":"tx","code":0}"#).await;
+        let client = JupiterClient::new(&api_url);
+
+        let (rpc_url, rpc_requests) = mock_server(valid(true)).await;
+        let rpc = SolanaRpc::new(&rpc_url);
+        let kept = client
+            .refresh_trigger_order(created.clone(), &order, &rpc)
+            .await
+            .unwrap();
+        assert_eq!(kept.request_id, "old");
+        assert!(api_requests.lock().unwrap().is_empty());
+        let check: serde_json::Value =
+            serde_json::from_str(&rpc_requests.lock().unwrap()[0]).unwrap();
+        assert_eq!(check["method"], "isBlockhashValid");
+        assert_eq!(check["params"][1]["commitment"], "processed");
+
+        let (rpc_url, _) = mock_server(valid(false)).await;
+        let rpc = SolanaRpc::new(&rpc_url);
+        let refreshed = client
+            .refresh_trigger_order(created, &order, &rpc)
+            .await
+            .unwrap();
+        assert_eq!(refreshed.request_id, "new");
+        assert_eq!(api_requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_generic_sign_and_execute() {
+        async fn run<T: Executable>(
+            client: &JupiterClient,
+            unsigned: &T,
+            keypair: &Keypair,
+        ) -> Result<T::Output, JupiterClientError> {
+            client.sign_and_execute(unsigned, keypair).await
+        }
+
+        let keypair = Keypair::new();
+        let tx = unsigned_transaction_base64(&keypair.pubkey(), &[Pubkey::new_unique()]);
+
+        let (url, requests) =
+            mock_server(r#"{"code":0,"signature":"trigger-sig","status":"Success"}"#).await;
+        let client = JupiterClient::new(&url);
+        let created = TriggerResponse {
+            request_id: "trigger-req".to_string(),
+            transaction: tx.clone(),
+            transactions: None,
+            order: None,
+            code: 0,
+        };
+        let executed = run(&client, &created, &keypair).await.unwrap();
+        assert_eq!(executed.signature, "trigger-sig");
+        {
+            let requests = requests.lock().unwrap();
+            assert!(requests[0].contains(r#""requestId":"trigger-req""#));
+            let body: serde_json::Value = serde_json::from_str(&requests[0]).unwrap();
+            let signed =
+                TransactionSummary::from_base64(body["signedTransaction"].as_str().unwrap());
+            assert!(signed.is_ok());
+        }
+
+        let (url, requests) =
+            mock_server(r#"{"signature":"recurring-sig","status":"Success"}"#).await;
+        let client = JupiterClient::new(&url);
+        let recurring = RecurringResponse {
+            request_id: "recurring-req".to_string(),
+            transaction: tx,
+        };
+        let executed = run(&client, &recurring, &keypair).await.unwrap();
+        assert_eq!(executed.signature, "recurring-sig");
+        assert!(requests.lock().unwrap()[0].contains(r#""requestId":"recurring-req""#));
+
+        // nothing to sign: no request is sent
+        let (url, requests) = mock_server("{}").await;
+        let client = JupiterClient::new(&url);
+        let empty = TriggerResponse {
+            request_id: "req".to_string(),
+            transaction: String::new(),
+            transactions: None,
+            order: None,
+            code: 0,
+        };
+        let res = run(&client, &empty, &keypair).await;
+        assert!(
+            matches!(res, Err(JupiterClientError::InvalidRequest(_))),
+            "{res:?}"
+        );
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sign_execute_and_confirm() {
+        let keypair = Keypair::new();
+        let tx = unsigned_transaction_base64(&keypair.pubkey(), &[Pubkey::new_unique()]);
+        let created = TriggerResponse {
+            request_id: "trigger-req".to_string(),
+            transaction: tx,
+            transactions: None,
+            order: None,
+            code: 0,
+        };
+        let confirmed = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":9},"value":[{"slot":8,"confirmations":1,"err":null,"confirmationStatus":"confirmed"}]}}"#;
+        let timeout = Duration::from_secs(5);
+
+        let (url, _) = mock_server(r#"{"code":0,"signature":"sig","status":"Success"}"#).await;
+        let client = JupiterClient::new(&url);
+        let (rpc_url, rpc_requests) = mock_server(confirmed).await;
+        let rpc = SolanaRpc::new(&rpc_url);
+        let (executed, status) = client
+            .sign_execute_and_confirm(
+                &created,
+                &keypair,
+                &rpc,
+                CommitmentLevel::Confirmed,
+                timeout,
+            )
+            .await
+            .unwrap();
+        assert_eq!(executed.signature, "sig");
+        assert_eq!(status.slot, 8);
+        let check: serde_json::Value =
+            serde_json::from_str(&rpc_requests.lock().unwrap()[0]).unwrap();
+        assert_eq!(check["method"], "getSignatureStatuses");
+        assert_eq!(check["params"][0][0], "sig");
+
+        // failed on-chain
+        let failed = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":9},"value":[{"slot":8,"confirmations":1,"err":{"InstructionError":[0,"Custom"]},"confirmationStatus":"confirmed"}]}}"#;
+        let (rpc_url, _) = mock_server(failed).await;
+        let res = client
+            .sign_execute_and_confirm(
+                &created,
+                &keypair,
+                &SolanaRpc::new(&rpc_url),
+                CommitmentLevel::Confirmed,
+                timeout,
+            )
+            .await;
+        assert!(
+            matches!(res, Err(JupiterClientError::TransactionFailed { ref signature, .. }) if signature == "sig"),
+            "{res:?}"
+        );
+
+        // reported failed by the execute endpoint: the RPC is not polled
+        let (url, _) = mock_server(r#"{"code":0,"signature":"sig","status":"Failed"}"#).await;
+        let client = JupiterClient::new(&url);
+        let (rpc_url, rpc_requests) = mock_server(confirmed).await;
+        let res = client
+            .sign_execute_and_confirm(
+                &created,
+                &keypair,
+                &SolanaRpc::new(&rpc_url),
+                CommitmentLevel::Confirmed,
+                timeout,
+            )
+            .await;
+        assert!(
+            matches!(res, Err(JupiterClientError::TransactionFailed { .. })),
+            "{res:?}"
+        );
+        assert!(rpc_requests.lock().unwrap().is_empty());
+    }
 }
@@ -1,13 +1,35 @@
 #[cfg(test)]
 mod ultra_tests {
-    use jup_ag_sdk::types::{UltraExecuteOrderRequest, UltraOrderRequest};
+    use std::{collections::HashMap, time::Duration};
 
-    use crate::common::{JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, create_test_client};
+    use jup_ag_sdk::{
+        JupiterClient, JupiterClientError, ValidationError, codec,
+        compute_budget::ComputeBudgetOverride,
+        introspection::{
+            COMPUTE_BUDGET_PROGRAM_ID, ComputeBudget, JUPITER_V6_PROGRAM_ID, ProgramAllowlist,
+            SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID, TransactionSummary, Transfer, TransferKind,
+        },
+        signer::{Keypair, KeypairSigner, PartialTransaction, TransactionSigner},
+        twap::{SliceStatus, TwapExecutor},
+        types::{
+            PolicyVerdict, PreTrade, Price, PriorityLevel, Severity, Shield, Status, TokenBalance,
+            TokenBalancesExt, TokenBalancesResponse, TokenInfo, TokenPolicy,
+            UltraExecuteOrderRequest, UltraExecuteOrderResponse, UltraOrderRequest,
+            UltraOrderResponse, ValuedBalances, Warning, WarningKind,
+        },
+    };
+
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+
+    use crate::common::{
+        JUP_MINT, SOL_MINT, TEST_AMOUNT, TEST_USER_PUBKEY, USDC_MINT, create_test_client,
+        mock_http_server, mock_server_sequence, unsigned_transaction_base64,
+    };
 
     #[test]
     fn test_ultra_order_request_builder() {
-        let order =
-            UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).add_taker(TEST_USER_PUBKEY);
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).taker(TEST_USER_PUBKEY);
 
         assert_eq!(order.input_mint, SOL_MINT, "input mint should match");
         assert_eq!(order.output_mint, JUP_MINT, "output mint should match");
@@ -23,8 +45,7 @@ mod ultra_tests {
     async fn test_get_ultra_order_successful() {
         let client = create_test_client();
 
-        let order =
-            UltraOrderRequest::new(SOL_MINT, JUP_MINT, 10000000).add_taker(TEST_USER_PUBKEY);
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 10000000).taker(TEST_USER_PUBKEY);
 
         match client.get_ultra_order(&order).await {
             Ok(order_res) => {
@@ -58,8 +79,7 @@ mod ultra_tests {
     async fn test_ultra_order_with_invalid_data() {
         let client = create_test_client();
 
-        let order =
-            UltraOrderRequest::new(SOL_MINT, JUP_MINT, 10000000).add_taker(TEST_USER_PUBKEY);
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 10000000).taker(TEST_USER_PUBKEY);
 
         let order_res = client
             .get_ultra_order(&order)
@@ -67,12 +87,12 @@ mod ultra_tests {
             .expect("get ultra order failed");
 
         let execute_res = client
-            .ultra_execute_order(&UltraExecuteOrderRequest {
-                signed_transaction: order_res
+            .ultra_execute_order(&UltraExecuteOrderRequest::new(
+                &order_res
                     .transaction
                     .expect("transaction is not present in the order response"),
-                request_id: order_res.request_id,
-            })
+                &order_res.request_id,
+            ))
             .await;
 
         assert!(
@@ -85,7 +105,7 @@ mod ultra_tests {
     async fn test_get_ultra_order_with_invalid_params() {
         let client = create_test_client();
 
-        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 10000).add_taker("invalid taker");
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, 10000).taker("invalid taker");
         // This account does not have that much SOL
 
         let res = client.get_ultra_order(&order).await;
@@ -122,12 +142,12 @@ mod ultra_tests {
         let shield_res = client.shield(&mints).await.expect("Failed to get shield");
         assert_eq!(
             shield_res.warnings.get(&mints[0]).expect("token not found")[0].warning_type,
-            "HAS_FREEZE_AUTHORITY"
+            WarningKind::HasFreezeAuthority
         );
 
         assert_eq!(
             shield_res.warnings.get(&mints[0]).expect("token not found")[0].severity,
-            "warning"
+            Severity::Warning
         );
     }
 
@@ -169,4 +189,947 @@ mod ultra_tests {
             .expect("Metis router not found");
         assert_eq!(metis.name, "Metis v1.6");
     }
+
+    #[tokio::test]
+    async fn test_validate_exclude_routers() {
+        let client = create_test_client();
+
+        let excluded = vec![
+            "metis".to_string(),
+            "OKX".to_string(),
+            "not-a-router".to_string(),
+        ];
+        let unknown = client
+            .validate_exclude_routers(&excluded)
+            .await
+            .expect("Failed to validate routers");
+        assert_eq!(unknown, vec!["not-a-router".to_string()]);
+
+        let cached = client
+            .routers()
+            .await
+            .expect("Failed to get cached routers");
+        let refreshed = client
+            .refresh_routers()
+            .await
+            .expect("Failed to refresh routers");
+        assert_eq!(
+            cached, refreshed,
+            "cached routers should match a fresh fetch"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ultra_execute_duplicate_guard() {
+        let failed = r#"{"status":"Failed","code":-1006,"error":"timed out"}"#;
+        let success = r#"{"status":"Success","code":0,"signature":"sig-1"}"#;
+        let req = UltraExecuteOrderRequest::new("signed-tx", "request-1");
+
+        // off by default: re-sending a successful order polls its status
+        let (url, requests) = mock_server_sequence(vec![success]).await;
+        let unguarded = JupiterClient::new(&url);
+        for _ in 0..2 {
+            let res = unguarded.ultra_execute_order(&req).await;
+            assert!(matches!(res, Ok(ref res) if res.status == Status::Success));
+        }
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        // nothing listens on port 1: an unanswered submission can be retried
+        let guarded = JupiterClient::new("http://127.0.0.1:1").with_execute_guard(16);
+        for _ in 0..2 {
+            let res = guarded.ultra_execute_order(&req).await;
+            assert!(matches!(res, Err(JupiterClientError::RequestError(_))));
+        }
+
+        let ok = |body: &str| {
+            format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        };
+        let (url, requests, _) = mock_http_server(vec![
+            "HTTP/1.1 400 Bad Request\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                .to_string(),
+            ok(failed),
+            ok(success),
+        ])
+        .await;
+        let client = JupiterClient::new(&url).with_execute_guard(16);
+        let rejected = client.ultra_execute_order(&req).await;
+        assert!(
+            matches!(rejected, Err(JupiterClientError::ApiError(_, status)) if status == 400),
+            "got {rejected:?}"
+        );
+        let first = client
+            .ultra_execute_order(&req)
+            .await
+            .expect("a rejected order can be retried");
+        assert_eq!(first.status, Status::Failed);
+
+        let second = client.ultra_execute_order(&req).await;
+        assert!(
+            matches!(second, Err(JupiterClientError::DuplicateSubmission(ref id)) if id == "request-1"),
+            "a submitted order should be refused even if it failed, got {second:?}"
+        );
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        assert!(client.forget_ultra_submission("request-1"));
+        assert!(!client.forget_ultra_submission("request-1"));
+
+        // the first call reserves the request_id before the second one checks it
+        let (first, second) = tokio::join!(
+            client.ultra_execute_order(&req),
+            client.ultra_execute_order(&req)
+        );
+        assert_eq!(first.expect("first call is sent").status, Status::Success);
+        assert!(matches!(
+            second,
+            Err(JupiterClientError::DuplicateSubmission(_))
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_token_balances_many_keys_by_wallet() {
+        let client = JupiterClient::new("http://127.0.0.1:1");
+        let wallets = vec![
+            TEST_USER_PUBKEY.to_string(),
+            SOL_MINT.to_string(),
+            TEST_USER_PUBKEY.to_string(),
+        ];
+
+        let balances = client
+            .get_token_balances_many_with_concurrency(&wallets, 0)
+            .await;
+        assert_eq!(
+            balances.len(),
+            2,
+            "duplicate wallets should be fetched once"
+        );
+        for wallet in [TEST_USER_PUBKEY, SOL_MINT] {
+            assert!(
+                matches!(
+                    balances.get(wallet),
+                    Some(Err(JupiterClientError::RequestError(_)))
+                ),
+                "each wallet should carry its own error"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ultra_execute_response_display() {
+        let parse = |value: serde_json::Value| -> UltraExecuteOrderResponse {
+            serde_json::from_value(value).expect("failed to parse execute response")
+        };
+
+        let success = parse(serde_json::json!({
+            "status": "Success",
+            "code": 0,
+            "signature": "5x",
+            "inputAmountResult": "1000000000",
+            "outputAmountResult": "400000000"
+        }));
+        assert_eq!(
+            success.to_string(),
+            "Success 5x in: 1000000000 out: 400000000"
+        );
+
+        let failed = parse(serde_json::json!({
+            "status": "Failed",
+            "code": -1005,
+            "error": "Order expired"
+        }));
+        assert_eq!(failed.to_string(), "Failed (-1005): Order expired");
+    }
+
+    #[test]
+    fn test_ultra_execute_response_is_expired() {
+        let parse = |value: serde_json::Value| -> UltraExecuteOrderResponse {
+            serde_json::from_value(value).expect("failed to parse execute response")
+        };
+
+        let expired = parse(serde_json::json!({
+            "status": "Failed",
+            "code": -1005,
+            "error": "Order expired"
+        }));
+        assert!(expired.is_expired());
+
+        let by_message = parse(serde_json::json!({
+            "status": "Failed",
+            "code": -1000,
+            "error": "Quote has expired"
+        }));
+        assert!(by_message.is_expired());
+
+        let slippage = parse(serde_json::json!({
+            "status": "Failed",
+            "code": 6001,
+            "error": "Slippage tolerance exceeded"
+        }));
+        assert!(!slippage.is_expired());
+
+        let success = parse(serde_json::json!({
+            "status": "Success",
+            "code": 0,
+            "signature": "5x"
+        }));
+        assert!(!success.is_expired());
+    }
+
+    #[test]
+    fn test_ultra_order_request_overrides_query() {
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .slippage_bps(75)
+            .priority_level(PriorityLevel::VeryHigh)
+            .param("receiver", TEST_USER_PUBKEY);
+        assert!(order.validate().is_ok());
+
+        let request = JupiterClient::new("https://lite-api.jup.ag")
+            .client
+            .get("https://lite-api.jup.ag/ultra/v1/order")
+            .query(&order)
+            .build()
+            .expect("failed to build request");
+        let query = request.url().query().expect("query should be set");
+        assert!(query.contains("slippageBps=75"), "query: {query}");
+        assert!(query.contains("priorityLevel=veryHigh"), "query: {query}");
+        assert!(
+            query.contains(&format!("receiver={TEST_USER_PUBKEY}")),
+            "query: {query}"
+        );
+        assert!(
+            !query.contains("taker"),
+            "unset options should be omitted: {query}"
+        );
+
+        let too_much = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).slippage_bps(10_001);
+        assert!(matches!(
+            too_much.validate(),
+            Err(ValidationError::BpsOutOfRange {
+                field: "slippage_bps",
+                ..
+            })
+        ));
+    }
+
+    fn balance(ui_amount: f64) -> TokenBalance {
+        TokenBalance {
+            amount: ((ui_amount * 1e6) as u64).to_string(),
+            ui_amount,
+            slot: 1,
+            is_frozen: false,
+        }
+    }
+
+    fn price(usd_price: f64) -> Price {
+        Price {
+            usd_price,
+            block_id: 1,
+            decimals: 6,
+            price_change_24h: None,
+        }
+    }
+
+    #[test]
+    fn test_valued_balances_from_prices() {
+        let balances: TokenBalancesResponse = [
+            ("SOL".to_string(), balance(2.0)),
+            (JUP_MINT.to_string(), balance(100.0)),
+            (
+                "UnpricedMint1111111111111111111111111111111".to_string(),
+                balance(5.0),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let prices: HashMap<String, Price> = [
+            (SOL_MINT.to_string(), price(150.0)),
+            (JUP_MINT.to_string(), price(0.5)),
+        ]
+        .into_iter()
+        .collect();
+
+        let valued = ValuedBalances::from_prices(balances, &prices);
+
+        assert_eq!(valued.total_usd_value, 350.0);
+        assert_eq!(valued.tokens[0].mint, "SOL");
+        assert_eq!(valued.tokens[0].usd_value, Some(300.0));
+        assert_eq!(valued.tokens[1].mint, JUP_MINT);
+        assert_eq!(valued.tokens[2].usd_price, None);
+        assert_eq!(valued.tokens[2].usd_value, None);
+    }
+
+    #[test]
+    fn test_token_balances_helpers() {
+        let mut frozen = balance(3.0);
+        frozen.is_frozen = true;
+        let balances: TokenBalancesResponse = [
+            ("SOL".to_string(), balance(2.0)),
+            (JUP_MINT.to_string(), balance(100.0)),
+            (USDC_MINT.to_string(), balance(0.0)),
+            (SOL_MINT.to_string(), frozen),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(balances.sol().map(|b| b.ui_amount), Some(2.0));
+        assert_eq!(
+            balances.get_by_mint(JUP_MINT).and_then(|b| b.amount_u64()),
+            Some(100_000_000)
+        );
+        assert!(balances.get_by_mint("missing").is_none());
+
+        let mut non_zero: Vec<&str> = balances.non_zero().map(|(m, _)| m.as_str()).collect();
+        non_zero.sort();
+        assert_eq!(non_zero, vec![JUP_MINT, "SOL", SOL_MINT]);
+
+        let mut spendable: Vec<&str> = balances.spendable().map(|(m, _)| m.as_str()).collect();
+        spendable.sort();
+        assert_eq!(spendable, vec![JUP_MINT, "SOL"]);
+    }
+
+    #[test]
+    fn test_shield_warning_deserialization() {
+        let json = r#"{
+            "warnings": {
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v": [
+                    { "type": "HAS_FREEZE_AUTHORITY", "message": "freeze", "severity": "warning" },
+                    { "type": "SOMETHING_NEW", "message": "new", "severity": "critical" }
+                ]
+            }
+        }"#;
+
+        let shield: Shield = serde_json::from_str(json).expect("failed to deserialize shield");
+        let warnings = &shield.warnings[USDC_MINT];
+
+        assert_eq!(warnings[0].warning_type, WarningKind::HasFreezeAuthority);
+        assert_eq!(warnings[0].severity, Severity::Warning);
+        assert_eq!(
+            warnings[1].warning_type,
+            WarningKind::Other("SOMETHING_NEW".to_string())
+        );
+        assert_eq!(warnings[1].severity, Severity::Critical);
+
+        let round_trip = serde_json::to_value(&warnings[1]).unwrap();
+        assert_eq!(round_trip["type"], "SOMETHING_NEW");
+        assert_eq!(round_trip["severity"], "critical");
+    }
+
+    #[test]
+    fn test_token_policy_evaluate() {
+        let token: TokenInfo = serde_json::from_value(serde_json::json!({
+            "id": JUP_MINT,
+            "name": "Jupiter",
+            "symbol": "JUP",
+            "decimals": 6,
+            "tokenProgram": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "organicScore": 20.0,
+            "organicScoreLabel": "low",
+            "isVerified": true,
+            "audit": { "mintAuthorityDisabled": true, "freezeAuthorityDisabled": false },
+        }))
+        .expect("failed to build token info");
+        let warning = |kind: &str, severity: &str| {
+            serde_json::from_value::<Warning>(serde_json::json!({
+                "type": kind,
+                "message": "warning",
+                "severity": severity,
+            }))
+            .unwrap()
+        };
+
+        let policy = TokenPolicy::default();
+        let decision = policy.evaluate(JUP_MINT, Some(&token), &[]);
+        assert_eq!(decision.verdict, PolicyVerdict::Flag);
+        assert_eq!(
+            decision.reasons.len(),
+            1,
+            "only the freeze authority is flagged"
+        );
+
+        let critical = [warning("NOT_SELLABLE", "critical")];
+        assert!(
+            policy
+                .evaluate(JUP_MINT, Some(&token), &critical)
+                .is_denied()
+        );
+        assert!(policy.evaluate("unknown", None, &[]).is_denied());
+
+        let lenient = TokenPolicy {
+            freeze_authority: PolicyVerdict::Allow,
+            ..TokenPolicy::default()
+        }
+        .warning(WarningKind::NotSellable, PolicyVerdict::Flag);
+        assert!(lenient.evaluate(JUP_MINT, Some(&token), &[]).is_allowed());
+        assert_eq!(
+            lenient.evaluate(JUP_MINT, Some(&token), &critical).verdict,
+            PolicyVerdict::Flag
+        );
+
+        let strict = lenient.min_organic_score(50.0, PolicyVerdict::Deny);
+        let decision = strict.evaluate(JUP_MINT, Some(&token), &[]);
+        assert!(decision.is_denied());
+        assert!(decision.reasons[0].message.contains("organic score"));
+    }
+
+    #[test]
+    fn test_ultra_order_request_validation() {
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).taker(TEST_USER_PUBKEY);
+        assert!(order.validate().is_ok());
+
+        let low_fee = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .referral_account(TEST_USER_PUBKEY)
+            .referral_fee(10);
+        assert_eq!(
+            low_fee.validate(),
+            Err(ValidationError::BpsOutOfRange {
+                field: "referral_fee",
+                value: 10,
+                min: 50,
+                max: 255,
+            })
+        );
+
+        let no_account = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).referral_fee(100);
+        assert_eq!(
+            no_account.validate(),
+            Err(ValidationError::MissingField("referral_account"))
+        );
+
+        let bad_taker = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT).taker("nope");
+        assert!(matches!(
+            bad_taker.validate(),
+            Err(ValidationError::InvalidAddress { field: "taker", .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_ultra_order_rejects_invalid_referral_fee() {
+        let client = create_test_client();
+        let order = UltraOrderRequest::new(SOL_MINT, JUP_MINT, TEST_AMOUNT)
+            .referral_account(TEST_USER_PUBKEY)
+            .referral_fee(1);
+
+        let res = client.get_ultra_order(&order).await;
+        assert!(matches!(res, Err(JupiterClientError::ValidationError(_))));
+    }
+
+    fn ultra_order_json(gasless: bool, fee_payer: &str) -> serde_json::Value {
+        serde_json::json!({
+            "mode": "ultra",
+            "inputMint": USDC_MINT,
+            "outputMint": SOL_MINT,
+            "inAmount": "10000000",
+            "outAmount": "66000000",
+            "otherAmountThreshold": "65670000",
+            "swapMode": "ExactIn",
+            "slippageBps": 50,
+            "priceImpactPct": "0",
+            "routePlan": [],
+            "feeBps": 10,
+            "prioritizationFeeLamports": 0,
+            "swapType": "aggregator",
+            "transaction": "AQAB",
+            "gasless": gasless,
+            "requestId": "req-1",
+            "totalTime": 120,
+            "taker": TEST_USER_PUBKEY,
+            "signatureFeePayer": fee_payer,
+        })
+    }
+
+    #[test]
+    fn test_ultra_order_gasless_helpers() {
+        let sponsor = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+        let gasless: UltraOrderResponse =
+            serde_json::from_value(ultra_order_json(true, sponsor)).unwrap();
+        assert!(gasless.is_gasless());
+        assert_eq!(gasless.fee_payer(), Some(sponsor));
+        assert_eq!(gasless.sponsor(), Some(sponsor));
+
+        let self_paid: UltraOrderResponse =
+            serde_json::from_value(ultra_order_json(false, TEST_USER_PUBKEY)).unwrap();
+        assert!(!self_paid.is_gasless());
+        assert_eq!(self_paid.fee_payer(), Some(TEST_USER_PUBKEY));
+        assert_eq!(self_paid.sponsor(), None);
+    }
+
+    #[test]
+    fn test_ultra_order_trade_cost() {
+        let mut json = ultra_order_json(false, TEST_USER_PUBKEY);
+        json["signatureFeeLamports"] = 5_000.into();
+        json["prioritizationFeeLamports"] = 20_000.into();
+        json["rentFeeLamports"] = 2_039_280.into();
+        json["platformFee"] = serde_json::json!({ "amount": "6600", "feeBps": 10 });
+        let order: UltraOrderResponse = serde_json::from_value(json.clone()).unwrap();
+
+        let cost = order.trade_cost();
+        assert_eq!(cost.sol_fees_lamports(), 5_000 + 20_000 + 2_039_280);
+        let totals = cost.total_by_mint();
+        // the platform fee is charged in SOL, the output mint, on top of the SOL costs
+        assert_eq!(totals[SOL_MINT], 5_000 + 20_000 + 2_039_280 + 6_600);
+
+        // a sponsor pays the signature and priority fees of gasless orders
+        let sponsor = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+        json["gasless"] = true.into();
+        json["signatureFeePayer"] = sponsor.into();
+        json["prioritizationFeePayer"] = sponsor.into();
+        let gasless: UltraOrderResponse = serde_json::from_value(json).unwrap();
+        let cost = gasless.trade_cost();
+        assert_eq!(cost.network_fee_lamports, 0);
+        assert_eq!(cost.priority_fee_lamports, 0);
+        assert_eq!(cost.rent_lamports, 2_039_280);
+    }
+
+    #[test]
+    fn test_pretrade_warnings() {
+        let warning = |kind: &str, severity: &str| -> Warning {
+            serde_json::from_value(serde_json::json!({
+                "type": kind,
+                "message": kind,
+                "severity": severity,
+            }))
+            .unwrap()
+        };
+        let order: UltraOrderResponse =
+            serde_json::from_value(ultra_order_json(false, TEST_USER_PUBKEY)).unwrap();
+
+        let mut pretrade = PreTrade {
+            order,
+            input_warnings: vec![warning("HAS_FREEZE_AUTHORITY", "warning")],
+            output_warnings: vec![],
+            input_price: Some(price(1.0)),
+            output_price: None,
+        };
+        assert_eq!(pretrade.warnings().count(), 1);
+        assert!(!pretrade.has_critical_warnings());
+
+        pretrade
+            .output_warnings
+            .push(warning("NOT_SELLABLE", "critical"));
+        assert_eq!(pretrade.warnings().count(), 2);
+        assert!(pretrade.has_critical_warnings());
+    }
+
+    #[test]
+    fn test_transaction_summary_and_allowlist() {
+        let payer = Pubkey::new_unique();
+        let jupiter: Pubkey = JUPITER_V6_PROGRAM_ID.parse().unwrap();
+        let unknown = Pubkey::new_unique();
+
+        let tx = unsigned_transaction_base64(&payer, &[jupiter, jupiter]);
+        let summary = TransactionSummary::from_base64(&tx).expect("failed to decode");
+        assert_eq!(summary.fee_payer, payer.to_string());
+        assert_eq!(summary.signers, vec![payer.to_string()]);
+        assert_eq!(summary.program_ids, vec![JUPITER_V6_PROGRAM_ID.to_string()]);
+        // payer plus one writable account per instruction
+        assert_eq!(summary.writable_accounts.len(), 3);
+        assert!(!summary.writable_accounts.contains(&jupiter.to_string()));
+        assert!(summary.lookup_tables.is_empty());
+
+        let allowlist = ProgramAllowlist::ultra_default();
+        assert!(allowlist.verify_base64(&tx).is_ok());
+
+        let spoofed = unsigned_transaction_base64(&payer, &[jupiter, unknown]);
+        let res = allowlist.verify_base64(&spoofed);
+        assert!(
+            matches!(&res, Err(JupiterClientError::PolicyViolation(msg)) if msg.contains(&unknown.to_string())),
+            "unknown program should be rejected: {:?}",
+            res
+        );
+        assert!(
+            allowlist
+                .allow(unknown.to_string())
+                .verify_base64(&spoofed)
+                .is_ok()
+        );
+
+        assert!(matches!(
+            TransactionSummary::from_base64("not base64!"),
+            Err(JupiterClientError::DeserializationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_keypair_signer_signs_transaction() {
+        use base64::{Engine, engine::general_purpose::STANDARD};
+        use solana_transaction::versioned::VersionedTransaction;
+
+        let keypair = Keypair::new();
+        let signer = KeypairSigner::from_bytes(&keypair.to_bytes()).expect("valid keypair");
+        assert_eq!(signer.address(), keypair.pubkey());
+        let from_base58 = KeypairSigner::from_base58(&keypair.to_base58_string()).unwrap();
+        assert_eq!(from_base58.address(), keypair.pubkey());
+
+        let tx = unsigned_transaction_base64(&signer.address(), &[Pubkey::new_unique()]);
+        let signed = signer.sign_transaction(&tx).await.expect("failed to sign");
+
+        let decoded: VersionedTransaction =
+            bincode::deserialize(&STANDARD.decode(&signed.transaction).unwrap()).unwrap();
+        assert_eq!(decoded.signatures[0].to_string(), signed.signature);
+        assert!(
+            decoded.signatures[0].verify(keypair.pubkey().as_ref(), &decoded.message.serialize())
+        );
+
+        let other = KeypairSigner::new(Keypair::new());
+        assert!(matches!(
+            other.sign_transaction(&tx).await,
+            Err(JupiterClientError::SigningError(_))
+        ));
+        assert!(matches!(
+            KeypairSigner::from_base58("not a key"),
+            Err(JupiterClientError::SigningError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_partial_transaction_multiple_signers() {
+        use base64::{Engine, engine::general_purpose::STANDARD};
+        use solana_instruction::{AccountMeta, Instruction};
+        use solana_message::{Message, VersionedMessage};
+        use solana_transaction::versioned::VersionedTransaction;
+
+        let fee_payer = Keypair::new();
+        let user = Keypair::new();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1],
+            vec![AccountMeta::new(user.pubkey(), true)],
+        );
+        let message =
+            VersionedMessage::Legacy(Message::new(&[instruction], Some(&fee_payer.pubkey())));
+        let tx = STANDARD.encode(
+            bincode::serialize(&VersionedTransaction {
+                signatures: vec![Default::default(); 2],
+                message,
+            })
+            .unwrap(),
+        );
+
+        // the user signs first without taking the fee payer's slot
+        let user_signed = user.sign_transaction(&tx).await.expect("failed to sign");
+        let mut partial = PartialTransaction::from_base64(&user_signed.transaction).unwrap();
+        assert_eq!(partial.signers(), &[fee_payer.pubkey(), user.pubkey()]);
+        assert_eq!(partial.missing_signers(), vec![fee_payer.pubkey()]);
+
+        let sponsor = fee_payer.sign_message(partial.message());
+        assert!(matches!(
+            partial.add_signature(&fee_payer.pubkey(), user.sign_message(partial.message())),
+            Err(JupiterClientError::SigningError(_))
+        ));
+        partial
+            .add_signature(&fee_payer.pubkey(), sponsor)
+            .expect("valid sponsor signature");
+        assert!(partial.is_fully_signed());
+
+        let decoded: VersionedTransaction =
+            bincode::deserialize(&STANDARD.decode(partial.to_base64().unwrap()).unwrap()).unwrap();
+        assert_eq!(decoded.signatures[0], sponsor);
+        assert_eq!(decoded.signatures[1].to_string(), user_signed.signature);
+        let message = decoded.message.serialize();
+        assert!(
+            decoded
+                .signatures
+                .iter()
+                .zip(decoded.message.static_account_keys())
+                .all(|(signature, key)| signature.verify(key.as_ref(), &message))
+        );
+
+        let mut unsigned = PartialTransaction::from_base64(&tx).unwrap();
+        assert!(matches!(
+            unsigned.sign(&Keypair::new()).await,
+            Err(JupiterClientError::SigningError(_))
+        ));
+        unsigned.sign(&fee_payer).await.unwrap();
+        unsigned.sign(&user).await.unwrap();
+        assert!(unsigned.is_fully_signed());
+    }
+
+    #[test]
+    fn test_transaction_codec_round_trip() {
+        let payer = Pubkey::new_unique();
+        let base64 = unsigned_transaction_base64(&payer, &[Pubkey::new_unique()]);
+
+        let tx = codec::decode_base64(&base64).expect("failed to decode base64");
+        assert_eq!(tx.message.static_account_keys()[0], payer);
+        assert_eq!(codec::encode_base64(&tx).unwrap(), base64);
+
+        let base58 = codec::base64_to_base58(&base64).expect("failed to convert");
+        assert_eq!(codec::encode_base58(&tx).unwrap(), base58);
+        assert_eq!(codec::decode_base58(&base58).unwrap(), tx);
+        assert_eq!(codec::base58_to_base64(&base58).unwrap(), base64);
+
+        for res in [
+            codec::base64_to_base58("not base64!"),
+            codec::base58_to_base64("0OIl"),
+            codec::base64_to_base58("AAAA"),
+        ] {
+            assert!(matches!(
+                res,
+                Err(JupiterClientError::DeserializationError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_compute_budget_override() {
+        use solana_message::{
+            MessageHeader, VersionedMessage,
+            compiled_instruction::CompiledInstruction,
+            v0::{self, MessageAddressTableLookup},
+        };
+        use solana_transaction::versioned::VersionedTransaction;
+
+        // v0 transaction whose instruction uses an account loaded from a lookup table
+        let payer = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let message = v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![payer, program],
+            recent_blockhash: Default::default(),
+            instructions: vec![CompiledInstruction::new_from_raw_parts(
+                1,
+                vec![9],
+                vec![0, 2],
+            )],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes: vec![0],
+                readonly_indexes: vec![],
+            }],
+        };
+        let mut tx = VersionedTransaction {
+            signatures: vec![Default::default()],
+            message: VersionedMessage::V0(message),
+        };
+
+        ComputeBudgetOverride::new()
+            .unit_limit(300_000)
+            .unit_price(50_000)
+            .apply(&mut tx)
+            .unwrap();
+        let header = tx.message.header();
+        assert_eq!(header.num_readonly_unsigned_accounts, 2);
+        let keys = tx.message.static_account_keys();
+        assert_eq!(keys[2].to_string(), COMPUTE_BUDGET_PROGRAM_ID);
+        let instructions = tx.message.instructions();
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0].data[0], 2, "unit limit goes first");
+        assert_eq!(instructions[1].data[0], 3);
+        // the lookup table account moved from index 2 to 3
+        assert_eq!(instructions[2].accounts, vec![0, 3]);
+
+        let summary = TransactionSummary::from_transaction(&tx);
+        assert_eq!(summary.compute_budget.unit_limit, Some(300_000));
+        assert_eq!(
+            summary.compute_budget.unit_price_micro_lamports,
+            Some(50_000)
+        );
+        assert_eq!(summary.writable_accounts, vec![payer.to_string()]);
+
+        // existing instructions are rewritten in place
+        let encoded = codec::encode_base64(&tx).unwrap();
+        let rewritten = ComputeBudgetOverride::new()
+            .unit_price(1)
+            .apply_base64(&encoded)
+            .unwrap();
+        let rewritten = codec::decode_base64(&rewritten).unwrap();
+        assert_eq!(rewritten.message.instructions().len(), 3);
+        let summary = TransactionSummary::from_transaction(&rewritten);
+        assert_eq!(summary.compute_budget.unit_limit, Some(300_000));
+        assert_eq!(summary.compute_budget.unit_price_micro_lamports, Some(1));
+
+        // a transaction at the size limit has no room for the compute budget instructions
+        let mut full = tx.clone();
+        let VersionedMessage::V0(message) = &mut full.message else {
+            unreachable!()
+        };
+        message.instructions.drain(..2);
+        message.instructions[0].data = Vec::new();
+        let padding = codec::MAX_TRANSACTION_SIZE - bincode::serialize(&full).unwrap().len() - 1;
+        let VersionedMessage::V0(message) = &mut full.message else {
+            unreachable!()
+        };
+        message.instructions[0].data = vec![0; padding];
+        assert_eq!(
+            bincode::serialize(&full).unwrap().len(),
+            codec::MAX_TRANSACTION_SIZE
+        );
+        let unchanged = full.clone();
+        assert!(matches!(
+            ComputeBudgetOverride::new().unit_limit(1).apply(&mut full),
+            Err(JupiterClientError::InvalidRequest(msg)) if msg.contains("1232")
+        ));
+        assert_eq!(full, unchanged);
+
+        // signatures would be invalidated
+        tx.signatures[0] = Keypair::new().sign_message(b"message");
+        assert!(matches!(
+            ComputeBudgetOverride::new().unit_price(1).apply(&mut tx),
+            Err(JupiterClientError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_transaction_summary_compute_budget_and_transfers() {
+        use solana_instruction::{AccountMeta, Instruction};
+        use solana_message::{Message, VersionedMessage};
+        use solana_transaction::versioned::VersionedTransaction;
+
+        let payer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let (source, mint, destination) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let compute_budget: Pubkey = COMPUTE_BUDGET_PROGRAM_ID.parse().unwrap();
+        let system: Pubkey = SYSTEM_PROGRAM_ID.parse().unwrap();
+        let token: Pubkey = TOKEN_PROGRAM_ID.parse().unwrap();
+
+        let with_tag = |tag: u8, value: &[u8]| [&[tag], value].concat();
+        let mut sol_transfer = 2u32.to_le_bytes().to_vec();
+        sol_transfer.extend(5_000u64.to_le_bytes());
+        let mut token_transfer = with_tag(12, &1_500_000u64.to_le_bytes());
+        token_transfer.push(6);
+
+        let instructions = [
+            Instruction::new_with_bytes(
+                compute_budget,
+                &with_tag(2, &200_000u32.to_le_bytes()),
+                vec![],
+            ),
+            Instruction::new_with_bytes(
+                compute_budget,
+                &with_tag(3, &10_001u64.to_le_bytes()),
+                vec![],
+            ),
+            Instruction::new_with_bytes(
+                system,
+                &sol_transfer,
+                vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(recipient, false),
+                ],
+            ),
+            Instruction::new_with_bytes(
+                token,
+                &token_transfer,
+                vec![
+                    AccountMeta::new(source, false),
+                    AccountMeta::new_readonly(mint, false),
+                    AccountMeta::new(destination, false),
+                    AccountMeta::new_readonly(payer, true),
+                ],
+            ),
+        ];
+        let message = VersionedMessage::Legacy(Message::new(&instructions, Some(&payer)));
+        let tx = VersionedTransaction {
+            signatures: vec![Default::default()],
+            message,
+        };
+
+        let summary = TransactionSummary::from_transaction(&tx);
+        assert_eq!(
+            summary.compute_budget,
+            ComputeBudget {
+                unit_limit: Some(200_000),
+                unit_price_micro_lamports: Some(10_001),
+                ..Default::default()
+            }
+        );
+        // 200_000 * 10_001 micro-lamports, rounded up
+        assert_eq!(summary.compute_budget.priority_fee_lamports(), Some(2_001));
+
+        assert_eq!(summary.transfers.len(), 2);
+        let sol = &summary.transfers[0];
+        assert_eq!(sol.kind, TransferKind::Sol);
+        assert_eq!(sol.destination, Some(recipient.to_string()));
+        assert_eq!(sol.amount, 5_000);
+        assert_eq!(summary.sol_sent_by(&payer.to_string()), 5_000);
+        let mut overflowing = summary.clone();
+        overflowing.transfers.push(Transfer {
+            amount: u64::MAX,
+            ..sol.clone()
+        });
+        assert_eq!(overflowing.sol_sent_by(&payer.to_string()), u64::MAX);
+
+        let spl = &summary.transfers[1];
+        assert_eq!(spl.kind, TransferKind::Token);
+        assert_eq!(spl.source, Some(source.to_string()));
+        assert_eq!(spl.destination, Some(destination.to_string()));
+        assert_eq!(spl.authority, Some(payer.to_string()));
+        assert_eq!(spl.mint, Some(mint.to_string()));
+        assert_eq!((spl.amount, spl.decimals), (1_500_000, Some(6)));
+
+        // summaries serialized before transfers were reported still deserialize
+        let mut json = serde_json::to_value(&summary).unwrap();
+        json.as_object_mut().unwrap().remove("transfers");
+        json.as_object_mut().unwrap().remove("computeBudget");
+        let old: TransactionSummary = serde_json::from_value(json).unwrap();
+        assert!(old.transfers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_twap_executor_guards_and_reports_slices() {
+        let keypair = Keypair::new();
+        let unsliceable = TwapExecutor::new(JupiterClient::new(""), USDC_MINT, SOL_MINT, 2, 3);
+        assert!(matches!(
+            unsliceable.run(&keypair).await,
+            Err(JupiterClientError::ValidationError(
+                ValidationError::ZeroAmount("total_amount")
+            ))
+        ));
+
+        let tx = unsigned_transaction_base64(&keypair.pubkey(), &[Pubkey::new_unique()]);
+        let order = |request_id: &str, impact: &str| -> &'static str {
+            let mut json = ultra_order_json(false, &keypair.pubkey().to_string());
+            json["taker"] = keypair.pubkey().to_string().into();
+            json["transaction"] = tx.clone().into();
+            json["requestId"] = request_id.into();
+            json["priceImpactPct"] = impact.into();
+            Box::leak(json.to_string().into_boxed_str())
+        };
+        let (url, _) = mock_server_sequence(vec![
+            order("req-1", "0.1"),
+            r#"{"status":"Success","signature":"sig-1","code":0,"inputAmountResult":"10000000","outputAmountResult":"65000000"}"#,
+            order("req-2", "2.5"),
+            order("req-3", "0.2"),
+            r#"{"status":"Failed","signature":"sig-3","code":-1005,"error":"order expired"}"#,
+        ])
+        .await;
+
+        let started = std::time::Instant::now();
+        let report =
+            TwapExecutor::new(JupiterClient::new(&url), USDC_MINT, SOL_MINT, 30_000_001, 3)
+                .window(Duration::from_millis(300))
+                .max_price_impact_pct(1.0)
+                .run(&keypair)
+                .await
+                .expect("valid order should run");
+        // slices start 100ms apart, the last one 200ms after the first
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(5));
+
+        let amounts: Vec<u64> = report.slices.iter().map(|slice| slice.in_amount).collect();
+        assert_eq!(amounts, vec![10_000_000, 10_000_000, 10_000_001]);
+        assert_eq!(report.slices[0].status, SliceStatus::Filled);
+        assert_eq!(report.slices[0].signature.as_deref(), Some("sig-1"));
+        assert!(matches!(report.slices[1].status, SliceStatus::Skipped(_)));
+        assert!(matches!(report.slices[2].status, SliceStatus::Failed(_)));
+
+        assert_eq!(report.filled_slices(), 1);
+        assert_eq!(report.in_filled(), 10_000_000);
+        assert_eq!(report.out_filled(), 65_000_000);
+        assert_eq!(report.average_price(), Some(6.5));
+        assert_eq!(report.max_price_impact_pct(), Some(0.1));
+    }
 }